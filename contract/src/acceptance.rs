@@ -0,0 +1,77 @@
+//! Buyer acceptance signed off-chain with an ed25519 key.
+//!
+//! # Design
+//! - The buyer registers an ed25519 public key once (`set_acceptance_key`).
+//! - Acceptance of a trade, or of a later amendment, is an ed25519 signature
+//!   over the XDR of an `AcceptancePayload` for the trade's current terms.
+//!   Anyone can submit it (`accept_trade_signed`), so approvals collected by
+//!   email or app flows can be settled by a relayer.
+//! - Each acceptance carries a nonce that must exceed the previous one for
+//!   the trade, so an old signature cannot be replayed over a newer one.
+
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env};
+
+use crate::errors::ContractError;
+use crate::types::{AcceptancePayload, BuyerAcceptance, Trade};
+
+// ---------------------------------------------------------------------------
+// Storage
+// ---------------------------------------------------------------------------
+
+const ACCEPTANCE_KEY_PREFIX: &str = "AK";
+pub(crate) const ACCEPTANCE_PREFIX: &str = "BA";
+
+pub fn set_key(env: &Env, buyer: &Address, public_key: &BytesN<32>) {
+    env.storage().persistent().set(&(ACCEPTANCE_KEY_PREFIX, buyer.clone()), public_key);
+}
+
+pub fn get_key(env: &Env, buyer: &Address) -> Option<BytesN<32>> {
+    env.storage().persistent().get(&(ACCEPTANCE_KEY_PREFIX, buyer.clone()))
+}
+
+pub fn get(env: &Env, trade_id: u64) -> Option<BuyerAcceptance> {
+    env.storage().persistent().get(&(ACCEPTANCE_PREFIX, trade_id))
+}
+
+// ---------------------------------------------------------------------------
+// Verification
+// ---------------------------------------------------------------------------
+
+/// Bytes the buyer signs to accept `terms_hash` for `trade`.
+pub fn payload(env: &Env, trade: &Trade, terms_hash: &BytesN<32>, nonce: u64) -> Bytes {
+    AcceptancePayload {
+        contract: env.current_contract_address(),
+        trade_id: trade.id,
+        seller: trade.seller.clone(),
+        buyer: trade.buyer.clone(),
+        amount: trade.amount,
+        currency: trade.currency.clone(),
+        terms_hash: terms_hash.clone(),
+        nonce,
+    }
+    .to_xdr(env)
+}
+
+/// Verify the buyer's signature and record the acceptance. An invalid
+/// signature aborts the call.
+pub fn accept(
+    env: &Env,
+    trade: &Trade,
+    terms_hash: &BytesN<32>,
+    nonce: u64,
+    signature: &BytesN<64>,
+) -> Result<BuyerAcceptance, ContractError> {
+    let public_key = get_key(env, &trade.buyer).ok_or(ContractError::NoAcceptanceKey)?;
+    if get(env, trade.id).is_some_and(|prev| nonce <= prev.nonce) {
+        return Err(ContractError::StaleAcceptanceNonce);
+    }
+    env.crypto()
+        .ed25519_verify(&public_key, &payload(env, trade, terms_hash, nonce), signature);
+    let acceptance = BuyerAcceptance {
+        terms_hash: terms_hash.clone(),
+        nonce,
+        accepted_at: env.ledger().timestamp(),
+    };
+    env.storage().persistent().set(&(ACCEPTANCE_PREFIX, trade.id), &acceptance);
+    Ok(acceptance)
+}
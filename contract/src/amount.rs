@@ -0,0 +1,33 @@
+//! Token amount validation.
+//!
+//! Every token amount the contract stores or reports is `i128`, matching the
+//! token interface and the full range of Stellar asset amounts. Amounts are
+//! never negative, and running totals fail with `Overflow` rather than
+//! saturate. User tier volumes are still `u64`; `narrow` converts into them.
+
+use crate::errors::ContractError;
+
+/// Reject anything but a strictly positive trade amount.
+pub fn require_positive(amount: i128) -> Result<(), ContractError> {
+    if amount <= 0 {
+        return Err(ContractError::InvalidAmount);
+    }
+    Ok(())
+}
+
+/// Reject a negative amount-valued setting (limit, cap, bounty). Zero is
+/// allowed and usually means "disabled".
+pub fn require_non_negative(amount: i128) -> Result<(), ContractError> {
+    if amount < 0 {
+        return Err(ContractError::InvalidAmount);
+    }
+    Ok(())
+}
+
+/// Convert an amount for a `u64` record.
+pub fn narrow(amount: i128) -> Result<u64, ContractError> {
+    if amount < 0 {
+        return Err(ContractError::InvalidAmount);
+    }
+    u64::try_from(amount).map_err(|_| ContractError::Overflow)
+}
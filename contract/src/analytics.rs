@@ -8,9 +8,14 @@
 //! - Unique address count (buyers + sellers that have ever interacted)
 //! - Time-windowed snapshots (24 h, 7 d, 30 d) via `TimeWindow`
 //! - Arbitrator performance (disputes handled, resolution breakdown)
+//! - Lifetime totals per currency (trades created/settled, settled volume,
+//!   fees collected and withdrawn) that only ever grow, independent of fee
+//!   withdrawals
 
 use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol};
 
+use crate::errors::ContractError;
+
 // ---------------------------------------------------------------------------
 // Storage keys
 // ---------------------------------------------------------------------------
@@ -20,6 +25,7 @@ fn key_window(tag: u32) -> (Symbol, u32) { (symbol_short!("WIN"), tag) }
 fn key_unique_count() -> Symbol { symbol_short!("UNIQ_CNT") }
 fn key_unique_addr(addr: &Address) -> (Symbol, Address) { (symbol_short!("UNIQ"), addr.clone()) }
 fn key_arb_stats(arb: &Address) -> (Symbol, Address) { (symbol_short!("ARB_STAT"), arb.clone()) }
+fn key_lifetime(currency: &Address) -> (Symbol, Address) { (symbol_short!("LIFETIME"), currency.clone()) }
 
 // ---------------------------------------------------------------------------
 // Types
@@ -30,7 +36,7 @@ fn key_arb_stats(arb: &Address) -> (Symbol, Address) { (symbol_short!("ARB_STAT"
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PlatformMetrics {
     /// Total USDC volume that has passed through escrow (in stroops).
-    pub total_volume: u64,
+    pub total_volume: i128,
     /// Number of trades ever created.
     pub trades_created: u64,
     /// Number of trades that reached Funded state.
@@ -42,7 +48,7 @@ pub struct PlatformMetrics {
     /// Number of trades cancelled.
     pub trades_cancelled: u64,
     /// Total platform fees accumulated (in stroops).
-    pub total_fees_collected: u64,
+    pub total_fees_collected: i128,
 }
 
 /// Metrics accumulated within a rolling time window.
@@ -54,7 +60,7 @@ pub struct WindowMetrics {
     pub window_start: u64,
     /// Duration of the window in seconds.
     pub window_seconds: u64,
-    pub volume: u64,
+    pub volume: i128,
     pub trades_created: u64,
     pub trades_completed: u64,
     pub trades_cancelled: u64,
@@ -85,6 +91,23 @@ pub struct ArbitratorMetrics {
     pub resolved_partial: u64,
 }
 
+/// Monotonic lifetime counters for one currency. Never reset or decremented,
+/// so analytics can read totals without replaying history. Amounts are in
+/// that currency's units.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LifetimeTotals {
+    pub trades_created: u64,
+    /// Trades that reached a settled outcome (completed, refunded or resolved).
+    pub trades_settled: u64,
+    /// Escrowed amount of settled trades (in stroops).
+    pub gross_volume: i128,
+    /// Fees booked at settlement, before burns and withdrawals.
+    pub fees_collected: i128,
+    /// Fees paid out of the contract by fee withdrawals.
+    pub fees_withdrawn: i128,
+}
+
 /// Derived statistics computed from `PlatformMetrics`.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -126,6 +149,17 @@ pub fn load_metrics(env: &Env) -> PlatformMetrics {
     })
 }
 
+fn update_lifetime<F: FnOnce(&mut LifetimeTotals) -> Option<()>>(
+    env: &Env,
+    currency: &Address,
+    f: F,
+) -> Result<(), ContractError> {
+    let mut t = get_lifetime_totals(env, currency);
+    f(&mut t).ok_or(ContractError::Overflow)?;
+    env.storage().persistent().set(&key_lifetime(currency), &t);
+    Ok(())
+}
+
 fn save_metrics(env: &Env, m: &PlatformMetrics) {
     env.storage().instance().set(&key_metrics(), m);
 }
@@ -189,12 +223,13 @@ fn save_window(env: &Env, w: &TimeWindow, wm: &WindowMetrics) {
     env.storage().instance().set(&key_window(window_tag(w)), wm);
 }
 
-fn update_all_windows<F: Fn(&mut WindowMetrics)>(env: &Env, f: F) {
+fn update_all_windows<F: Fn(&mut WindowMetrics) -> Option<()>>(env: &Env, f: F) -> Result<(), ContractError> {
     for w in &[TimeWindow::Last24h, TimeWindow::Last7d, TimeWindow::Last30d, TimeWindow::AllTime] {
         let mut wm = load_or_reset_window(env, w);
-        f(&mut wm);
+        f(&mut wm).ok_or(ContractError::Overflow)?;
         save_window(env, w, &wm);
     }
+    Ok(())
 }
 
 fn load_unique_count(env: &Env) -> u64 {
@@ -230,19 +265,30 @@ fn save_arb_metrics(env: &Env, arb: &Address, m: &ArbitratorMetrics) {
 // Update hooks — called from lib.rs on each state transition
 // ---------------------------------------------------------------------------
 
-pub fn on_trade_created(env: &Env, amount: u64, seller: &Address, buyer: &Address) {
+pub fn on_trade_created(
+    env: &Env,
+    currency: &Address,
+    amount: i128,
+    seller: &Address,
+    buyer: &Address,
+) -> Result<(), ContractError> {
     let mut m = load_metrics(env);
     m.trades_created = m.trades_created.saturating_add(1);
-    m.total_volume = m.total_volume.saturating_add(amount);
+    m.total_volume = m.total_volume.checked_add(amount).ok_or(ContractError::Overflow)?;
     save_metrics(env, &m);
 
     update_all_windows(env, |wm| {
         wm.trades_created = wm.trades_created.saturating_add(1);
-        wm.volume = wm.volume.saturating_add(amount);
-    });
+        wm.volume = wm.volume.checked_add(amount)?;
+        Some(())
+    })?;
 
     record_address(env, seller);
     record_address(env, buyer);
+    update_lifetime(env, currency, |t| {
+        t.trades_created = t.trades_created.saturating_add(1);
+        Some(())
+    })
 }
 
 pub fn on_trade_funded(env: &Env) {
@@ -251,35 +297,63 @@ pub fn on_trade_funded(env: &Env) {
     save_metrics(env, &m);
 }
 
-pub fn on_trade_completed(env: &Env, fee: u64) {
+pub fn on_trade_completed(env: &Env, fee: i128) -> Result<(), ContractError> {
     let mut m = load_metrics(env);
     m.trades_completed = m.trades_completed.saturating_add(1);
-    m.total_fees_collected = m.total_fees_collected.saturating_add(fee);
+    m.total_fees_collected = m.total_fees_collected.checked_add(fee).ok_or(ContractError::Overflow)?;
     save_metrics(env, &m);
 
     update_all_windows(env, |wm| {
         wm.trades_completed = wm.trades_completed.saturating_add(1);
-    });
+        Some(())
+    })
 }
 
-pub fn on_trade_disputed(env: &Env) {
+pub fn on_trade_disputed(env: &Env) -> Result<(), ContractError> {
     let mut m = load_metrics(env);
     m.trades_disputed = m.trades_disputed.saturating_add(1);
     save_metrics(env, &m);
 
     update_all_windows(env, |wm| {
         wm.trades_disputed = wm.trades_disputed.saturating_add(1);
-    });
+        Some(())
+    })
 }
 
-pub fn on_trade_cancelled(env: &Env) {
+pub fn on_trade_cancelled(env: &Env) -> Result<(), ContractError> {
     let mut m = load_metrics(env);
     m.trades_cancelled = m.trades_cancelled.saturating_add(1);
     save_metrics(env, &m);
 
     update_all_windows(env, |wm| {
         wm.trades_cancelled = wm.trades_cancelled.saturating_add(1);
-    });
+        Some(())
+    })
+}
+
+/// Called once per trade when it reaches a settled outcome.
+pub fn on_trade_settled(env: &Env, currency: &Address, amount: i128) -> Result<(), ContractError> {
+    update_lifetime(env, currency, |t| {
+        t.trades_settled = t.trades_settled.saturating_add(1);
+        t.gross_volume = t.gross_volume.checked_add(amount)?;
+        Some(())
+    })
+}
+
+/// Called whenever a trade fee is booked in `currency`.
+pub fn on_fees_collected(env: &Env, currency: &Address, fee: i128) -> Result<(), ContractError> {
+    update_lifetime(env, currency, |t| {
+        t.fees_collected = t.fees_collected.checked_add(fee)?;
+        Some(())
+    })
+}
+
+/// Called when accumulated fees in `currency` leave the contract.
+pub fn on_fees_withdrawn(env: &Env, currency: &Address, amount: i128) -> Result<(), ContractError> {
+    update_lifetime(env, currency, |t| {
+        t.fees_withdrawn = t.fees_withdrawn.checked_add(amount)?;
+        Some(())
+    })
 }
 
 /// Called when an arbitrator resolves a dispute.
@@ -304,6 +378,11 @@ pub fn get_metrics(env: &Env) -> PlatformMetrics {
     load_metrics(env)
 }
 
+/// Return the monotonic lifetime totals for `currency`.
+pub fn get_lifetime_totals(env: &Env, currency: &Address) -> LifetimeTotals {
+    env.storage().persistent().get(&key_lifetime(currency)).unwrap_or_default()
+}
+
 /// Return derived platform statistics including success rate and dispute rate.
 pub fn get_stats(env: &Env) -> PlatformStats {
     let m = load_metrics(env);
@@ -475,9 +554,9 @@ pub fn get_analytics_by_period(env: &Env, start_time: u64, end_time: u64) -> Per
 pub struct VolumeStats {
     pub trades_created: u64,
     /// All-time cumulative value (stroops).
-    pub total_value: u64,
+    pub total_value: i128,
     /// Volume within the requested time window (stroops).
-    pub window_volume: u64,
+    pub window_volume: i128,
     /// Trades created within the requested time window.
     pub window_trades: u64,
 }
@@ -499,7 +578,7 @@ pub struct SuccessRateStats {
 pub struct PlatformUsage {
     pub active_escrows: u64,
     pub unique_participants: u64,
-    pub total_fees_collected: u64,
+    pub total_fees_collected: i128,
 }
 
 /// Returned by `get_analytics_by_period`.
@@ -510,7 +589,7 @@ pub struct PeriodAnalytics {
     pub end_time: u64,
     /// `true` when a stored window overlaps the requested range.
     pub window_matched: bool,
-    pub volume: u64,
+    pub volume: i128,
     pub trades_created: u64,
     pub trades_completed: u64,
     pub trades_disputed: u64,
@@ -518,5 +597,5 @@ pub struct PeriodAnalytics {
     pub success_rate_bps: u32,
     pub active_escrows: u64,
     pub unique_participants: u64,
-    pub total_fees_collected: u64,
+    pub total_fees_collected: i128,
 }
@@ -0,0 +1,62 @@
+//! Optional KYC gate backed by an external attestation contract.
+//!
+//! # Design
+//! - The admin registers an attestation contract (`set_attestation_contract`).
+//! - The attestation contract must expose `has_valid_kyc(subject) -> bool`.
+//! - While one is registered, both parties must hold a valid credential to
+//!   create or fund a trade. A failing or misbehaving attestation contract
+//!   counts as no credential.
+//! - With no attestation contract registered, the gate is off.
+
+use soroban_sdk::{contractclient, symbol_short, Address, Env};
+
+use crate::errors::ContractError;
+
+// ---------------------------------------------------------------------------
+// Attestation cross-contract interface
+// ---------------------------------------------------------------------------
+
+#[contractclient(name = "AttestationClient")]
+pub trait AttestationInterface {
+    fn has_valid_kyc(env: Env, subject: Address) -> bool;
+}
+
+// ---------------------------------------------------------------------------
+// Storage
+// ---------------------------------------------------------------------------
+
+fn attestation_key() -> soroban_sdk::Symbol {
+    symbol_short!("KYC_ATT")
+}
+
+pub fn set_attestation_contract(env: &Env, contract: &Address) {
+    env.storage().instance().set(&attestation_key(), contract);
+}
+
+pub fn remove_attestation_contract(env: &Env) {
+    env.storage().instance().remove(&attestation_key());
+}
+
+pub fn get_attestation_contract(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&attestation_key())
+}
+
+// ---------------------------------------------------------------------------
+// Checks
+// ---------------------------------------------------------------------------
+
+/// Require a valid KYC credential for every party, if the gate is on.
+pub fn require_attested(env: &Env, parties: &[&Address]) -> Result<(), ContractError> {
+    let contract = match get_attestation_contract(env) {
+        Some(c) => c,
+        None => return Ok(()),
+    };
+    let client = AttestationClient::new(env, &contract);
+    for party in parties {
+        let valid = matches!(client.try_has_valid_kyc(party), Ok(Ok(true)));
+        if !valid {
+            return Err(ContractError::ComplianceCheckFailed);
+        }
+    }
+    Ok(())
+}
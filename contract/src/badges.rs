@@ -0,0 +1,53 @@
+//! Soulbound trust badges earned from a clean settlement history.
+//!
+//! Every completed trade adds to both parties' clean streak; losing a
+//! dispute resets the streak and revokes any badge. Users claim a level once
+//! their streak qualifies. Badges cannot be transferred; other features read
+//! them through `level`.
+
+use soroban_sdk::{Address, Env};
+
+use crate::errors::ContractError;
+use crate::events;
+use crate::storage::{get_badge_level, get_track_record, save_track_record, set_badge_level};
+use crate::types::{BADGE_TRADES_PER_LEVEL, MAX_BADGE_LEVEL};
+
+/// Count a completed trade towards `user`'s clean streak.
+pub fn record_completed(env: &Env, user: &Address) {
+    let mut record = get_track_record(env, user);
+    record.clean_trades = record.clean_trades.saturating_add(1);
+    save_track_record(env, user, &record);
+}
+
+/// Reset `user`'s clean streak after a lost dispute and revoke their badge.
+pub fn record_dispute_lost(env: &Env, user: &Address) {
+    let mut record = get_track_record(env, user);
+    record.clean_trades = 0;
+    record.disputes_lost = record.disputes_lost.saturating_add(1);
+    save_track_record(env, user, &record);
+    if get_badge_level(env, user) > 0 {
+        set_badge_level(env, user, 0);
+        events::emit_badge_revoked(env, user.clone());
+    }
+}
+
+/// Level `user`'s current streak qualifies for.
+pub fn eligible_level(env: &Env, user: &Address) -> u32 {
+    (get_track_record(env, user).clean_trades / BADGE_TRADES_PER_LEVEL).min(MAX_BADGE_LEVEL)
+}
+
+/// Raise `user`'s badge to the level their streak qualifies for.
+pub fn claim(env: &Env, user: &Address) -> Result<u32, ContractError> {
+    let level = eligible_level(env, user);
+    if level <= get_badge_level(env, user) {
+        return Err(ContractError::BadgeNotEarned);
+    }
+    set_badge_level(env, user, level);
+    events::emit_badge_claimed(env, user.clone(), level);
+    Ok(level)
+}
+
+/// Claimed badge level for `user`; zero if none.
+pub fn level(env: &Env, user: &Address) -> u32 {
+    get_badge_level(env, user)
+}
@@ -0,0 +1,105 @@
+//! Basket escrow: extra tokens locked alongside a trade's primary currency.
+//!
+//! Legs are pulled from the buyer in the same call that funds the primary
+//! amount, so funding is all-or-nothing. They are paid out in step with the
+//! primary leg: when `amount` of it leaves escrow, each leg releases the same
+//! share of its own amount, measured against the primary net of fee. Fees are
+//! charged on the primary leg only. Once the primary leg is fully paid out,
+//! whatever remains of each leg (rounding dust included) goes with it.
+
+use soroban_sdk::{token, Address, Env, Vec};
+
+use crate::errors::ContractError;
+use crate::storage::{get_basket, save_basket};
+use crate::transfer;
+use crate::types::{Basket, BasketLeg, Trade, MAX_BASKET_LEGS};
+
+pub fn set(env: &Env, trade: &Trade, legs: &Vec<BasketLeg>) -> Result<(), ContractError> {
+    if legs.is_empty() || legs.len() > MAX_BASKET_LEGS {
+        return Err(ContractError::InvalidBasket);
+    }
+    let mut released = Vec::new(env);
+    for (i, leg) in legs.iter().enumerate() {
+        if leg.amount <= 0 || leg.token == trade.currency {
+            return Err(ContractError::InvalidBasket);
+        }
+        if legs.iter().skip(i + 1).any(|other| other.token == leg.token) {
+            return Err(ContractError::InvalidBasket);
+        }
+        released.push_back(0i128);
+    }
+    save_basket(env, trade.id, &Basket { legs: legs.clone(), released, primary_released: 0 });
+    Ok(())
+}
+
+pub fn legs(env: &Env, trade_id: u64) -> Option<Vec<BasketLeg>> {
+    get_basket(env, trade_id).map(|b| b.legs)
+}
+
+pub fn is_basket(env: &Env, trade_id: u64) -> bool {
+    get_basket(env, trade_id).is_some()
+}
+
+/// Pull every leg from the buyer into escrow.
+pub fn pull(env: &Env, trade: &Trade) -> Result<(), ContractError> {
+    if let Some(basket) = get_basket(env, trade.id) {
+        let contract = env.current_contract_address();
+        for leg in basket.legs.iter() {
+            transfer::send(&token::Client::new(env, &leg.token), &trade.buyer, &contract, leg.amount)?;
+        }
+    }
+    Ok(())
+}
+
+/// Pull every leg from allowances the buyer granted this contract.
+pub fn pull_from_allowance(env: &Env, trade: &Trade) -> Result<(), ContractError> {
+    let basket = match get_basket(env, trade.id) {
+        Some(b) => b,
+        None => return Ok(()),
+    };
+    let contract = env.current_contract_address();
+    for leg in basket.legs.iter() {
+        if token::Client::new(env, &leg.token).allowance(&trade.buyer, &contract) < leg.amount {
+            return Err(ContractError::InsufficientAllowance);
+        }
+    }
+    for leg in basket.legs.iter() {
+        transfer::send_from(
+            &token::Client::new(env, &leg.token),
+            &contract,
+            &trade.buyer,
+            &contract,
+            leg.amount,
+        )?;
+    }
+    Ok(())
+}
+
+/// Record `amount` of the primary leg leaving escrow and return each leg's
+/// matching share, to be sent to the same side.
+pub fn release(env: &Env, trade: &Trade, amount: i128) -> Result<Vec<BasketLeg>, ContractError> {
+    let mut parts = Vec::new(env);
+    let mut basket = match get_basket(env, trade.id) {
+        Some(b) => b,
+        None => return Ok(parts),
+    };
+    let net = trade.amount.checked_sub(trade.fee).ok_or(ContractError::Overflow)?;
+    basket.primary_released = basket.primary_released.checked_add(amount).ok_or(ContractError::Overflow)?;
+    let exhausted = basket.primary_released >= net;
+    for (i, leg) in basket.legs.iter().enumerate() {
+        let i = i as u32;
+        let left = leg.amount - basket.released.get(i).unwrap_or(0);
+        let part = if exhausted || net == 0 {
+            left
+        } else {
+            let share = leg.amount.checked_mul(amount).ok_or(ContractError::Overflow)? / net;
+            share.min(left)
+        };
+        basket.released.set(i, leg.amount - left + part);
+        if part > 0 {
+            parts.push_back(BasketLeg { token: leg.token, amount: part });
+        }
+    }
+    save_basket(env, trade.id, &basket);
+    Ok(parts)
+}
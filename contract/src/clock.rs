@@ -0,0 +1,43 @@
+//! Clock for deadline features: ledger sequence or unix timestamp.
+//!
+//! Each deadline feature names the unit its windows are configured in (its
+//! `native` mode): funding, completion and confirmation deadlines and the
+//! release hold count ledgers; refund responses, arbitrator assignment, the
+//! re-open window, dispute bonds and dispute responses count seconds. Until
+//! the admin picks a timing mode each feature keeps its native unit. Once a
+//! mode is set, all of them measure time in it, converting windows at
+//! `SECONDS_PER_LEDGER`. Admin timelocks are unaffected.
+
+use soroban_sdk::Env;
+
+use crate::errors::ContractError;
+use crate::storage;
+use crate::types::{TimingMode, SECONDS_PER_LEDGER};
+
+pub fn mode(env: &Env, native: TimingMode) -> TimingMode {
+    storage::get_timing_mode(env).unwrap_or(native)
+}
+
+/// Current time in the unit deadlines of a `native` feature are kept in.
+pub fn now(env: &Env, native: TimingMode) -> u64 {
+    match mode(env, native) {
+        TimingMode::Ledger => env.ledger().sequence() as u64,
+        TimingMode::Timestamp => env.ledger().timestamp(),
+    }
+}
+
+/// Convert a `window` given in `native` units into the active unit.
+pub fn span(env: &Env, native: TimingMode, window: u64) -> u64 {
+    match (native, mode(env, native)) {
+        (TimingMode::Ledger, TimingMode::Timestamp) => window.saturating_mul(SECONDS_PER_LEDGER),
+        (TimingMode::Timestamp, TimingMode::Ledger) => window / SECONDS_PER_LEDGER,
+        _ => window,
+    }
+}
+
+/// Deadline `window` (in `native` units) from now.
+pub fn after(env: &Env, native: TimingMode, window: u64) -> Result<u64, ContractError> {
+    now(env, native)
+        .checked_add(span(env, native, window))
+        .ok_or(ContractError::Overflow)
+}
@@ -0,0 +1,126 @@
+//! Symmetric dispute bonds.
+//!
+//! When bonds are configured, opening a dispute gives both parties until a
+//! deadline to post the same bond in the trade's currency. The arbitrator
+//! rules once both have posted, or once the deadline passes with neither
+//! posted. If only one party posted, the dispute defaults in their favour.
+//! At settlement the winner's bond is returned and the loser's is split
+//! between the arbitrator and the winner; a partial ruling returns both.
+
+use soroban_sdk::{token, Address, Env};
+
+use crate::clock;
+use crate::errors::ContractError;
+use crate::events;
+use crate::storage;
+use crate::transfer;
+use crate::types::{DisputeBonds, DisputeResolution, TimingMode, Trade};
+
+/// Start the bond window for a newly opened dispute, if bonds are enabled.
+pub fn open(env: &Env, trade_id: u64) -> Result<(), ContractError> {
+    let policy = storage::get_dispute_bond_policy(env);
+    if policy.amount == 0 {
+        return Ok(());
+    }
+    let deadline = clock::after(env, TimingMode::Timestamp, policy.window)?;
+    storage::save_dispute_bonds(
+        env,
+        trade_id,
+        &DisputeBonds { amount: policy.amount, deadline, buyer_posted: false, seller_posted: false },
+    );
+    Ok(())
+}
+
+pub fn post(env: &Env, trade: &Trade, party: &Address) -> Result<(), ContractError> {
+    let mut bonds = storage::get_dispute_bonds(env, trade.id).ok_or(ContractError::NoDisputeBond)?;
+    if clock::now(env, TimingMode::Timestamp) >= bonds.deadline {
+        return Err(ContractError::BondWindowClosed);
+    }
+    let posted = if *party == trade.buyer {
+        &mut bonds.buyer_posted
+    } else if *party == trade.seller {
+        &mut bonds.seller_posted
+    } else {
+        return Err(ContractError::Unauthorized);
+    };
+    if *posted {
+        return Err(ContractError::BondAlreadyPosted);
+    }
+    *posted = true;
+    transfer::send(&token::Client::new(env, &trade.currency), party, &env.current_contract_address(), bonds.amount)?;
+    storage::add_total_locked(env, &trade.currency, bonds.amount)?;
+    storage::save_dispute_bonds(env, trade.id, &bonds);
+    events::emit_dispute_bond_posted(env, trade.id, party.clone(), bonds.amount);
+    Ok(())
+}
+
+/// Reject an arbitrator's ruling while bonds are outstanding or the
+/// dispute has defaulted.
+pub fn require_ruling_open(env: &Env, trade_id: u64) -> Result<(), ContractError> {
+    let bonds = match storage::get_dispute_bonds(env, trade_id) {
+        Some(b) => b,
+        None => return Ok(()),
+    };
+    if bonds.buyer_posted && bonds.seller_posted {
+        return Ok(());
+    }
+    if clock::now(env, TimingMode::Timestamp) < bonds.deadline {
+        return Err(ContractError::DisputeBondsPending);
+    }
+    if bonds.buyer_posted || bonds.seller_posted {
+        return Err(ContractError::DisputeBondDefaulted);
+    }
+    Ok(())
+}
+
+/// Ruling a defaulted dispute resolves to: in favour of the only party that
+/// posted once the deadline has passed.
+pub fn default_ruling(env: &Env, trade_id: u64) -> Result<DisputeResolution, ContractError> {
+    let bonds = storage::get_dispute_bonds(env, trade_id).ok_or(ContractError::NoDisputeBond)?;
+    if clock::now(env, TimingMode::Timestamp) < bonds.deadline {
+        return Err(ContractError::DisputeBondsPending);
+    }
+    match (bonds.buyer_posted, bonds.seller_posted) {
+        (true, false) => Ok(DisputeResolution::ReleaseToBuyer),
+        (false, true) => Ok(DisputeResolution::ReleaseToSeller),
+        _ => Err(ContractError::NoDisputeBond),
+    }
+}
+
+/// Return or forfeit posted bonds according to the ruling.
+pub fn settle(
+    env: &Env,
+    trade: &Trade,
+    arbitrator: &Address,
+    resolution: &DisputeResolution,
+) -> Result<(), ContractError> {
+    let bonds = match storage::get_dispute_bonds(env, trade.id) {
+        Some(b) => b,
+        None => return Ok(()),
+    };
+    storage::remove_dispute_bonds(env, trade.id);
+    let winner = match resolution {
+        DisputeResolution::ReleaseToBuyer => Some(&trade.buyer),
+        DisputeResolution::ReleaseToSeller => Some(&trade.seller),
+        DisputeResolution::Partial(_) => None,
+    };
+    let token_client = token::Client::new(env, &trade.currency);
+    let contract = env.current_contract_address();
+    for (party, posted) in [(&trade.buyer, bonds.buyer_posted), (&trade.seller, bonds.seller_posted)] {
+        if !posted {
+            continue;
+        }
+        storage::sub_total_locked(env, &trade.currency, bonds.amount)?;
+        match winner {
+            Some(w) if w != party => {
+                let to_arbitrator = bonds.amount / 2;
+                if to_arbitrator > 0 {
+                    transfer::send(&token_client, &contract, arbitrator, to_arbitrator)?;
+                }
+                transfer::send(&token_client, &contract, w, bonds.amount - to_arbitrator)?;
+            }
+            _ => transfer::send(&token_client, &contract, party, bonds.amount)?,
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,63 @@
+//! Dispute response deadlines and default victory.
+//!
+//! When a response window is configured, each dispute records a deadline by
+//! which both parties must engage: raising the dispute counts for the
+//! raiser, submitting evidence counts for either side. If only one party
+//! responded by the deadline, anyone may settle the dispute in their favour.
+
+use soroban_sdk::{Address, Env};
+
+use crate::clock;
+use crate::errors::ContractError;
+use crate::storage;
+use crate::types::{DisputeResolution, DisputeResponses, TimingMode, Trade};
+
+/// Start tracking responses for a newly opened dispute raised by `raiser`.
+pub fn open(env: &Env, trade: &Trade, raiser: &Address) -> Result<(), ContractError> {
+    let window = storage::get_dispute_response_window(env);
+    if window == 0 {
+        return Ok(());
+    }
+    let deadline = clock::after(env, TimingMode::Timestamp, window)?;
+    storage::save_dispute_responses(
+        env,
+        trade.id,
+        &DisputeResponses {
+            deadline,
+            buyer_responded: *raiser == trade.buyer,
+            seller_responded: *raiser == trade.seller,
+        },
+    );
+    Ok(())
+}
+
+/// Mark `party` as having responded. No-op when responses aren't tracked.
+pub fn record(env: &Env, trade: &Trade, party: &Address) -> Result<(), ContractError> {
+    let mut responses = match storage::get_dispute_responses(env, trade.id) {
+        Some(r) => r,
+        None => return Ok(()),
+    };
+    if clock::now(env, TimingMode::Timestamp) >= responses.deadline {
+        return Err(ContractError::ResponseWindowClosed);
+    }
+    if *party == trade.buyer {
+        responses.buyer_responded = true;
+    } else {
+        responses.seller_responded = true;
+    }
+    storage::save_dispute_responses(env, trade.id, &responses);
+    Ok(())
+}
+
+/// Ruling in favour of the only party that responded in time.
+pub fn default_ruling(env: &Env, trade_id: u64) -> Result<DisputeResolution, ContractError> {
+    let responses = storage::get_dispute_responses(env, trade_id).ok_or(ContractError::NoDefaultVictor)?;
+    if clock::now(env, TimingMode::Timestamp) < responses.deadline {
+        return Err(ContractError::ResponseWindowOpen);
+    }
+    match (responses.buyer_responded, responses.seller_responded) {
+        (true, false) => Ok(DisputeResolution::ReleaseToBuyer),
+        (false, true) => Ok(DisputeResolution::ReleaseToSeller),
+        _ => Err(ContractError::NoDefaultVictor),
+    }
+}
@@ -0,0 +1,108 @@
+//! Optional charity donations taken from a trade's payout.
+//!
+//! # Design
+//! - The admin configures one charity address (`set_charity`).
+//! - When confirming receipt the buyer may choose a donation
+//!   (`confirm_receipt_with_donation`): round the seller payout down to a
+//!   multiple of a unit and donate the remainder, or donate a fixed share in
+//!   basis points. Either is capped at `MAX_DONATION_BPS` of the payout.
+//! - The donation leaves escrow when the payout does, including after a
+//!   release hold. It is tracked per currency and emitted on its own topic,
+//!   separate from platform fees.
+
+use soroban_sdk::{symbol_short, token, Address, Env, Symbol};
+
+use crate::errors::ContractError;
+use crate::events;
+use crate::transfer;
+use crate::types::{DonationChoice, Trade, MAX_DONATION_BPS};
+
+// ---------------------------------------------------------------------------
+// Storage
+// ---------------------------------------------------------------------------
+
+pub(crate) const DONATION_PREFIX: &str = "DV";
+const DONATED_PREFIX: &str = "DT";
+
+fn charity_key() -> Symbol {
+    symbol_short!("CHARITY")
+}
+
+pub fn set_charity(env: &Env, charity: &Option<Address>) {
+    match charity {
+        Some(c) => env.storage().instance().set(&charity_key(), c),
+        None => env.storage().instance().remove(&charity_key()),
+    }
+}
+
+pub fn get_charity(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&charity_key())
+}
+
+pub fn total_donated(env: &Env, currency: &Address) -> i128 {
+    env.storage().persistent().get(&(DONATED_PREFIX, currency.clone())).unwrap_or(0)
+}
+
+// ---------------------------------------------------------------------------
+// Donations
+// ---------------------------------------------------------------------------
+
+/// Amount `choice` donates out of `payout`.
+fn amount(choice: &DonationChoice, payout: i128) -> Result<i128, ContractError> {
+    let donation = match *choice {
+        DonationChoice::RoundDown(unit) => {
+            if unit <= 0 {
+                return Err(ContractError::InvalidDonation);
+            }
+            payout % unit
+        }
+        DonationChoice::Bps(bps) => payout.checked_mul(i128::from(bps)).ok_or(ContractError::Overflow)? / 10_000,
+    };
+    let cap = payout.checked_mul(i128::from(MAX_DONATION_BPS)).ok_or(ContractError::Overflow)? / 10_000;
+    if donation > cap {
+        return Err(ContractError::InvalidDonation);
+    }
+    Ok(donation)
+}
+
+/// Record the buyer's donation choice for `trade`, checked against `payout`.
+pub fn choose(env: &Env, trade: &Trade, choice: &DonationChoice, payout: i128) -> Result<(), ContractError> {
+    if get_charity(env).is_none() {
+        return Err(ContractError::NoCharity);
+    }
+    amount(choice, payout)?;
+    env.storage().persistent().set(&(DONATION_PREFIX, trade.id), choice);
+    Ok(())
+}
+
+/// Send the chosen donation out of `payout` to the charity and return what
+/// is left for the seller. Without a choice, or once the charity has been
+/// unset, the payout is untouched.
+pub fn take(env: &Env, trade: &Trade, payout: i128) -> Result<i128, ContractError> {
+    let key = (DONATION_PREFIX, trade.id);
+    let choice: DonationChoice = match env.storage().persistent().get(&key) {
+        Some(c) => c,
+        None => return Ok(payout),
+    };
+    env.storage().persistent().remove(&key);
+    let charity = match get_charity(env) {
+        Some(c) => c,
+        None => return Ok(payout),
+    };
+    let donation = amount(&choice, payout)?;
+    if donation == 0 {
+        return Ok(payout);
+    }
+    transfer::send(
+        &token::Client::new(env, &trade.currency),
+        &env.current_contract_address(),
+        &charity,
+        donation,
+    )?;
+    let total = total_donated(env, &trade.currency)
+        .checked_add(donation)
+        .ok_or(ContractError::Overflow)?;
+    env.storage().persistent().set(&(DONATED_PREFIX, trade.currency.clone()), &total);
+    events::emit_donated(env, trade.id, charity, trade.currency.clone(), donation);
+    Ok(payout - donation)
+}
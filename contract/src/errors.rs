@@ -123,4 +123,277 @@ pub enum ContractError {
     NotFollowing = 71,
     NoTrigger = 113,
     PriceConditionNotMet = 114,
+    // Arbitrator assignment errors (120–129)
+    /// Assigned arbitrator is not tagged with the category requested by the trade.
+    ArbitratorCategoryMismatch = 120,
+    /// More category tags supplied than `MAX_ARBITRATOR_CATEGORIES`.
+    TooManyCategories = 121,
+    /// A category was requested but the trade names no arbitrator.
+    CategoryRequiresArbitrator = 122,
+    /// Arbitrator already has the maximum number of open disputes.
+    ArbitratorCaseloadFull = 123,
+    /// The named arbitrator has not yet accepted the assignment.
+    AssignmentNotAccepted = 124,
+    /// The assignment has already been accepted.
+    AssignmentAlreadyAccepted = 125,
+    /// The acceptance grace period for this assignment has passed.
+    AssignmentExpired = 126,
+    // Emergency withdrawal errors (130–134)
+    /// An emergency withdrawal is already scheduled.
+    EmergencyWithdrawalPending = 130,
+    /// No emergency withdrawal has been initiated.
+    NoEmergencyWithdrawal = 131,
+    /// The mandatory emergency withdrawal delay has not elapsed.
+    EmergencyDelayNotElapsed = 132,
+    // Circuit breaker errors (135–139)
+    /// Trade creation is halted because the funding-volume breaker tripped.
+    CircuitBreakerTripped = 135,
+    /// Breaker window must be at least one ledger.
+    InvalidBreakerWindow = 136,
+    // Trade annotation errors (140–149)
+    /// Memo exceeds `MAX_MEMO_LEN` bytes.
+    MemoTooLong = 140,
+    /// Payee may not be the buyer or the escrow contract itself.
+    InvalidPayee = 141,
+    /// Payout shares must be non-zero and sum to exactly 10 000 bps.
+    InvalidPayoutSplit = 142,
+    /// More payout recipients than `MAX_PAYOUT_RECIPIENTS`.
+    TooManyPayoutRecipients = 143,
+    /// A trade already exists for this `external_ref`.
+    DuplicateExternalRef = 144,
+    // Refund request errors (145–149)
+    /// A refund request is already open for this trade.
+    RefundAlreadyRequested = 145,
+    /// No refund request is open for this trade.
+    NoRefundRequest = 146,
+    /// The seller's response window has not yet elapsed.
+    RefundWindowOpen = 147,
+    // Dispute re-open errors (150–154)
+    /// The re-open window for this resolution has closed.
+    ReopenWindowClosed = 150,
+    /// The resolution cannot execute until the re-open window closes.
+    ReopenWindowOpen = 151,
+    /// This dispute has already been re-opened once.
+    AlreadyReopened = 152,
+    /// No resolution is pending for this trade.
+    NoPendingResolution = 153,
+    // Supervised arbitration errors (155–159)
+    /// The resolution needs an admin countersignature before payout.
+    ResolutionAwaitingReview = 155,
+    /// The pending resolution is not awaiting admin review.
+    ResolutionNotUnderReview = 156,
+    // Funding deadline errors (160–164)
+    /// The trade's funding deadline has not yet passed.
+    FundingDeadlineNotReached = 160,
+    /// The trade's funding deadline has passed; it can only be expired.
+    FundingDeadlinePassed = 161,
+    // Storage TTL errors (165–169)
+    /// TTL threshold must not exceed the extension target, which must not
+    /// exceed the network's maximum entry TTL.
+    InvalidTtlConfig = 165,
+    // Fee schedule errors (170–179)
+    /// Trade amount is too small for the minimum fee to stay a minority share.
+    TradeTooSmallForFee = 170,
+    /// Minimum fee would exceed the fee cap.
+    InvalidFeeBounds = 171,
+    /// Promotional window must end after it starts and use a valid rate.
+    InvalidPromoWindow = 172,
+    // Order structure errors (180–189)
+    /// `quantity * unit_price` does not equal the trade amount.
+    QuantityMismatch = 180,
+    /// Line item amounts do not sum to the trade amount.
+    BundleItemsMismatch = 185,
+    /// Bundle is empty or exceeds `MAX_LINE_ITEMS`.
+    TooManyLineItems = 186,
+    /// No line item at the given index.
+    LineItemNotFound = 187,
+    /// Line item has already been settled or is not in the required state.
+    LineItemNotOpen = 188,
+    /// Bundle trades settle per item, not as a whole.
+    BundleRequiresItemSettlement = 189,
+    // Funding errors (190–199)
+    /// Buyer has not approved enough allowance for this contract.
+    InsufficientAllowance = 190,
+    /// Contribution would push the pooled total past the trade amount.
+    ContributionExceedsAmount = 191,
+    /// Trade is being funded by contributors; use `contribute`.
+    PooledFundingInProgress = 192,
+    /// Payer has no contribution on this trade.
+    NoContribution = 193,
+    // Streaming release errors (200–204)
+    /// Trade has no stream schedule.
+    StreamNotConfigured = 200,
+    /// Stream has already commenced.
+    StreamAlreadyStarted = 201,
+    /// Nothing has vested since the last claim.
+    NothingToClaim = 202,
+    /// Streamed trades settle through `claim_streamed` or a dispute.
+    StreamInProgress = 203,
+    /// Stream duration must be non-zero.
+    InvalidStreamDuration = 204,
+    // Release hold errors (205–206)
+    /// Hold window has not elapsed yet.
+    HoldWindowActive = 205,
+    // Rating errors (207–208)
+    /// Ratings must be between 1 and 5 stars.
+    InvalidRating = 207,
+    /// This party has already rated for this trade.
+    AlreadyRated = 208,
+    // Party validation errors (210–214)
+    /// Buyer and seller must be distinct, and the buyer may not receive seller proceeds.
+    InvalidParties = 210,
+    /// Arbitrator may not be the buyer or seller of the trade they arbitrate.
+    ArbitratorIsParty = 211,
+    /// Seller and buyer already have the maximum number of open trades together.
+    PairTradeLimitReached = 212,
+    /// Trade amount is above the threshold that requires an arbitrator.
+    ArbitratorRequired = 213,
+    // Deadline errors (215–219)
+    /// The trade's deadline for this stage has not yet passed.
+    DeadlineNotReached = 215,
+    /// Deadlines must lie in the future and run fund, complete, confirm.
+    InvalidDeadlines = 216,
+    // Fee withdrawal errors (220–224)
+    /// A fee withdrawal is already requested.
+    FeeWithdrawalPending = 220,
+    /// No fee withdrawal has been requested.
+    NoFeeWithdrawal = 221,
+    /// The fee withdrawal delay has not elapsed.
+    FeeWithdrawalDelayNotElapsed = 222,
+    /// Fee withdrawal delay is below `MIN_FEE_WITHDRAWAL_DELAY`.
+    InvalidFeeWithdrawalDelay = 223,
+    // Loyalty errors (225–229)
+    /// No loyalty programme is configured.
+    LoyaltyNotConfigured = 225,
+    /// Redemption rate must be non-zero and the discount cap at most 10_000 bps.
+    InvalidLoyaltyConfig = 226,
+    /// User does not hold enough points for this redemption.
+    InsufficientPoints = 227,
+    /// Redemption would push the held discount past the configured cap.
+    DiscountCapExceeded = 228,
+    // Attestation errors (230–234)
+    /// A party lacks a valid KYC credential from the attestation contract.
+    ComplianceCheckFailed = 230,
+    // Badge errors (235–239)
+    /// Track record does not yet qualify for a higher badge level.
+    BadgeNotEarned = 235,
+    // Payout assignment errors (240–244)
+    /// The seller's payout rights for this trade are assigned and can no longer change.
+    PayoutAssigned = 240,
+    // Basket errors (245–249)
+    /// Basket legs must be distinct non-primary tokens with non-zero amounts.
+    InvalidBasket = 245,
+    /// Basket trades must be funded by the buyer in one transfer set.
+    BasketNotPoolable = 246,
+    // Quote errors (250–254)
+    /// Quoted amount must be non-zero and the reference asset differ from the currency.
+    InvalidQuote = 250,
+    // Batch errors (255–259)
+    /// Batch exceeds `MAX_BATCH_RESOLUTIONS` items.
+    BatchTooLarge = 255,
+    // Arbitrator earnings errors (260–264)
+    /// Arbitrator has no earnings to withdraw.
+    NoArbitratorEarnings = 260,
+    /// Arbitrator fee rate exceeds the admin-set cap.
+    ArbitratorFeeTooHigh = 261,
+    // Dispute bond errors (265–269)
+    /// The dispute is waiting for both parties to post bonds.
+    DisputeBondsPending = 265,
+    /// Only one party posted a bond; the dispute defaults to them.
+    DisputeBondDefaulted = 266,
+    /// This dispute has no bond requirement, or it has not defaulted.
+    NoDisputeBond = 267,
+    /// Caller has already posted their bond for this dispute.
+    BondAlreadyPosted = 268,
+    /// The window for posting dispute bonds has closed.
+    BondWindowClosed = 269,
+    // Dispute response errors (270–274)
+    /// The dispute response window is still open.
+    ResponseWindowOpen = 270,
+    /// The dispute response window has closed.
+    ResponseWindowClosed = 271,
+    /// No single party is in default: both or neither responded, or the
+    /// dispute has no response window.
+    NoDefaultVictor = 272,
+    // Timing errors (275–279)
+    /// The timing mode can only be chosen before the first trade is created.
+    TimingModeLocked = 275,
+    // Settlement errors (280–284)
+    /// A payout of this trade is already in progress.
+    SettlementInProgress = 280,
+    /// The trade's token refused a transfer; nothing was moved.
+    TokenTransferFailed = 281,
+    /// The contract no longer holds the escrowed amount; the asset issuer
+    /// clawed it back.
+    EscrowClawedBack = 282,
+    // Trusted caller errors (285–289)
+    /// `create_trade_via` was called by a contract not on the allowlist.
+    CallerNotTrusted = 285,
+    /// Integrations-only mode is on; trades must come through a trusted caller.
+    DirectCreationDisabled = 286,
+    // Signed acceptance errors (290–294)
+    /// The buyer has not registered an ed25519 acceptance key.
+    NoAcceptanceKey = 290,
+    /// The acceptance nonce is not above the last one recorded for the trade.
+    StaleAcceptanceNonce = 291,
+    // Settlement batch errors (295–304)
+    /// No settlement batch with this id.
+    SettlementBatchNotFound = 295,
+    /// A batch needs at least one leaf and a non-zero claim window.
+    InvalidSettlementBatch = 296,
+    /// The leaf does not hash up to the batch root, or exceeds its total.
+    InvalidMerkleProof = 297,
+    /// This leaf of the batch has already been paid out.
+    BatchLeafClaimed = 298,
+    /// The claim window has closed or the batch was reclaimed.
+    SettlementBatchClosed = 299,
+    /// The operator cannot reclaim before the claim window closes.
+    SettlementBatchOpen = 300,
+    // Pause errors (305–309)
+    /// This capability is paused by `set_paused_functions`.
+    FunctionPaused = 305,
+    /// The pause flags include unknown bits.
+    InvalidPauseFlags = 306,
+    // Trade freeze errors (310–314)
+    /// The trade is under a compliance freeze.
+    TradeFrozen = 310,
+    /// The trade is not frozen.
+    TradeNotFrozen = 311,
+    // Regulated asset errors (315–319)
+    /// The asset issuer has not authorized an account involved in the transfer.
+    AssetAuthorizationRequired = 315,
+    /// Nothing is held for this recipient in this token.
+    NoHeldPayout = 316,
+    // Hashed trade errors (320–324)
+    /// No hashed trade with this id.
+    HashedTradeNotFound = 320,
+    /// The details do not match the trade's commitment or amount bucket.
+    CommitmentMismatch = 321,
+    // Payout swap errors (325–329)
+    /// The swap adapter is not whitelisted.
+    SwapAdapterNotAllowed = 325,
+    /// A payout swap must target a token other than the trade's currency.
+    InvalidPayoutSwap = 326,
+    // Donation errors (330–334)
+    /// No charity is configured.
+    NoCharity = 330,
+    /// The donation unit is not positive or the donation exceeds the cap.
+    InvalidDonation = 331,
+    // Partner errors (335–339)
+    /// No partner is registered under this id.
+    PartnerNotFound = 335,
+    /// The partner's fee exceeds 100%.
+    InvalidPartnerFee = 336,
+    // Referral errors (340–344)
+    /// The address is not a registered referrer.
+    ReferrerNotFound = 340,
+    /// The referrer has not been approved, or has been suspended.
+    ReferrerNotApproved = 341,
+    /// The referral share exceeds `MAX_REFERRAL_SHARE_BPS`.
+    InvalidReferralShare = 342,
+    /// The buyer is already attributed to a referrer.
+    AlreadyReferred = 343,
+    // Query errors (345–349)
+    /// `to_id` is before `from_id` or the range is wider than `MAX_TRADE_RANGE`.
+    InvalidTradeRange = 345,
 }
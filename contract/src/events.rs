@@ -12,7 +12,7 @@ pub fn emit_trade_created(
     _trade_id: u64,
     _seller: Address,
     _buyer: Address,
-    _amount: u64,
+    _amount: i128,
     _currency: Address,
 ) {
 }
@@ -21,12 +21,12 @@ pub fn emit_compliance_passed(
     _trade_id: u64,
     _seller: Address,
     _buyer: Address,
-    _amount: u64,
+    _amount: i128,
 ) {
 }
 pub fn emit_trade_funded(_env: &Env, _trade_id: u64) {}
 pub fn emit_trade_completed(_env: &Env, _trade_id: u64) {}
-pub fn emit_trade_confirmed(_env: &Env, _trade_id: u64, _payout: u64, _fee: u64) {}
+pub fn emit_trade_confirmed(_env: &Env, _trade_id: u64, _payout: i128, _fee: i128) {}
 pub fn emit_dispute_raised(_env: &Env, _trade_id: u64, _raised_by: Address) {}
 pub fn emit_dispute_resolved(
     _env: &Env,
@@ -38,13 +38,13 @@ pub fn emit_dispute_resolved(
 pub fn emit_partial_resolved(
     _env: &Env,
     _trade_id: u64,
-    _buyer_amount: u64,
-    _seller_amount: u64,
-    _fee: u64,
+    _buyer_amount: i128,
+    _seller_amount: i128,
+    _fee: i128,
 ) {
 }
 pub fn emit_trade_cancelled(_env: &Env, _trade_id: u64) {}
-pub fn emit_fees_withdrawn(_env: &Env, _amount: u64, _to: Address) {}
+pub fn emit_fees_withdrawn(_env: &Env, _currency: Address, _amount: i128, _to: Address) {}
 pub fn emit_paused(_env: &Env, _admin: Address) {}
 pub fn emit_unpaused(_env: &Env, _admin: Address) {}
 pub fn emit_bridge_oracle_set(_env: &Env, _oracle: Address) {}
@@ -57,18 +57,18 @@ pub fn emit_insurance_purchased(
     _env: &Env,
     _trade_id: u64,
     _provider: Address,
-    _premium: u64,
-    _coverage: u64,
+    _premium: i128,
+    _coverage: i128,
 ) {
 }
-pub fn emit_insurance_claimed(_env: &Env, _trade_id: u64, _payout: u64, _recipient: Address) {}
+pub fn emit_insurance_claimed(_env: &Env, _trade_id: u64, _payout: i128, _recipient: Address) {}
 pub fn emit_migrated(_env: &Env, _from_version: u32, _to_version: u32) {}
 /// Current event schema version. Bump when payload fields change.
-pub const EVENT_VERSION: u32 = 2;
+pub const EVENT_VERSION: u32 = 4;
 
-use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol};
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, IntoVal, String, Symbol, Val, Vec};
 
-use crate::types::{DisputeResolution, SubscriptionTier, UserTier};
+use crate::types::{DisputeResolution, ReferrerStatus, SubscriptionTier, TradeStatus, UserTier};
 
 // ---------------------------------------------------------------------------
 // Event categories (used as the first topic for indexer filtering)
@@ -88,53 +88,167 @@ fn cat_sys()   -> Symbol { symbol_short!("sys") }
 fn cat_ins()   -> Symbol { symbol_short!("ins") }
 fn cat_brg()   -> Symbol { symbol_short!("brg") }
 
+/// Ledger close time and sequence stamped into trade lifecycle payloads, so
+/// indexers reading only the event stream can order and time trades.
+fn stamp(env: &Env) -> (u64, u32) {
+    (env.ledger().timestamp(), env.ledger().sequence())
+}
+
 // ---------------------------------------------------------------------------
 // Structured event payloads
 // ---------------------------------------------------------------------------
 
 #[contracttype] #[derive(Clone, Debug)]
-pub struct EvTradeCreated   { pub v: u32, pub trade_id: u64, pub seller: Address, pub buyer: Address, pub amount: u64, pub currency: Address }
+pub struct EvTradeCreated   { pub v: u32, pub ts: u64, pub seq: u32, pub trade_id: u64, pub seller: Address, pub buyer: Address, pub amount: i128, pub currency: Address }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvTradeMemo      { pub v: u32, pub trade_id: u64, pub memo: String }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvTradeAccepted  { pub v: u32, pub trade_id: u64, pub buyer: Address, pub terms_hash: BytesN<32>, pub nonce: u64 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvTermsAgreed    { pub v: u32, pub trade_id: u64, pub seller: Address, pub buyer: Address, pub terms_hash: BytesN<32> }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvTradeExpired   { pub v: u32, pub ts: u64, pub seq: u32, pub trade_id: u64 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvFeeApplied     { pub v: u32, pub trade_id: u64, pub fee_bps: u32, pub effective_bps: u32, pub promo: bool, pub fee: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvPromoSet       { pub v: u32, pub start_ledger: u32, pub end_ledger: u32, pub fee_bps: u32 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvFeeCapSet      { pub v: u32, pub cap: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvMinFeeSet      { pub v: u32, pub min: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvFeeScheduled   { pub v: u32, pub fee_bps: u32, pub effective_from: u32 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvTokenFeeSet    { pub v: u32, pub token: Address, pub fee_bps: u32 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvProtoShareSet  { pub v: u32, pub recipient: Address, pub bps: u32 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvProtoFeePaid   { pub v: u32, pub currency: Address, pub recipient: Address, pub amount: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvOrderSet       { pub v: u32, pub trade_id: u64, pub quantity: u32, pub unit_price: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvItemReleased   { pub v: u32, pub trade_id: u64, pub index: u32, pub payout: i128, pub fee: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvItemDisputed   { pub v: u32, pub trade_id: u64, pub index: u32, pub raised_by: Address }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvItemResolved   { pub v: u32, pub trade_id: u64, pub index: u32, pub to_buyer: bool, pub payout: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvContributed    { pub v: u32, pub trade_id: u64, pub payer: Address, pub amount: i128, pub total: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvContribOut     { pub v: u32, pub trade_id: u64, pub payer: Address, pub amount: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvStreamStarted  { pub v: u32, pub trade_id: u64, pub started_at: u64, pub ends_at: u64 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvStreamClaimed  { pub v: u32, pub trade_id: u64, pub amount: i128, pub claimed: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvReleaseHeld    { pub v: u32, pub trade_id: u64, pub release_at: u64 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvPartyRated     { pub v: u32, pub trade_id: u64, pub rater: Address, pub ratee: Address, pub stars: u32 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvDeliveryProof  { pub v: u32, pub trade_id: u64, pub proof_hash: BytesN<32> }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvMsgAnchored    { pub v: u32, pub trade_id: u64, pub author: Address, pub msg_hash: BytesN<32> }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvStatusChanged  { pub v: u32, pub ts: u64, pub seq: u32, pub trade_id: u64, pub old_status: TradeStatus, pub new_status: TradeStatus, pub actor: Address }
 #[contracttype] #[derive(Clone, Debug)]
-pub struct EvTradeFunded    { pub v: u32, pub trade_id: u64 }
+pub struct EvPayeeSet       { pub v: u32, pub trade_id: u64, pub payee: Address }
 #[contracttype] #[derive(Clone, Debug)]
-pub struct EvTradeCompleted { pub v: u32, pub trade_id: u64 }
+pub struct EvBuyerChanged   { pub v: u32, pub trade_id: u64, pub old_buyer: Address, pub new_buyer: Address }
 #[contracttype] #[derive(Clone, Debug)]
-pub struct EvTradeConfirmed { pub v: u32, pub trade_id: u64, pub payout: u64, pub fee: u64 }
+pub struct EvPayoutAssigned { pub v: u32, pub trade_id: u64, pub assignee: Address }
 #[contracttype] #[derive(Clone, Debug)]
-pub struct EvTradeCancelled { pub v: u32, pub trade_id: u64 }
+pub struct EvBasketSet      { pub v: u32, pub trade_id: u64, pub legs: u32 }
 #[contracttype] #[derive(Clone, Debug)]
-pub struct EvTimeReleased   { pub v: u32, pub trade_id: u64, pub seller: Address, pub payout: u64 }
+pub struct EvQuoteSet       { pub v: u32, pub trade_id: u64, pub reference: Address, pub quoted_amount: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvQuoteFixed     { pub v: u32, pub trade_id: u64, pub price: i128, pub decimals: u32, pub amount: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvPayoutSplitSet { pub v: u32, pub trade_id: u64, pub recipients: u32 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvTradeFunded    { pub v: u32, pub ts: u64, pub seq: u32, pub trade_id: u64 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvTradeCompleted { pub v: u32, pub ts: u64, pub seq: u32, pub trade_id: u64 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvTradeConfirmed { pub v: u32, pub ts: u64, pub seq: u32, pub trade_id: u64, pub payout: i128, pub fee: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvTradeCancelled { pub v: u32, pub ts: u64, pub seq: u32, pub trade_id: u64 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvTimeReleased   { pub v: u32, pub trade_id: u64, pub seller: Address, pub payout: i128 }
 #[contracttype] #[derive(Clone, Debug)]
 pub struct EvMetaUpdated    { pub v: u32, pub trade_id: u64 }
 
 #[contracttype] #[derive(Clone, Debug)]
-pub struct EvDisputeRaised  { pub v: u32, pub trade_id: u64, pub raised_by: Address }
+pub struct EvDisputeRaised  { pub v: u32, pub ts: u64, pub seq: u32, pub trade_id: u64, pub raised_by: Address }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvResolutionPending { pub v: u32, pub trade_id: u64, pub resolution: DisputeResolution, pub executable_at: u64 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvResolutionReview { pub v: u32, pub trade_id: u64, pub admin: Address }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvEvidence       { pub v: u32, pub trade_id: u64, pub party: Address, pub evidence_hash: BytesN<32> }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvBondPosted     { pub v: u32, pub trade_id: u64, pub party: Address, pub amount: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvDisputeReopened { pub v: u32, pub trade_id: u64, pub reopened_by: Address, pub evidence_hash: BytesN<32>, pub bond: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvRefundRequested { pub v: u32, pub trade_id: u64, pub reason_hash: BytesN<32>, pub escalate_after: u64 }
 #[contracttype] #[derive(Clone, Debug)]
-pub struct EvDisputeResolved { pub v: u32, pub trade_id: u64, pub resolution: DisputeResolution, pub recipient: Address }
+pub struct EvTradeRefunded   { pub v: u32, pub ts: u64, pub seq: u32, pub trade_id: u64, pub amount: i128, pub fee: i128 }
 #[contracttype] #[derive(Clone, Debug)]
-pub struct EvPartialResolved { pub v: u32, pub trade_id: u64, pub buyer_amount: u64, pub seller_amount: u64, pub fee: u64 }
+pub struct EvRefundApproved  { pub v: u32, pub trade_id: u64, pub amount: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvDisputeResolved { pub v: u32, pub ts: u64, pub seq: u32, pub trade_id: u64, pub resolution: DisputeResolution, pub recipient: Address, pub reason_hash: BytesN<32> }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvPartialResolved { pub v: u32, pub ts: u64, pub seq: u32, pub trade_id: u64, pub buyer_amount: i128, pub seller_amount: i128, pub fee: i128, pub reason_hash: BytesN<32> }
 
 #[contracttype] #[derive(Clone, Debug)]
 pub struct EvArbRegistered  { pub v: u32, pub arbitrator: Address }
 #[contracttype] #[derive(Clone, Debug)]
+pub struct EvArbEarned      { pub v: u32, pub arbitrator: Address, pub trade_id: u64, pub amount: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvArbWithdrawn   { pub v: u32, pub arbitrator: Address, pub to: Address, pub amount: i128 }
+#[contracttype] #[derive(Clone, Debug)]
 pub struct EvArbRemoved     { pub v: u32, pub arbitrator: Address }
 #[contracttype] #[derive(Clone, Debug)]
 pub struct EvArbRated       { pub v: u32, pub arbitrator: Address, pub trade_id: u64, pub rater: Address, pub stars: u32 }
 #[contracttype] #[derive(Clone, Debug)]
 pub struct EvArbRepUpdated  { pub v: u32, pub arbitrator: Address, pub resolved: u32, pub rating_sum: u32, pub rating_count: u32 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvArbCategories  { pub v: u32, pub arbitrator: Address, pub categories: Vec<Symbol> }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvCaseloadCapSet { pub v: u32, pub cap: u32 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvAssignmentAccepted { pub v: u32, pub trade_id: u64, pub arbitrator: Address }
 
 #[contracttype] #[derive(Clone, Debug)]
 pub struct EvFeeUpdated     { pub v: u32, pub fee_bps: u32 }
 #[contracttype] #[derive(Clone, Debug)]
-pub struct EvFeesWithdrawn  { pub v: u32, pub amount: u64, pub to: Address }
+pub struct EvFeesWithdrawn  { pub v: u32, pub currency: Address, pub amount: i128, pub to: Address }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvFeeBurned      { pub v: u32, pub currency: Address, pub amount: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvKeeperPaid     { pub v: u32, pub trade_id: u64, pub keeper: Address, pub currency: Address, pub amount: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvFeeForwarded   { pub v: u32, pub currency: Address, pub treasury: Address, pub amount: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvFeeFwdFailed   { pub v: u32, pub currency: Address, pub treasury: Address, pub amount: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvFeeWdRequested { pub v: u32, pub currency: Address, pub to: Address, pub amount: i128, pub executable_at: u64 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvFeeWdCancelled { pub v: u32, pub currency: Address, pub to: Address, pub amount: i128 }
 #[contracttype] #[derive(Clone, Debug)]
-pub struct EvFeesDistributed { pub v: u32, pub to: Address, pub amount: u64 }
+pub struct EvFeesDistributed { pub v: u32, pub to: Address, pub amount: i128 }
 #[contracttype] #[derive(Clone, Debug)]
 pub struct EvCustomFeeSet   { pub v: u32, pub user: Address, pub fee_bps: u32 }
 
 #[contracttype] #[derive(Clone, Debug)]
 pub struct EvTierUpgraded   { pub v: u32, pub user: Address, pub tier: UserTier }
 #[contracttype] #[derive(Clone, Debug)]
+pub struct EvBadgeChanged   { pub v: u32, pub user: Address, pub level: u32 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvPointsEarned   { pub v: u32, pub user: Address, pub earned: u64, pub balance: u64 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvPointsRedeemed { pub v: u32, pub user: Address, pub spent: u64, pub discount_bps: u32 }
+#[contracttype] #[derive(Clone, Debug)]
 pub struct EvTierDowngraded { pub v: u32, pub user: Address, pub tier: UserTier }
 #[contracttype] #[derive(Clone, Debug)]
 pub struct EvTierConfigUpdated { pub v: u32 }
@@ -191,11 +305,23 @@ pub struct EvPaused           { pub v: u32, pub admin: Address }
 #[contracttype] #[derive(Clone, Debug)]
 pub struct EvUnpaused         { pub v: u32, pub admin: Address }
 #[contracttype] #[derive(Clone, Debug)]
-pub struct EvEmergencyWithdraw { pub v: u32, pub to: Address, pub amount: u64 }
+pub struct EvEmergencyWithdraw { pub v: u32, pub to: Address, pub amount: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvEmergencyInit    { pub v: u32, pub to: Address, pub executable_at: u64 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvEmergencyCancel  { pub v: u32, pub to: Address }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvVolumeAlert      { pub v: u32, pub currency: Address, pub volume: i128, pub threshold: i128, pub window_start: u32 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvBreakerReset     { pub v: u32, pub admin: Address }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvSolvencyWarning  { pub v: u32, pub currency: Address, pub balance: i128, pub liabilities: i128 }
 #[contracttype] #[derive(Clone, Debug)]
 pub struct EvUpgraded         { pub v: u32, pub new_version: u32 }
 #[contracttype] #[derive(Clone, Debug)]
 pub struct EvMigrated         { pub v: u32, pub from_version: u32, pub to_version: u32 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvTradeMigrated    { pub v: u32, pub trade_id: u64, pub to_version: u32 }
 
 #[contracttype] #[derive(Clone, Debug)]
 pub struct EvPrivacySet        { pub v: u32, pub trade_id: u64 }
@@ -218,16 +344,95 @@ pub struct EvInsProviderRegistered { pub v: u32, pub provider: Address }
 #[contracttype] #[derive(Clone, Debug)]
 pub struct EvInsProviderRemoved    { pub v: u32, pub provider: Address }
 #[contracttype] #[derive(Clone, Debug)]
-pub struct EvInsPurchased          { pub v: u32, pub trade_id: u64, pub provider: Address, pub premium: u64, pub coverage: u64 }
+pub struct EvInsPurchased          { pub v: u32, pub trade_id: u64, pub provider: Address, pub premium: i128, pub coverage: i128 }
 #[contracttype] #[derive(Clone, Debug)]
-pub struct EvInsClaimed            { pub v: u32, pub trade_id: u64, pub payout: u64, pub recipient: Address }
+pub struct EvInsClaimed            { pub v: u32, pub trade_id: u64, pub payout: i128, pub recipient: Address }
 
 // ---------------------------------------------------------------------------
-// Emit helpers — topic: (category, event_name)
+// Emit helpers — topic: (category, event_name[, trade_id[, party]])
+//
+// Trade-scoped events carry the trade id as the third topic and, where one
+// party drives the event, that address as the fourth, so RPC filters can
+// select by trade or user without scanning every event.
 // ---------------------------------------------------------------------------
 
-pub fn emit_trade_created(env: &Env, trade_id: u64, seller: Address, buyer: Address, amount: u64, currency: Address) {
-    env.events().publish((cat_trade(), symbol_short!("created")), EvTradeCreated { v: EVENT_VERSION, trade_id, seller, buyer, amount, currency });
+pub fn emit_trade_created(env: &Env, trade_id: u64, seller: Address, buyer: Address, amount: i128, currency: Address) {
+    let (ts, seq) = stamp(env);
+    env.events().publish((cat_trade(), symbol_short!("created"), trade_id, seller.clone()), EvTradeCreated { v: EVENT_VERSION, ts, seq, trade_id, seller, buyer, amount, currency });
+}
+pub fn emit_status_changed(env: &Env, trade_id: u64, old_status: TradeStatus, new_status: TradeStatus, actor: Address) {
+    let (ts, seq) = stamp(env);
+    env.events().publish((cat_trade(), symbol_short!("status"), trade_id, actor.clone()), EvStatusChanged { v: EVENT_VERSION, ts, seq, trade_id, old_status, new_status, actor });
+}
+pub fn emit_trade_expired(env: &Env, trade_id: u64) {
+    let (ts, seq) = stamp(env);
+    env.events().publish((cat_trade(), symbol_short!("expired"), trade_id), EvTradeExpired { v: EVENT_VERSION, ts, seq, trade_id });
+}
+pub fn emit_order_set(env: &Env, trade_id: u64, quantity: u32, unit_price: i128) {
+    env.events().publish((cat_trade(), symbol_short!("order_set"), trade_id), EvOrderSet { v: EVENT_VERSION, trade_id, quantity, unit_price });
+}
+pub fn emit_item_released(env: &Env, trade_id: u64, index: u32, payout: i128, fee: i128) {
+    env.events().publish((cat_trade(), symbol_short!("item_rel"), trade_id), EvItemReleased { v: EVENT_VERSION, trade_id, index, payout, fee });
+}
+pub fn emit_item_disputed(env: &Env, trade_id: u64, index: u32, raised_by: Address) {
+    env.events().publish((cat_trade(), symbol_short!("item_disp"), trade_id, raised_by.clone()), EvItemDisputed { v: EVENT_VERSION, trade_id, index, raised_by });
+}
+pub fn emit_item_resolved(env: &Env, trade_id: u64, index: u32, to_buyer: bool, payout: i128) {
+    env.events().publish((cat_trade(), symbol_short!("item_res"), trade_id), EvItemResolved { v: EVENT_VERSION, trade_id, index, to_buyer, payout });
+}
+pub fn emit_contributed(env: &Env, trade_id: u64, payer: Address, amount: i128, total: i128) {
+    env.events().publish((cat_trade(), symbol_short!("contrib"), trade_id, payer.clone()), EvContributed { v: EVENT_VERSION, trade_id, payer, amount, total });
+}
+pub fn emit_contribution_returned(env: &Env, trade_id: u64, payer: Address, amount: i128) {
+    env.events().publish((cat_trade(), symbol_short!("contr_out"), trade_id, payer.clone()), EvContribOut { v: EVENT_VERSION, trade_id, payer, amount });
+}
+pub fn emit_stream_started(env: &Env, trade_id: u64, started_at: u64, ends_at: u64) {
+    env.events().publish((cat_trade(), symbol_short!("strm_go"), trade_id), EvStreamStarted { v: EVENT_VERSION, trade_id, started_at, ends_at });
+}
+pub fn emit_stream_claimed(env: &Env, trade_id: u64, amount: i128, claimed: i128) {
+    env.events().publish((cat_trade(), symbol_short!("strm_clm"), trade_id), EvStreamClaimed { v: EVENT_VERSION, trade_id, amount, claimed });
+}
+pub fn emit_release_held(env: &Env, trade_id: u64, release_at: u64) {
+    env.events().publish((cat_trade(), symbol_short!("held"), trade_id), EvReleaseHeld { v: EVENT_VERSION, trade_id, release_at });
+}
+pub fn emit_party_rated(env: &Env, trade_id: u64, rater: Address, ratee: Address, stars: u32) {
+    env.events().publish((cat_trade(), symbol_short!("rated"), trade_id, rater.clone()), EvPartyRated { v: EVENT_VERSION, trade_id, rater, ratee, stars });
+}
+pub fn emit_message_anchored(env: &Env, trade_id: u64, author: Address, msg_hash: BytesN<32>) {
+    env.events().publish((cat_trade(), symbol_short!("msg_anch"), trade_id), EvMsgAnchored { v: EVENT_VERSION, trade_id, author, msg_hash });
+}
+pub fn emit_delivery_proof(env: &Env, trade_id: u64, proof_hash: BytesN<32>) {
+    env.events().publish((cat_trade(), symbol_short!("dlv_proof"), trade_id), EvDeliveryProof { v: EVENT_VERSION, trade_id, proof_hash });
+}
+pub fn emit_payee_set(env: &Env, trade_id: u64, payee: Address) {
+    env.events().publish((cat_trade(), symbol_short!("payee_set"), trade_id, payee.clone()), EvPayeeSet { v: EVENT_VERSION, trade_id, payee });
+}
+pub fn emit_buyer_transferred(env: &Env, trade_id: u64, old_buyer: Address, new_buyer: Address) {
+    env.events().publish((cat_trade(), symbol_short!("buyer_chg"), trade_id, new_buyer.clone()), EvBuyerChanged { v: EVENT_VERSION, trade_id, old_buyer, new_buyer });
+}
+pub fn emit_payout_assigned(env: &Env, trade_id: u64, assignee: Address) {
+    env.events().publish((cat_trade(), symbol_short!("pay_asgn"), trade_id, assignee.clone()), EvPayoutAssigned { v: EVENT_VERSION, trade_id, assignee });
+}
+pub fn emit_basket_set(env: &Env, trade_id: u64, legs: u32) {
+    env.events().publish((cat_trade(), symbol_short!("basket"), trade_id), EvBasketSet { v: EVENT_VERSION, trade_id, legs });
+}
+pub fn emit_quote_set(env: &Env, trade_id: u64, reference: Address, quoted_amount: i128) {
+    env.events().publish((cat_trade(), symbol_short!("quote_set"), trade_id), EvQuoteSet { v: EVENT_VERSION, trade_id, reference, quoted_amount });
+}
+pub fn emit_quote_fixed(env: &Env, trade_id: u64, price: i128, decimals: u32, amount: i128) {
+    env.events().publish((cat_trade(), symbol_short!("quote_fix"), trade_id), EvQuoteFixed { v: EVENT_VERSION, trade_id, price, decimals, amount });
+}
+pub fn emit_payout_split_set(env: &Env, trade_id: u64, recipients: u32) {
+    env.events().publish((cat_trade(), symbol_short!("split_set"), trade_id), EvPayoutSplitSet { v: EVENT_VERSION, trade_id, recipients });
+}
+pub fn emit_trade_memo(env: &Env, trade_id: u64, memo: String) {
+    env.events().publish((cat_trade(), symbol_short!("memo"), trade_id), EvTradeMemo { v: EVENT_VERSION, trade_id, memo });
+}
+pub fn emit_trade_accepted(env: &Env, trade_id: u64, buyer: Address, terms_hash: BytesN<32>, nonce: u64) {
+    env.events().publish((cat_trade(), symbol_short!("accepted"), trade_id), EvTradeAccepted { v: EVENT_VERSION, trade_id, buyer, terms_hash, nonce });
+}
+pub fn emit_terms_agreed(env: &Env, trade_id: u64, seller: Address, buyer: Address, terms_hash: BytesN<32>) {
+    env.events().publish((cat_trade(), symbol_short!("terms"), trade_id), EvTermsAgreed { v: EVENT_VERSION, trade_id, seller, buyer, terms_hash });
 }
 
 // ---------------------------------------------------------------------------
@@ -239,9 +444,13 @@ fn cat_compliance() -> Symbol { symbol_short!("compl") }
 #[contracttype] #[derive(Clone, Debug)]
 pub struct EvComplianceFailed { pub v: u32, pub user: Address, pub reason: String }
 #[contracttype] #[derive(Clone, Debug)]
-pub struct EvCompliancePassed { pub v: u32, pub trade_id: u64, pub seller: Address, pub buyer: Address, pub amount: u64 }
+pub struct EvCompliancePassed { pub v: u32, pub trade_id: u64, pub seller: Address, pub buyer: Address, pub amount: i128 }
 #[contracttype] #[derive(Clone, Debug)]
 pub struct EvComplianceUpdated { pub v: u32, pub user: Address }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvTradeFrozen { pub v: u32, pub trade_id: u64, pub reason_code: u32, pub admin: Address }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvTradeUnfrozen { pub v: u32, pub trade_id: u64, pub admin: Address }
 
 pub fn emit_compliance_failed(env: &Env, user: Address, reason: &String) {
     env.events().publish(
@@ -250,13 +459,27 @@ pub fn emit_compliance_failed(env: &Env, user: Address, reason: &String) {
     );
 }
 
-pub fn emit_compliance_passed(env: &Env, trade_id: u64, seller: Address, buyer: Address, amount: u64) {
+pub fn emit_compliance_passed(env: &Env, trade_id: u64, seller: Address, buyer: Address, amount: i128) {
     env.events().publish(
-        (cat_compliance(), symbol_short!("passed")),
+        (cat_compliance(), symbol_short!("passed"), trade_id, seller.clone()),
         EvCompliancePassed { v: EVENT_VERSION, trade_id, seller, buyer, amount },
     );
 }
 
+pub fn emit_trade_frozen(env: &Env, trade_id: u64, reason_code: u32, admin: Address) {
+    env.events().publish(
+        (cat_compliance(), symbol_short!("frozen"), trade_id),
+        EvTradeFrozen { v: EVENT_VERSION, trade_id, reason_code, admin },
+    );
+}
+
+pub fn emit_trade_unfrozen(env: &Env, trade_id: u64, admin: Address) {
+    env.events().publish(
+        (cat_compliance(), symbol_short!("unfrozen"), trade_id),
+        EvTradeUnfrozen { v: EVENT_VERSION, trade_id, admin },
+    );
+}
+
 pub fn emit_compliance_updated(env: &Env, user: Address) {
     env.events().publish(
         (cat_compliance(), symbol_short!("updated")),
@@ -266,65 +489,172 @@ pub fn emit_compliance_failed(env: &Env, user: Address, reason: &soroban_sdk::St
     env.events().publish((cat_sys(), symbol_short!("compl_fail")), EvComplianceFailed { v: EVENT_VERSION, user, reason: reason.clone() });
 }
 
-pub fn emit_compliance_passed(env: &Env, trade_id: u64, seller: Address, buyer: Address, amount: u64) {
-    env.events().publish((cat_sys(), symbol_short!("compl_pass")), EvCompliancePassed { v: EVENT_VERSION, trade_id, seller, buyer, amount });
+pub fn emit_compliance_passed(env: &Env, trade_id: u64, seller: Address, buyer: Address, amount: i128) {
+    env.events().publish((cat_sys(), symbol_short!("compl_pass"), trade_id, seller.clone()), EvCompliancePassed { v: EVENT_VERSION, trade_id, seller, buyer, amount });
 }
 
 pub fn emit_trade_funded(env: &Env, trade_id: u64) {
-    env.events().publish((cat_trade(), symbol_short!("funded")), EvTradeFunded { v: EVENT_VERSION, trade_id });
+    let (ts, seq) = stamp(env);
+    env.events().publish((cat_trade(), symbol_short!("funded"), trade_id), EvTradeFunded { v: EVENT_VERSION, ts, seq, trade_id });
 }
 pub fn emit_trade_completed(env: &Env, trade_id: u64) {
-    env.events().publish((cat_trade(), symbol_short!("complete")), EvTradeCompleted { v: EVENT_VERSION, trade_id });
+    let (ts, seq) = stamp(env);
+    env.events().publish((cat_trade(), symbol_short!("complete"), trade_id), EvTradeCompleted { v: EVENT_VERSION, ts, seq, trade_id });
 }
-pub fn emit_trade_confirmed(env: &Env, trade_id: u64, payout: u64, fee: u64) {
-    env.events().publish((cat_trade(), symbol_short!("confirm")), EvTradeConfirmed { v: EVENT_VERSION, trade_id, payout, fee });
+pub fn emit_trade_confirmed(env: &Env, trade_id: u64, payout: i128, fee: i128) {
+    let (ts, seq) = stamp(env);
+    env.events().publish((cat_trade(), symbol_short!("confirm"), trade_id), EvTradeConfirmed { v: EVENT_VERSION, ts, seq, trade_id, payout, fee });
 }
 pub fn emit_trade_cancelled(env: &Env, trade_id: u64) {
-    env.events().publish((cat_trade(), symbol_short!("cancel")), EvTradeCancelled { v: EVENT_VERSION, trade_id });
+    let (ts, seq) = stamp(env);
+    env.events().publish((cat_trade(), symbol_short!("cancel"), trade_id), EvTradeCancelled { v: EVENT_VERSION, ts, seq, trade_id });
 }
-pub fn emit_time_released(env: &Env, trade_id: u64, seller: Address, payout: u64) {
-    env.events().publish((cat_trade(), symbol_short!("time_rel")), EvTimeReleased { v: EVENT_VERSION, trade_id, seller, payout });
+pub fn emit_time_released(env: &Env, trade_id: u64, seller: Address, payout: i128) {
+    env.events().publish((cat_trade(), symbol_short!("time_rel"), trade_id, seller.clone()), EvTimeReleased { v: EVENT_VERSION, trade_id, seller, payout });
 }
 pub fn emit_metadata_updated(env: &Env, trade_id: u64) {
-    env.events().publish((cat_trade(), symbol_short!("meta_upd")), EvMetaUpdated { v: EVENT_VERSION, trade_id });
+    env.events().publish((cat_trade(), symbol_short!("meta_upd"), trade_id), EvMetaUpdated { v: EVENT_VERSION, trade_id });
 }
 
 pub fn emit_dispute_raised(env: &Env, trade_id: u64, raised_by: Address) {
-    env.events().publish((cat_trade(), symbol_short!("dispute")), EvDisputeRaised { v: EVENT_VERSION, trade_id, raised_by });
+    let (ts, seq) = stamp(env);
+    env.events().publish((cat_trade(), symbol_short!("dispute"), trade_id, raised_by.clone()), EvDisputeRaised { v: EVENT_VERSION, ts, seq, trade_id, raised_by });
+}
+pub fn emit_resolution_pending(env: &Env, trade_id: u64, resolution: DisputeResolution, executable_at: u64) {
+    env.events().publish((cat_trade(), symbol_short!("res_pend"), trade_id), EvResolutionPending { v: EVENT_VERSION, trade_id, resolution, executable_at });
+}
+pub fn emit_resolution_approved(env: &Env, trade_id: u64, admin: Address) {
+    env.events().publish((cat_trade(), symbol_short!("res_appr"), trade_id, admin.clone()), EvResolutionReview { v: EVENT_VERSION, trade_id, admin });
+}
+pub fn emit_resolution_vetoed(env: &Env, trade_id: u64, admin: Address) {
+    env.events().publish((cat_trade(), symbol_short!("res_veto"), trade_id, admin.clone()), EvResolutionReview { v: EVENT_VERSION, trade_id, admin });
+}
+pub fn emit_evidence_submitted(env: &Env, trade_id: u64, party: Address, evidence_hash: BytesN<32>) {
+    env.events().publish((cat_trade(), symbol_short!("evidence"), trade_id, party.clone()), EvEvidence { v: EVENT_VERSION, trade_id, party, evidence_hash });
+}
+pub fn emit_dispute_bond_posted(env: &Env, trade_id: u64, party: Address, amount: i128) {
+    env.events().publish((cat_trade(), symbol_short!("bond_post"), trade_id, party.clone()), EvBondPosted { v: EVENT_VERSION, trade_id, party, amount });
+}
+pub fn emit_dispute_reopened(env: &Env, trade_id: u64, reopened_by: Address, evidence_hash: BytesN<32>, bond: i128) {
+    env.events().publish((cat_trade(), symbol_short!("reopened"), trade_id, reopened_by.clone()), EvDisputeReopened { v: EVENT_VERSION, trade_id, reopened_by, evidence_hash, bond });
+}
+pub fn emit_refund_requested(env: &Env, trade_id: u64, reason_hash: BytesN<32>, escalate_after: u64) {
+    env.events().publish((cat_trade(), symbol_short!("rfnd_req"), trade_id), EvRefundRequested { v: EVENT_VERSION, trade_id, reason_hash, escalate_after });
+}
+pub fn emit_trade_refunded(env: &Env, trade_id: u64, amount: i128, fee: i128) {
+    let (ts, seq) = stamp(env);
+    env.events().publish((cat_trade(), symbol_short!("refunded"), trade_id), EvTradeRefunded { v: EVENT_VERSION, ts, seq, trade_id, amount, fee });
 }
-pub fn emit_dispute_resolved(env: &Env, trade_id: u64, resolution: DisputeResolution, recipient: Address) {
-    env.events().publish((cat_trade(), symbol_short!("resolved")), EvDisputeResolved { v: EVENT_VERSION, trade_id, resolution, recipient });
+pub fn emit_refund_approved(env: &Env, trade_id: u64, amount: i128) {
+    env.events().publish((cat_trade(), symbol_short!("rfnd_ok"), trade_id), EvRefundApproved { v: EVENT_VERSION, trade_id, amount });
 }
-pub fn emit_partial_resolved(env: &Env, trade_id: u64, buyer_amount: u64, seller_amount: u64, fee: u64) {
-    env.events().publish((cat_trade(), symbol_short!("part_res")), EvPartialResolved { v: EVENT_VERSION, trade_id, buyer_amount, seller_amount, fee });
+pub fn emit_dispute_resolved(env: &Env, trade_id: u64, resolution: DisputeResolution, recipient: Address, reason_hash: BytesN<32>) {
+    let (ts, seq) = stamp(env);
+    env.events().publish((cat_trade(), symbol_short!("resolved"), trade_id, recipient.clone()), EvDisputeResolved { v: EVENT_VERSION, ts, seq, trade_id, resolution, recipient, reason_hash });
+}
+pub fn emit_partial_resolved(env: &Env, trade_id: u64, buyer_amount: i128, seller_amount: i128, fee: i128, reason_hash: BytesN<32>) {
+    let (ts, seq) = stamp(env);
+    env.events().publish((cat_trade(), symbol_short!("part_res"), trade_id), EvPartialResolved { v: EVENT_VERSION, ts, seq, trade_id, buyer_amount, seller_amount, fee, reason_hash });
 }
 
 pub fn emit_arbitrator_registered(env: &Env, arbitrator: Address) {
     env.events().publish((cat_arb(), symbol_short!("arb_reg")), EvArbRegistered { v: EVENT_VERSION, arbitrator });
 }
+pub fn emit_arb_earned(env: &Env, arbitrator: Address, trade_id: u64, amount: i128) {
+    env.events().publish((cat_arb(), symbol_short!("arb_earn"), arbitrator.clone()), EvArbEarned { v: EVENT_VERSION, arbitrator, trade_id, amount });
+}
+pub fn emit_arb_withdrawn(env: &Env, arbitrator: Address, to: Address, amount: i128) {
+    env.events().publish((cat_arb(), symbol_short!("arb_wd"), arbitrator.clone()), EvArbWithdrawn { v: EVENT_VERSION, arbitrator, to, amount });
+}
 pub fn emit_arbitrator_removed(env: &Env, arbitrator: Address) {
     env.events().publish((cat_arb(), symbol_short!("arb_rem")), EvArbRemoved { v: EVENT_VERSION, arbitrator });
 }
 pub fn emit_arb_rated(env: &Env, arbitrator: Address, trade_id: u64, rater: Address, stars: u32) {
-    env.events().publish((cat_arb(), symbol_short!("arb_rate")), EvArbRated { v: EVENT_VERSION, arbitrator, trade_id, rater, stars });
+    env.events().publish((cat_arb(), symbol_short!("arb_rate"), trade_id, arbitrator.clone()), EvArbRated { v: EVENT_VERSION, arbitrator, trade_id, rater, stars });
 }
 pub fn emit_arb_rep_updated(env: &Env, arbitrator: Address, resolved_count: u32, rating_sum: u32, rating_count: u32) {
     env.events().publish((cat_arb(), symbol_short!("arb_rep")), EvArbRepUpdated { v: EVENT_VERSION, arbitrator, resolved: resolved_count, rating_sum, rating_count });
 }
+pub fn emit_arb_categories_set(env: &Env, arbitrator: Address, categories: Vec<Symbol>) {
+    env.events().publish((cat_arb(), symbol_short!("arb_cat")), EvArbCategories { v: EVENT_VERSION, arbitrator, categories });
+}
+pub fn emit_caseload_cap_set(env: &Env, cap: u32) {
+    env.events().publish((cat_arb(), symbol_short!("case_cap")), EvCaseloadCapSet { v: EVENT_VERSION, cap });
+}
+pub fn emit_assignment_accepted(env: &Env, trade_id: u64, arbitrator: Address) {
+    env.events().publish((cat_arb(), symbol_short!("arb_acc"), trade_id, arbitrator.clone()), EvAssignmentAccepted { v: EVENT_VERSION, trade_id, arbitrator });
+}
 
 pub fn emit_fee_updated(env: &Env, fee_bps: u32) {
     env.events().publish((cat_fee(), symbol_short!("fee_upd")), EvFeeUpdated { v: EVENT_VERSION, fee_bps });
 }
-pub fn emit_fees_withdrawn(env: &Env, amount: u64, to: Address) {
-    env.events().publish((cat_fee(), symbol_short!("fees_out")), EvFeesWithdrawn { v: EVENT_VERSION, amount, to });
+pub fn emit_fees_withdrawn(env: &Env, currency: Address, amount: i128, to: Address) {
+    env.events().publish((cat_fee(), symbol_short!("fees_out")), EvFeesWithdrawn { v: EVENT_VERSION, currency, amount, to });
+}
+pub fn emit_fee_burned(env: &Env, currency: Address, amount: i128) {
+    env.events().publish((cat_fee(), symbol_short!("burned")), EvFeeBurned { v: EVENT_VERSION, currency, amount });
 }
-pub fn emit_fees_distributed(env: &Env, to: Address, amount: u64) {
+pub fn emit_fee_burn_failed(env: &Env, currency: Address, amount: i128) {
+    env.events().publish((cat_fee(), symbol_short!("burn_fail")), EvFeeBurned { v: EVENT_VERSION, currency, amount });
+}
+pub fn emit_keeper_paid(env: &Env, trade_id: u64, keeper: Address, currency: Address, amount: i128) {
+    env.events().publish((cat_fee(), symbol_short!("keeper"), trade_id, keeper.clone()), EvKeeperPaid { v: EVENT_VERSION, trade_id, keeper, currency, amount });
+}
+pub fn emit_fee_forwarded(env: &Env, currency: Address, treasury: Address, amount: i128) {
+    env.events().publish((cat_fee(), symbol_short!("fwd")), EvFeeForwarded { v: EVENT_VERSION, currency, treasury, amount });
+}
+pub fn emit_fee_forward_failed(env: &Env, currency: Address, treasury: Address, amount: i128) {
+    env.events().publish((cat_fee(), symbol_short!("fwd_fail")), EvFeeFwdFailed { v: EVENT_VERSION, currency, treasury, amount });
+}
+pub fn emit_fee_withdrawal_requested(env: &Env, currency: Address, to: Address, amount: i128, executable_at: u64) {
+    env.events().publish((cat_fee(), symbol_short!("wd_req")), EvFeeWdRequested { v: EVENT_VERSION, currency, to, amount, executable_at });
+}
+pub fn emit_fee_withdrawal_cancelled(env: &Env, currency: Address, to: Address, amount: i128) {
+    env.events().publish((cat_fee(), symbol_short!("wd_cncl")), EvFeeWdCancelled { v: EVENT_VERSION, currency, to, amount });
+}
+pub fn emit_fees_distributed(env: &Env, to: Address, amount: i128) {
     env.events().publish((cat_fee(), symbol_short!("fee_dst")), EvFeesDistributed { v: EVENT_VERSION, to, amount });
 }
+pub fn emit_fee_applied(env: &Env, trade_id: u64, fee_bps: u32, effective_bps: u32, promo: bool, fee: i128) {
+    env.events().publish((cat_fee(), symbol_short!("fee_appl"), trade_id), EvFeeApplied { v: EVENT_VERSION, trade_id, fee_bps, effective_bps, promo, fee });
+}
+pub fn emit_promo_set(env: &Env, start_ledger: u32, end_ledger: u32, fee_bps: u32) {
+    env.events().publish((cat_fee(), symbol_short!("promo_set")), EvPromoSet { v: EVENT_VERSION, start_ledger, end_ledger, fee_bps });
+}
+pub fn emit_fee_cap_set(env: &Env, cap: i128) {
+    env.events().publish((cat_fee(), symbol_short!("fee_cap")), EvFeeCapSet { v: EVENT_VERSION, cap });
+}
+pub fn emit_min_fee_set(env: &Env, min: i128) {
+    env.events().publish((cat_fee(), symbol_short!("fee_min")), EvMinFeeSet { v: EVENT_VERSION, min });
+}
+pub fn emit_protocol_share_set(env: &Env, recipient: Address, bps: u32) {
+    env.events().publish((cat_fee(), symbol_short!("prot_set")), EvProtoShareSet { v: EVENT_VERSION, recipient, bps });
+}
+pub fn emit_protocol_fee_paid(env: &Env, currency: Address, recipient: Address, amount: i128) {
+    env.events().publish((cat_fee(), symbol_short!("prot_fee")), EvProtoFeePaid { v: EVENT_VERSION, currency, recipient, amount });
+}
+pub fn emit_fee_scheduled(env: &Env, fee_bps: u32, effective_from: u32) {
+    env.events().publish((cat_fee(), symbol_short!("fee_sched")), EvFeeScheduled { v: EVENT_VERSION, fee_bps, effective_from });
+}
+pub fn emit_token_fee_set(env: &Env, token: Address, fee_bps: u32) {
+    env.events().publish((cat_fee(), symbol_short!("tok_fee")), EvTokenFeeSet { v: EVENT_VERSION, token, fee_bps });
+}
 pub fn emit_custom_fee_set(env: &Env, user: Address, fee_bps: u32) {
     env.events().publish((cat_fee(), symbol_short!("cust_fee")), EvCustomFeeSet { v: EVENT_VERSION, user, fee_bps });
 }
 
+pub fn emit_points_earned(env: &Env, user: Address, earned: u64, balance: u64) {
+    env.events().publish((cat_fee(), symbol_short!("pts_earn"), user.clone()), EvPointsEarned { v: EVENT_VERSION, user, earned, balance });
+}
+pub fn emit_points_redeemed(env: &Env, user: Address, spent: u64, discount_bps: u32) {
+    env.events().publish((cat_fee(), symbol_short!("pts_rdm"), user.clone()), EvPointsRedeemed { v: EVENT_VERSION, user, spent, discount_bps });
+}
+pub fn emit_badge_claimed(env: &Env, user: Address, level: u32) {
+    env.events().publish((cat_fee(), symbol_short!("badge"), user.clone()), EvBadgeChanged { v: EVENT_VERSION, user, level });
+}
+pub fn emit_badge_revoked(env: &Env, user: Address) {
+    env.events().publish((cat_fee(), symbol_short!("badge_rev"), user.clone()), EvBadgeChanged { v: EVENT_VERSION, user, level: 0 });
+}
 pub fn emit_tier_upgraded(env: &Env, user: Address, new_tier: UserTier) {
     env.events().publish((cat_fee(), symbol_short!("tier_up")), EvTierUpgraded { v: EVENT_VERSION, user, tier: new_tier });
 }
@@ -345,7 +675,7 @@ pub fn emit_template_deactivated(env: &Env, template_id: u64) {
     env.events().publish((cat_tmpl(), symbol_short!("tmpl_off")), EvTemplateDeactivated { v: EVENT_VERSION, template_id });
 }
 pub fn emit_template_trade(env: &Env, trade_id: u64, template_id: u64, version: u32) {
-    env.events().publish((cat_tmpl(), symbol_short!("tmpl_tr")), EvTemplateTrade { v: EVENT_VERSION, trade_id, template_id, version });
+    env.events().publish((cat_tmpl(), symbol_short!("tmpl_tr"), trade_id), EvTemplateTrade { v: EVENT_VERSION, trade_id, template_id, version });
 }
 
 pub fn emit_subscribed(env: &Env, subscriber: Address, tier: SubscriptionTier, expires_at: u32) {
@@ -381,36 +711,68 @@ pub fn emit_paused(env: &Env, admin: Address) {
 pub fn emit_unpaused(env: &Env, admin: Address) {
     env.events().publish((cat_sys(), symbol_short!("unpaused")), EvUnpaused { v: EVENT_VERSION, admin });
 }
-pub fn emit_emergency_withdraw(env: &Env, to: Address, amount: u64) {
+/// Admin configuration change. The setting name is the third topic; data is
+/// `(v, old, new)` so indexers can replay configuration history.
+pub fn emit_config_changed<T: IntoVal<Env, Val>>(env: &Env, key: Symbol, old: T, new: T) {
+    env.events().publish((cat_sys(), symbol_short!("cfg"), key), (EVENT_VERSION, old, new));
+}
+/// As `emit_config_changed`, for settings keyed by a token, user, or category
+/// carried as the fourth topic.
+pub fn emit_config_entry_changed<S, T>(env: &Env, key: Symbol, subject: S, old: T, new: T)
+where
+    S: IntoVal<Env, Val>,
+    T: IntoVal<Env, Val>,
+{
+    env.events().publish((cat_sys(), symbol_short!("cfg"), key, subject), (EVENT_VERSION, old, new));
+}
+pub fn emit_emergency_withdraw(env: &Env, to: Address, amount: i128) {
     env.events().publish((cat_sys(), symbol_short!("emrg_wd")), EvEmergencyWithdraw { v: EVENT_VERSION, to, amount });
 }
+pub fn emit_emergency_initiated(env: &Env, to: Address, executable_at: u64) {
+    env.events().publish((cat_sys(), symbol_short!("emrg_init")), EvEmergencyInit { v: EVENT_VERSION, to, executable_at });
+}
+pub fn emit_emergency_cancelled(env: &Env, to: Address) {
+    env.events().publish((cat_sys(), symbol_short!("emrg_cncl")), EvEmergencyCancel { v: EVENT_VERSION, to });
+}
+pub fn emit_volume_alert(env: &Env, currency: Address, volume: i128, threshold: i128, window_start: u32) {
+    env.events().publish((cat_sys(), symbol_short!("vol_alert")), EvVolumeAlert { v: EVENT_VERSION, currency, volume, threshold, window_start });
+}
+pub fn emit_breaker_reset(env: &Env, admin: Address) {
+    env.events().publish((cat_sys(), symbol_short!("brk_reset")), EvBreakerReset { v: EVENT_VERSION, admin });
+}
+pub fn emit_solvency_warning(env: &Env, currency: Address, balance: i128, liabilities: i128) {
+    env.events().publish((cat_sys(), symbol_short!("insolvent")), EvSolvencyWarning { v: EVENT_VERSION, currency, balance, liabilities });
+}
 pub fn emit_upgraded(env: &Env, new_version: u32) {
     env.events().publish((cat_sys(), symbol_short!("upgraded")), EvUpgraded { v: EVENT_VERSION, new_version });
 }
 pub fn emit_migrated(env: &Env, from_version: u32, to_version: u32) {
     env.events().publish((cat_sys(), symbol_short!("migrated")), EvMigrated { v: EVENT_VERSION, from_version, to_version });
 }
+pub fn emit_trade_migrated(env: &Env, trade_id: u64, to_version: u32) {
+    env.events().publish((cat_sys(), symbol_short!("trade_mig"), trade_id), EvTradeMigrated { v: EVENT_VERSION, trade_id, to_version });
+}
 pub fn emit_privacy_set(env: &Env, trade_id: u64) {
-    env.events().publish((cat_sys(), symbol_short!("priv_set")), EvPrivacySet { v: EVENT_VERSION, trade_id });
+    env.events().publish((cat_sys(), symbol_short!("priv_set"), trade_id), EvPrivacySet { v: EVENT_VERSION, trade_id });
 }
 pub fn emit_disclosure_granted(env: &Env, trade_id: u64, grantee: Address) {
-    env.events().publish((cat_sys(), symbol_short!("disc_gr")), EvDisclosureGranted { v: EVENT_VERSION, trade_id, grantee });
+    env.events().publish((cat_sys(), symbol_short!("disc_gr"), trade_id, grantee.clone()), EvDisclosureGranted { v: EVENT_VERSION, trade_id, grantee });
 }
 pub fn emit_disclosure_revoked(env: &Env, trade_id: u64, grantee: Address) {
-    env.events().publish((cat_sys(), symbol_short!("disc_rv")), EvDisclosureRevoked { v: EVENT_VERSION, trade_id, grantee });
+    env.events().publish((cat_sys(), symbol_short!("disc_rv"), trade_id, grantee.clone()), EvDisclosureRevoked { v: EVENT_VERSION, trade_id, grantee });
 }
 
 pub fn emit_bridge_oracle_set(env: &Env, oracle: Address) {
     env.events().publish((cat_sys(), symbol_short!("brg_set")), EvBridgeOracleSet { v: EVENT_VERSION, oracle });
 }
 pub fn emit_bridge_trade_created(env: &Env, trade_id: u64, source_chain: String) {
-    env.events().publish((cat_sys(), symbol_short!("brg_cr")), EvBridgeTradeCreated { v: EVENT_VERSION, trade_id, source_chain });
+    env.events().publish((cat_sys(), symbol_short!("brg_cr"), trade_id), EvBridgeTradeCreated { v: EVENT_VERSION, trade_id, source_chain });
 }
 pub fn emit_bridge_deposit_confirmed(env: &Env, trade_id: u64) {
-    env.events().publish((cat_sys(), symbol_short!("brg_ok")), EvBridgeDepositConfirmed { v: EVENT_VERSION, trade_id });
+    env.events().publish((cat_sys(), symbol_short!("brg_ok"), trade_id), EvBridgeDepositConfirmed { v: EVENT_VERSION, trade_id });
 }
 pub fn emit_bridge_trade_expired(env: &Env, trade_id: u64) {
-    env.events().publish((cat_sys(), symbol_short!("brg_exp")), EvBridgeTradeExpired { v: EVENT_VERSION, trade_id });
+    env.events().publish((cat_sys(), symbol_short!("brg_exp"), trade_id), EvBridgeTradeExpired { v: EVENT_VERSION, trade_id });
 }
 
 pub fn emit_insurance_provider_registered(env: &Env, provider: Address) {
@@ -419,11 +781,11 @@ pub fn emit_insurance_provider_registered(env: &Env, provider: Address) {
 pub fn emit_insurance_provider_removed(env: &Env, provider: Address) {
     env.events().publish((cat_ins(), symbol_short!("ins_rem")), EvInsProviderRemoved { v: EVENT_VERSION, provider });
 }
-pub fn emit_insurance_purchased(env: &Env, trade_id: u64, provider: Address, premium: u64, coverage: u64) {
-    env.events().publish((cat_ins(), symbol_short!("ins_buy")), EvInsPurchased { v: EVENT_VERSION, trade_id, provider, premium, coverage });
+pub fn emit_insurance_purchased(env: &Env, trade_id: u64, provider: Address, premium: i128, coverage: i128) {
+    env.events().publish((cat_ins(), symbol_short!("ins_buy"), trade_id, provider.clone()), EvInsPurchased { v: EVENT_VERSION, trade_id, provider, premium, coverage });
 }
-pub fn emit_insurance_claimed(env: &Env, trade_id: u64, payout: u64, recipient: Address) {
-    env.events().publish((cat_ins(), symbol_short!("ins_pay")), EvInsClaimed { v: EVENT_VERSION, trade_id, payout, recipient });
+pub fn emit_insurance_claimed(env: &Env, trade_id: u64, payout: i128, recipient: Address) {
+    env.events().publish((cat_ins(), symbol_short!("ins_pay"), trade_id, recipient.clone()), EvInsClaimed { v: EVENT_VERSION, trade_id, payout, recipient });
 }
 
 // ---------------------------------------------------------------------------
@@ -456,7 +818,7 @@ pub fn emit_oracle_unavailable(env: &Env, base: Address, quote: Address) {
     env.events().publish((cat_oracle(), symbol_short!("orc_err")), EvOracleUnavailable { v: EVENT_VERSION, base, quote });
 }
 pub fn emit_trigger_executed(env: &Env, trade_id: u64, action: &crate::types::TriggerAction) {
-    env.events().publish((cat_oracle(), symbol_short!("trig_ex")), EvTriggerExecuted { v: EVENT_VERSION, trade_id, action: action.clone() });
+    env.events().publish((cat_oracle(), symbol_short!("trig_ex"), trade_id), EvTriggerExecuted { v: EVENT_VERSION, trade_id, action: action.clone() });
 }
 
 // ---------------------------------------------------------------------------
@@ -470,7 +832,7 @@ pub fn emit_trigger_executed(env: &Env, trade_id: u64, action: &crate::types::Tr
 #[contracttype] #[derive(Clone, Debug)]
 pub struct EvComplianceFailed  { pub v: u32, pub user: Address, pub reason: String }
 #[contracttype] #[derive(Clone, Debug)]
-pub struct EvCompliancePassed  { pub v: u32, pub trade_id: u64, pub seller: Address, pub buyer: Address, pub amount: u64 }
+pub struct EvCompliancePassed  { pub v: u32, pub trade_id: u64, pub seller: Address, pub buyer: Address, pub amount: i128 }
 
 // ---------------------------------------------------------------------------
 // Upgrade system events
@@ -507,11 +869,148 @@ pub struct EvMultiSigConsensus { pub v: u32, pub trade_id: u64 }
 pub struct EvMultiSigExpired { pub v: u32, pub trade_id: u64 }
 
 pub fn emit_arbitrator_vote_cast(env: &Env, trade_id: u64, arbitrator: Address, resolution: crate::types::DisputeResolution) {
-    env.events().publish((cat_multisig(), symbol_short!("ms_vote")), EvArbVoteCast { v: EVENT_VERSION, trade_id, arbitrator, resolution });
+    env.events().publish((cat_multisig(), symbol_short!("ms_vote"), trade_id, arbitrator.clone()), EvArbVoteCast { v: EVENT_VERSION, trade_id, arbitrator, resolution });
 }
 pub fn emit_multisig_consensus(env: &Env, trade_id: u64) {
-    env.events().publish((cat_multisig(), symbol_short!("ms_cons")), EvMultiSigConsensus { v: EVENT_VERSION, trade_id });
+    env.events().publish((cat_multisig(), symbol_short!("ms_cons"), trade_id), EvMultiSigConsensus { v: EVENT_VERSION, trade_id });
 }
 pub fn emit_multisig_expired(env: &Env, trade_id: u64) {
-    env.events().publish((cat_multisig(), symbol_short!("ms_exp")), EvMultiSigExpired { v: EVENT_VERSION, trade_id });
+    env.events().publish((cat_multisig(), symbol_short!("ms_exp"), trade_id), EvMultiSigExpired { v: EVENT_VERSION, trade_id });
+}
+
+// ---------------------------------------------------------------------------
+// Settlement batch events
+// ---------------------------------------------------------------------------
+
+fn cat_batch() -> Symbol { symbol_short!("batch") }
+
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvBatchCommitted { pub v: u32, pub batch_id: u64, pub operator: Address, pub currency: Address, pub root: BytesN<32>, pub total: i128, pub leaves: u32 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvBatchClaimed { pub v: u32, pub batch_id: u64, pub index: u32, pub seller: Address, pub amount: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvBatchReclaimed { pub v: u32, pub batch_id: u64, pub operator: Address, pub amount: i128 }
+
+pub fn emit_batch_committed(env: &Env, batch_id: u64, operator: Address, currency: Address, root: BytesN<32>, total: i128, leaves: u32) {
+    env.events().publish((cat_batch(), symbol_short!("b_commit"), batch_id), EvBatchCommitted { v: EVENT_VERSION, batch_id, operator, currency, root, total, leaves });
+}
+pub fn emit_batch_claimed(env: &Env, batch_id: u64, index: u32, seller: Address, amount: i128) {
+    env.events().publish((cat_batch(), symbol_short!("b_claim"), batch_id, seller.clone()), EvBatchClaimed { v: EVENT_VERSION, batch_id, index, seller, amount });
+}
+pub fn emit_batch_reclaimed(env: &Env, batch_id: u64, operator: Address, amount: i128) {
+    env.events().publish((cat_batch(), symbol_short!("b_reclaim"), batch_id), EvBatchReclaimed { v: EVENT_VERSION, batch_id, operator, amount });
+}
+
+// ---------------------------------------------------------------------------
+// Regulated asset events
+// ---------------------------------------------------------------------------
+
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvPayoutHeld { pub v: u32, pub recipient: Address, pub token: Address, pub amount: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvHeldPayoutClaimed { pub v: u32, pub recipient: Address, pub token: Address, pub amount: i128 }
+
+pub fn emit_payout_held(env: &Env, recipient: Address, token: Address, amount: i128) {
+    env.events().publish((cat_trade(), symbol_short!("held"), recipient.clone()), EvPayoutHeld { v: EVENT_VERSION, recipient, token, amount });
+}
+pub fn emit_held_payout_claimed(env: &Env, recipient: Address, token: Address, amount: i128) {
+    env.events().publish((cat_trade(), symbol_short!("held_clm"), recipient.clone()), EvHeldPayoutClaimed { v: EVENT_VERSION, recipient, token, amount });
+}
+
+// ---------------------------------------------------------------------------
+// Hashed trade events
+// ---------------------------------------------------------------------------
+// Carry no party or amount data; disputes expose it only through the record.
+
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvHashedTradeCreated { pub v: u32, pub trade_id: u64, pub commitment: BytesN<32>, pub amount_bucket: u32 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvHashedTradeStatus { pub v: u32, pub trade_id: u64, pub status: TradeStatus }
+
+pub fn emit_hashed_trade_created(env: &Env, trade_id: u64, commitment: BytesN<32>, amount_bucket: u32) {
+    env.events().publish((cat_trade(), symbol_short!("h_created"), trade_id), EvHashedTradeCreated { v: EVENT_VERSION, trade_id, commitment, amount_bucket });
+}
+pub fn emit_hashed_trade_status(env: &Env, trade_id: u64, status: TradeStatus) {
+    env.events().publish((cat_trade(), symbol_short!("h_status"), trade_id), EvHashedTradeStatus { v: EVENT_VERSION, trade_id, status });
+}
+pub fn emit_hashed_trade_frozen(env: &Env, trade_id: u64, reason_code: u32, admin: Address) {
+    env.events().publish(
+        (cat_compliance(), symbol_short!("h_frozen"), trade_id),
+        EvTradeFrozen { v: EVENT_VERSION, trade_id, reason_code, admin },
+    );
+}
+pub fn emit_hashed_trade_unfrozen(env: &Env, trade_id: u64, admin: Address) {
+    env.events().publish(
+        (cat_compliance(), symbol_short!("h_unfrozn"), trade_id),
+        EvTradeUnfrozen { v: EVENT_VERSION, trade_id, admin },
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Payout swap events
+// ---------------------------------------------------------------------------
+
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvPayoutSwapSet { pub v: u32, pub trade_id: u64, pub adapter: Address, pub token_out: Address, pub min_out: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvPayoutSwapped { pub v: u32, pub trade_id: u64, pub token_out: Address, pub amount_in: i128, pub amount_out: i128 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvPayoutSwapFailed { pub v: u32, pub trade_id: u64, pub adapter: Address }
+
+pub fn emit_payout_swap_set(env: &Env, trade_id: u64, adapter: Address, token_out: Address, min_out: i128) {
+    env.events().publish((cat_trade(), symbol_short!("swap_set"), trade_id), EvPayoutSwapSet { v: EVENT_VERSION, trade_id, adapter, token_out, min_out });
+}
+pub fn emit_payout_swapped(env: &Env, trade_id: u64, token_out: Address, amount_in: i128, amount_out: i128) {
+    env.events().publish((cat_trade(), symbol_short!("swapped"), trade_id), EvPayoutSwapped { v: EVENT_VERSION, trade_id, token_out, amount_in, amount_out });
+}
+pub fn emit_payout_swap_failed(env: &Env, trade_id: u64, adapter: Address) {
+    env.events().publish((cat_trade(), symbol_short!("swap_fail"), trade_id), EvPayoutSwapFailed { v: EVENT_VERSION, trade_id, adapter });
+}
+
+// ---------------------------------------------------------------------------
+// Donation events
+// ---------------------------------------------------------------------------
+
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvDonated { pub v: u32, pub trade_id: u64, pub charity: Address, pub currency: Address, pub amount: i128 }
+
+pub fn emit_donated(env: &Env, trade_id: u64, charity: Address, currency: Address, amount: i128) {
+    env.events().publish((cat_trade(), symbol_short!("donated"), trade_id), EvDonated { v: EVENT_VERSION, trade_id, charity, currency, amount });
+}
+
+// ---------------------------------------------------------------------------
+// Partner events
+// ---------------------------------------------------------------------------
+
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvPartnerFeePaid { pub v: u32, pub trade_id: u64, pub partner_id: u32, pub recipient: Address, pub currency: Address, pub amount: i128 }
+
+pub fn emit_partner_fee_paid(env: &Env, trade_id: u64, partner_id: u32, recipient: Address, currency: Address, amount: i128) {
+    env.events().publish((cat_fee(), symbol_short!("partner"), trade_id, partner_id), EvPartnerFeePaid { v: EVENT_VERSION, trade_id, partner_id, recipient, currency, amount });
+}
+
+// ---------------------------------------------------------------------------
+// Referral events
+// ---------------------------------------------------------------------------
+
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvReferrerRegistered { pub v: u32, pub referrer: Address, pub payout: Address, pub share_bps: u32 }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvReferrerStatus { pub v: u32, pub referrer: Address, pub status: ReferrerStatus }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvBuyerReferred { pub v: u32, pub buyer: Address, pub referrer: Address }
+#[contracttype] #[derive(Clone, Debug)]
+pub struct EvReferralPaid { pub v: u32, pub trade_id: u64, pub referrer: Address, pub payout: Address, pub currency: Address, pub amount: i128 }
+
+pub fn emit_referrer_registered(env: &Env, referrer: Address, payout: Address, share_bps: u32) {
+    env.events().publish((cat_fee(), symbol_short!("ref_reg"), referrer.clone()), EvReferrerRegistered { v: EVENT_VERSION, referrer, payout, share_bps });
+}
+pub fn emit_referrer_status(env: &Env, referrer: Address, status: ReferrerStatus) {
+    env.events().publish((cat_fee(), symbol_short!("ref_stat"), referrer.clone()), EvReferrerStatus { v: EVENT_VERSION, referrer, status });
+}
+pub fn emit_buyer_referred(env: &Env, buyer: Address, referrer: Address) {
+    env.events().publish((cat_fee(), symbol_short!("referred"), referrer.clone()), EvBuyerReferred { v: EVENT_VERSION, buyer, referrer });
+}
+pub fn emit_referral_paid(env: &Env, trade_id: u64, referrer: Address, payout: Address, currency: Address, amount: i128) {
+    env.events().publish((cat_fee(), symbol_short!("ref_paid"), trade_id, referrer.clone()), EvReferralPaid { v: EVENT_VERSION, trade_id, referrer, payout, currency, amount });
 }
@@ -0,0 +1,164 @@
+//! Snapshots of contract state for migrations and audits.
+//!
+//! `export_config` returns the platform-wide settings and totals in one
+//! struct; `export_trades` pages through trades with the per-trade entries
+//! stored beside them. Both are plain reads of the same storage the
+//! contract uses, so a new instance can be seeded (or an audit reconciled)
+//! without decoding raw ledger entries.
+
+use soroban_sdk::{contracttype, Address, BytesN, Env, String, Symbol, Vec};
+
+use crate::donation;
+use crate::errors::ContractError;
+use crate::partners;
+use crate::storage;
+use crate::types::{
+    ArbitratorAssignment, Contribution, Deadlines, PayoutShare, PromoWindow, ProtocolFeeShare, TimingMode, Trade,
+    TradeFreeze, TradePartner,
+};
+
+/// Most trades `export_trades` returns per call.
+pub const MAX_EXPORT_TRADES: u32 = 50;
+
+// ---------------------------------------------------------------------------
+// Snapshot types
+// ---------------------------------------------------------------------------
+
+/// Escrow and fee balances held in one currency.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CurrencyTotals {
+    pub currency: Address,
+    pub locked: i128,
+    pub withdrawable_fees: i128,
+    pub burned: i128,
+}
+
+/// Platform-wide configuration and running totals.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigSnapshot {
+    pub admin: Address,
+    pub usdc_token: Address,
+    pub fee_bps: u32,
+    pub fee_cap: i128,
+    pub min_fee: i128,
+    pub fee_burn_bps: u32,
+    pub promo_window: Option<PromoWindow>,
+    pub treasury: Option<Address>,
+    pub protocol_fee_share: Option<ProtocolFeeShare>,
+    pub charity: Option<Address>,
+    pub keeper_bounty_bps: u32,
+    pub arbitrators: Vec<Address>,
+    pub max_arbitrator_fee_bps: u32,
+    pub arbitrator_threshold: i128,
+    pub timing_mode: Option<TimingMode>,
+    pub paused: bool,
+    pub paused_functions: u32,
+    pub trade_counter: u64,
+    /// Per-currency totals, the platform token first.
+    pub currencies: Vec<CurrencyTotals>,
+    pub accumulated_fees: i128,
+}
+
+/// A trade with the optional entries stored alongside it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TradeSnapshot {
+    pub trade: Trade,
+    pub created_at: Option<u64>,
+    pub category: Option<Symbol>,
+    pub memo: Option<String>,
+    pub deadlines: Option<Deadlines>,
+    pub terms_hash: Option<BytesN<32>>,
+    pub assignment: Option<ArbitratorAssignment>,
+    pub payee: Option<Address>,
+    pub payout_assignee: Option<Address>,
+    pub payout_split: Option<Vec<PayoutShare>>,
+    pub contributions: Option<Vec<Contribution>>,
+    pub partner: Option<TradePartner>,
+    pub freeze: Option<TradeFreeze>,
+    pub held_until: Option<u64>,
+}
+
+// ---------------------------------------------------------------------------
+// Exports
+// ---------------------------------------------------------------------------
+
+pub fn config(env: &Env) -> Result<ConfigSnapshot, ContractError> {
+    Ok(ConfigSnapshot {
+        admin: storage::get_admin(env)?,
+        usdc_token: storage::get_usdc_token(env)?,
+        fee_bps: storage::get_fee_bps(env)?,
+        fee_cap: storage::get_fee_cap(env),
+        min_fee: storage::get_min_fee(env),
+        fee_burn_bps: storage::get_fee_burn_bps(env),
+        promo_window: storage::get_promo_window(env),
+        treasury: storage::get_treasury(env),
+        protocol_fee_share: storage::get_protocol_fee_share(env),
+        charity: donation::get_charity(env),
+        keeper_bounty_bps: storage::get_keeper_bounty_bps(env),
+        arbitrators: storage::get_arbitrator_list(env),
+        max_arbitrator_fee_bps: storage::get_max_arbitrator_fee_bps(env),
+        arbitrator_threshold: storage::get_arbitrator_threshold(env),
+        timing_mode: storage::get_timing_mode(env),
+        paused: storage::is_paused(env),
+        paused_functions: storage::get_paused_functions(env),
+        trade_counter: storage::get_trade_counter(env)?,
+        currencies: currency_totals(env)?,
+        accumulated_fees: storage::get_accumulated_fees(env)?,
+    })
+}
+
+/// Totals for the platform token and every other currency held in escrow.
+fn currency_totals(env: &Env) -> Result<Vec<CurrencyTotals>, ContractError> {
+    let usdc = storage::get_usdc_token(env)?;
+    let mut currencies = Vec::from_array(env, [usdc.clone()]);
+    for currency in storage::get_currencies(env).iter() {
+        if currency != usdc {
+            currencies.push_back(currency);
+        }
+    }
+    let mut out = Vec::new(env);
+    for currency in currencies.iter() {
+        out.push_back(CurrencyTotals {
+            locked: storage::get_locked(env, &currency),
+            withdrawable_fees: storage::get_withdrawable_fees(env, &currency)?,
+            burned: storage::get_total_burned(env, &currency),
+            currency,
+        });
+    }
+    Ok(out)
+}
+
+/// Snapshots of trades by ascending id from `start_id`, at most `limit`
+/// (capped at `MAX_EXPORT_TRADES`). Missing ids are skipped, so callers page
+/// by passing `last.trade.id + 1` as the next `start_id`.
+pub fn trades(env: &Env, start_id: u64, limit: u32) -> Result<Vec<TradeSnapshot>, ContractError> {
+    let total = storage::get_trade_counter(env)?;
+    let limit = limit.min(MAX_EXPORT_TRADES);
+    let mut out = Vec::new(env);
+    let mut id = start_id.max(1);
+    while id <= total && out.len() < limit {
+        if let Ok(trade) = storage::get_trade(env, id) {
+            out.push_back(TradeSnapshot {
+                created_at: storage::get_created_at(env, id),
+                category: storage::get_trade_category(env, id),
+                memo: storage::get_trade_memo(env, id),
+                deadlines: storage::get_trade_deadlines(env, id),
+                terms_hash: storage::get_terms_hash(env, id),
+                assignment: storage::get_assignment(env, id),
+                payee: storage::get_trade_payee(env, id),
+                payout_assignee: storage::get_payout_assignee(env, id),
+                payout_split: storage::get_payout_split(env, id),
+                contributions: storage::get_contributions(env, id),
+                partner: partners::get_trade_partner(env, id),
+                freeze: storage::get_trade_freeze(env, id),
+                held_until: storage::get_held_until(env, id),
+                trade,
+            });
+        }
+        id += 1;
+    }
+    Ok(out)
+}
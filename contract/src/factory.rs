@@ -0,0 +1,112 @@
+use soroban_sdk::{contract, contractimpl, symbol_short, token, Address, BytesN, Env, Symbol, Vec};
+
+use crate::StellarEscrowContractClient;
+
+const STORAGE_ADMIN: Symbol = symbol_short!("ADMIN");
+const STORAGE_WASM: Symbol = symbol_short!("ESC_WASM");
+const STORAGE_SHARE: Symbol = symbol_short!("PROT_BPS");
+const STORAGE_INSTANCES: Symbol = symbol_short!("INSTANCES");
+
+const OPERATOR_PREFIX: &str = "OP";
+
+/// Deploys per-marketplace escrow instances. Each instance gets its own
+/// admin, token and fee config; the factory receives a protocol-level share
+/// of the fees each instance withdraws.
+#[contract]
+pub struct EscrowFactory;
+
+#[contractimpl]
+impl EscrowFactory {
+    /// Initialize the factory with its admin, the escrow wasm to deploy and
+    /// the protocol fee share applied to new instances.
+    pub fn init_factory(env: Env, admin: Address, escrow_wasm_hash: BytesN<32>, protocol_share_bps: u32) {
+        if env.storage().instance().has(&STORAGE_ADMIN) {
+            panic!("Already initialized");
+        }
+        if protocol_share_bps > crate::MAX_PROTOCOL_FEE_SHARE_BPS {
+            panic!("Share too high");
+        }
+        admin.require_auth();
+        env.storage().instance().set(&STORAGE_ADMIN, &admin);
+        env.storage().instance().set(&STORAGE_WASM, &escrow_wasm_hash);
+        env.storage().instance().set(&STORAGE_SHARE, &protocol_share_bps);
+        env.storage().instance().set(&STORAGE_INSTANCES, &Vec::<Address>::new(&env));
+    }
+
+    /// Deploy and initialize an escrow instance administered by `operator`.
+    /// The factory is registered as the instance's protocol fee recipient.
+    pub fn deploy_escrow(
+        env: Env,
+        operator: Address,
+        token: Address,
+        fee_bps: u32,
+        salt: BytesN<32>,
+    ) -> Address {
+        operator.require_auth();
+        let wasm_hash: BytesN<32> = env.storage().instance().get(&STORAGE_WASM).expect("Not initialized");
+        let share: u32 = env.storage().instance().get(&STORAGE_SHARE).unwrap_or(0);
+
+        let instance = env.deployer().with_current_contract(salt).deploy(wasm_hash);
+        let client = StellarEscrowContractClient::new(&env, &instance);
+        client.initialize(&operator, &token, &fee_bps);
+        client.set_protocol_fee_share(&env.current_contract_address(), &share);
+
+        let mut instances: Vec<Address> = env.storage().instance().get(&STORAGE_INSTANCES).unwrap_or(Vec::new(&env));
+        instances.push_back(instance.clone());
+        env.storage().instance().set(&STORAGE_INSTANCES, &instances);
+
+        let key = (OPERATOR_PREFIX, operator.clone());
+        let mut owned: Vec<Address> = env.storage().persistent().get(&key).unwrap_or(Vec::new(&env));
+        owned.push_back(instance.clone());
+        env.storage().persistent().set(&key, &owned);
+
+        env.events().publish((symbol_short!("deployed"), operator), instance.clone());
+        instance
+    }
+
+    /// Update the wasm used for future deployments. Existing instances are
+    /// upgraded through their own admin.
+    pub fn set_escrow_wasm(env: Env, escrow_wasm_hash: BytesN<32>) {
+        let admin: Address = env.storage().instance().get(&STORAGE_ADMIN).expect("Not initialized");
+        admin.require_auth();
+        env.storage().instance().set(&STORAGE_WASM, &escrow_wasm_hash);
+    }
+
+    /// Set the protocol share for future deployments.
+    pub fn set_protocol_share(env: Env, protocol_share_bps: u32) {
+        let admin: Address = env.storage().instance().get(&STORAGE_ADMIN).expect("Not initialized");
+        admin.require_auth();
+        if protocol_share_bps > crate::MAX_PROTOCOL_FEE_SHARE_BPS {
+            panic!("Share too high");
+        }
+        env.storage().instance().set(&STORAGE_SHARE, &protocol_share_bps);
+    }
+
+    /// Change the protocol share on an already deployed instance.
+    pub fn set_instance_share(env: Env, instance: Address, protocol_share_bps: u32) {
+        let admin: Address = env.storage().instance().get(&STORAGE_ADMIN).expect("Not initialized");
+        admin.require_auth();
+        StellarEscrowContractClient::new(&env, &instance)
+            .set_protocol_fee_share(&env.current_contract_address(), &protocol_share_bps);
+    }
+
+    /// Withdraw protocol fees forwarded to the factory.
+    pub fn withdraw_protocol_fees(env: Env, token: Address, to: Address, amount: i128) {
+        let admin: Address = env.storage().instance().get(&STORAGE_ADMIN).expect("Not initialized");
+        admin.require_auth();
+        token::Client::new(&env, &token).transfer(&env.current_contract_address(), &to, &amount);
+    }
+
+    pub fn get_instances(env: Env) -> Vec<Address> {
+        env.storage().instance().get(&STORAGE_INSTANCES).unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_operator_instances(env: Env, operator: Address) -> Vec<Address> {
+        let key = (OPERATOR_PREFIX, operator);
+        env.storage().persistent().get(&key).unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_protocol_share(env: Env) -> u32 {
+        env.storage().instance().get(&STORAGE_SHARE).unwrap_or(0)
+    }
+}
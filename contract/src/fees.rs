@@ -0,0 +1,162 @@
+//! Fee computation shared by trade creation, settlement and read-only
+//! quote queries.
+//!
+//! Every path that charges a platform fee goes through `quote` so that the
+//! figure a frontend is shown before submission matches what `create_trade`
+//! records on the trade. Likewise `breakdown` reuses the settlement helpers
+//! so receipts show exactly what a trade pays out.
+
+use soroban_sdk::{Address, Env};
+
+use crate::errors::ContractError;
+use crate::storage::{
+    get_arbitrator_fee, get_arbitrator_fee_bps, get_fee_bps, get_fee_cap, get_max_arbitrator_fee_bps, get_min_fee,
+    get_promo_window, get_token_fee_bps, get_usdc_token,
+};
+use crate::loyalty;
+use crate::partners;
+use crate::referrals;
+use crate::subscription;
+use crate::tiers;
+use crate::types::{FeeBreakdown, FeeQuote, Trade, TradeStatus, MAX_MIN_FEE_SHARE_BPS};
+
+/// Base platform rate for trades settled in `token`: its per-token override
+/// if configured, otherwise the global rate.
+pub fn token_fee_bps(env: &Env, token: &Address) -> Result<u32, ContractError> {
+    match get_token_fee_bps(env, token) {
+        Some(bps) => Ok(bps),
+        None => get_fee_bps(env),
+    }
+}
+
+/// Fee rate in basis points that applies to `seller`'s next trade in
+/// `token`: the token's base rate adjusted for volume tier or custom
+/// override, minus any active subscription discount and redeemed loyalty
+/// discount.
+pub fn effective_fee_bps(env: &Env, seller: &Address, token: &Address) -> Result<u32, ContractError> {
+    let base = token_fee_bps(env, token)?;
+    let tiered = tiers::effective_fee_bps(env, seller, base);
+    let discount = subscription::subscription_discount_bps(env, seller)
+        .saturating_add(loyalty::discount_bps(env, seller));
+    Ok(tiered.saturating_sub(discount))
+}
+
+/// Promotional rate in force at the current ledger, if any.
+pub fn active_promo_bps(env: &Env) -> Option<u32> {
+    let window = get_promo_window(env)?;
+    let seq = env.ledger().sequence();
+    if seq >= window.start_ledger && seq < window.end_ledger {
+        Some(window.fee_bps)
+    } else {
+        None
+    }
+}
+
+/// Fee and net payout for a trade of `amount` in `token` created by `seller`. The
+/// percentage fee is clamped between the admin-configured floor and cap.
+/// Trades too small to carry the floor without it taking more than
+/// `MAX_MIN_FEE_SHARE_BPS` of the amount are rejected.
+pub fn quote(env: &Env, seller: &Address, token: &Address, amount: i128) -> Result<FeeQuote, ContractError> {
+    if amount < 0 {
+        return Err(ContractError::InvalidAmount);
+    }
+    let regular_bps = effective_fee_bps(env, seller, token)?;
+    // A promotion only ever lowers the rate a seller would otherwise pay.
+    let (fee_bps, promo) = match active_promo_bps(env) {
+        Some(p) if p < regular_bps => (p, true),
+        _ => (regular_bps, false),
+    };
+    let fee = fee_at_rate(env, amount, fee_bps)?;
+    let payout = amount.checked_sub(fee).ok_or(ContractError::Overflow)?;
+    Ok(FeeQuote { amount, fee_bps, effective_bps: effective_bps(amount, fee), promo, fee, payout })
+}
+
+/// Fee on `amount` at `fee_bps`, clamped between the admin-configured floor
+/// and cap.
+pub fn fee_at_rate(env: &Env, amount: i128, fee_bps: u32) -> Result<i128, ContractError> {
+    let mut fee = amount
+        .checked_mul(i128::from(fee_bps))
+        .ok_or(ContractError::Overflow)?
+        .checked_div(10_000)
+        .ok_or(ContractError::Overflow)?;
+    let cap = get_fee_cap(env);
+    if cap > 0 && fee > cap {
+        fee = cap;
+    }
+    let min = get_min_fee(env);
+    if min > 0 && fee < min {
+        let max_share = amount
+            .checked_mul(i128::from(MAX_MIN_FEE_SHARE_BPS))
+            .ok_or(ContractError::Overflow)?
+            / 10_000;
+        if min > max_share {
+            return Err(ContractError::TradeTooSmallForFee);
+        }
+        fee = min;
+    }
+    Ok(fee)
+}
+
+/// Rate for a new trade in `token` whose seller is not known on-chain: the
+/// token's base rate, or the active promotion if lower. Seller discounts do
+/// not apply.
+pub fn anonymous_fee_bps(env: &Env, token: &Address) -> Result<u32, ContractError> {
+    let base = token_fee_bps(env, token)?;
+    Ok(match active_promo_bps(env) {
+        Some(p) if p < base => p,
+        _ => base,
+    })
+}
+
+/// Ruling fee withheld from `disputed` at the arbitrator's rate, capped by
+/// the platform maximum.
+pub fn ruling_fee(env: &Env, arbitrator: &Address, disputed: i128) -> Result<i128, ContractError> {
+    let bps = get_arbitrator_fee_bps(env, arbitrator).min(get_max_arbitrator_fee_bps(env));
+    Ok(disputed.checked_mul(i128::from(bps)).ok_or(ContractError::Overflow)? / 10_000)
+}
+
+/// Arbitrator's declared service fee for ruling on `trade`, taken out of the
+/// trade fee and capped at it. Only trades in the platform token pay it.
+pub fn arbitrator_service_fee(env: &Env, trade: &Trade, arbitrator: &Address) -> Result<i128, ContractError> {
+    if trade.currency != get_usdc_token(env)? {
+        return Ok(0);
+    }
+    Ok(get_arbitrator_fee(env, arbitrator).max(0).min(trade.fee))
+}
+
+/// Breakdown of `trade`'s amount: for a disputed or ruled trade as a ruling
+/// would settle it, otherwise as a release to the seller.
+pub fn breakdown(env: &Env, trade: &Trade) -> Result<FeeBreakdown, ContractError> {
+    let net = trade.amount.checked_sub(trade.fee).ok_or(ContractError::Overflow)?;
+    let ruled = matches!(trade.status, TradeStatus::Disputed | TradeStatus::Resolved);
+    let (arbitrator_fee, service_fee, payout) = match trade.arbitrator.as_ref() {
+        Some(arb) if ruled => {
+            let ruling = ruling_fee(env, arb, net)?;
+            let service = arbitrator_service_fee(env, trade, arb)?;
+            (ruling + service, service, net - ruling)
+        }
+        _ => (0, 0, net),
+    };
+    let booked = trade.fee - service_fee;
+    let partner_fee = partners::share(env, trade, booked)?;
+    let referral_fee = referrals::share(env, trade, booked - partner_fee)?;
+    Ok(FeeBreakdown {
+        amount: trade.amount,
+        fee: trade.fee,
+        platform_fee: booked - partner_fee - referral_fee,
+        arbitrator_fee,
+        partner_fee,
+        referral_fee,
+        payout,
+    })
+}
+
+/// `fee` expressed as basis points of `amount`, rounded down.
+fn effective_bps(amount: i128, fee: i128) -> u32 {
+    if amount <= 0 {
+        return 0;
+    }
+    // The fee never exceeds the amount, so neither step can overflow and
+    // the result is at most 10_000.
+    (fee.saturating_mul(10_000) / amount) as u32
+}
@@ -113,7 +113,7 @@ pub fn execute_proposal(env: &Env, proposal_id: u64) -> Result<(), ContractError
             if bps > 10000 {
                 return Err(ContractError::InvalidFeeBps);
             }
-            set_fee_bps(env, bps);
+            set_fee_bps(env, bps)?;
             events::emit_fee_updated(env, bps);
         }
         ProposalAction::UpdateTierConfig(config) => {
@@ -130,7 +130,7 @@ pub fn execute_proposal(env: &Env, proposal_id: u64) -> Result<(), ContractError
                 &recipient,
                 &(fees as i128),
             );
-            set_accumulated_fees(env, 0);
+            set_accumulated_fees(env, 0)?;
             events::emit_fees_distributed(env, recipient, fees);
         }
     }
@@ -231,7 +231,7 @@ pub fn distribute_fees_to_holders(
         recipient,
         &(fees as i128),
     );
-    set_accumulated_fees(env, 0);
+    set_accumulated_fees(env, 0)?;
     events::emit_fees_distributed(env, recipient.clone(), fees);
     Ok(())
 }
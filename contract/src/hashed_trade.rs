@@ -0,0 +1,134 @@
+//! Hashed trades: escrow whose stored record holds only a commitment.
+//!
+//! # Design
+//! - The parties agree on `HashedTradeDetails` (seller, buyer, amount and a
+//!   random salt) off-chain. The record stores `sha256(xdr(details))`, the
+//!   amount's bucket (its number of decimal digits), the currency, the
+//!   arbitrator and the fee rate, never the parties or the exact amount.
+//! - Each step (`fund_hashed_trade`, `release_hashed_trade`) takes the
+//!   details as an argument and checks them against the commitment; they
+//!   are not written to storage or events.
+//! - Only storage is hashed. Call arguments are part of the submitted
+//!   transaction, so anyone reading the ledger's transaction history sees
+//!   the details of every hashed trade that was funded or released, as do
+//!   the token transfers themselves. This keeps the details out of contract
+//!   state and indexers built on it, not out of public view.
+//! - Raising a dispute stores the details on the record so the arbitrator
+//!   can read and rule on them.
+//! - Status changes follow the `state` graph, a frozen hashed trade cannot
+//!   move, and an arbitrator who turns out to be a party is refused once the
+//!   details are presented.
+//! - The fee rate is fixed at creation. The seller is not known then, so
+//!   seller discounts do not apply; the floor and cap are applied when the
+//!   fee is charged.
+//! - Hashed trades have their own id sequence and freezes, separate from
+//!   `Trade` ids.
+
+use soroban_sdk::{symbol_short, xdr::ToXdr, Address, BytesN, Env, Symbol};
+
+use crate::errors::ContractError;
+use crate::state::{self, TradeEvent};
+use crate::types::{HashedTrade, HashedTradeDetails, TradeFreeze, TradeStatus};
+
+// ---------------------------------------------------------------------------
+// Storage
+// ---------------------------------------------------------------------------
+
+const HASHED_TRADE_PREFIX: &str = "HT";
+const HASHED_FREEZE_PREFIX: &str = "HF";
+
+fn counter_key() -> Symbol {
+    symbol_short!("HT_CTR")
+}
+
+pub fn get(env: &Env, id: u64) -> Result<HashedTrade, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&(HASHED_TRADE_PREFIX, id))
+        .ok_or(ContractError::HashedTradeNotFound)
+}
+
+pub fn save(env: &Env, id: u64, trade: &HashedTrade) {
+    env.storage().persistent().set(&(HASHED_TRADE_PREFIX, id), trade);
+}
+
+pub fn get_freeze(env: &Env, id: u64) -> Option<TradeFreeze> {
+    env.storage().persistent().get(&(HASHED_FREEZE_PREFIX, id))
+}
+
+pub fn save_freeze(env: &Env, id: u64, freeze: &TradeFreeze) {
+    env.storage().persistent().set(&(HASHED_FREEZE_PREFIX, id), freeze);
+}
+
+pub fn remove_freeze(env: &Env, id: u64) {
+    env.storage().persistent().remove(&(HASHED_FREEZE_PREFIX, id));
+}
+
+pub fn is_frozen(env: &Env, id: u64) -> bool {
+    env.storage().persistent().has(&(HASHED_FREEZE_PREFIX, id))
+}
+
+// ---------------------------------------------------------------------------
+// Commitments
+// ---------------------------------------------------------------------------
+
+/// Public commitment to `details`.
+pub fn commitment(env: &Env, details: &HashedTradeDetails) -> BytesN<32> {
+    env.crypto().sha256(&details.clone().to_xdr(env)).to_bytes()
+}
+
+/// Number of decimal digits in `amount`: its order of magnitude, which is all
+/// the record reveals about it.
+pub fn amount_bucket(amount: i128) -> u32 {
+    amount.checked_ilog10().map_or(0, |digits| digits + 1)
+}
+
+/// Load trade `id` and check `details` open its commitment.
+pub fn open(env: &Env, id: u64, details: &HashedTradeDetails) -> Result<HashedTrade, ContractError> {
+    let trade = get(env, id)?;
+    if commitment(env, details) != trade.commitment {
+        return Err(ContractError::CommitmentMismatch);
+    }
+    Ok(trade)
+}
+
+// ---------------------------------------------------------------------------
+// Lifecycle
+// ---------------------------------------------------------------------------
+
+pub fn create(
+    env: &Env,
+    commitment: BytesN<32>,
+    amount_bucket: u32,
+    currency: Address,
+    arbitrator: Address,
+    fee_bps: u32,
+) -> u64 {
+    let id: u64 = env.storage().instance().get(&counter_key()).unwrap_or(0) + 1;
+    env.storage().instance().set(&counter_key(), &id);
+    save(
+        env,
+        id,
+        &HashedTrade {
+            commitment,
+            amount_bucket,
+            currency,
+            arbitrator,
+            fee_bps,
+            status: TradeStatus::Created,
+            created_at: env.ledger().timestamp(),
+            revealed: None,
+        },
+    );
+    id
+}
+
+/// Apply `event` to hashed trade `id`, refusing it while the trade is frozen
+/// or if its status does not allow it.
+pub fn advance(env: &Env, id: u64, trade: &mut HashedTrade, event: TradeEvent) -> Result<(), ContractError> {
+    if is_frozen(env, id) {
+        return Err(ContractError::TradeFrozen);
+    }
+    trade.status = state::next(&trade.status, event)?;
+    Ok(())
+}
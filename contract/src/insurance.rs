@@ -9,7 +9,7 @@ use crate::events;
 
 /// Calculate insurance premium based on trade amount (e.g., 2% flat for this implementation)
 /// Supports multiple providers by allowing them to define premiums (simulated here)
-pub fn calculate_premium(_env: &Env, amount: u64, _provider: &Address) -> u64 {
+pub fn calculate_premium(_env: &Env, amount: i128, _provider: &Address) -> i128 {
     // 2% premium (200 bps)
     amount * 200 / 10000
 }
@@ -39,12 +39,12 @@ pub fn purchase_insurance(
     }
 
     let premium = calculate_premium(env, trade.amount, &provider);
-    if premium > (trade.amount * crate::types::MAX_INSURANCE_PREMIUM_BPS as u64 / 10000) {
+    if premium > (trade.amount * i128::from(crate::types::MAX_INSURANCE_PREMIUM_BPS) / 10000) {
         return Err(ContractError::InsurancePremiumTooHigh);
     }
 
     let token_client = token::Client::new(env, &trade.currency);
-    token_client.transfer(&buyer, &env.current_contract_address(), &(premium as i128));
+    token_client.transfer(&buyer, &env.current_contract_address(), &premium);
 
     let coverage = trade.amount; // 100% coverage
     let policy = InsurancePolicy {
@@ -86,7 +86,7 @@ pub fn claim_insurance(
     let token_client = token::Client::new(env, &trade.currency);
     
     // Transfer from provider to recipient
-    token_client.transfer(&policy.provider, &recipient, &(payout as i128));
+    token_client.transfer(&policy.provider, &recipient, &payout);
 
     policy.claimed = true;
     save_insurance_policy(env, trade_id, &policy);
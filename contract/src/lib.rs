@@ -3,26 +3,52 @@
 #[cfg(test)]
 extern crate std;
 
+mod acceptance;
+mod amount;
 mod analytics;
 mod errors;
 mod events;
+mod export;
+mod fees;
 mod storage;
 pub mod types;
 mod subscription;
 mod templates;
 mod tiers;
+mod loyalty;
+mod badges;
 mod types;
 mod upgrade;
 mod proxy;
+mod factory;
+mod receipt;
+mod attestation;
+mod basket;
+mod pricing;
+mod dispute_bonds;
+mod dispute_responses;
+mod donation;
+mod clock;
+mod user_stats;
+mod hashed_trade;
+mod partners;
+mod payout_swap;
+mod referrals;
+mod regulated;
+mod settlement_batch;
+mod state;
+mod transfer;
+mod trusted_callers;
 mod insurance;
 
-use soroban_sdk::{contract, contractimpl, token, Address, Env, String};
+use soroban_sdk::{contract, contractimpl, symbol_short, token, Address, Env, String};
 
 pub use analytics::{
-    AnalyticsResult, ArbitratorMetrics, PeriodAnalytics, PlatformMetrics, PlatformStats,
+    AnalyticsResult, ArbitratorMetrics, LifetimeTotals, PeriodAnalytics, PlatformMetrics, PlatformStats,
     SuccessRateStats, TimeWindow, VolumeStats,
 };
 pub use errors::ContractError;
+use state::TradeEvent;
 pub use types::{
     ArbitrationConfig, ArbitratorReputation, ArbitratorVote, DisclosureGrant, DisputeResolution,
     InsurancePolicy, MultiSigConfig, Proposal, ProposalAction, ProposalStatus, Subscription,
@@ -31,27 +57,45 @@ pub use types::{
     SubscriptionTier, TierConfig, TierStatus, TierThresholds, TemplateTerms, TemplateVersion,
     Trade, TradePrivacy, TradeStatus, TradeTemplate, UserTier, UserTierInfo, VotingSummary,
     ArbitrationConfig, CrossChainInfo, DisputeResolution, InsurancePolicy, KycStatus,
-    OptionalMetadata, Trade, TradeStatus, UserCompliance, MAX_INSURANCE_PREMIUM_BPS,
+    OptionalMetadata, Trade, TradeStatus, UserCompliance, ArbitratorAssignment, Config,
+    AcceptancePayload, AdminAction, AdminLogEntry, Basket, BasketLeg, BatchLeaf,
+    BatchResolutionResult, BuyerAcceptance, ContractVersion, DisputeBondPolicy, DisputeBonds,
+    DisputeResponses, TimingMode, FixedRate, TradeQuote, Contribution, Deadlines, TradeOptions,
+    LoyaltyConfig, MessageAnchor, PendingFeeWithdrawal, TrackRecord, UserStats, FeeQuote,
+    FeeSchedule, LineItem, LineItemStatus, LineItemTerms, OrderStructure, PartyReputation,
+    PendingFeeChange, ProtocolFeeShare, PendingEmergencyWithdrawal, PayoutShare, PendingResolution,
+    PromoWindow, Referrer, ReferrerStatus, RefundRequest, ReopenBond, ReopenPolicy, DonationChoice,
+    FeeBreakdown, HashedTrade, HashedTradeDetails, Partner, PayoutSwap, SettlementBatch,
+    SolvencyReport, StreamSchedule, TradeFreeze, TradePartner, TtlConfig, VersionedTrade,
+    VolumeBreakerConfig, VolumeWindow, ASSIGNMENT_GRACE_PERIOD, EMERGENCY_WITHDRAWAL_DELAY,
+    MAX_BATCH_RESOLUTIONS, MAX_BULK_TRADES, MAX_SWEEP_TRADES, MAX_DONATION_BPS,
+    MAX_REFERRAL_SHARE_BPS, PAUSE_ALL_FUNCTIONS, PAUSE_CREATE, PAUSE_DISPUTE, PAUSE_FUND,
+    PAUSE_WITHDRAW, SECONDS_PER_LEDGER, MAX_LINE_ITEMS, MAX_MEMO_LEN, MAX_MIN_FEE_SHARE_BPS,
+    MAX_PAYOUT_RECIPIENTS, MAX_PROTOCOL_FEE_SHARE_BPS, REFUND_RESPONSE_WINDOW,
+    MAX_ARBITRATOR_CATEGORIES, MAX_MESSAGE_ANCHORS, MIN_FEE_WITHDRAWAL_DELAY,
+    TRADE_SCHEMA_VERSION, MAX_INSURANCE_PREMIUM_BPS,
     MAX_METADATA_SIZE,
     ArbitrationConfig, ArbitratorReputation, ArbitratorVote, DisclosureGrant, DisputeResolution,
     MultiSigConfig, PriceTrigger, Proposal, ProposalAction, ProposalStatus, Subscription,
     SubscriptionTier, TemplateTerms, TemplateVersion, Trade, TradePrivacy, TradeStatus,
     TradeTemplate, TriggerAction, UserTier, UserTierInfo, VotingSummary,
 };
-pub use queries::{PageParams, SortDirection, TradeFilter, TradeSortField, TradeStats};
+pub use queries::{PageParams, SortDirection, TradeFilter, TradeSortField, TradeStats, TradeSummary};
 pub use oracle::{OracleEntry, PriceData, PriceValidation};
+pub use receipt::{ReceiptOutcome, TradeReceipt};
 pub use bridge::{BridgeProvider, CrossChainTrade, BridgeAttestation, BridgeValidation};
 pub use upgrade::{RollbackSnapshot, UpgradeProposal};
 pub use proxy::*;
+pub use factory::*;
 
 use storage::{
-    add_accumulated_fees, add_currency_fees, get_accumulated_fees, get_admin, get_currency_fees,
+    add_accumulated_fees, get_accumulated_fees, get_admin, get_currency_fees,
     get_fee_bps, get_trade, get_trade_counter, get_usdc_token, has_arbitrator, has_rated,
     increment_trade_counter, is_initialized, is_paused, mark_rated, remove_arbitrator,
     save_arbitrator, save_arbitrator_reputation, save_trade, set_accumulated_fees, set_admin,
-    set_currency_fees, set_fee_bps, set_initialized, set_paused, set_trade_counter, set_usdc_token,
-    set_currency_fees, set_fee_bps, set_initialized, set_paused, set_trade_counter,
-    set_usdc_token, CrossChainInfo, InsurancePolicy,
+    set_currency_fees, set_fee_bps, set_paused,
+    set_currency_fees, set_fee_bps, set_paused,
+    CrossChainInfo, InsurancePolicy,
     has_insurance_provider, save_insurance_provider, remove_insurance_provider,
 };
 
@@ -69,6 +113,14 @@ fn require_not_paused(env: &Env) -> Result<(), ContractError> {
     Ok(())
 }
 
+/// Reject a call whose capability (`PAUSE_*`) is paused on its own.
+fn require_function_active(env: &Env, function: u32) -> Result<(), ContractError> {
+    if storage::get_paused_functions(env) & function != 0 {
+        return Err(ContractError::FunctionPaused);
+    }
+    Ok(())
+}
+
 fn validate_metadata(metadata: &OptionalMetadata) -> Result<(), ContractError> {
     match metadata {
         OptionalMetadata::None => Ok(()),
@@ -85,7 +137,7 @@ fn validate_metadata(metadata: &OptionalMetadata) -> Result<(), ContractError> {
     }
 }
 
-fn validate_user_compliance(env: &Env, user: &Address, amount: u64) -> Result<(), ContractError> {
+fn validate_user_compliance(env: &Env, user: &Address, amount: i128) -> Result<(), ContractError> {
     let compliance = storage::get_user_compliance(env, user).ok_or(ContractError::ComplianceDataMissing)?;
     if compliance.kyc_status != KycStatus::Verified {
         return Err(ContractError::KycNotVerified);
@@ -106,18 +158,680 @@ fn validate_user_compliance(env: &Env, user: &Address, amount: u64) -> Result<()
     Ok(())
 }
 
-fn calc_fee(env: &Env, amount: u64) -> Result<u64, ContractError> {
-    amount
-        .checked_mul(storage::get_fee_bps(env)? as u64)
+/// Address that receives the seller's side of a release: the assignee of
+/// the payout rights, else the designated payee, else the seller.
+fn seller_payee(env: &Env, trade: &Trade) -> Address {
+    storage::get_payout_assignee(env, trade.id)
+        .or_else(|| storage::get_trade_payee(env, trade.id))
+        .unwrap_or(trade.seller.clone())
+}
+
+/// Transfer the seller's side of a release, along with the matching share
+/// of any basket legs, under the trade's settlement guard.
+fn pay_seller_side(
+    env: &Env,
+    token_client: &token::Client,
+    trade: &Trade,
+    amount: i128,
+) -> Result<(), ContractError> {
+    state::begin_settlement(env, trade)?;
+    transfer_seller_side(env, token_client, trade, amount)?;
+    for part in basket::release(env, trade, amount)?.iter() {
+        transfer_seller_side(env, &token::Client::new(env, &part.token), trade, part.amount)?;
+    }
+    state::end_settlement(env, trade.id);
+    Ok(())
+}
+
+/// Transfer `amount` of one token to the seller side, distributing it across
+/// the trade's payout split if one is set. An assignment of the payout
+/// rights overrides both. The last recipient absorbs rounding dust.
+fn transfer_seller_side(
+    env: &Env,
+    token_client: &token::Client,
+    trade: &Trade,
+    amount: i128,
+) -> Result<(), ContractError> {
+    if let Some(assignee) = storage::get_payout_assignee(env, trade.id) {
+        return regulated::pay_out(env, token_client, &assignee, amount);
+    }
+    let shares = match storage::get_payout_split(env, trade.id) {
+        Some(s) => s,
+        None => {
+            return payout_swap::pay_seller(env, token_client, trade, &seller_payee(env, trade), amount);
+        }
+    };
+    let mut remaining = amount;
+    let last = shares.len() - 1;
+    for (i, share) in shares.iter().enumerate() {
+        let part = if i as u32 == last {
+            remaining
+        } else {
+            amount
+                .checked_mul(i128::from(share.bps))
+                .ok_or(ContractError::Overflow)?
+                / 10_000
+        };
+        remaining = remaining.checked_sub(part).ok_or(ContractError::Overflow)?;
+        if part > 0 {
+            regulated::pay_out(env, token_client, &share.recipient, part)?;
+        }
+    }
+    Ok(())
+}
+
+/// Transfer the buyer's side of a refund under the trade's settlement guard.
+fn pay_buyer_side(
+    env: &Env,
+    token_client: &token::Client,
+    trade: &Trade,
+    amount: i128,
+) -> Result<(), ContractError> {
+    state::begin_settlement(env, trade)?;
+    transfer_buyer_side(env, token_client, trade, amount)?;
+    state::end_settlement(env, trade.id);
+    Ok(())
+}
+
+/// Pooled trades return `amount` to each contributor in proportion to what
+/// they put in; the last contributor absorbs rounding dust.
+fn transfer_buyer_side(
+    env: &Env,
+    token_client: &token::Client,
+    trade: &Trade,
+    amount: i128,
+) -> Result<(), ContractError> {
+    // Basket trades cannot be pooled, so their legs always go to the buyer.
+    for part in basket::release(env, trade, amount)?.iter() {
+        regulated::pay_out(env, &token::Client::new(env, &part.token), &trade.buyer, part.amount)?;
+    }
+    let contributions = match storage::get_contributions(env, trade.id) {
+        Some(c) if !c.is_empty() => c,
+        _ => {
+            return regulated::pay_out(env, token_client, &trade.buyer, amount);
+        }
+    };
+    let mut remaining = amount;
+    let last = contributions.len() - 1;
+    for (i, c) in contributions.iter().enumerate() {
+        let part = if i as u32 == last {
+            remaining
+        } else {
+            amount
+                .checked_mul(c.amount)
+                .ok_or(ContractError::Overflow)?
+                / trade.amount
+        };
+        remaining = remaining.checked_sub(part).ok_or(ContractError::Overflow)?;
+        if part > 0 {
+            regulated::pay_out(env, token_client, &c.payer, part)?;
+        }
+    }
+    Ok(())
+}
+
+/// Who rules on `trade`'s dispute: its arbitrator, or the admin for a trade
+/// that was escalated without one.
+fn dispute_resolver(env: &Env, trade: &Trade) -> Result<Address, ContractError> {
+    match trade.arbitrator.clone() {
+        Some(arbitrator) => Ok(arbitrator),
+        None => storage::get_admin(env),
+    }
+}
+
+/// Checks a ruling on `trade` must pass before any token moves.
+fn check_resolution(env: &Env, trade: &Trade, resolution: &DisputeResolution) -> Result<(), ContractError> {
+    if trade.status != TradeStatus::Disputed {
+        return Err(ContractError::InvalidStatus);
+    }
+    state::require_not_frozen(env, trade.id)?;
+    dispute_bonds::require_ruling_open(env, trade.id)?;
+    if let DisputeResolution::Partial(buyer_bps) = resolution {
+        if *buyer_bps > 10_000 {
+            return Err(ContractError::InvalidSplitBps);
+        }
+    }
+    Ok(())
+}
+
+/// Record an authorised arbitrator's ruling on a disputed trade: settle it
+/// now, or hold it as pending while a re-open window or admin supervision
+/// applies.
+fn apply_resolution(
+    env: &Env,
+    trade: &mut Trade,
+    arbitrator: &Address,
+    resolution: DisputeResolution,
+    reason_hash: soroban_sdk::BytesN<32>,
+) -> Result<(), ContractError> {
+    check_resolution(env, trade, &resolution)?;
+    let policy = storage::get_reopen_policy(env);
+    let threshold = storage::get_supervision_threshold(env);
+    // An admin ruling on an arbitrator-less trade needs no countersignature.
+    let awaiting_admin = threshold > 0 && trade.amount >= threshold && trade.arbitrator.is_some();
+    if policy.window == 0 && !awaiting_admin {
+        state::transition(env, trade, TradeEvent::Rule, arbitrator)?;
+        return settle_resolution(env, trade, arbitrator, resolution, reason_hash);
+    }
+    let executable_at = clock::after(env, TimingMode::Timestamp, policy.window)?;
+    storage::save_pending_resolution(
+        env,
+        trade.id,
+        &PendingResolution { resolution: resolution.clone(), reason_hash, executable_at, awaiting_admin },
+    );
+    state::transition(env, trade, TradeEvent::Rule, arbitrator)?;
+    storage::save_trade(env, trade.id, trade);
+    events::emit_resolution_pending(env, trade.id, resolution, executable_at);
+    Ok(())
+}
+
+/// Credit the arbitrator for a ruling: their declared service fee, capped
+/// at the trade fee and taken out of the platform fee, plus `ruling_fee`
+/// already withheld from the disputed amount at their fee rate. Earnings are
+/// held in the platform token; on trades in other currencies the service
+/// fee stays with the platform and the ruling fee is paid out directly.
+/// Returns the part taken from the platform fee.
+fn credit_arbitrator(env: &Env, trade: &Trade, arbitrator: &Address, ruling_fee: i128) -> Result<i128, ContractError> {
+    if trade.currency != storage::get_usdc_token(env)? {
+        if ruling_fee > 0 {
+            transfer::send(
+                &token::Client::new(env, &trade.currency),
+                &env.current_contract_address(),
+                arbitrator,
+                ruling_fee,
+            )?;
+            events::emit_arb_earned(env, arbitrator.clone(), trade.id, ruling_fee);
+        }
+        return Ok(0);
+    }
+    let from_platform = fees::arbitrator_service_fee(env, trade, arbitrator)?;
+    let earned = from_platform.checked_add(ruling_fee).ok_or(ContractError::Overflow)?;
+    if earned == 0 {
+        return Ok(0);
+    }
+    let total = storage::get_arbitrator_earnings(env, arbitrator)
+        .checked_add(earned)
+        .ok_or(ContractError::Overflow)?;
+    storage::set_arbitrator_earnings(env, arbitrator, total);
+    events::emit_arb_earned(env, arbitrator.clone(), trade.id, earned);
+    Ok(from_platform)
+}
+
+/// Settle a dispute one party has defaulted on, without an arbitrator
+/// ruling. Carries no reason hash.
+fn settle_by_default(env: &Env, mut trade: Trade, ruling: DisputeResolution) -> Result<(), ContractError> {
+    let arbitrator = dispute_resolver(env, &trade)?;
+    state::transition(env, &mut trade, TradeEvent::Rule, &env.current_contract_address())?;
+    settle_resolution(env, &mut trade, &arbitrator, ruling, soroban_sdk::BytesN::from_array(env, &[0u8; 32]))
+}
+
+/// Execute an arbitrator's ruling on a `Resolved` trade: transfer funds,
+/// book the fee, release the arbitrator's caseload slot, settle any re-open
+/// bond and move the trade to `Settled`. Saves the trade. When the admin
+/// rules on a trade without an arbitrator, no arbitrator fee is earned and
+/// no caseload slot is released.
+fn settle_resolution(
+    env: &Env,
+    trade: &mut Trade,
+    arbitrator: &Address,
+    resolution: DisputeResolution,
+    reason_hash: soroban_sdk::BytesN<32>,
+) -> Result<(), ContractError> {
+    let streamed = storage::get_streamed_amount(env, trade.id);
+    let disputed = trade
+        .amount
+        .checked_sub(trade.fee)
+        .and_then(|n| n.checked_sub(streamed))
+        .ok_or(ContractError::Overflow)?;
+    let admin_ruling = trade.arbitrator.is_none();
+    let ruling_fee = if admin_ruling { 0 } else { fees::ruling_fee(env, arbitrator, disputed)? };
+    let net = disputed - ruling_fee;
+    let token_client = token::Client::new(env, &trade.currency);
+    storage::sub_total_locked(env, &trade.currency, trade.amount - streamed)?;
+    match resolution.clone() {
+        DisputeResolution::ReleaseToBuyer => {
+            pay_buyer_side(env, &token_client, trade, net)?;
+            badges::record_dispute_lost(env, &trade.seller);
+            user_stats::record_dispute(env, &trade.buyer, &trade.seller);
+            events::emit_dispute_resolved(env, trade.id, resolution.clone(), trade.buyer.clone(), reason_hash.clone());
+        }
+        DisputeResolution::ReleaseToSeller => {
+            pay_seller_side(env, &token_client, trade, net)?;
+            badges::record_dispute_lost(env, &trade.buyer);
+            user_stats::record_dispute(env, &trade.seller, &trade.buyer);
+            events::emit_dispute_resolved(env, trade.id, resolution.clone(), seller_payee(env, trade), reason_hash.clone());
+        }
+        DisputeResolution::Partial(buyer_bps) => {
+            let buyer_amount = net
+                .checked_mul(i128::from(buyer_bps))
+                .ok_or(ContractError::Overflow)?
+                .checked_div(10_000)
+                .ok_or(ContractError::Overflow)?;
+            let seller_amount = net
+                .checked_sub(buyer_amount)
+                .ok_or(ContractError::Overflow)?;
+            if buyer_amount > 0 {
+                pay_buyer_side(env, &token_client, trade, buyer_amount)?;
+            }
+            if seller_amount > 0 {
+                pay_seller_side(env, &token_client, trade, seller_amount)?;
+            }
+            events::emit_partial_resolved(env, trade.id, buyer_amount, seller_amount, trade.fee, reason_hash.clone());
+        }
+    }
+    if admin_ruling {
+        book_trade_fee(env, trade, trade.fee)?;
+    } else {
+        let from_platform = credit_arbitrator(env, trade, arbitrator, ruling_fee)?;
+        book_trade_fee(env, trade, trade.fee - from_platform)?;
+        storage::decrement_arbitrator_caseload(env, arbitrator);
+    }
+    storage::save_resolution_reason(env, trade.id, &reason_hash);
+    receipt::issue(env, trade, ReceiptOutcome::Resolved);
+    record_settlement(env, trade)?;
+    dispute_bonds::settle(env, trade, arbitrator, &resolution)?;
+    storage::remove_dispute_responses(env, trade.id);
+    if let Some(bond) = storage::get_reopen_bond(env, trade.id) {
+        if bond.amount > 0 {
+            storage::sub_total_locked(env, &trade.currency, bond.amount)?;
+            if bond.contested != resolution {
+                transfer::send(&token_client, &env.current_contract_address(), &bond.payer, bond.amount)?;
+            } else {
+                storage::add_withdrawable_fees(env, &trade.currency, bond.amount)?;
+            }
+        }
+    }
+    state::transition(env, trade, TradeEvent::Settle, arbitrator)?;
+    storage::save_trade(env, trade.id, trade);
+    Ok(())
+}
+
+/// Fee share attributable to line item `index`. Items pay a pro-rata share
+/// of the trade fee; the last item to settle absorbs rounding dust so the
+/// bundle books exactly `trade.fee` in total.
+fn line_item_fee(
+    trade: &Trade,
+    items: &soroban_sdk::Vec<LineItem>,
+    index: u32,
+) -> Result<i128, ContractError> {
+    let pro_rata = |amount: i128| -> Result<i128, ContractError> {
+        Ok(trade
+            .fee
+            .checked_mul(amount)
+            .ok_or(ContractError::Overflow)?
+            / trade.amount)
+    };
+    let last_open = items
+        .iter()
+        .enumerate()
+        .all(|(i, item)| i as u32 == index || !is_line_item_unsettled(&item));
+    if !last_open {
+        let item = items.get(index).ok_or(ContractError::LineItemNotFound)?;
+        return pro_rata(item.amount);
+    }
+    let mut others = 0i128;
+    for (i, item) in items.iter().enumerate() {
+        if i as u32 != index {
+            others = others.checked_add(pro_rata(item.amount)?).ok_or(ContractError::Overflow)?;
+        }
+    }
+    trade.fee.checked_sub(others).ok_or(ContractError::Overflow)
+}
+
+fn is_line_item_unsettled(item: &LineItem) -> bool {
+    item.status == LineItemStatus::Open || item.status == LineItemStatus::Disputed
+}
+
+/// Book a settled line item and close the trade once every item is settled.
+fn close_line_item(
+    env: &Env,
+    actor: &Address,
+    trade: &mut Trade,
+    items: &mut soroban_sdk::Vec<LineItem>,
+    index: u32,
+    status: LineItemStatus,
+    fee: i128,
+) -> Result<(), ContractError> {
+    let mut item = items.get(index).ok_or(ContractError::LineItemNotFound)?;
+    item.status = status;
+    items.set(index, item.clone());
+    storage::save_line_items(env, trade.id, items);
+    book_trade_fee(env, trade, fee)?;
+    if !items.iter().any(|i| is_line_item_unsettled(&i)) {
+        state::transition(env, trade, TradeEvent::Settle, actor)?;
+        storage::save_trade(env, trade.id, trade);
+        receipt::issue(env, trade, ReceiptOutcome::Completed);
+        record_settlement(env, trade)?;
+    }
+    Ok(())
+}
+
+/// Forward the protocol's share of a fee withdrawal, if one is configured.
+/// Returns what is left for the operator.
+fn forward_protocol_share(env: &Env, currency: &Address, amount: i128) -> Result<i128, ContractError> {
+    let share = match storage::get_protocol_fee_share(env) {
+        Some(s) if s.bps > 0 => s,
+        _ => return Ok(amount),
+    };
+    let cut = protocol_cut(env, amount)?;
+    if cut > 0 {
+        transfer::send(&token::Client::new(env, currency), &env.current_contract_address(), &share.recipient, cut)?;
+        events::emit_protocol_fee_paid(env, currency.clone(), share.recipient, cut);
+    }
+    amount.checked_sub(cut).ok_or(ContractError::Overflow)
+}
+
+/// Protocol fee share owed on `amount`.
+fn protocol_cut(env: &Env, amount: i128) -> Result<i128, ContractError> {
+    match storage::get_protocol_fee_share(env) {
+        Some(s) => Ok(amount.checked_mul(i128::from(s.bps)).ok_or(ContractError::Overflow)? / 10_000),
+        None => Ok(0),
+    }
+}
+
+/// Count a trade that reached a settled outcome in platform and per-user totals.
+fn record_settlement(env: &Env, trade: &Trade) -> Result<(), ContractError> {
+    analytics::on_trade_settled(env, &trade.currency, trade.amount)?;
+    user_stats::record_settled(env, trade)
+}
+
+/// Book `fee` collected on `trade`, paying any partner and then the buyer's
+/// referrer their shares first.
+fn book_trade_fee(env: &Env, trade: &Trade, fee: i128) -> Result<(), ContractError> {
+    let platform = partners::take(env, trade, fee)?;
+    let platform = referrals::take(env, trade, platform)?;
+    book_fee(env, &trade.currency, platform)
+}
+
+/// Book a collected trade fee: burn the configured share, then forward the
+/// rest to the treasury if one is set, else accumulate it for withdrawal in
+/// `currency`. A share the token refuses to burn is kept for withdrawal.
+fn book_fee(env: &Env, currency: &Address, fee: i128) -> Result<(), ContractError> {
+    analytics::on_fees_collected(env, currency, fee)?;
+    let burned = fee
+        .checked_mul(i128::from(storage::get_fee_burn_bps(env)))
         .ok_or(ContractError::Overflow)?
-        .checked_div(10_000)
-        .ok_or(ContractError::Overflow)
+        / 10_000;
+    let token_client = token::Client::new(env, currency);
+    if burned > 0 {
+        // A token that cannot burn must never block settlement.
+        if transfer::burn(&token_client, burned).is_ok() {
+            storage::add_total_burned(env, currency, burned)?;
+            events::emit_fee_burned(env, currency.clone(), burned);
+        } else {
+            storage::add_withdrawable_fees(env, currency, burned)?;
+            events::emit_fee_burn_failed(env, currency.clone(), burned);
+        }
+    }
+    let net = fee - burned;
+    if net == 0 {
+        return Ok(());
+    }
+    if let Some(treasury) = storage::get_treasury(env) {
+        let to_treasury = net.checked_sub(protocol_cut(env, net)?).ok_or(ContractError::Overflow)?;
+        // A treasury that cannot receive must never block settlement; keep
+        // the fee in accumulation instead.
+        let sent = token_client
+            .try_transfer(&env.current_contract_address(), &treasury, &to_treasury)
+            .is_ok_and(|r| r.is_ok());
+        if sent {
+            forward_protocol_share(env, currency, net)?;
+            events::emit_fee_forwarded(env, currency.clone(), treasury, to_treasury);
+            return Ok(());
+        }
+        events::emit_fee_forward_failed(env, currency.clone(), treasury, net);
+    }
+    storage::add_withdrawable_fees(env, currency, net)
+}
+
+/// Checks shared by every funding path.
+fn require_fundable(env: &Env, trade: &Trade) -> Result<(), ContractError> {
+    if trade.status != TradeStatus::Created {
+        return Err(ContractError::InvalidStatus);
+    }
+    if funding_deadline_passed(env, trade.id) {
+        return Err(ContractError::FundingDeadlinePassed);
+    }
+    attestation::require_attested(env, &[&trade.seller, &trade.buyer])?;
+    if let Some(assignment) = storage::get_assignment(env, trade.id) {
+        if !assignment.accepted {
+            return Err(ContractError::AssignmentNotAccepted);
+        }
+    }
+    Ok(())
+}
+
+/// A `Created` trade can be expired once its funding deadline has passed.
+fn check_expirable(env: &Env, trade: &Trade) -> Result<(), ContractError> {
+    if trade.status != TradeStatus::Created {
+        return Err(ContractError::InvalidStatus);
+    }
+    if !funding_deadline_passed(env, trade.id) {
+        return Err(ContractError::FundingDeadlineNotReached);
+    }
+    Ok(())
+}
+
+/// A `Funded` trade can be refunded in full once its completion deadline
+/// has passed, unless part of it has already been paid out.
+fn check_overdue_refund(env: &Env, trade: &Trade) -> Result<(), ContractError> {
+    require_not_paused(env)?;
+    if storage::is_bundle(env, trade.id) {
+        return Err(ContractError::BundleRequiresItemSettlement);
+    }
+    if storage::get_streamed_amount(env, trade.id) > 0 {
+        return Err(ContractError::StreamInProgress);
+    }
+    if trade.status != TradeStatus::Funded {
+        return Err(ContractError::InvalidStatus);
+    }
+    if !deadline_passed(env, trade_deadlines(env, trade.id).complete_by) {
+        return Err(ContractError::DeadlineNotReached);
+    }
+    Ok(())
+}
+
+/// Hand back every contribution to a pooled trade that never became fully funded.
+fn return_contributions(env: &Env, trade: &Trade) -> Result<(), ContractError> {
+    if let Some(contributions) = storage::get_contributions(env, trade.id) {
+        let token_client = token::Client::new(env, &trade.currency);
+        for c in contributions.iter() {
+            transfer::send(&token_client, &env.current_contract_address(), &c.payer, c.amount)?;
+            events::emit_contribution_returned(env, trade.id, c.payer, c.amount);
+        }
+        storage::remove_contributions(env, trade.id);
+    }
+    Ok(())
+}
+
+fn require_not_pooled(env: &Env, trade_id: u64) -> Result<(), ContractError> {
+    match storage::get_contributions(env, trade_id) {
+        Some(c) if !c.is_empty() => Err(ContractError::PooledFundingInProgress),
+        _ => Ok(()),
+    }
+}
+
+/// Book a trade as funded once its amount is held by the contract.
+fn mark_funded(env: &Env, actor: &Address, trade: &mut Trade) -> Result<(), ContractError> {
+    state::transition(env, trade, TradeEvent::Fund, actor)?;
+    storage::save_trade(env, trade.id, trade);
+    storage::add_total_locked(env, &trade.currency, trade.amount)?;
+    record_funding_volume(env, &trade.currency, trade.amount)?;
+    events::emit_trade_funded(env, trade.id);
+    analytics::on_trade_funded(env);
+    Ok(())
+}
+
+/// Post-confirmation hold for a trade: its category's override, else the default.
+fn release_hold(env: &Env, trade_id: u64) -> u32 {
+    storage::get_trade_category(env, trade_id)
+        .and_then(|c| storage::get_category_hold(env, &c))
+        .unwrap_or_else(|| storage::get_default_hold(env))
+}
+
+/// Reject a new trade once the seller/buyer pair hits the open-trade cap.
+fn require_pair_capacity(env: &Env, seller: &Address, buyer: &Address) -> Result<(), ContractError> {
+    let cap = storage::get_pair_trade_cap(env);
+    if cap > 0 && storage::get_pair_open_trades(env, seller, buyer) >= cap {
+        return Err(ContractError::PairTradeLimitReached);
+    }
+    Ok(())
+}
+
+/// An arbitrator with a stake in the trade cannot rule on it.
+fn require_disinterested(arbitrator: &Address, seller: &Address, buyer: &Address) -> Result<(), ContractError> {
+    if arbitrator == seller || arbitrator == buyer {
+        return Err(ContractError::ArbitratorIsParty);
+    }
+    Ok(())
+}
+
+/// A trade's deadlines, with any stage left unset falling back to the admin
+/// window (in ledgers) counted from creation. Zero means no timeout.
+fn trade_deadlines(env: &Env, trade_id: u64) -> Deadlines {
+    let mut deadlines = storage::get_trade_deadlines(env, trade_id).unwrap_or_default();
+    let created = storage::get_created_at(env, trade_id);
+    let from_window = |window: u32| match created {
+        Some(c) if window > 0 => c.saturating_add(clock::span(env, TimingMode::Ledger, window as u64)),
+        _ => 0,
+    };
+    if deadlines.fund_by == 0 {
+        deadlines.fund_by = from_window(storage::get_funding_deadline(env));
+    }
+    if deadlines.complete_by == 0 {
+        deadlines.complete_by = from_window(storage::get_completion_window(env));
+    }
+    if deadlines.confirm_by == 0 {
+        deadlines.confirm_by = from_window(storage::get_confirmation_window(env));
+    }
+    deadlines
+}
+
+fn deadline_passed(env: &Env, deadline: u64) -> bool {
+    deadline > 0 && clock::now(env, TimingMode::Ledger) >= deadline
+}
+
+/// True once a `Created` trade has sat unfunded past its funding deadline.
+fn funding_deadline_passed(env: &Env, trade_id: u64) -> bool {
+    deadline_passed(env, trade_deadlines(env, trade_id).fund_by)
+}
+
+/// Explicit deadlines must lie in the future and not run out of order.
+fn validate_deadlines(env: &Env, deadlines: &Deadlines) -> Result<(), ContractError> {
+    let now = clock::now(env, TimingMode::Ledger);
+    let mut last = 0u64;
+    for stage in [deadlines.fund_by, deadlines.complete_by, deadlines.confirm_by] {
+        if stage == 0 {
+            continue;
+        }
+        if stage <= now || stage < last {
+            return Err(ContractError::InvalidDeadlines);
+        }
+        last = stage;
+    }
+    Ok(())
+}
+
+/// Reject new trades and funding while the funding-volume circuit breaker
+/// is tripped.
+fn require_breaker_clear(env: &Env) -> Result<(), ContractError> {
+    if storage::is_breaker_tripped(env) {
+        return Err(ContractError::CircuitBreakerTripped);
+    }
+    Ok(())
+}
+
+/// Add `amount` to `currency`'s breaker window, rolling it over when expired,
+/// and trip the breaker once the configured threshold is exceeded. Amounts in
+/// different tokens are never summed; each currency is measured in its own units.
+fn record_funding_volume(env: &Env, currency: &Address, amount: i128) -> Result<(), ContractError> {
+    let config = match storage::get_volume_breaker(env) {
+        Some(c) => c,
+        None => return Ok(()),
+    };
+    let seq = env.ledger().sequence();
+    let end = |w: &VolumeWindow| w.window_start.saturating_add(config.window_ledgers);
+    let windows = storage::get_volume_windows(env);
+    let mut window = match windows.get(currency.clone()) {
+        Some(w) if seq < end(&w) => w,
+        _ => VolumeWindow { window_start: seq, volume: 0 },
+    };
+    window.volume = window.volume.checked_add(amount).ok_or(ContractError::Overflow)?;
+    if !storage::is_breaker_tripped(env) && window.volume > config.threshold {
+        storage::set_breaker_tripped(env, true);
+        events::emit_volume_alert(env, currency.clone(), window.volume, config.threshold, window.window_start);
+    }
+    // Drop windows that have rolled over; the entry lives as long as the last open one.
+    let mut live = soroban_sdk::Map::new(env);
+    let mut remaining = end(&window).saturating_sub(seq);
+    live.set(currency.clone(), window);
+    for (token, w) in windows.iter() {
+        if token != *currency && seq < end(&w) {
+            remaining = remaining.max(end(&w).saturating_sub(seq));
+            live.set(token, w);
+        }
+    }
+    storage::set_volume_windows(env, &live, remaining);
+    Ok(())
+}
+
+/// Reject assignment to an arbitrator already at the configured caseload cap.
+fn require_caseload_capacity(env: &Env, arbitrator: &Address) -> Result<(), ContractError> {
+    let cap = storage::get_arbitrator_caseload_cap(env);
+    if cap > 0 && storage::get_arbitrator_caseload(env, arbitrator) >= cap {
+        return Err(ContractError::ArbitratorCaseloadFull);
+    }
+    Ok(())
+}
+
+/// A trade requesting `category` must name an arbitrator tagged with it.
+fn validate_arbitrator_category(
+    env: &Env,
+    arbitrator: Option<&Address>,
+    category: &soroban_sdk::Symbol,
+) -> Result<(), ContractError> {
+    let arbitrator = arbitrator.ok_or(ContractError::CategoryRequiresArbitrator)?;
+    if !storage::get_arbitrator_categories(env, arbitrator).contains(category) {
+        return Err(ContractError::ArbitratorCategoryMismatch);
+    }
+    Ok(())
+}
+
+fn calc_fee(env: &Env, seller: &Address, token: &Address, amount: i128) -> Result<i128, ContractError> {
+    Ok(fees::quote(env, seller, token, amount)?.fee)
 }
 
 fn usdc_client<'a>(env: &'a Env) -> Result<token::Client<'a>, ContractError> {
     Ok(token::Client::new(env, &storage::get_usdc_token(env)?))
 }
 
+/// Keeper bounty for triggering a timeout on a funded `trade`: the
+/// configured share of the trade's own fee, never more than the fee. Zero
+/// without a keeper.
+fn keeper_bounty(env: &Env, trade: &Trade, keeper: &Option<Address>) -> Result<i128, ContractError> {
+    if keeper.is_none() {
+        return Ok(0);
+    }
+    let bounty = trade
+        .fee
+        .checked_mul(i128::from(storage::get_keeper_bounty_bps(env)))
+        .ok_or(ContractError::Overflow)?
+        / 10_000;
+    Ok(bounty.clamp(0, trade.fee.max(0)))
+}
+
+/// Pay `bounty`, already withheld from `trade`'s escrow, to the keeper in
+/// the trade's currency.
+fn pay_keeper(env: &Env, trade: &Trade, keeper: Option<Address>, bounty: i128) -> Result<(), ContractError> {
+    let keeper = match keeper {
+        Some(k) if bounty > 0 => k,
+        _ => return Ok(()),
+    };
+    transfer::send(&token::Client::new(env, &trade.currency), &env.current_contract_address(), &keeper, bounty)?;
+    events::emit_keeper_paid(env, trade.id, keeper, trade.currency.clone(), bounty);
+    Ok(())
+}
+
 #[contract]
 pub struct StellarEscrowContract;
 
@@ -136,21 +850,29 @@ impl StellarEscrowContract {
             return Err(ContractError::InvalidFeeBps);
         }
         admin.require_auth();
-        storage::set_admin(&env, &admin);
-        storage::set_usdc_token(&env, &usdc_token);
-        storage::set_fee_bps(&env, fee_bps);
-        storage::set_trade_counter(&env, 0);
-        storage::set_accumulated_fees(&env, 0);
+        storage::save_config(
+            &env,
+            &Config {
+                admin,
+                usdc_token,
+                fee_bps,
+                trade_counter: 0,
+                accumulated_fees: 0,
+                total_locked: 0,
+            },
+        );
         storage::set_version(&env, 1);
-        storage::set_initialized(&env);
+        storage::set_contract_version(&env, &upgrade::build_version(None));
         Ok(())
     }
 
     pub fn register_arbitrator(env: Env, arbitrator: Address) -> Result<(), ContractError> {
         require_initialized(&env)?;
         require_not_paused(&env)?;
-        storage::get_admin(&env)?.require_auth();
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
         storage::save_arbitrator(&env, &arbitrator);
+        storage::append_admin_log(&env, &admin, AdminAction::ArbitratorRegistered);
         events::emit_arbitrator_registered(&env, arbitrator);
         Ok(())
     }
@@ -158,8 +880,10 @@ impl StellarEscrowContract {
     pub fn remove_arbitrator_fn(env: Env, arbitrator: Address) -> Result<(), ContractError> {
         require_initialized(&env)?;
         require_not_paused(&env)?;
-        storage::get_admin(&env)?.require_auth();
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
         storage::remove_arbitrator(&env, &arbitrator);
+        storage::append_admin_log(&env, &admin, AdminAction::ArbitratorRemoved);
         events::emit_arbitrator_removed(&env, arbitrator);
         Ok(())
     }
@@ -168,12 +892,14 @@ impl StellarEscrowContract {
     // Issue #120 — Arbitrator self-registration registry
     // -------------------------------------------------------------------------
 
-    /// Self-register as an arbitrator with a declared service fee.
-    /// Requires the arbitrator's own auth. Fee must be > 0.
+    /// Self-register as an arbitrator with a declared service fee and a
+    /// dispute fee rate (`fee_bps` of the disputed amount, up to the admin
+    /// cap). Requires the arbitrator's own auth. Fee must be > 0.
     pub fn register_arbitrator_self(
         env: Env,
         arbitrator: Address,
         fee: i128,
+        fee_bps: u32,
     ) -> Result<(), ContractError> {
         require_initialized(&env)?;
         require_not_paused(&env)?;
@@ -181,8 +907,12 @@ impl StellarEscrowContract {
         if fee <= 0 {
             return Err(ContractError::InvalidFeeBps); // reuse closest error variant
         }
+        if fee_bps > storage::get_max_arbitrator_fee_bps(&env) {
+            return Err(ContractError::ArbitratorFeeTooHigh);
+        }
         storage::save_arbitrator(&env, &arbitrator);
         storage::save_arbitrator_fee(&env, &arbitrator, fee);
+        storage::save_arbitrator_fee_bps(&env, &arbitrator, fee_bps);
         storage::add_to_arbitrator_list(&env, &arbitrator);
         env.events().publish(
             (soroban_sdk::Symbol::new(&env, "arbitrator"), soroban_sdk::Symbol::new(&env, "registered")),
@@ -198,6 +928,8 @@ impl StellarEscrowContract {
         arbitrator.require_auth();
         storage::remove_arbitrator(&env, &arbitrator);
         storage::remove_arbitrator_fee(&env, &arbitrator);
+        storage::remove_arbitrator_fee_bps(&env, &arbitrator);
+        storage::remove_arbitrator_categories(&env, &arbitrator);
         storage::remove_from_arbitrator_list(&env, &arbitrator);
         env.events().publish(
             (soroban_sdk::Symbol::new(&env, "arbitrator"), soroban_sdk::Symbol::new(&env, "deregistered")),
@@ -216,65 +948,224 @@ impl StellarEscrowContract {
         storage::get_arbitrator_fee(&env, &arbitrator)
     }
 
-    pub fn is_arbitrator_registered(env: Env, arbitrator: Address) -> bool {
-        storage::has_arbitrator(&env, &arbitrator)
+    pub fn get_arbitrator_fee_bps(env: Env, arbitrator: Address) -> u32 {
+        storage::get_arbitrator_fee_bps(&env, &arbitrator)
+    }
+
+    /// Cap the dispute fee rate arbitrators may charge (admin only). Rates
+    /// set above a lowered cap are clamped to it at resolution.
+    pub fn set_max_arbitrator_fee_bps(env: Env, bps: u32) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        if bps > 10_000 {
+            return Err(ContractError::InvalidFeeBps);
+        }
+        let old = storage::get_max_arbitrator_fee_bps(&env);
+        storage::set_max_arbitrator_fee_bps(&env, bps);
+        events::emit_config_changed(&env, symbol_short!("arb_feemx"), old, bps);
+        Ok(())
+    }
+
+    pub fn get_max_arbitrator_fee_bps(env: Env) -> u32 {
+        storage::get_max_arbitrator_fee_bps(&env)
+    }
+
+    /// Fees `arbitrator` has earned from resolved disputes and not yet withdrawn.
+    pub fn get_arbitrator_earnings(env: Env, arbitrator: Address) -> i128 {
+        storage::get_arbitrator_earnings(&env, &arbitrator)
+    }
+
+    /// Pay out all of `arbitrator`'s earnings to `to`. Arbitrator only.
+    pub fn withdraw_arbitrator_earnings(env: Env, arbitrator: Address, to: Address) -> Result<i128, ContractError> {
+        require_initialized(&env)?;
+        require_function_active(&env, PAUSE_WITHDRAW)?;
+        arbitrator.require_auth();
+        let amount = storage::get_arbitrator_earnings(&env, &arbitrator);
+        if amount == 0 {
+            return Err(ContractError::NoArbitratorEarnings);
+        }
+        storage::set_arbitrator_earnings(&env, &arbitrator, 0);
+        transfer::send(&usdc_client(&env)?, &env.current_contract_address(), &to, amount)?;
+        events::emit_arb_withdrawn(&env, arbitrator, to, amount);
+        Ok(amount)
+    }
+
     // -------------------------------------------------------------------------
-    // Multi-Signature Arbitration
+    // Arbitrator categories / specializations
     // -------------------------------------------------------------------------
 
-    /// Create a trade with multi-signature arbitration.
-    /// All arbitrators in `config` must be registered; threshold must be > 0
-    /// and ≤ arbitrators count.
-
-    /// Cast a vote on a disputed multi-sig trade.
-    pub fn cast_vote(
+    /// Declare the categories (e.g. `electronics`, `services`, `high_value`) a
+    /// registered arbitrator is willing to handle. Replaces any previous tags.
+    pub fn set_arbitrator_categories(
         env: Env,
-        trade_id: u64,
         arbitrator: Address,
-        resolution: DisputeResolution,
+        categories: soroban_sdk::Vec<soroban_sdk::Symbol>,
     ) -> Result<(), ContractError> {
         require_initialized(&env)?;
         require_not_paused(&env)?;
-        multisig::cast_vote(&env, trade_id, &arbitrator, resolution)
+        arbitrator.require_auth();
+        if !storage::has_arbitrator(&env, &arbitrator) {
+            return Err(ContractError::ArbitratorNotRegistered);
+        }
+        if categories.len() > MAX_ARBITRATOR_CATEGORIES {
+            return Err(ContractError::TooManyCategories);
+        }
+        storage::save_arbitrator_categories(&env, &arbitrator, &categories);
+        events::emit_arb_categories_set(&env, arbitrator, categories);
+        Ok(())
     }
 
-    /// Return the current voting state for a multi-sig trade.
-    pub fn get_voting_summary(env: Env, trade_id: u64) -> Result<VotingSummary, ContractError> {
-        require_initialized(&env)?;
-        multisig::voting_summary(&env, trade_id)
+    /// Category tags declared by an arbitrator (empty if none).
+    pub fn get_arbitrator_categories(env: Env, arbitrator: Address) -> soroban_sdk::Vec<soroban_sdk::Symbol> {
+        storage::get_arbitrator_categories(&env, &arbitrator)
     }
 
-    /// Force-resolve a multi-sig dispute after the voting window expires without
-    /// consensus. Defaults to refunding the buyer. Admin only.
-    pub fn resolve_expired_dispute(
-        env: Env,
-        admin: Address,
-        trade_id: u64,
-    ) -> Result<(), ContractError> {
-        require_initialized(&env)?;
-        require_not_paused(&env)?;
-        require_admin(&env, &admin)?;
-        let resolution = multisig::resolve_expired_dispute(&env, trade_id, &admin)?;
-        let trade = get_trade(&env, trade_id)?;
-        StellarEscrowContract::execute_dispute_resolution(env, trade_id, resolution, trade)
+    /// Returns true if `arbitrator` is tagged with `category`.
+    pub fn arbitrator_has_category(env: Env, arbitrator: Address, category: soroban_sdk::Symbol) -> bool {
+        storage::get_arbitrator_categories(&env, &arbitrator).contains(&category)
+    }
+
+    /// Category requested when the trade was created, if any.
+    pub fn get_trade_category(env: Env, trade_id: u64) -> Option<soroban_sdk::Symbol> {
+        storage::get_trade_category(&env, trade_id)
     }
 
+    // -------------------------------------------------------------------------
+    // Arbitrator caseload cap
     // -------------------------------------------------------------------------
 
-    /// Rate the arbitrator of a disputed trade (buyer or seller, once each).
-    pub fn rate_arbitrator(
-        env: Env,
-        trade_id: u64,
-        rater: Address,
-        stars: u32,
-    ) -> Result<(), ContractError> {
+    /// Set the maximum number of open disputes any single arbitrator may hold
+    /// before new assignments are rejected (admin only). 0 disables the cap.
+    pub fn set_arbitrator_caseload_cap(env: Env, cap: u32) -> Result<(), ContractError> {
         require_initialized(&env)?;
-        let trade = get_trade(&env, trade_id)?;
-        let arbitrator = match &trade.arbitrator {
-            Some(arb) => arb.clone(),
-            None => return Err(ContractError::NoArbitrator),
-        };
-        rater.require_auth();
+        storage::get_admin(&env)?.require_auth();
+        let old = storage::get_arbitrator_caseload_cap(&env);
+        storage::set_arbitrator_caseload_cap(&env, cap);
+        events::emit_config_changed(&env, symbol_short!("case_cap"), old, cap);
+        events::emit_caseload_cap_set(&env, cap);
+        Ok(())
+    }
+
+    pub fn get_arbitrator_caseload_cap(env: Env) -> u32 {
+        storage::get_arbitrator_caseload_cap(&env)
+    }
+
+    /// Number of disputes currently open with `arbitrator`.
+    pub fn get_arbitrator_open_disputes(env: Env, arbitrator: Address) -> u32 {
+        storage::get_arbitrator_caseload(&env, &arbitrator)
+    }
+
+    // -------------------------------------------------------------------------
+    // Arbitrator assignment acceptance
+    // -------------------------------------------------------------------------
+
+    /// Accept the assignment to arbitrate `trade_id`. Must be called by the
+    /// named arbitrator within `ASSIGNMENT_GRACE_PERIOD` of trade creation;
+    /// the trade cannot be funded until the assignment is accepted.
+    pub fn accept_assignment(env: Env, trade_id: u64) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Created {
+            return Err(ContractError::InvalidStatus);
+        }
+        let mut assignment = storage::get_assignment(&env, trade_id).ok_or(ContractError::NoArbitrator)?;
+        if assignment.accepted {
+            return Err(ContractError::AssignmentAlreadyAccepted);
+        }
+        assignment.arbitrator.require_auth();
+        require_disinterested(&assignment.arbitrator, &trade.seller, &trade.buyer)?;
+        let deadline = assignment
+            .assigned_at
+            .checked_add(clock::span(&env, TimingMode::Timestamp, ASSIGNMENT_GRACE_PERIOD))
+            .ok_or(ContractError::Overflow)?;
+        if clock::now(&env, TimingMode::Timestamp) > deadline {
+            return Err(ContractError::AssignmentExpired);
+        }
+        assignment.accepted = true;
+        storage::save_assignment(&env, trade_id, &assignment);
+        events::emit_assignment_accepted(&env, trade_id, assignment.arbitrator);
+        Ok(())
+    }
+
+    /// Acceptance state of the arbitrator assignment for `trade_id`, if any.
+    pub fn get_assignment(env: Env, trade_id: u64) -> Option<ArbitratorAssignment> {
+        storage::get_assignment(&env, trade_id)
+    }
+
+    /// Trades ever assigned to `arbitrator`, in any status, oldest first.
+    pub fn get_assigned_trades(
+        env: Env,
+        arbitrator: Address,
+        start: u32,
+        limit: u32,
+    ) -> soroban_sdk::Vec<u64> {
+        storage::get_arbitrator_trades(&env, &arbitrator, start, limit.min(100))
+    }
+
+    pub fn get_assigned_trade_count(env: Env, arbitrator: Address) -> u32 {
+        storage::get_arbitrator_trade_count(&env, &arbitrator)
+    }
+
+    pub fn is_arbitrator_registered(env: Env, arbitrator: Address) -> bool {
+        storage::has_arbitrator(&env, &arbitrator)
+    // -------------------------------------------------------------------------
+    // Multi-Signature Arbitration
+    // -------------------------------------------------------------------------
+
+    /// Create a trade with multi-signature arbitration.
+    /// All arbitrators in `config` must be registered; threshold must be > 0
+    /// and ≤ arbitrators count.
+
+    /// Cast a vote on a disputed multi-sig trade.
+    pub fn cast_vote(
+        env: Env,
+        trade_id: u64,
+        arbitrator: Address,
+        resolution: DisputeResolution,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        multisig::cast_vote(&env, trade_id, &arbitrator, resolution)
+    }
+
+    /// Return the current voting state for a multi-sig trade.
+    pub fn get_voting_summary(env: Env, trade_id: u64) -> Result<VotingSummary, ContractError> {
+        require_initialized(&env)?;
+        multisig::voting_summary(&env, trade_id)
+    }
+
+    /// Force-resolve a multi-sig dispute after the voting window expires without
+    /// consensus. Defaults to refunding the buyer. Admin only.
+    pub fn resolve_expired_dispute(
+        env: Env,
+        admin: Address,
+        trade_id: u64,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        require_admin(&env, &admin)?;
+        let resolution = multisig::resolve_expired_dispute(&env, trade_id, &admin)?;
+        let trade = get_trade(&env, trade_id)?;
+        StellarEscrowContract::execute_dispute_resolution(env, trade_id, resolution, trade)
+    }
+
+    // -------------------------------------------------------------------------
+
+    /// Rate the arbitrator of a disputed trade (buyer or seller, once each).
+    pub fn rate_arbitrator(
+        env: Env,
+        trade_id: u64,
+        rater: Address,
+        stars: u32,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        let trade = get_trade(&env, trade_id)?;
+        let arbitrator = match &trade.arbitrator {
+            Some(arb) => arb.clone(),
+            None => return Err(ContractError::NoArbitrator),
+        };
+        rater.require_auth();
         reputation::rate_arbitrator(
             &env,
             trade_id,
@@ -324,18 +1215,162 @@ impl StellarEscrowContract {
         reputation::get_reputations(&env, &arbitrators)
     }
 
-    pub fn update_fee(env: Env, fee_bps: u32) -> Result<(), ContractError> {
+    /// Change the platform fee from `effective_from_ledger` onward (admin
+    /// only). A ledger at or before the current one applies immediately.
+    /// Trades already created keep the fee snapshotted at creation.
+    pub fn update_fee(env: Env, fee_bps: u32, effective_from_ledger: u32) -> Result<(), ContractError> {
         require_initialized(&env)?;
         require_not_paused(&env)?;
         if fee_bps > 10_000 {
             return Err(ContractError::InvalidFeeBps);
         }
-        storage::get_admin(&env)?.require_auth();
-        storage::set_fee_bps(&env, fee_bps);
-        events::emit_fee_updated(&env, fee_bps);
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        storage::append_admin_log(&env, &admin, AdminAction::FeeUpdated);
+        if effective_from_ledger <= env.ledger().sequence() {
+            let old = storage::get_fee_bps(&env)?;
+            storage::set_fee_bps(&env, fee_bps)?;
+            events::emit_config_changed(&env, symbol_short!("fee_bps"), old, fee_bps);
+            events::emit_fee_updated(&env, fee_bps);
+            return Ok(());
+        }
+        // Fold in a previously scheduled change that has already matured so
+        // it isn't lost when replaced.
+        let current = storage::get_fee_bps(&env)?;
+        storage::set_fee_bps(&env, current)?;
+        storage::set_pending_fee_change(
+            &env,
+            &PendingFeeChange { fee_bps, effective_from: effective_from_ledger },
+        );
+        events::emit_fee_scheduled(&env, fee_bps, effective_from_ledger);
+        Ok(())
+    }
+
+    /// Platform rate in force now and any scheduled change.
+    pub fn get_fee_schedule(env: Env) -> Result<FeeSchedule, ContractError> {
+        let current_bps = storage::get_fee_bps(&env)?;
+        let pending = storage::get_pending_fee_change(&env)
+            .filter(|p| env.ledger().sequence() < p.effective_from);
+        Ok(FeeSchedule { current_bps, pending })
+    }
+
+    /// Cap the fee charged on any single trade (admin only). Zero removes the cap.
+    pub fn set_fee_cap(env: Env, cap: i128) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        amount::require_non_negative(cap)?;
+        if cap > 0 && cap < storage::get_min_fee(&env) {
+            return Err(ContractError::InvalidFeeBounds);
+        }
+        let old = storage::get_fee_cap(&env);
+        storage::set_fee_cap(&env, cap);
+        events::emit_config_changed(&env, symbol_short!("fee_cap"), old, cap);
+        storage::append_admin_log(&env, &admin, AdminAction::FeeBoundsSet);
+        events::emit_fee_cap_set(&env, cap);
+        Ok(())
+    }
+
+    pub fn get_fee_cap(env: Env) -> i128 {
+        storage::get_fee_cap(&env)
+    }
+
+    /// Set a minimum fee so tiny trades still cover operating costs (admin
+    /// only). Zero removes the floor.
+    pub fn set_min_fee(env: Env, min: i128) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        amount::require_non_negative(min)?;
+        let cap = storage::get_fee_cap(&env);
+        if cap > 0 && min > cap {
+            return Err(ContractError::InvalidFeeBounds);
+        }
+        let old = storage::get_min_fee(&env);
+        storage::set_min_fee(&env, min);
+        events::emit_config_changed(&env, symbol_short!("min_fee"), old, min);
+        storage::append_admin_log(&env, &admin, AdminAction::FeeBoundsSet);
+        events::emit_min_fee_set(&env, min);
+        Ok(())
+    }
+
+    pub fn get_min_fee(env: Env) -> i128 {
+        storage::get_min_fee(&env)
+    }
+
+    /// Override the base fee rate for trades settled in `token` (admin only),
+    /// e.g. to charge more on volatile assets.
+    pub fn set_token_fee_bps(env: Env, token: Address, fee_bps: u32) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        if fee_bps > 10_000 {
+            return Err(ContractError::InvalidFeeBps);
+        }
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        let old = storage::get_token_fee_bps(&env, &token);
+        storage::set_token_fee_bps(&env, &token, fee_bps);
+        events::emit_config_entry_changed(&env, symbol_short!("tok_fee"), token.clone(), old, Some(fee_bps));
+        storage::append_admin_log(&env, &admin, AdminAction::TokenFeeSet);
+        events::emit_token_fee_set(&env, token, fee_bps);
+        Ok(())
+    }
+
+    /// Drop a token's override so it falls back to the global rate (admin only).
+    pub fn remove_token_fee_bps(env: Env, token: Address) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        let old = storage::get_token_fee_bps(&env, &token);
+        storage::remove_token_fee_bps(&env, &token);
+        events::emit_config_entry_changed(&env, symbol_short!("tok_fee"), token, old, None);
+        storage::append_admin_log(&env, &admin, AdminAction::TokenFeeSet);
+        Ok(())
+    }
+
+    /// Base fee rate that applies to trades in `token`, after fallback to the
+    /// global rate.
+    pub fn get_token_fee_bps(env: Env, token: Address) -> Result<u32, ContractError> {
+        fees::token_fee_bps(&env, &token)
+    }
+
+    /// Schedule a reduced- or zero-fee period between two ledgers (admin
+    /// only). Replaces any previously scheduled window.
+    pub fn set_promo_window(
+        env: Env,
+        start_ledger: u32,
+        end_ledger: u32,
+        fee_bps: u32,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        if end_ledger <= start_ledger || fee_bps > 10_000 {
+            return Err(ContractError::InvalidPromoWindow);
+        }
+        let old = storage::get_promo_window(&env);
+        let window = PromoWindow { start_ledger, end_ledger, fee_bps };
+        storage::set_promo_window(&env, &window);
+        events::emit_config_changed(&env, symbol_short!("promo"), old, Some(window));
+        storage::append_admin_log(&env, &admin, AdminAction::PromoWindowSet);
+        events::emit_promo_set(&env, start_ledger, end_ledger, fee_bps);
+        Ok(())
+    }
+
+    pub fn clear_promo_window(env: Env) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        let old = storage::get_promo_window(&env);
+        storage::remove_promo_window(&env);
+        events::emit_config_changed(&env, symbol_short!("promo"), old, None);
+        storage::append_admin_log(&env, &admin, AdminAction::PromoWindowSet);
         Ok(())
     }
 
+    pub fn get_promo_window(env: Env) -> Option<PromoWindow> {
+        storage::get_promo_window(&env)
+    }
+
     pub fn get_platform_fee_bps(env: Env) -> Result<u32, ContractError> {
         storage::get_fee_bps(&env)
     // -------------------------------------------------------------------------
@@ -388,7 +1423,7 @@ impl StellarEscrowContract {
     }
 
     /// Calculate insurance premium for a given trade amount
-    pub fn get_insurance_premium(env: Env, amount: u64, provider: Address) -> u64 {
+    pub fn get_insurance_premium(env: Env, amount: i128, provider: Address) -> i128 {
         insurance::calculate_premium(&env, amount, &provider)
     }
 
@@ -413,103 +1448,684 @@ impl StellarEscrowContract {
         storage::get_user_compliance(&env, &user)
     }
 
-    pub fn set_user_trade_limit(
-        env: Env,
-        admin: Address,
-        user: Address,
-        limit: u64,
-    ) -> Result<(), ContractError> {
+    /// Place a compliance hold on one trade (admin only). Until unfrozen the
+    /// trade cannot change status or pay out; the rest of the platform keeps
+    /// running. Allowed while paused.
+    pub fn freeze_trade(env: Env, trade_id: u64, reason_code: u32) -> Result<(), ContractError> {
         require_initialized(&env)?;
-        require_not_paused(&env)?;
-        if admin != storage::get_admin(&env)? {
-            return Err(ContractError::Unauthorized);
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        let trade = storage::get_trade(&env, trade_id)?;
+        if trade.status.is_terminal() {
+            return Err(ContractError::InvalidStatus);
         }
+        state::require_not_frozen(&env, trade_id)?;
+        storage::save_trade_freeze(&env, trade_id, &TradeFreeze { reason_code, frozen_at: env.ledger().timestamp() });
+        storage::append_admin_log(&env, &admin, AdminAction::TradeFrozen);
+        events::emit_trade_frozen(&env, trade_id, reason_code, admin);
+        Ok(())
+    }
+
+    /// Lift a compliance hold (admin only).
+    pub fn unfreeze_trade(env: Env, trade_id: u64) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        let admin = storage::get_admin(&env)?;
         admin.require_auth();
-        storage::set_user_trade_limit(&env, &user, limit);
+        if !storage::is_trade_frozen(&env, trade_id) {
+            return Err(ContractError::TradeNotFrozen);
+        }
+        storage::remove_trade_freeze(&env, trade_id);
+        storage::append_admin_log(&env, &admin, AdminAction::TradeUnfrozen);
+        events::emit_trade_unfrozen(&env, trade_id, admin);
         Ok(())
     }
 
-    pub fn set_jurisdiction_rule(
+    pub fn get_trade_freeze(env: Env, trade_id: u64) -> Option<TradeFreeze> {
+        storage::get_trade_freeze(&env, trade_id)
+    }
+
+    /// Mark `token` as a regulated (SEP-8) asset (admin only). Payouts in it
+    /// to accounts the issuer has not authorized are held for them until
+    /// they claim with `claim_held_payout`.
+    pub fn set_regulated_asset(env: Env, token: Address, regulated: bool) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        let old = regulated::is_regulated(&env, &token);
+        regulated::set_regulated(&env, &token, regulated);
+        events::emit_config_entry_changed(&env, symbol_short!("reg_asset"), token, old, regulated);
+        Ok(())
+    }
+
+    pub fn is_regulated_asset(env: Env, token: Address) -> bool {
+        regulated::is_regulated(&env, &token)
+    }
+
+    /// Collect payouts held for `recipient` in a regulated `token` once the
+    /// issuer has authorized them. Callable by anyone; funds only go to
+    /// `recipient`.
+    pub fn claim_held_payout(env: Env, recipient: Address, token: Address) -> Result<i128, ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let amount = regulated::claim(&env, &recipient, &token)?;
+        events::emit_held_payout_claimed(&env, recipient, token, amount);
+        Ok(amount)
+    }
+
+    pub fn get_held_payout(env: Env, recipient: Address, token: Address) -> i128 {
+        regulated::held(&env, &recipient, &token)
+    }
+
+    /// Open a hashed trade: only `commitment` (see `get_hashed_trade_commitment`),
+    /// the amount's bucket and the current fee rate are recorded. `currency`
+    /// defaults to the platform token. Returns an id from the hashed-trade
+    /// sequence.
+    pub fn create_hashed_trade(
         env: Env,
-        admin: Address,
-        jurisdiction: String,
-        allowed: bool,
-    ) -> Result<(), ContractError> {
+        commitment: soroban_sdk::BytesN<32>,
+        amount_bucket: u32,
+        currency: Option<Address>,
+        arbitrator: Address,
+    ) -> Result<u64, ContractError> {
         require_initialized(&env)?;
         require_not_paused(&env)?;
-        if admin != storage::get_admin(&env)? {
-            return Err(ContractError::Unauthorized);
+        require_function_active(&env, PAUSE_CREATE)?;
+        require_breaker_clear(&env)?;
+        if !storage::has_arbitrator(&env, &arbitrator) {
+            return Err(ContractError::ArbitratorNotRegistered);
         }
-        admin.require_auth();
-        storage::set_jurisdiction_rule(&env, &jurisdiction, allowed);
+        let currency = match currency {
+            Some(c) => c,
+            None => storage::get_usdc_token(&env)?,
+        };
+        let fee_bps = fees::anonymous_fee_bps(&env, &currency)?;
+        let id = hashed_trade::create(&env, commitment.clone(), amount_bucket, currency, arbitrator, fee_bps);
+        events::emit_hashed_trade_created(&env, id, commitment, amount_bucket);
+        Ok(id)
+    }
+
+    /// Buyer funds a hashed trade, proving the details match its commitment.
+    /// The details are public in the call's arguments.
+    pub fn fund_hashed_trade(env: Env, trade_id: u64, details: HashedTradeDetails) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        require_function_active(&env, PAUSE_FUND)?;
+        require_breaker_clear(&env)?;
+        let mut trade = hashed_trade::open(&env, trade_id, &details)?;
+        amount::require_positive(details.amount)?;
+        if hashed_trade::amount_bucket(details.amount) != trade.amount_bucket {
+            return Err(ContractError::CommitmentMismatch);
+        }
+        if details.buyer == details.seller {
+            return Err(ContractError::InvalidParties);
+        }
+        require_disinterested(&trade.arbitrator, &details.seller, &details.buyer)?;
+        details.buyer.require_auth();
+        hashed_trade::advance(&env, trade_id, &mut trade, TradeEvent::Fund)?;
+        transfer::send(
+            &token::Client::new(&env, &trade.currency),
+            &details.buyer,
+            &env.current_contract_address(),
+            details.amount,
+        )?;
+        storage::add_total_locked(&env, &trade.currency, details.amount)?;
+        record_funding_volume(&env, &trade.currency, details.amount)?;
+        hashed_trade::save(&env, trade_id, &trade);
+        events::emit_hashed_trade_status(&env, trade_id, trade.status);
         Ok(())
     }
 
-    pub fn set_global_trade_limit(
+    /// Buyer releases a funded hashed trade to the seller, less the
+    /// platform fee at the rate fixed on creation.
+    pub fn release_hashed_trade(env: Env, trade_id: u64, details: HashedTradeDetails) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let mut trade = hashed_trade::open(&env, trade_id, &details)?;
+        details.buyer.require_auth();
+        hashed_trade::advance(&env, trade_id, &mut trade, TradeEvent::Settle)?;
+        let fee = fees::fee_at_rate(&env, details.amount, trade.fee_bps)?;
+        let token_client = token::Client::new(&env, &trade.currency);
+        storage::sub_total_locked(&env, &trade.currency, details.amount)?;
+        regulated::pay_out(&env, &token_client, &details.seller, details.amount - fee)?;
+        book_fee(&env, &trade.currency, fee)?;
+        hashed_trade::save(&env, trade_id, &trade);
+        events::emit_hashed_trade_status(&env, trade_id, trade.status);
+        Ok(())
+    }
+
+    /// Buyer or seller disputes a funded hashed trade. This reveals the
+    /// details on the record so the arbitrator can rule.
+    pub fn raise_hashed_dispute(
         env: Env,
-        admin: Address,
-        limit: u64,
+        trade_id: u64,
+        details: HashedTradeDetails,
+        caller: Address,
     ) -> Result<(), ContractError> {
         require_initialized(&env)?;
         require_not_paused(&env)?;
-        if admin != storage::get_admin(&env)? {
+        require_function_active(&env, PAUSE_DISPUTE)?;
+        let mut trade = hashed_trade::open(&env, trade_id, &details)?;
+        if caller != details.buyer && caller != details.seller {
             return Err(ContractError::Unauthorized);
         }
-        admin.require_auth();
-        storage::set_global_trade_limit(&env, limit);
+        require_disinterested(&trade.arbitrator, &details.seller, &details.buyer)?;
+        caller.require_auth();
+        hashed_trade::advance(&env, trade_id, &mut trade, TradeEvent::Dispute)?;
+        trade.revealed = Some(details);
+        hashed_trade::save(&env, trade_id, &trade);
+        events::emit_hashed_trade_status(&env, trade_id, trade.status);
         Ok(())
     }
 
-    pub fn create_trade(
+    /// The trade's arbitrator rules on a disputed hashed trade using the
+    /// revealed details. The platform fee comes off before the split.
+    pub fn resolve_hashed_dispute(
         env: Env,
-        seller: Address,
-        buyer: Address,
-        amount: u64,
-        arbitrator: Option<Address>,
-        metadata: OptionalMetadata,
-        expiry_time: Option<u64>,
-        currency: Option<Address>,
-        metadata: Option<soroban_sdk::String>,
-        trigger: Option<PriceTrigger>,
-    ) -> Result<u64, ContractError> {
+        trade_id: u64,
+        resolution: DisputeResolution,
+    ) -> Result<(), ContractError> {
         require_initialized(&env)?;
         require_not_paused(&env)?;
-        if amount == 0 {
-            return Err(ContractError::InvalidAmount);
-        }
-        validate_metadata(&metadata)?;
-        seller.require_auth();
-        validate_user_compliance(&env, &seller, amount)?;
-        validate_user_compliance(&env, &buyer, amount)?;
-        let arbitration = match arbitrator {
-            Some(addr) => {
-                if !storage::has_arbitrator(&env, &addr) {
-                    return Err(ContractError::ArbitratorNotRegistered);
+        let mut trade = hashed_trade::get(&env, trade_id)?;
+        trade.arbitrator.require_auth();
+        hashed_trade::advance(&env, trade_id, &mut trade, TradeEvent::Rule)?;
+        let details = trade.revealed.clone().ok_or(ContractError::InvalidStatus)?;
+        hashed_trade::advance(&env, trade_id, &mut trade, TradeEvent::Settle)?;
+        let fee = fees::fee_at_rate(&env, details.amount, trade.fee_bps)?;
+        let net = details.amount - fee;
+        let buyer_amount = match resolution {
+            DisputeResolution::ReleaseToBuyer => net,
+            DisputeResolution::ReleaseToSeller => 0,
+            DisputeResolution::Partial(buyer_bps) => {
+                if buyer_bps > 10_000 {
+                    return Err(ContractError::InvalidSplitBps);
                 }
-                Some(addr)
+                net.checked_mul(i128::from(buyer_bps)).ok_or(ContractError::Overflow)? / 10_000
             }
-            None => None,
         };
-        let trade_id = storage::increment_trade_counter(&env)?;
+        let token_client = token::Client::new(&env, &trade.currency);
+        storage::sub_total_locked(&env, &trade.currency, details.amount)?;
+        if buyer_amount > 0 {
+            regulated::pay_out(&env, &token_client, &details.buyer, buyer_amount)?;
         }
-        if let Some(ref meta) = metadata {
-            validate_metadata(meta)?;
+        if net - buyer_amount > 0 {
+            regulated::pay_out(&env, &token_client, &details.seller, net - buyer_amount)?;
         }
-        // Default to USDC when no currency specified (backward compat)
-        let token = currency.unwrap_or(get_usdc_token(&env)?);
-        validate_metadata(&metadata)?;
-        let trade_id = increment_trade_counter(&env)?;
-        let fee_bps = get_fee_bps(&env)?;
-        let effective_bps = tiers::effective_fee_bps(&env, &seller, fee_bps);
-        let discount = subscription::subscription_discount_bps(&env, &seller);
-        let final_bps = effective_bps.saturating_sub(discount);
-        let fee = amount
-            .checked_mul(final_bps as u64)
-            .ok_or(ContractError::Overflow)?
-            .checked_div(10000)
-            .ok_or(ContractError::Overflow)?;
+        book_fee(&env, &trade.currency, fee)?;
+        hashed_trade::save(&env, trade_id, &trade);
+        events::emit_hashed_trade_status(&env, trade_id, trade.status);
+        Ok(())
+    }
+
+    pub fn get_hashed_trade(env: Env, trade_id: u64) -> Result<HashedTrade, ContractError> {
+        hashed_trade::get(&env, trade_id)
+    }
+
+    /// Place a compliance hold on one hashed trade (admin only). Until
+    /// unfrozen it cannot change status or pay out. Allowed while paused.
+    pub fn freeze_hashed_trade(env: Env, trade_id: u64, reason_code: u32) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        let trade = hashed_trade::get(&env, trade_id)?;
+        if trade.status.is_terminal() {
+            return Err(ContractError::InvalidStatus);
+        }
+        if hashed_trade::is_frozen(&env, trade_id) {
+            return Err(ContractError::TradeFrozen);
+        }
+        hashed_trade::save_freeze(&env, trade_id, &TradeFreeze { reason_code, frozen_at: env.ledger().timestamp() });
+        storage::append_admin_log(&env, &admin, AdminAction::TradeFrozen);
+        events::emit_hashed_trade_frozen(&env, trade_id, reason_code, admin);
+        Ok(())
+    }
+
+    /// Lift a compliance hold on a hashed trade (admin only).
+    pub fn unfreeze_hashed_trade(env: Env, trade_id: u64) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        if !hashed_trade::is_frozen(&env, trade_id) {
+            return Err(ContractError::TradeNotFrozen);
+        }
+        hashed_trade::remove_freeze(&env, trade_id);
+        storage::append_admin_log(&env, &admin, AdminAction::TradeUnfrozen);
+        events::emit_hashed_trade_unfrozen(&env, trade_id, admin);
+        Ok(())
+    }
+
+    pub fn get_hashed_trade_freeze(env: Env, trade_id: u64) -> Option<TradeFreeze> {
+        hashed_trade::get_freeze(&env, trade_id)
+    }
+
+    /// Commitment and amount bucket for `details`, for building a hashed
+    /// trade off-chain. Simulate rather than submit: the arguments of a
+    /// submitted call are public.
+    pub fn get_hashed_trade_commitment(env: Env, details: HashedTradeDetails) -> (soroban_sdk::BytesN<32>, u32) {
+        (hashed_trade::commitment(&env, &details), hashed_trade::amount_bucket(details.amount))
+    }
+
+    pub fn set_user_trade_limit(
+        env: Env,
+        admin: Address,
+        user: Address,
+        limit: i128,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        if admin != storage::get_admin(&env)? {
+            return Err(ContractError::Unauthorized);
+        }
+        admin.require_auth();
+        amount::require_non_negative(limit)?;
+        let old = storage::get_user_trade_limit(&env, &user);
+        storage::set_user_trade_limit(&env, &user, limit);
+        events::emit_config_entry_changed(&env, symbol_short!("usr_limit"), user, old, limit);
+        Ok(())
+    }
+
+    pub fn set_jurisdiction_rule(
+        env: Env,
+        admin: Address,
+        jurisdiction: String,
+        allowed: bool,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        if admin != storage::get_admin(&env)? {
+            return Err(ContractError::Unauthorized);
+        }
+        admin.require_auth();
+        let old = storage::is_jurisdiction_allowed(&env, &jurisdiction);
+        storage::set_jurisdiction_rule(&env, &jurisdiction, allowed);
+        events::emit_config_entry_changed(&env, symbol_short!("juris"), jurisdiction, old, allowed);
+        Ok(())
+    }
+
+    pub fn set_global_trade_limit(
+        env: Env,
+        admin: Address,
+        limit: i128,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        if admin != storage::get_admin(&env)? {
+            return Err(ContractError::Unauthorized);
+        }
+        admin.require_auth();
+        amount::require_non_negative(limit)?;
+        let old = storage::get_global_trade_limit(&env);
+        storage::set_global_trade_limit(&env, limit);
+        events::emit_config_changed(&env, symbol_short!("glb_limit"), old, limit);
+        Ok(())
+    }
+
+    /// Configure the funding-volume circuit breaker (admin only). When more than
+    /// `threshold` of any one currency (in that token's units) is funded within
+    /// `window_ledgers`, new trades and funding halt until `reset_volume_breaker`
+    /// is called.
+    pub fn set_volume_breaker(
+        env: Env,
+        window_ledgers: u32,
+        threshold: i128,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        if window_ledgers == 0 {
+            return Err(ContractError::InvalidBreakerWindow);
+        }
+        amount::require_positive(threshold)?;
+        let old = storage::get_volume_breaker(&env);
+        let config = VolumeBreakerConfig { window_ledgers, threshold };
+        storage::set_volume_breaker(&env, &config);
+        events::emit_config_changed(&env, symbol_short!("breaker"), old, Some(config));
+        Ok(())
+    }
+
+    /// Disable the circuit breaker entirely (admin only).
+    pub fn disable_volume_breaker(env: Env) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        let old = storage::get_volume_breaker(&env);
+        storage::remove_volume_breaker(&env);
+        events::emit_config_changed(&env, symbol_short!("breaker"), old, None);
+        storage::clear_volume_windows(&env);
+        storage::set_breaker_tripped(&env, false);
+        Ok(())
+    }
+
+    /// Clear a tripped breaker and start a fresh window (admin only).
+    pub fn reset_volume_breaker(env: Env) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        storage::clear_volume_windows(&env);
+        storage::set_breaker_tripped(&env, false);
+        events::emit_breaker_reset(&env, admin);
+        Ok(())
+    }
+
+    pub fn get_volume_breaker(env: Env) -> Option<VolumeBreakerConfig> {
+        storage::get_volume_breaker(&env)
+    }
+
+    /// The current breaker window for `currency`, if one is open.
+    pub fn get_volume_window(env: Env, currency: Address) -> Option<VolumeWindow> {
+        storage::get_volume_windows(&env).get(currency)
+    }
+
+    pub fn is_volume_breaker_tripped(env: Env) -> bool {
+        storage::is_breaker_tripped(&env)
+    }
+
+    pub fn create_trade(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        amount: i128,
+        arbitrator: Option<Address>,
+        metadata: OptionalMetadata,
+        metadata: Option<soroban_sdk::String>,
+        options: TradeOptions,
+    ) -> Result<u64, ContractError> {
+        Self::open_trade(env, None, seller, buyer, amount, arbitrator, metadata, metadata, options)
+    }
+
+    /// Create a trade on the seller's behalf from a trusted router or
+    /// marketplace contract. Needs `caller`'s auth and the seller's; the
+    /// seller authorises this call as nested under the router's.
+    pub fn create_trade_via(
+        env: Env,
+        caller: Address,
+        seller: Address,
+        buyer: Address,
+        amount: i128,
+        arbitrator: Option<Address>,
+        metadata: OptionalMetadata,
+        metadata: Option<soroban_sdk::String>,
+        options: TradeOptions,
+    ) -> Result<u64, ContractError> {
+        Self::open_trade(env, Some(caller), seller, buyer, amount, arbitrator, metadata, metadata, options)
+    }
+
+    /// Create a trade that both parties authorise in the same invocation.
+    /// The buyer's signature covers the amount, the other terms and
+    /// `terms_hash` (a hash of the off-chain agreement), so neither side can
+    /// later deny having agreed to them.
+    pub fn create_trade_dual_auth(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        amount: i128,
+        terms_hash: soroban_sdk::BytesN<32>,
+        arbitrator: Option<Address>,
+        metadata: OptionalMetadata,
+        metadata: Option<soroban_sdk::String>,
+        options: TradeOptions,
+    ) -> Result<u64, ContractError> {
+        buyer.require_auth();
+        let trade_id = Self::open_trade(
+            env.clone(), None, seller.clone(), buyer.clone(), amount, arbitrator, metadata, metadata, options,
+        )?;
+        storage::save_terms_hash(&env, trade_id, &terms_hash);
+        events::emit_terms_agreed(&env, trade_id, seller, buyer, terms_hash);
+        Ok(trade_id)
+    }
+
+    /// Terms hash both parties signed, for trades made with
+    /// `create_trade_dual_auth`.
+    pub fn get_trade_terms_hash(env: Env, trade_id: u64) -> Option<soroban_sdk::BytesN<32>> {
+        storage::get_terms_hash(&env, trade_id)
+    }
+
+    /// Register the ed25519 public key whose signatures count as this
+    /// buyer's acceptance (buyer only).
+    pub fn set_acceptance_key(env: Env, buyer: Address, public_key: soroban_sdk::BytesN<32>) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        buyer.require_auth();
+        let old = acceptance::get_key(&env, &buyer);
+        acceptance::set_key(&env, &buyer, &public_key);
+        events::emit_config_entry_changed(&env, symbol_short!("acpt_key"), buyer, old, Some(public_key));
+        Ok(())
+    }
+
+    pub fn get_acceptance_key(env: Env, buyer: Address) -> Option<soroban_sdk::BytesN<32>> {
+        acceptance::get_key(&env, &buyer)
+    }
+
+    /// Bytes the buyer signs to accept `terms_hash` for the trade as it
+    /// currently stands.
+    pub fn get_acceptance_payload(
+        env: Env,
+        trade_id: u64,
+        terms_hash: soroban_sdk::BytesN<32>,
+        nonce: u64,
+    ) -> Result<soroban_sdk::Bytes, ContractError> {
+        let trade = storage::get_trade(&env, trade_id)?;
+        Ok(acceptance::payload(&env, &trade, &terms_hash, nonce))
+    }
+
+    /// Record the buyer's acceptance of a trade or an amendment from an
+    /// ed25519 signature over `get_acceptance_payload`. Callable by anyone;
+    /// an invalid signature aborts the call.
+    pub fn accept_trade_signed(
+        env: Env,
+        trade_id: u64,
+        terms_hash: soroban_sdk::BytesN<32>,
+        nonce: u64,
+        signature: soroban_sdk::BytesN<64>,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let trade = storage::get_trade(&env, trade_id)?;
+        if trade.status.is_terminal() {
+            return Err(ContractError::InvalidStatus);
+        }
+        acceptance::accept(&env, &trade, &terms_hash, nonce, &signature)?;
+        events::emit_trade_accepted(&env, trade_id, trade.buyer, terms_hash, nonce);
+        Ok(())
+    }
+
+    pub fn get_buyer_acceptance(env: Env, trade_id: u64) -> Option<BuyerAcceptance> {
+        acceptance::get(&env, trade_id)
+    }
+
+    /// Commit a Merkle root of `leaves` micro-trade payouts settled
+    /// off-chain, depositing their `total` in `currency`. Sellers can claim
+    /// for `claim_window` seconds; returns the batch id.
+    pub fn commit_settlement_batch(
+        env: Env,
+        operator: Address,
+        currency: Address,
+        root: soroban_sdk::BytesN<32>,
+        total: i128,
+        leaves: u32,
+        claim_window: u64,
+    ) -> Result<u64, ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        require_function_active(&env, PAUSE_CREATE)?;
+        require_breaker_clear(&env)?;
+        operator.require_auth();
+        let batch_id = settlement_batch::commit(&env, &operator, &currency, &root, total, leaves, claim_window)?;
+        record_funding_volume(&env, &currency, total)?;
+        events::emit_batch_committed(&env, batch_id, operator, currency, root, total, leaves);
+        Ok(batch_id)
+    }
+
+    /// Pay one leaf of a settlement batch to its seller. Callable by anyone
+    /// holding the Merkle proof.
+    pub fn claim_batch_payout(
+        env: Env,
+        leaf: BatchLeaf,
+        proof: soroban_sdk::Vec<soroban_sdk::BytesN<32>>,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        settlement_batch::claim(&env, &leaf, &proof)?;
+        events::emit_batch_claimed(&env, leaf.batch_id, leaf.index, leaf.seller, leaf.amount);
+        Ok(())
+    }
+
+    /// Return the unclaimed remainder of a batch to its operator once the
+    /// claim window has closed (operator only).
+    pub fn reclaim_settlement_batch(env: Env, batch_id: u64) -> Result<i128, ContractError> {
+        require_initialized(&env)?;
+        let (batch, remaining) = settlement_batch::reclaim(&env, batch_id)?;
+        events::emit_batch_reclaimed(&env, batch_id, batch.operator, remaining);
+        Ok(remaining)
+    }
+
+    pub fn get_settlement_batch(env: Env, batch_id: u64) -> Result<SettlementBatch, ContractError> {
+        settlement_batch::get(&env, batch_id)
+    }
+
+    pub fn is_batch_leaf_claimed(env: Env, batch_id: u64, index: u32) -> bool {
+        settlement_batch::is_claimed(&env, batch_id, index)
+    }
+
+    /// Canonical hash of a batch leaf, for building trees off-chain.
+    pub fn get_batch_leaf_hash(env: Env, leaf: BatchLeaf) -> soroban_sdk::BytesN<32> {
+        settlement_batch::leaf_hash(&env, &leaf)
+    }
+
+    /// Trust or distrust an integration contract for `create_trade_via`
+    /// (admin only).
+    pub fn set_trusted_caller(env: Env, caller: Address, trusted: bool) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        let old = trusted_callers::is_trusted(&env, &caller);
+        trusted_callers::set_trusted(&env, &caller, trusted);
+        events::emit_config_entry_changed(&env, symbol_short!("tr_call"), caller, old, trusted);
+        Ok(())
+    }
+
+    pub fn is_trusted_caller(env: Env, caller: Address) -> bool {
+        trusted_callers::is_trusted(&env, &caller)
+    }
+
+    /// Switch integrations-only mode (admin only). While on, `create_trade`
+    /// is refused and trades can only be opened through a trusted caller.
+    pub fn set_trusted_callers_only(env: Env, enabled: bool) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        let old = trusted_callers::is_only(&env);
+        trusted_callers::set_only(&env, enabled);
+        events::emit_config_changed(&env, symbol_short!("tc_only"), old, enabled);
+        Ok(())
+    }
+
+    pub fn is_trusted_callers_only(env: Env) -> bool {
+        trusted_callers::is_only(&env)
+    }
 
-        let fee = calc_fee(&env, &seller, amount)?;
+    fn open_trade(
+        env: Env,
+        via: Option<Address>,
+        seller: Address,
+        buyer: Address,
+        amount: i128,
+        arbitrator: Option<Address>,
+        metadata: OptionalMetadata,
+        metadata: Option<soroban_sdk::String>,
+        options: TradeOptions,
+    ) -> Result<u64, ContractError> {
+        let TradeOptions { expiry_time, currency, trigger, category, memo, external_ref, deadlines, partner } = options;
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        require_function_active(&env, PAUSE_CREATE)?;
+        trusted_callers::require_permitted(&env, via.as_ref())?;
+        amount::require_positive(amount)?;
+        if buyer == seller {
+            return Err(ContractError::InvalidParties);
+        }
+        validate_metadata(&metadata)?;
+        require_breaker_clear(&env)?;
+        if let Some(ref r) = external_ref {
+            if storage::get_trade_by_external_ref(&env, r).is_some() {
+                return Err(ContractError::DuplicateExternalRef);
+            }
+        }
+        if let Some(ref m) = memo {
+            if m.len() > MAX_MEMO_LEN {
+                return Err(ContractError::MemoTooLong);
+            }
+        }
+        if let Some(ref d) = deadlines {
+            validate_deadlines(&env, d)?;
+        }
+        seller.require_auth();
+        validate_user_compliance(&env, &seller, amount)?;
+        validate_user_compliance(&env, &buyer, amount)?;
+        attestation::require_attested(&env, &[&seller, &buyer])?;
+        let arbitration = match arbitrator {
+            Some(addr) => {
+                if !storage::has_arbitrator(&env, &addr) {
+                    return Err(ContractError::ArbitratorNotRegistered);
+                }
+                require_disinterested(&addr, &seller, &buyer)?;
+                require_caseload_capacity(&env, &addr)?;
+                Some(addr)
+            }
+            None => {
+                let threshold = storage::get_arbitrator_threshold(&env);
+                if threshold > 0 && amount > threshold {
+                    return Err(ContractError::ArbitratorRequired);
+                }
+                None
+            }
+        };
+        if let Some(ref cat) = category {
+            validate_arbitrator_category(&env, arbitration.as_ref(), cat)?;
+        }
+        require_pair_capacity(&env, &seller, &buyer)?;
+        let trade_id = storage::increment_trade_counter(&env)?;
+        storage::open_pair_trade(&env, trade_id, &seller, &buyer);
+        if let Some(ref cat) = category {
+            storage::save_trade_category(&env, trade_id, cat);
+        }
+        storage::save_created_at(&env, trade_id, clock::now(&env, TimingMode::Ledger));
+        if let Some(ref d) = deadlines {
+            storage::save_trade_deadlines(&env, trade_id, d);
+        }
+        if let Some(ref r) = external_ref {
+            storage::save_external_ref(&env, r, trade_id);
+        }
+        if let Some(ref m) = memo {
+            storage::save_trade_memo(&env, trade_id, m);
+            events::emit_trade_memo(&env, trade_id, m.clone());
+        }
+        if let Some(ref arb) = arbitration {
+            storage::save_assignment(
+                &env,
+                trade_id,
+                &ArbitratorAssignment {
+                    arbitrator: arb.clone(),
+                    assigned_at: clock::now(&env, TimingMode::Timestamp),
+                    accepted: false,
+                },
+            );
+            storage::index_arbitrator_trade(&env, arb, trade_id);
+        }
+        }
+        if let Some(ref meta) = metadata {
+            validate_metadata(meta)?;
+        }
+        // Default to USDC when no currency specified (backward compat)
+        let token = currency.unwrap_or(get_usdc_token(&env)?);
+        validate_metadata(&metadata)?;
+        let trade_id = increment_trade_counter(&env)?;
+        let fee = calc_fee(&env, &seller, &token, amount)?;
+        let (fee, partner_terms) = match partner {
+            Some(id) => {
+                let (total, terms) = partners::quote(&env, id, amount, fee)?;
+                (total, Some(terms))
+            }
+            None => (fee, None),
+        };
+        if let Some(ref terms) = partner_terms {
+            partners::attach(&env, trade_id, terms);
+        }
+        loyalty::consume_discount(&env, &seller);
         let arbitrator_config = arbitrator.map(ArbitrationConfig::Single);
         let trade = Trade {
             id: trade_id,
@@ -527,7 +2143,8 @@ impl StellarEscrowContract {
         save_trade(&env, trade_id, &trade);
         events::emit_trade_created(&env, trade_id, seller.clone(), buyer.clone(), amount);
         events::emit_compliance_passed(&env, trade_id, seller, buyer, amount);
-        analytics::on_trade_created(&env, amount, &trade.seller, &trade.buyer);
+        analytics::on_trade_created(&env, &trade.currency, amount, &trade.seller, &trade.buyer)?;
+        user_stats::record_created(&env, &trade.seller, &trade.buyer);
         Ok(trade_id)
     }
 
@@ -537,7 +2154,7 @@ impl StellarEscrowContract {
         env: Env,
         seller: Address,
         buyer: Address,
-        amount: u64,
+        amount: i128,
         multisig_config: MultiSigConfig,
         expiry_time: Option<u64>,
         currency: Option<Address>,
@@ -546,8 +2163,12 @@ impl StellarEscrowContract {
     ) -> Result<u64, ContractError> {
         require_initialized(&env)?;
         require_not_paused(&env)?;
-        if amount == 0 {
-            return Err(ContractError::InvalidAmount);
+        require_function_active(&env, PAUSE_CREATE)?;
+        require_breaker_clear(&env)?;
+        trusted_callers::require_permitted(&env, None)?;
+        amount::require_positive(amount)?;
+        if buyer == seller {
+            return Err(ContractError::InvalidParties);
         }
 
         // Validate multi-sig configuration
@@ -564,6 +2185,7 @@ impl StellarEscrowContract {
             if !has_arbitrator(&env, &arb) {
                 return Err(ContractError::ArbitratorNotRegistered);
             }
+            require_disinterested(&arb, &seller, &buyer)?;
         }
 
         // expiry_time must be in the future (Stellar ledger time is UTC seconds)
@@ -586,14 +2208,19 @@ impl StellarEscrowContract {
         let token = currency.unwrap_or(get_usdc_token(&env)?);
         validate_metadata(&metadata)?;
 
+        require_pair_capacity(&env, &seller, &buyer)?;
         let trade_id = increment_trade_counter(&env)?;
-        let fee = calc_fee(&env, &seller, amount)?;
+        storage::open_pair_trade(&env, trade_id, &seller, &buyer);
+        for arb in multisig_config.arbitrators.iter() {
+            storage::index_arbitrator_trade(&env, &arb, trade_id);
+        }
+        let quote = fees::quote(&env, &seller, &token, amount)?;
         let trade = Trade {
             id: trade_id,
             seller: seller.clone(),
             buyer: buyer.clone(),
             amount,
-            fee: calc_fee(&env, amount)?,
+            fee: quote.fee,
             arbitrator: arbitration,
             status: TradeStatus::Created,
             expiry_time: None,
@@ -603,82 +2230,598 @@ impl StellarEscrowContract {
         };
         storage::save_trade(&env, trade_id, &trade);
         events::emit_trade_created(&env, trade_id, seller.clone(), buyer.clone(), amount, trade.currency.clone());
+        events::emit_fee_applied(&env, trade_id, quote.fee_bps, quote.effective_bps, quote.promo, quote.fee);
         events::emit_compliance_passed(&env, trade_id, seller, buyer, amount);
-        analytics::on_trade_created(&env, amount, &trade.seller, &trade.buyer);
+        analytics::on_trade_created(&env, &trade.currency, amount, &trade.seller, &trade.buyer)?;
+        user_stats::record_created(&env, &trade.seller, &trade.buyer);
         Ok(trade_id)
     }
 
     pub fn fund_trade(env: Env, trade_id: u64) -> Result<(), ContractError> {
         require_initialized(&env)?;
         require_not_paused(&env)?;
+        require_function_active(&env, PAUSE_FUND)?;
+        require_breaker_clear(&env)?;
         let mut trade = storage::get_trade(&env, trade_id)?;
-        if trade.status != TradeStatus::Created {
-            return Err(ContractError::InvalidStatus);
-        }
+        require_fundable(&env, &trade)?;
+        require_not_pooled(&env, trade_id)?;
         trade.buyer.require_auth();
-        token::Client::new(&env, &trade.currency).transfer(
+        pricing::fix_settlement(&env, &mut trade)?;
+        transfer::send(
+            &token::Client::new(&env, &trade.currency),
             &trade.buyer,
             &env.current_contract_address(),
-            &(trade.amount as i128),
-        );
-        trade.status = TradeStatus::Funded;
-        storage::save_trade(&env, trade_id, &trade);
-        events::emit_trade_funded(&env, trade_id);
-        analytics::on_trade_funded(&env);
-        Ok(())
+            trade.amount,
+        )?;
+        basket::pull(&env, &trade)?;
+        mark_funded(&env, &trade.buyer.clone(), &mut trade)
     }
 
-    pub fn complete_trade(env: Env, trade_id: u64) -> Result<(), ContractError> {
+    /// Fund a trade by pulling the amount from an allowance the buyer has
+    /// already granted this contract (`approve`). Callable by anyone, so
+    /// treasury contracts and bots can fund without the buyer signing this call.
+    pub fn fund_trade_from_allowance(env: Env, trade_id: u64) -> Result<(), ContractError> {
         require_initialized(&env)?;
         require_not_paused(&env)?;
+        require_function_active(&env, PAUSE_FUND)?;
+        require_breaker_clear(&env)?;
         let mut trade = storage::get_trade(&env, trade_id)?;
-        if trade.status != TradeStatus::Funded {
-            return Err(ContractError::InvalidStatus);
+        require_fundable(&env, &trade)?;
+        require_not_pooled(&env, trade_id)?;
+        pricing::fix_settlement(&env, &mut trade)?;
+        let token_client = token::Client::new(&env, &trade.currency);
+        let contract = env.current_contract_address();
+        if token_client.allowance(&trade.buyer, &contract) < trade.amount {
+            return Err(ContractError::InsufficientAllowance);
+        }
+        transfer::send_from(&token_client, &contract, &trade.buyer, &contract, trade.amount)?;
+        basket::pull_from_allowance(&env, &trade)?;
+        mark_funded(&env, &trade.buyer.clone(), &mut trade)
+    }
+
+    /// Pay part of a trade's amount as one of several contributors. The
+    /// trade becomes `Funded` once contributions reach the full amount;
+    /// refunds are then split back pro rata.
+    pub fn contribute(env: Env, trade_id: u64, payer: Address, amount: i128) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        require_function_active(&env, PAUSE_FUND)?;
+        require_breaker_clear(&env)?;
+        let mut trade = storage::get_trade(&env, trade_id)?;
+        require_fundable(&env, &trade)?;
+        payer.require_auth();
+        if basket::is_basket(&env, trade_id) {
+            return Err(ContractError::BasketNotPoolable);
+        }
+        amount::require_positive(amount)?;
+        pricing::fix_settlement(&env, &mut trade)?;
+        let mut contributions = storage::get_contributions(&env, trade_id).unwrap_or(soroban_sdk::Vec::new(&env));
+        let mut total = 0i128;
+        let mut existing = None;
+        for (i, c) in contributions.iter().enumerate() {
+            total = total.checked_add(c.amount).ok_or(ContractError::Overflow)?;
+            if c.payer == payer {
+                existing = Some((i as u32, c.amount));
+            }
+        }
+        total = total.checked_add(amount).ok_or(ContractError::Overflow)?;
+        if total > trade.amount {
+            return Err(ContractError::ContributionExceedsAmount);
+        }
+        transfer::send(
+            &token::Client::new(&env, &trade.currency),
+            &payer,
+            &env.current_contract_address(),
+            amount,
+        )?;
+        match existing {
+            Some((i, prev)) => contributions.set(
+                i,
+                Contribution { payer: payer.clone(), amount: prev.checked_add(amount).ok_or(ContractError::Overflow)? },
+            ),
+            None => contributions.push_back(Contribution { payer: payer.clone(), amount }),
+        }
+        storage::save_contributions(&env, trade_id, &contributions);
+        events::emit_contributed(&env, trade_id, payer, amount, total);
+        if total == trade.amount {
+            mark_funded(&env, &payer, &mut trade)?;
+        }
+        Ok(())
+    }
+
+    /// Take back a contribution before the pool is complete.
+    pub fn withdraw_contribution(env: Env, trade_id: u64, payer: Address) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        let trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Created {
+            return Err(ContractError::InvalidStatus);
+        }
+        payer.require_auth();
+        let mut contributions = storage::get_contributions(&env, trade_id).ok_or(ContractError::NoContribution)?;
+        let index = contributions
+            .iter()
+            .position(|c| c.payer == payer)
+            .ok_or(ContractError::NoContribution)? as u32;
+        let amount = contributions.get(index).ok_or(ContractError::NoContribution)?.amount;
+        contributions.remove(index);
+        storage::save_contributions(&env, trade_id, &contributions);
+        transfer::send(
+            &token::Client::new(&env, &trade.currency),
+            &env.current_contract_address(),
+            &payer,
+            amount,
+        )?;
+        events::emit_contribution_returned(&env, trade_id, payer, amount);
+        Ok(())
+    }
+
+    pub fn get_contributions(env: Env, trade_id: u64) -> soroban_sdk::Vec<Contribution> {
+        storage::get_contributions(&env, trade_id).unwrap_or(soroban_sdk::Vec::new(&env))
+    }
+
+    /// Move a `Created` trade left unfunded past the funding deadline to
+    /// `Expired`. Callable by anyone. No keeper bounty is paid: an unfunded
+    /// trade has no fee to pay it from.
+    pub fn expire_trade(env: Env, trade_id: u64) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        let mut trade = storage::get_trade(&env, trade_id)?;
+        check_expirable(&env, &trade)?;
+        return_contributions(&env, &trade)?;
+        state::transition(&env, &mut trade, TradeEvent::Expire, &env.current_contract_address())?;
+        storage::save_trade(&env, trade_id, &trade);
+        events::emit_trade_expired(&env, trade_id);
+        Ok(())
+    }
+
+    /// Set how many ledgers a trade may stay unfunded before it can be
+    /// expired (admin only). Zero disables expiry.
+    pub fn set_funding_deadline(env: Env, ledgers: u32) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        let old = storage::get_funding_deadline(&env);
+        storage::set_funding_deadline(&env, ledgers);
+        events::emit_config_changed(&env, symbol_short!("fund_dl"), old, ledgers);
+        Ok(())
+    }
+
+    pub fn get_funding_deadline(env: Env) -> u32 {
+        storage::get_funding_deadline(&env)
+    }
+
+    /// Bounty for whoever triggers a timeout on a funded trade (overdue
+    /// refund or release, held release) as keeper, in basis points of that
+    /// trade's fee (admin only). It is withheld from the trade's escrow in
+    /// its currency: from the fee on a release, from the refund on an
+    /// overdue refund. Zero disables.
+    pub fn set_keeper_bounty(env: Env, bps: u32) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        if bps > 10_000 {
+            return Err(ContractError::InvalidFeeBps);
+        }
+        let old = storage::get_keeper_bounty_bps(&env);
+        storage::set_keeper_bounty_bps(&env, bps);
+        events::emit_config_changed(&env, symbol_short!("keeper"), old, bps);
+        Ok(())
+    }
+
+    pub fn get_keeper_bounty(env: Env) -> u32 {
+        storage::get_keeper_bounty_bps(&env)
+    }
+
+    /// Choose whether every deadline feature measures time in ledger
+    /// sequences or timestamps (admin only). Windows configured in the other
+    /// unit are converted at `SECONDS_PER_LEDGER`. Only allowed before the
+    /// first trade, so stored deadlines never change meaning.
+    pub fn set_timing_mode(env: Env, mode: TimingMode) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        if storage::get_trade_counter(&env)? > 0 {
+            return Err(ContractError::TimingModeLocked);
+        }
+        let old = storage::get_timing_mode(&env);
+        storage::set_timing_mode(&env, mode);
+        events::emit_config_changed(&env, symbol_short!("timing"), old, Some(mode));
+        Ok(())
+    }
+
+    /// The admin-chosen timing mode, or `None` if each feature keeps its
+    /// native unit.
+    pub fn get_timing_mode(env: Env) -> Option<TimingMode> {
+        storage::get_timing_mode(&env)
+    }
+
+    /// Set how many ledgers after creation a funded trade has to be marked
+    /// complete before the buyer can reclaim it (admin only). Zero disables.
+    pub fn set_completion_window(env: Env, ledgers: u32) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        let old = storage::get_completion_window(&env);
+        storage::set_completion_window(&env, ledgers);
+        events::emit_config_changed(&env, symbol_short!("cmpl_win"), old, ledgers);
+        Ok(())
+    }
+
+    pub fn get_completion_window(env: Env) -> u32 {
+        storage::get_completion_window(&env)
+    }
+
+    /// Set how many ledgers after creation a completed trade has to be
+    /// confirmed before it releases to the seller (admin only). Zero disables.
+    pub fn set_confirmation_window(env: Env, ledgers: u32) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        let old = storage::get_confirmation_window(&env);
+        storage::set_confirmation_window(&env, ledgers);
+        events::emit_config_changed(&env, symbol_short!("conf_win"), old, ledgers);
+        Ok(())
+    }
+
+    pub fn get_confirmation_window(env: Env) -> u32 {
+        storage::get_confirmation_window(&env)
+    }
+
+    /// Effective deadlines for a trade, with admin defaults filled in.
+    pub fn get_trade_deadlines(env: Env, trade_id: u64) -> Result<Deadlines, ContractError> {
+        storage::get_trade(&env, trade_id)?;
+        Ok(trade_deadlines(&env, trade_id))
+    }
+
+    /// Return the full escrow to the buyer of a funded trade the seller did
+    /// not mark complete by its `complete_by` deadline. Callable by anyone;
+    /// a `keeper` earns the keeper bounty.
+    pub fn refund_overdue(env: Env, trade_id: u64, keeper: Option<Address>) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        let mut trade = storage::get_trade(&env, trade_id)?;
+        check_overdue_refund(&env, &trade)?;
+        let bounty = keeper_bounty(&env, &trade, &keeper)?;
+        let refund = trade.amount.checked_sub(bounty).ok_or(ContractError::Overflow)?;
+        storage::sub_total_locked(&env, &trade.currency, trade.amount)?;
+        pay_buyer_side(&env, &token::Client::new(&env, &trade.currency), &trade, refund)?;
+        state::transition(&env, &mut trade, TradeEvent::Refund, &env.current_contract_address())?;
+        storage::save_trade(&env, trade_id, &trade);
+        storage::remove_refund_request(&env, trade_id);
+        events::emit_trade_refunded(&env, trade_id, refund, 0);
+        receipt::issue(&env, &trade, ReceiptOutcome::Refunded);
+        record_settlement(&env, &trade)?;
+        pay_keeper(&env, &trade, keeper, bounty)
+    }
+
+    /// Keeper sweep: expire each unfunded trade past its funding deadline
+    /// and refund each funded trade past its completion deadline. Callable
+    /// by anyone; a `keeper` earns the keeper bounty per funded trade refunded.
+    /// Trades that are not due are skipped and reported in the result; a
+    /// failure once a trade's refunds have started fails the whole sweep, so
+    /// every transfer is rolled back.
+    pub fn sweep_expired(
+        env: Env,
+        ids: soroban_sdk::Vec<u64>,
+        keeper: Option<Address>,
+    ) -> Result<soroban_sdk::Vec<BatchResolutionResult>, ContractError> {
+        require_initialized(&env)?;
+        if ids.len() > MAX_SWEEP_TRADES {
+            return Err(ContractError::BatchTooLarge);
+        }
+        let mut results = soroban_sdk::Vec::new(&env);
+        for trade_id in ids.iter() {
+            let checked = storage::get_trade(&env, trade_id).and_then(|trade| {
+                state::require_not_frozen(&env, trade_id)?;
+                match trade.status {
+                    TradeStatus::Created => check_expirable(&env, &trade),
+                    TradeStatus::Funded => check_overdue_refund(&env, &trade),
+                    _ => Err(ContractError::InvalidStatus),
+                }?;
+                Ok(trade.status)
+            });
+            let error = match checked {
+                Ok(TradeStatus::Created) => {
+                    Self::expire_trade(env.clone(), trade_id)?;
+                    None
+                }
+                Ok(_) => {
+                    Self::refund_overdue(env.clone(), trade_id, keeper.clone())?;
+                    None
+                }
+                Err(e) => Some(e as u32),
+            };
+            results.push_back(BatchResolutionResult { trade_id, error });
+        }
+        Ok(results)
+    }
+
+    /// Pay out a completed trade the buyer neither confirmed nor disputed by
+    /// its `confirm_by` deadline. Callable by anyone; a `keeper` earns the
+    /// keeper bounty.
+    pub fn release_overdue(env: Env, trade_id: u64, keeper: Option<Address>) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let mut trade = storage::get_trade(&env, trade_id)?;
+        if storage::is_bundle(&env, trade_id) {
+            return Err(ContractError::BundleRequiresItemSettlement);
+        }
+        if storage::get_streamed_amount(&env, trade_id) > 0 {
+            return Err(ContractError::StreamInProgress);
+        }
+        if trade.status != TradeStatus::Completed {
+            return Err(ContractError::InvalidStatus);
+        }
+        if !deadline_passed(&env, trade_deadlines(&env, trade_id).confirm_by) {
+            return Err(ContractError::DeadlineNotReached);
+        }
+        let payout = trade.amount.checked_sub(trade.fee).ok_or(ContractError::Overflow)?;
+        storage::sub_total_locked(&env, &trade.currency, trade.amount)?;
+        pay_seller_side(&env, &token::Client::new(&env, &trade.currency), &trade, payout)?;
+        let bounty = keeper_bounty(&env, &trade, &keeper)?;
+        book_trade_fee(&env, &trade, trade.fee - bounty)?;
+        tiers::record_volume(&env, &trade.seller, trade.amount)?;
+        tiers::record_volume(&env, &trade.buyer, trade.amount)?;
+        loyalty::accrue(&env, &trade.seller, trade.amount)?;
+        loyalty::accrue(&env, &trade.buyer, trade.amount)?;
+        badges::record_completed(&env, &trade.seller);
+        badges::record_completed(&env, &trade.buyer);
+        events::emit_trade_confirmed(&env, trade_id, payout, trade.fee);
+        analytics::on_trade_completed(&env, trade.fee)?;
+        state::transition(&env, &mut trade, TradeEvent::Settle, &env.current_contract_address())?;
+        receipt::issue(&env, &trade, ReceiptOutcome::Completed);
+        record_settlement(&env, &trade)?;
+        pay_keeper(&env, &trade, keeper, bounty)
+    }
+
+    /// Seller marks the trade delivered, optionally attaching a proof hash
+    /// (tracking number, signed delivery receipt digest) for arbitrators.
+    pub fn complete_trade(
+        env: Env,
+        trade_id: u64,
+        proof_hash: Option<soroban_sdk::BytesN<32>>,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let mut trade = storage::get_trade(&env, trade_id)?;
+        state::check(&trade, TradeEvent::Complete)?;
+        trade.seller.require_auth();
+        let actor = trade.seller.clone();
+        state::transition(&env, &mut trade, TradeEvent::Complete, &actor)?;
+        storage::save_trade(&env, trade_id, &trade);
+        if let Some(proof_hash) = proof_hash {
+            storage::save_delivery_proof(&env, trade_id, &proof_hash);
+            events::emit_delivery_proof(&env, trade_id, proof_hash);
         }
-        trade.seller.require_auth();
-        trade.status = TradeStatus::Completed;
-        storage::save_trade(&env, trade_id, &trade);
         events::emit_trade_completed(&env, trade_id);
         Ok(())
     }
 
-    pub fn confirm_receipt(env: Env, trade_id: u64) -> Result<(), ContractError> {
+    pub fn get_delivery_proof(env: Env, trade_id: u64) -> Option<soroban_sdk::BytesN<32>> {
+        storage::get_delivery_proof(&env, trade_id)
+    }
+
+    /// Anchor the hash of an off-chain message (chat line, negotiation
+    /// transcript) to the trade. Callable by the buyer, seller, or
+    /// arbitrator; keeps the last `MAX_MESSAGE_ANCHORS` digests.
+    pub fn anchor_message(
+        env: Env,
+        trade_id: u64,
+        caller: Address,
+        msg_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), ContractError> {
         require_initialized(&env)?;
         require_not_paused(&env)?;
         let trade = storage::get_trade(&env, trade_id)?;
+        if caller != trade.buyer && caller != trade.seller && trade.arbitrator.as_ref() != Some(&caller) {
+            return Err(ContractError::Unauthorized);
+        }
+        caller.require_auth();
+        let mut anchors = storage::get_message_anchors(&env, trade_id);
+        if anchors.len() >= MAX_MESSAGE_ANCHORS {
+            anchors.pop_front();
+        }
+        anchors.push_back(MessageAnchor {
+            author: caller.clone(),
+            msg_hash: msg_hash.clone(),
+            ledger: env.ledger().sequence(),
+        });
+        storage::save_message_anchors(&env, trade_id, &anchors);
+        events::emit_message_anchored(&env, trade_id, caller, msg_hash);
+        Ok(())
+    }
+
+    /// Anchored message digests for a trade, oldest first.
+    pub fn get_message_anchors(env: Env, trade_id: u64) -> soroban_sdk::Vec<MessageAnchor> {
+        storage::get_message_anchors(&env, trade_id)
+    }
+
+    /// Confirm receipt and donate part of the seller payout to the
+    /// configured charity: the remainder after rounding down to a unit, or a
+    /// share in basis points (buyer only).
+    pub fn confirm_receipt_with_donation(
+        env: Env,
+        trade_id: u64,
+        seller_rating: Option<u32>,
+        donation: DonationChoice,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        let trade = storage::get_trade(&env, trade_id)?;
+        trade.buyer.require_auth();
+        let payout = trade.amount.checked_sub(trade.fee).ok_or(ContractError::Overflow)?;
+        donation::choose(&env, &trade, &donation, payout)?;
+        Self::confirm_receipt(env, trade_id, seller_rating)
+    }
+
+    /// Set or clear the charity that receives buyer donations (admin only).
+    pub fn set_charity(env: Env, charity: Option<Address>) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        let old = donation::get_charity(&env);
+        donation::set_charity(&env, &charity);
+        events::emit_config_changed(&env, symbol_short!("charity"), old, charity);
+        Ok(())
+    }
+
+    pub fn get_charity(env: Env) -> Option<Address> {
+        donation::get_charity(&env)
+    }
+
+    /// Total donated to charity so far in `currency`.
+    pub fn get_total_donated(env: Env, currency: Address) -> i128 {
+        donation::total_donated(&env, &currency)
+    }
+
+    /// Buyer confirms delivery, optionally rating the seller 1–5 stars.
+    pub fn confirm_receipt(
+        env: Env,
+        trade_id: u64,
+        seller_rating: Option<u32>,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let mut trade = storage::get_trade(&env, trade_id)?;
+        if storage::is_bundle(&env, trade_id) {
+            return Err(ContractError::BundleRequiresItemSettlement);
+        }
+        if storage::get_streamed_amount(&env, trade_id) > 0 {
+            return Err(ContractError::StreamInProgress);
+        }
         if trade.status != TradeStatus::Completed {
             return Err(ContractError::InvalidStatus);
         }
         trade.buyer.require_auth();
-        let payout = trade.amount.checked_sub(trade.fee).ok_or(ContractError::Overflow)?;
-        // Single token transfer using the trade's currency directly (no extra USDC lookup).
-        let token_client = TokenClient::new(&env, &trade.currency);
-        token_client.transfer(&env.current_contract_address(), &trade.seller, &(payout as i128));
-        // Atomic read-modify-write avoids a separate get + set call for per-currency fees.
-        add_currency_fees(&env, &trade.currency, trade.fee)?;
+        if let Some(stars) = seller_rating {
+            reputation::rate_counterparty(&env, trade_id, &trade.buyer, &trade.seller, stars)?;
+        }
+        let hold = release_hold(&env, trade_id);
+        if hold > 0 {
+            let release_at = clock::after(&env, TimingMode::Ledger, hold as u64)?;
+            let actor = trade.buyer.clone();
+            state::transition(&env, &mut trade, TradeEvent::Hold, &actor)?;
+            storage::save_trade(&env, trade_id, &trade);
+            storage::set_held_until(&env, trade_id, release_at);
+            events::emit_release_held(&env, trade_id, release_at);
+            return Ok(());
+        }
         tiers::record_volume(&env, &trade.seller, trade.amount)?;
         tiers::record_volume(&env, &trade.buyer, trade.amount)?;
+        loyalty::accrue(&env, &trade.seller, trade.amount)?;
+        loyalty::accrue(&env, &trade.buyer, trade.amount)?;
+        badges::record_completed(&env, &trade.seller);
+        badges::record_completed(&env, &trade.buyer);
         let payout = trade
             .amount
             .checked_sub(trade.fee)
             .ok_or(ContractError::Overflow)?;
         let token_client = token::Client::new(&env, &trade.currency);
         let contract_balance = token_client.balance(&env.current_contract_address());
-        let required_balance = trade.amount as i128;
-        if contract_balance < required_balance {
-            token_client.transfer(
+        if contract_balance < trade.amount {
+            transfer::send(
+                &token_client,
                 &trade.buyer,
                 &env.current_contract_address(),
-                &(required_balance - contract_balance),
-            );
+                trade.amount - contract_balance,
+            )?;
+        }
+        storage::sub_total_locked(&env, &trade.currency, trade.amount)?;
+        let payout = donation::take(&env, &trade, payout)?;
+        pay_seller_side(&env, &token_client, &trade, payout)?;
+        book_trade_fee(&env, &trade, trade.fee)?;
+        events::emit_trade_confirmed(&env, trade_id, payout, trade.fee);
+        analytics::on_trade_completed(&env, trade.fee)?;
+        let actor = trade.buyer.clone();
+        state::transition(&env, &mut trade, TradeEvent::Settle, &actor)?;
+        receipt::issue(&env, &trade, ReceiptOutcome::Completed);
+        record_settlement(&env, &trade)?;
+        Ok(())
+    }
+
+    /// Pay out a trade whose post-confirmation hold has elapsed. Callable by
+    /// anyone, with a `keeper` earning the keeper bounty; the buyer may
+    /// still dispute until then.
+    pub fn claim_release(env: Env, trade_id: u64, keeper: Option<Address>) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let mut trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::PendingRelease {
+            return Err(ContractError::InvalidStatus);
+        }
+        let release_at = storage::get_held_until(&env, trade_id).unwrap_or(0);
+        if clock::now(&env, TimingMode::Ledger) < release_at {
+            return Err(ContractError::HoldWindowActive);
         }
-        token_client.transfer(&env.current_contract_address(), &trade.seller, &(payout as i128));
-        storage::add_accumulated_fees(&env, trade.fee)?;
+        let payout = trade.amount.checked_sub(trade.fee).ok_or(ContractError::Overflow)?;
+        storage::sub_total_locked(&env, &trade.currency, trade.amount)?;
+        let payout = donation::take(&env, &trade, payout)?;
+        pay_seller_side(&env, &token::Client::new(&env, &trade.currency), &trade, payout)?;
+        let bounty = keeper_bounty(&env, &trade, &keeper)?;
+        book_trade_fee(&env, &trade, trade.fee - bounty)?;
+        tiers::record_volume(&env, &trade.seller, trade.amount)?;
+        tiers::record_volume(&env, &trade.buyer, trade.amount)?;
+        loyalty::accrue(&env, &trade.seller, trade.amount)?;
+        loyalty::accrue(&env, &trade.buyer, trade.amount)?;
+        badges::record_completed(&env, &trade.seller);
+        badges::record_completed(&env, &trade.buyer);
+        state::transition(&env, &mut trade, TradeEvent::Settle, &env.current_contract_address())?;
+        storage::save_trade(&env, trade_id, &trade);
         events::emit_trade_confirmed(&env, trade_id, payout, trade.fee);
-        analytics::on_trade_completed(&env, trade.fee);
+        analytics::on_trade_completed(&env, trade.fee)?;
+        receipt::issue(&env, &trade, ReceiptOutcome::Completed);
+        record_settlement(&env, &trade)?;
+        pay_keeper(&env, &trade, keeper, bounty)
+    }
+
+    /// Ledgers funds stay held after `confirm_receipt` (admin only). Zero
+    /// releases immediately.
+    pub fn set_default_hold(env: Env, ledgers: u32) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        let old = storage::get_default_hold(&env);
+        storage::set_default_hold(&env, ledgers);
+        events::emit_config_changed(&env, symbol_short!("hold"), old, ledgers);
         Ok(())
     }
 
+    /// Override the hold for a high-risk category (admin only).
+    pub fn set_category_hold(env: Env, category: soroban_sdk::Symbol, ledgers: u32) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        let old = storage::get_category_hold(&env, &category);
+        storage::set_category_hold(&env, &category, ledgers);
+        events::emit_config_entry_changed(&env, symbol_short!("cat_hold"), category, old, Some(ledgers));
+        Ok(())
+    }
+
+    pub fn get_release_hold(env: Env, trade_id: u64) -> u32 {
+        release_hold(&env, trade_id)
+    }
+
+    /// Rate the other party of a settled trade (1–5 stars). Either side may
+    /// rate once; a buyer who rated via `confirm_receipt` has used their rating.
+    pub fn rate_counterparty(
+        env: Env,
+        trade_id: u64,
+        rater: Address,
+        stars: u32,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        let trade = storage::get_trade(&env, trade_id)?;
+        let settled = matches!(
+            trade.status,
+            TradeStatus::Settled | TradeStatus::PendingRelease | TradeStatus::Refunded
+        );
+        if !settled {
+            return Err(ContractError::InvalidStatus);
+        }
+        let ratee = if rater == trade.buyer {
+            trade.seller.clone()
+        } else if rater == trade.seller {
+            trade.buyer.clone()
+        } else {
+            return Err(ContractError::Unauthorized);
+        };
+        rater.require_auth();
+        reputation::rate_counterparty(&env, trade_id, &rater, &ratee, stars)
+    }
+
+    pub fn get_party_reputation(env: Env, party: Address) -> PartyReputation {
+        storage::get_party_reputation(&env, &party)
+    }
+
     pub fn cancel_trade(env: Env, trade_id: u64) -> Result<(), ContractError> {
         require_initialized(&env)?;
         require_not_paused(&env)?;
@@ -690,24 +2833,34 @@ impl StellarEscrowContract {
             return Err(ContractError::NotInitialized);
         }
         trade.seller.require_auth();
-        trade.status = TradeStatus::Cancelled;
+        return_contributions(&env, &trade)?;
+        let actor = trade.seller.clone();
+        state::transition(&env, &mut trade, TradeEvent::Cancel, &actor)?;
         storage::save_trade(&env, trade_id, &trade);
         events::emit_trade_cancelled(&env, trade_id);
-        analytics::on_trade_cancelled(&env);
+        analytics::on_trade_cancelled(&env)?;
         Ok(())
     }
 
+    /// Buyer or seller escalates a trade to its arbitrator. `caller` must
+    /// authorize and is recorded as the party that raised the dispute.
     pub fn raise_dispute(
         env: Env,
-        trade_id: u64,
         caller: Address,
+        trade_id: u64,
     ) -> Result<(), ContractError> {
         require_initialized(&env)?;
         require_not_paused(&env)?;
+        require_function_active(&env, PAUSE_DISPUTE)?;
         let mut trade = storage::get_trade(&env, trade_id)?;
-        if trade.status != TradeStatus::Funded && trade.status != TradeStatus::Completed {
+        if storage::is_bundle(&env, trade_id) {
+            return Err(ContractError::BundleRequiresItemSettlement);
+        }
+        if trade.status == TradeStatus::Completed && storage::get_streamed_amount(&env, trade_id) > 0 {
+            // A fully streamed trade has nothing left in escrow to dispute.
             return Err(ContractError::InvalidStatus);
         }
+        state::check(&trade, TradeEvent::Dispute)?;
         if trade.arbitrator.is_none() {
             return Err(ContractError::ArbitratorNotRegistered);
         }
@@ -715,139 +2868,1267 @@ impl StellarEscrowContract {
         if caller != trade.buyer && caller != trade.seller {
             return Err(ContractError::Unauthorized);
         }
-        trade.status = TradeStatus::Disputed;
-        storage::save_trade(&env, trade_id, &trade);
-        events::emit_dispute_raised(&env, trade_id, caller);
-        analytics::on_trade_disputed(&env);
+        state::transition(&env, &mut trade, TradeEvent::Dispute, &caller)?;
+        storage::save_trade(&env, trade_id, &trade);
+        storage::save_dispute_raiser(&env, trade_id, &caller);
+        dispute_bonds::open(&env, trade_id)?;
+        dispute_responses::open(&env, &trade, &caller)?;
+        if let Some(ref arb) = trade.arbitrator {
+            storage::increment_arbitrator_caseload(&env, arb)?;
+        }
+        events::emit_dispute_raised(&env, trade_id, caller);
+        analytics::on_trade_disputed(&env)?;
+        Ok(())
+    }
+
+    /// Party that raised or re-opened the most recent dispute on this trade.
+    pub fn get_dispute_raiser(env: Env, trade_id: u64) -> Option<Address> {
+        storage::get_dispute_raiser(&env, trade_id)
+    }
+
+    // -------------------------------------------------------------------------
+    // Refund requests
+    // -------------------------------------------------------------------------
+
+    /// Buyer asks the seller for a refund on a funded or completed trade. The
+    /// seller may `approve_refund`; otherwise the buyer can `escalate_refund`
+    /// to a dispute once `REFUND_RESPONSE_WINDOW` has passed.
+    pub fn request_refund(
+        env: Env,
+        trade_id: u64,
+        reason_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let trade = storage::get_trade(&env, trade_id)?;
+        if storage::is_bundle(&env, trade_id) {
+            return Err(ContractError::BundleRequiresItemSettlement);
+        }
+        if storage::get_streamed_amount(&env, trade_id) > 0 {
+            return Err(ContractError::StreamInProgress);
+        }
+        if trade.status != TradeStatus::Funded && trade.status != TradeStatus::Completed {
+            return Err(ContractError::InvalidStatus);
+        }
+        trade.buyer.require_auth();
+        if storage::get_refund_request(&env, trade_id).is_some() {
+            return Err(ContractError::RefundAlreadyRequested);
+        }
+        let now = clock::now(&env, TimingMode::Timestamp);
+        let escalate_after = clock::after(&env, TimingMode::Timestamp, REFUND_RESPONSE_WINDOW)?;
+        storage::save_refund_request(
+            &env,
+            trade_id,
+            &RefundRequest { reason_hash: reason_hash.clone(), requested_at: now, escalate_after },
+        );
+        events::emit_refund_requested(&env, trade_id, reason_hash, escalate_after);
+        Ok(())
+    }
+
+    /// Seller accepts an open refund request; the full escrow returns to the
+    /// buyer immediately and no platform fee is charged.
+    pub fn approve_refund(env: Env, trade_id: u64) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let mut trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Funded && trade.status != TradeStatus::Completed {
+            return Err(ContractError::InvalidStatus);
+        }
+        if storage::get_refund_request(&env, trade_id).is_none() {
+            return Err(ContractError::NoRefundRequest);
+        }
+        trade.seller.require_auth();
+        storage::sub_total_locked(&env, &trade.currency, trade.amount)?;
+        pay_buyer_side(&env, &token::Client::new(&env, &trade.currency), &trade, trade.amount)?;
+        let actor = trade.seller.clone();
+        state::transition(&env, &mut trade, TradeEvent::Refund, &actor)?;
+        storage::save_trade(&env, trade_id, &trade);
+        storage::remove_refund_request(&env, trade_id);
+        events::emit_refund_approved(&env, trade_id, trade.amount);
+        receipt::issue(&env, &trade, ReceiptOutcome::Refunded);
+        record_settlement(&env, &trade)?;
+        Ok(())
+    }
+
+    /// Buyer escalates an unanswered refund request to a dispute once the
+    /// seller's response window has elapsed. A trade without an arbitrator
+    /// goes to the admin, who rules on it through `resolve_dispute`.
+    pub fn escalate_refund(env: Env, trade_id: u64) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        require_function_active(&env, PAUSE_DISPUTE)?;
+        let mut trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Funded && trade.status != TradeStatus::Completed {
+            return Err(ContractError::InvalidStatus);
+        }
+        let request = storage::get_refund_request(&env, trade_id)
+            .ok_or(ContractError::NoRefundRequest)?;
+        if clock::now(&env, TimingMode::Timestamp) < request.escalate_after {
+            return Err(ContractError::RefundWindowOpen);
+        }
+        trade.buyer.require_auth();
+        let actor = trade.buyer.clone();
+        state::transition(&env, &mut trade, TradeEvent::Dispute, &actor)?;
+        storage::save_trade(&env, trade_id, &trade);
+        storage::remove_refund_request(&env, trade_id);
+        dispute_bonds::open(&env, trade_id)?;
+        dispute_responses::open(&env, &trade, &trade.buyer)?;
+        if let Some(ref arb) = trade.arbitrator {
+            storage::increment_arbitrator_caseload(&env, arb)?;
+        }
+        events::emit_dispute_raised(&env, trade_id, trade.buyer);
+        analytics::on_trade_disputed(&env)?;
+        Ok(())
+    }
+
+    /// Seller voluntarily refunds a funded or completed trade. With
+    /// `waive_fee` the buyer receives the full escrow; otherwise the platform
+    /// fee is retained as on a normal release.
+    pub fn refund_trade(env: Env, trade_id: u64, waive_fee: bool) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let mut trade = storage::get_trade(&env, trade_id)?;
+        if storage::is_bundle(&env, trade_id) {
+            return Err(ContractError::BundleRequiresItemSettlement);
+        }
+        if storage::get_streamed_amount(&env, trade_id) > 0 {
+            return Err(ContractError::StreamInProgress);
+        }
+        if trade.status != TradeStatus::Funded && trade.status != TradeStatus::Completed {
+            return Err(ContractError::InvalidStatus);
+        }
+        trade.seller.require_auth();
+        let fee = if waive_fee { 0 } else { trade.fee };
+        let refund = trade.amount.checked_sub(fee).ok_or(ContractError::Overflow)?;
+        storage::sub_total_locked(&env, &trade.currency, trade.amount)?;
+        pay_buyer_side(&env, &token::Client::new(&env, &trade.currency), &trade, refund)?;
+        let actor = trade.seller.clone();
+        state::transition(&env, &mut trade, TradeEvent::Refund, &actor)?;
+        storage::save_trade(&env, trade_id, &trade);
+        storage::remove_refund_request(&env, trade_id);
+        if fee > 0 {
+            book_trade_fee(&env, &trade, fee)?;
+        }
+        events::emit_trade_refunded(&env, trade_id, refund, fee);
+        receipt::issue(&env, &trade, ReceiptOutcome::Refunded);
+        record_settlement(&env, &trade)?;
+        Ok(())
+    }
+
+    pub fn get_refund_request(env: Env, trade_id: u64) -> Option<RefundRequest> {
+        storage::get_refund_request(&env, trade_id)
+    }
+
+    /// Use `DisputeResolution::Partial { buyer_bps }` for a split:
+    /// `buyer_bps` is the buyer's share of the net payout in basis points (0–10000).
+    /// Arbitrator rules on a disputed trade. `reason_hash` anchors the
+    /// off-chain written decision and is stored with the trade. When a
+    /// re-open window is configured the payout is deferred: the trade moves
+    /// to `Resolved` and settles via `execute_resolution` once it closes.
+    /// Trades at or above the supervision threshold additionally wait for
+    /// `approve_resolution` from the admin. A trade escalated without an
+    /// arbitrator is ruled on by the admin directly.
+    pub fn resolve_dispute(
+        env: Env,
+        trade_id: u64,
+        resolution: DisputeResolution,
+        reason_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let mut trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Disputed {
+            return Err(ContractError::InvalidStatus);
+        }
+        let arbitrator = dispute_resolver(&env, &trade)?;
+        arbitrator.require_auth();
+        apply_resolution(&env, &mut trade, &arbitrator, resolution, reason_hash)
+    }
+
+    /// Resolve several disputes assigned to `arbitrator` in one call, all
+    /// with the same `reason_hash`. Items that fail their checks are skipped
+    /// and reported in the result; a failure once an item's payout has
+    /// started fails the whole batch, so every transfer is rolled back.
+    pub fn resolve_disputes(
+        env: Env,
+        arbitrator: Address,
+        items: soroban_sdk::Vec<(u64, DisputeResolution)>,
+        reason_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<soroban_sdk::Vec<BatchResolutionResult>, ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        if items.len() > MAX_BATCH_RESOLUTIONS {
+            return Err(ContractError::BatchTooLarge);
+        }
+        arbitrator.require_auth();
+        let mut results = soroban_sdk::Vec::new(&env);
+        for (trade_id, resolution) in items.iter() {
+            let checked = storage::get_trade(&env, trade_id).and_then(|trade| {
+                if trade.arbitrator.as_ref() != Some(&arbitrator) {
+                    return Err(ContractError::Unauthorized);
+                }
+                check_resolution(&env, &trade, &resolution)?;
+                Ok(trade)
+            });
+            let error = match checked {
+                Ok(mut trade) => {
+                    apply_resolution(&env, &mut trade, &arbitrator, resolution, reason_hash.clone())?;
+                    None
+                }
+                Err(e) => Some(e as u32),
+            };
+            results.push_back(BatchResolutionResult { trade_id, error });
+        }
+        Ok(results)
+    }
+
+    /// Either party re-opens a `Resolved` trade with new evidence before the
+    /// window closes, posting the configured bond. Allowed once per trade.
+    pub fn reopen_dispute(
+        env: Env,
+        trade_id: u64,
+        caller: Address,
+        evidence_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        require_function_active(&env, PAUSE_DISPUTE)?;
+        let mut trade = storage::get_trade(&env, trade_id)?;
+        state::check(&trade, TradeEvent::Reopen)?;
+        let pending = storage::get_pending_resolution(&env, trade_id)
+            .ok_or(ContractError::NoPendingResolution)?;
+        if clock::now(&env, TimingMode::Timestamp) >= pending.executable_at {
+            return Err(ContractError::ReopenWindowClosed);
+        }
+        if storage::get_reopen_bond(&env, trade_id).is_some() {
+            return Err(ContractError::AlreadyReopened);
+        }
+        caller.require_auth();
+        if caller != trade.buyer && caller != trade.seller {
+            return Err(ContractError::Unauthorized);
+        }
+        let bond = storage::get_reopen_policy(&env).bond;
+        if bond > 0 {
+            transfer::send(
+                &token::Client::new(&env, &trade.currency),
+                &caller,
+                &env.current_contract_address(),
+                bond,
+            )?;
+            storage::add_total_locked(&env, &trade.currency, bond)?;
+        }
+        storage::save_reopen_bond(
+            &env,
+            trade_id,
+            &ReopenBond { payer: caller.clone(), amount: bond, contested: pending.resolution },
+        );
+        storage::remove_pending_resolution(&env, trade_id);
+        state::transition(&env, &mut trade, TradeEvent::Reopen, &caller)?;
+        storage::save_trade(&env, trade_id, &trade);
+        storage::save_dispute_raiser(&env, trade_id, &caller);
+        events::emit_dispute_reopened(&env, trade_id, caller, evidence_hash, bond);
+        Ok(())
+    }
+
+    /// Pay out a deferred ruling once its re-open window has closed.
+    /// Callable by anyone.
+    pub fn execute_resolution(env: Env, trade_id: u64) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let mut trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Resolved {
+            return Err(ContractError::InvalidStatus);
+        }
+        let pending = storage::get_pending_resolution(&env, trade_id)
+            .ok_or(ContractError::NoPendingResolution)?;
+        if pending.awaiting_admin {
+            return Err(ContractError::ResolutionAwaitingReview);
+        }
+        if clock::now(&env, TimingMode::Timestamp) < pending.executable_at {
+            return Err(ContractError::ReopenWindowOpen);
+        }
+        let arbitrator = dispute_resolver(&env, &trade)?;
+        storage::remove_pending_resolution(&env, trade_id);
+        settle_resolution(&env, &mut trade, &arbitrator, pending.resolution, pending.reason_hash)
+    }
+
+    /// Admin countersigns a supervised ruling. Pays out immediately if the
+    /// re-open window has already closed, otherwise clears it for
+    /// `execute_resolution`.
+    pub fn approve_resolution(env: Env, trade_id: u64) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        let mut trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Resolved {
+            return Err(ContractError::InvalidStatus);
+        }
+        let mut pending = storage::get_pending_resolution(&env, trade_id)
+            .ok_or(ContractError::NoPendingResolution)?;
+        if !pending.awaiting_admin {
+            return Err(ContractError::ResolutionNotUnderReview);
+        }
+        events::emit_resolution_approved(&env, trade_id, admin);
+        if clock::now(&env, TimingMode::Timestamp) >= pending.executable_at {
+            let arbitrator = dispute_resolver(&env, &trade)?;
+            storage::remove_pending_resolution(&env, trade_id);
+            return settle_resolution(&env, &mut trade, &arbitrator, pending.resolution, pending.reason_hash);
+        }
+        pending.awaiting_admin = false;
+        storage::save_pending_resolution(&env, trade_id, &pending);
+        Ok(())
+    }
+
+    /// Admin rejects a supervised ruling; the trade returns to `Disputed` so
+    /// the arbitrator can rule again.
+    pub fn veto_resolution(env: Env, trade_id: u64) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        let mut trade = storage::get_trade(&env, trade_id)?;
+        state::check(&trade, TradeEvent::Reopen)?;
+        let pending = storage::get_pending_resolution(&env, trade_id)
+            .ok_or(ContractError::NoPendingResolution)?;
+        if !pending.awaiting_admin {
+            return Err(ContractError::ResolutionNotUnderReview);
+        }
+        storage::remove_pending_resolution(&env, trade_id);
+        state::transition(&env, &mut trade, TradeEvent::Reopen, &admin)?;
+        storage::save_trade(&env, trade_id, &trade);
+        events::emit_resolution_vetoed(&env, trade_id, admin);
+        Ok(())
+    }
+
+    /// Require admin countersignature on rulings for trades of at least
+    /// `threshold` (admin only). Zero disables supervision.
+    pub fn set_supervision_threshold(env: Env, threshold: i128) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        amount::require_non_negative(threshold)?;
+        let old = storage::get_supervision_threshold(&env);
+        storage::set_supervision_threshold(&env, threshold);
+        events::emit_config_changed(&env, symbol_short!("supv"), old, threshold);
+        Ok(())
+    }
+
+    pub fn get_supervision_threshold(env: Env) -> i128 {
+        storage::get_supervision_threshold(&env)
+    }
+
+    /// Configure the dispute re-open window and bond (admin only).
+    pub fn set_reopen_policy(env: Env, window: u64, bond: i128) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        amount::require_non_negative(bond)?;
+        let old = storage::get_reopen_policy(&env);
+        let policy = ReopenPolicy { window, bond };
+        storage::set_reopen_policy(&env, &policy);
+        events::emit_config_changed(&env, symbol_short!("reopen"), old, policy);
+        Ok(())
+    }
+
+    pub fn get_reopen_policy(env: Env) -> ReopenPolicy {
+        storage::get_reopen_policy(&env)
+    }
+
+    /// Require both parties to post a bond of `amount` within `window`
+    /// seconds of a dispute opening (admin only). Zero `amount` disables.
+    pub fn set_dispute_bond_policy(env: Env, amount: i128, window: u64) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        amount::require_non_negative(amount)?;
+        let old = storage::get_dispute_bond_policy(&env);
+        let policy = DisputeBondPolicy { amount, window };
+        storage::set_dispute_bond_policy(&env, &policy);
+        events::emit_config_changed(&env, symbol_short!("dsp_bond"), old, policy);
+        Ok(())
+    }
+
+    pub fn get_dispute_bond_policy(env: Env) -> DisputeBondPolicy {
+        storage::get_dispute_bond_policy(&env)
+    }
+
+    /// Post `caller`'s bond on a disputed trade. Buyer or seller only,
+    /// before the bond window closes.
+    pub fn post_dispute_bond(env: Env, trade_id: u64, caller: Address) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Disputed {
+            return Err(ContractError::InvalidStatus);
+        }
+        caller.require_auth();
+        dispute_bonds::post(&env, &trade, &caller)
+    }
+
+    pub fn get_dispute_bonds(env: Env, trade_id: u64) -> Option<DisputeBonds> {
+        storage::get_dispute_bonds(&env, trade_id)
+    }
+
+    /// Settle a dispute in favour of the only party that posted its bond,
+    /// once the bond window has closed. Callable by anyone.
+    pub fn claim_bond_default(env: Env, trade_id: u64) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Disputed {
+            return Err(ContractError::InvalidStatus);
+        }
+        let ruling = dispute_bonds::default_ruling(&env, trade_id)?;
+        settle_by_default(&env, trade, ruling)
+    }
+
+    /// Seconds each party has to engage once a dispute opens (admin only).
+    /// Zero disables default victory.
+    pub fn set_dispute_response_window(env: Env, window: u64) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        let old = storage::get_dispute_response_window(&env);
+        storage::set_dispute_response_window(&env, window);
+        events::emit_config_changed(&env, symbol_short!("dsp_resp"), old, window);
+        Ok(())
+    }
+
+    pub fn get_dispute_response_window(env: Env) -> u64 {
+        storage::get_dispute_response_window(&env)
+    }
+
+    /// Submit evidence on a disputed trade. Buyer or seller only; within a
+    /// response window this also records the party as responsive.
+    pub fn submit_evidence(
+        env: Env,
+        trade_id: u64,
+        caller: Address,
+        evidence_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Disputed {
+            return Err(ContractError::InvalidStatus);
+        }
+        caller.require_auth();
+        if caller != trade.buyer && caller != trade.seller {
+            return Err(ContractError::Unauthorized);
+        }
+        dispute_responses::record(&env, &trade, &caller)?;
+        events::emit_evidence_submitted(&env, trade_id, caller, evidence_hash);
+        Ok(())
+    }
+
+    pub fn get_dispute_responses(env: Env, trade_id: u64) -> Option<DisputeResponses> {
+        storage::get_dispute_responses(&env, trade_id)
+    }
+
+    /// Settle a dispute in favour of the only party that responded within
+    /// the response window, once it has closed. Callable by anyone.
+    pub fn claim_default_victory(env: Env, trade_id: u64) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Disputed {
+            return Err(ContractError::InvalidStatus);
+        }
+        let ruling = dispute_responses::default_ruling(&env, trade_id)?;
+        settle_by_default(&env, trade, ruling)
+    }
+
+    pub fn get_pending_resolution(env: Env, trade_id: u64) -> Option<PendingResolution> {
+        storage::get_pending_resolution(&env, trade_id)
+    }
+
+    pub fn get_trade(env: Env, trade_id: u64) -> Result<Trade, ContractError> {
+        storage::get_trade(&env, trade_id)
+    }
+
+    /// Fetch up to `MAX_BULK_TRADES` trades in one call. Unknown ids are
+    /// skipped; callers match results back by `Trade::id`.
+    pub fn get_trades(
+        env: Env,
+        ids: soroban_sdk::Vec<u64>,
+    ) -> Result<soroban_sdk::Vec<Trade>, ContractError> {
+        if ids.len() > MAX_BULK_TRADES {
+            return Err(ContractError::BatchTooLarge);
+        }
+        let mut trades = soroban_sdk::Vec::new(&env);
+        for trade_id in ids.iter() {
+            if let Ok(trade) = storage::get_trade(&env, trade_id) {
+                trades.push_back(trade);
+            }
+        }
+        Ok(trades)
+    }
+
+    /// Hash of the arbitrator's written decision, if the trade was resolved.
+    pub fn get_resolution_reason(env: Env, trade_id: u64) -> Option<soroban_sdk::BytesN<32>> {
+        storage::get_resolution_reason(&env, trade_id)
+    }
+
+    /// Status of a trade without decoding the full `Trade`; cheap enough for
+    /// bots and frontends to poll.
+    pub fn get_trade_status(env: Env, trade_id: u64) -> Result<TradeStatus, ContractError> {
+        storage::get_trade_status(&env, trade_id)
+    }
+
+    /// Returns true if a trade with this ID exists.
+    pub fn has_trade(env: Env, trade_id: u64) -> bool {
+        storage::has_trade(&env, trade_id)
+    }
+
+    /// Record the order as `quantity` units at `unit_price` (seller only,
+    /// while `Created`). The product must equal the trade amount.
+    pub fn set_trade_quantity(
+        env: Env,
+        trade_id: u64,
+        quantity: u32,
+        unit_price: i128,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Created {
+            return Err(ContractError::InvalidStatus);
+        }
+        trade.seller.require_auth();
+        let total = i128::from(quantity)
+            .checked_mul(unit_price)
+            .ok_or(ContractError::Overflow)?;
+        if quantity == 0 || total != trade.amount {
+            return Err(ContractError::QuantityMismatch);
+        }
+        storage::save_order_structure(&env, trade_id, &OrderStructure { quantity, unit_price });
+        events::emit_order_set(&env, trade_id, quantity, unit_price);
+        Ok(())
+    }
+
+    pub fn get_trade_quantity(env: Env, trade_id: u64) -> Option<OrderStructure> {
+        storage::get_order_structure(&env, trade_id)
+    }
+
+    /// Split a `Created` trade into line items settled independently (seller
+    /// only). Item amounts must sum to the trade amount.
+    pub fn set_line_items(
+        env: Env,
+        trade_id: u64,
+        items: soroban_sdk::Vec<LineItemTerms>,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Created {
+            return Err(ContractError::InvalidStatus);
+        }
+        trade.seller.require_auth();
+        if items.is_empty() || items.len() > MAX_LINE_ITEMS {
+            return Err(ContractError::TooManyLineItems);
+        }
+        let mut total = 0i128;
+        let mut stored = soroban_sdk::Vec::new(&env);
+        for item in items.iter() {
+            if item.amount <= 0 {
+                return Err(ContractError::BundleItemsMismatch);
+            }
+            total = total.checked_add(item.amount).ok_or(ContractError::Overflow)?;
+            stored.push_back(LineItem {
+                amount: item.amount,
+                terms_hash: item.terms_hash,
+                status: LineItemStatus::Open,
+            });
+        }
+        if total != trade.amount {
+            return Err(ContractError::BundleItemsMismatch);
+        }
+        storage::save_line_items(&env, trade_id, &stored);
+        Ok(())
+    }
+
+    pub fn get_line_items(env: Env, trade_id: u64) -> Option<soroban_sdk::Vec<LineItem>> {
+        storage::get_line_items(&env, trade_id)
+    }
+
+    /// Buyer accepts one line item, releasing its amount (less its fee share)
+    /// to the seller side.
+    pub fn release_item(env: Env, trade_id: u64, index: u32) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let mut trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Funded {
+            return Err(ContractError::InvalidStatus);
+        }
+        trade.buyer.require_auth();
+        let mut items = storage::get_line_items(&env, trade_id).ok_or(ContractError::LineItemNotFound)?;
+        let item = items.get(index).ok_or(ContractError::LineItemNotFound)?;
+        if item.status != LineItemStatus::Open {
+            return Err(ContractError::LineItemNotOpen);
+        }
+        let fee = line_item_fee(&trade, &items, index)?;
+        let payout = item.amount.checked_sub(fee).ok_or(ContractError::Overflow)?;
+        let token_client = token::Client::new(&env, &trade.currency);
+        storage::sub_total_locked(&env, &trade.currency, item.amount)?;
+        pay_seller_side(&env, &token_client, &trade, payout)?;
+        close_line_item(&env, &trade.buyer.clone(), &mut trade, &mut items, index, LineItemStatus::Released, fee)?;
+        events::emit_item_released(&env, trade_id, index, payout, fee);
+        Ok(())
+    }
+
+    /// Buyer or seller disputes a single line item. The rest of the bundle
+    /// can still be released normally.
+    pub fn dispute_item(
+        env: Env,
+        trade_id: u64,
+        index: u32,
+        caller: Address,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        require_function_active(&env, PAUSE_DISPUTE)?;
+        let trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Funded {
+            return Err(ContractError::InvalidStatus);
+        }
+        if caller != trade.buyer && caller != trade.seller {
+            return Err(ContractError::Unauthorized);
+        }
+        caller.require_auth();
+        let arbitrator = trade.arbitrator.clone().ok_or(ContractError::NoArbitrator)?;
+        let mut items = storage::get_line_items(&env, trade_id).ok_or(ContractError::LineItemNotFound)?;
+        let mut item = items.get(index).ok_or(ContractError::LineItemNotFound)?;
+        if item.status != LineItemStatus::Open {
+            return Err(ContractError::LineItemNotOpen);
+        }
+        item.status = LineItemStatus::Disputed;
+        items.set(index, item);
+        storage::save_line_items(&env, trade_id, &items);
+        storage::increment_arbitrator_caseload(&env, &arbitrator)?;
+        events::emit_item_disputed(&env, trade_id, index, caller);
+        Ok(())
+    }
+
+    /// Arbitrator rules on a disputed line item, sending its amount (less its
+    /// fee share) to the buyer or the seller side.
+    pub fn resolve_item(
+        env: Env,
+        trade_id: u64,
+        index: u32,
+        to_buyer: bool,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let mut trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Funded {
+            return Err(ContractError::InvalidStatus);
+        }
+        let arbitrator = trade.arbitrator.clone().ok_or(ContractError::NoArbitrator)?;
+        arbitrator.require_auth();
+        let mut items = storage::get_line_items(&env, trade_id).ok_or(ContractError::LineItemNotFound)?;
+        let item = items.get(index).ok_or(ContractError::LineItemNotFound)?;
+        if item.status != LineItemStatus::Disputed {
+            return Err(ContractError::LineItemNotOpen);
+        }
+        let fee = line_item_fee(&trade, &items, index)?;
+        let payout = item.amount.checked_sub(fee).ok_or(ContractError::Overflow)?;
+        let token_client = token::Client::new(&env, &trade.currency);
+        storage::sub_total_locked(&env, &trade.currency, item.amount)?;
+        let status = if to_buyer {
+            pay_buyer_side(&env, &token_client, &trade, payout)?;
+            LineItemStatus::Refunded
+        } else {
+            pay_seller_side(&env, &token_client, &trade, payout)?;
+            LineItemStatus::Released
+        };
+        storage::decrement_arbitrator_caseload(&env, &arbitrator);
+        close_line_item(&env, &arbitrator, &mut trade, &mut items, index, status, fee)?;
+        events::emit_item_resolved(&env, trade_id, index, to_buyer, payout);
+        Ok(())
+    }
+
+    /// Opt a `Created` trade into streaming release (seller only): once the
+    /// buyer confirms commencement, the payout vests linearly over `duration`
+    /// seconds.
+    pub fn set_stream(env: Env, trade_id: u64, duration: u64) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Created {
+            return Err(ContractError::InvalidStatus);
+        }
+        trade.seller.require_auth();
+        if duration == 0 {
+            return Err(ContractError::InvalidStreamDuration);
+        }
+        storage::save_stream(&env, trade_id, &StreamSchedule { duration, started_at: 0, claimed: 0 });
+        Ok(())
+    }
+
+    /// Buyer confirms work has commenced, starting the vesting clock.
+    pub fn start_stream(env: Env, trade_id: u64) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Funded {
+            return Err(ContractError::InvalidStatus);
+        }
+        trade.buyer.require_auth();
+        let mut stream = storage::get_stream(&env, trade_id).ok_or(ContractError::StreamNotConfigured)?;
+        if stream.started_at != 0 {
+            return Err(ContractError::StreamAlreadyStarted);
+        }
+        stream.started_at = env.ledger().timestamp();
+        let ends_at = stream.started_at.checked_add(stream.duration).ok_or(ContractError::Overflow)?;
+        storage::save_stream(&env, trade_id, &stream);
+        events::emit_stream_started(&env, trade_id, stream.started_at, ends_at);
+        Ok(())
+    }
+
+    /// Seller claims whatever has vested since the last claim. A dispute
+    /// freezes the stream; the ruling then covers only the unclaimed remainder.
+    pub fn claim_streamed(env: Env, trade_id: u64) -> Result<i128, ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let mut trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Funded {
+            return Err(ContractError::InvalidStatus);
+        }
+        trade.seller.require_auth();
+        let mut stream = storage::get_stream(&env, trade_id).ok_or(ContractError::StreamNotConfigured)?;
+        if stream.started_at == 0 {
+            return Err(ContractError::StreamNotConfigured);
+        }
+        let net = trade.amount.checked_sub(trade.fee).ok_or(ContractError::Overflow)?;
+        let elapsed = env.ledger().timestamp().saturating_sub(stream.started_at).min(stream.duration);
+        let vested = net
+            .checked_mul(i128::from(elapsed))
+            .ok_or(ContractError::Overflow)?
+            / i128::from(stream.duration);
+        let amount = vested.checked_sub(stream.claimed).ok_or(ContractError::Overflow)?;
+        if amount == 0 {
+            return Err(ContractError::NothingToClaim);
+        }
+        storage::sub_total_locked(&env, &trade.currency, amount)?;
+        pay_seller_side(&env, &token::Client::new(&env, &trade.currency), &trade, amount)?;
+        stream.claimed = vested;
+        storage::save_stream(&env, trade_id, &stream);
+        if vested == net {
+            storage::sub_total_locked(&env, &trade.currency, trade.fee)?;
+            book_trade_fee(&env, &trade, trade.fee)?;
+            let actor = trade.seller.clone();
+            state::transition(&env, &mut trade, TradeEvent::Settle, &actor)?;
+            storage::save_trade(&env, trade_id, &trade);
+            receipt::issue(&env, &trade, ReceiptOutcome::Completed);
+            record_settlement(&env, &trade)?;
+        }
+        events::emit_stream_claimed(&env, trade_id, amount, vested);
+        Ok(amount)
+    }
+
+    pub fn get_stream(env: Env, trade_id: u64) -> Option<StreamSchedule> {
+        storage::get_stream(&env, trade_id)
+    }
+
+    /// Designate an alternate address (cold wallet, payment processor) to
+    /// receive the seller's proceeds for this trade. The seller sets it before
+    /// funding; once the buyer's money is in escrow the buyer must co-sign.
+    pub fn set_trade_payee(env: Env, trade_id: u64, payee: Address) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let trade = storage::get_trade(&env, trade_id)?;
+        match trade.status {
+            TradeStatus::Created => trade.seller.require_auth(),
+            TradeStatus::Funded => {
+                trade.seller.require_auth();
+                trade.buyer.require_auth();
+            }
+            _ => return Err(ContractError::InvalidStatus),
+        }
+        if storage::get_payout_assignee(&env, trade_id).is_some() {
+            return Err(ContractError::PayoutAssigned);
+        }
+        if payee == trade.buyer || payee == env.current_contract_address() {
+            return Err(ContractError::InvalidPayee);
+        }
+        storage::save_trade_payee(&env, trade_id, &payee);
+        events::emit_payee_set(&env, trade_id, payee);
+        Ok(())
+    }
+
+    /// Allow or disallow a swap adapter for seller payout swaps (admin only).
+    pub fn set_swap_adapter(env: Env, adapter: Address, allowed: bool) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        let old = payout_swap::is_adapter(&env, &adapter);
+        payout_swap::set_adapter(&env, &adapter, allowed);
+        events::emit_config_entry_changed(&env, symbol_short!("swap_adp"), adapter, old, allowed);
+        Ok(())
+    }
+
+    pub fn is_swap_adapter(env: Env, adapter: Address) -> bool {
+        payout_swap::is_adapter(&env, &adapter)
+    }
+
+    /// Seller opts to receive this trade's payout in another token through a
+    /// whitelisted swap adapter, or opts out with `None`. If the swap fails
+    /// at release the payout falls back to the trade's currency.
+    pub fn set_payout_swap(env: Env, trade_id: u64, swap: Option<PayoutSwap>) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let trade = storage::get_trade(&env, trade_id)?;
+        if !matches!(trade.status, TradeStatus::Created | TradeStatus::Funded) {
+            return Err(ContractError::InvalidStatus);
+        }
+        trade.seller.require_auth();
+        if storage::get_payout_assignee(&env, trade_id).is_some() {
+            return Err(ContractError::PayoutAssigned);
+        }
+        match swap {
+            Some(swap) => {
+                if !payout_swap::is_adapter(&env, &swap.adapter) {
+                    return Err(ContractError::SwapAdapterNotAllowed);
+                }
+                if swap.token_out == trade.currency {
+                    return Err(ContractError::InvalidPayoutSwap);
+                }
+                amount::require_non_negative(swap.min_out)?;
+                payout_swap::set(&env, trade_id, &swap);
+                events::emit_payout_swap_set(&env, trade_id, swap.adapter, swap.token_out, swap.min_out);
+            }
+            None => payout_swap::remove(&env, trade_id),
+        }
+        Ok(())
+    }
+
+    pub fn get_payout_swap(env: Env, trade_id: u64) -> Option<PayoutSwap> {
+        payout_swap::get(&env, trade_id)
+    }
+
+    /// Register, update or (with `None`) remove the white-label partner
+    /// `partner_id`. Trades already created through it keep their terms.
+    pub fn set_partner(env: Env, partner_id: u32, partner: Option<Partner>) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        if let Some(ref p) = partner {
+            if p.fee_bps > 10_000 {
+                return Err(ContractError::InvalidPartnerFee);
+            }
+        }
+        let old = partners::get(&env, partner_id);
+        partners::set(&env, partner_id, &partner);
+        events::emit_config_entry_changed(&env, symbol_short!("partner"), partner_id, old, partner);
+        Ok(())
+    }
+
+    pub fn get_partner(env: Env, partner_id: u32) -> Option<Partner> {
+        partners::get(&env, partner_id)
+    }
+
+    /// Partner a trade was created through and its margin on that trade.
+    pub fn get_trade_partner(env: Env, trade_id: u64) -> Option<TradePartner> {
+        partners::get_trade_partner(&env, trade_id)
+    }
+
+    /// Total fees paid to `partner_id` so far in `currency`.
+    pub fn get_partner_earnings(env: Env, partner_id: u32, currency: Address) -> i128 {
+        partners::earned(&env, partner_id, &currency)
+    }
+
+    /// Register as a referrer, or change an existing registration's terms.
+    /// Either way the registration waits for admin approval.
+    pub fn register_referrer(env: Env, referrer: Address, payout: Address, share_bps: u32) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        referrer.require_auth();
+        referrals::register(&env, &referrer, &payout, share_bps)?;
+        events::emit_referrer_registered(&env, referrer, payout, share_bps);
+        Ok(())
+    }
+
+    /// Approve, suspend or return a referrer to review (admin only).
+    pub fn set_referrer_status(env: Env, referrer: Address, status: ReferrerStatus) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        referrals::set_status(&env, &referrer, status)?;
+        storage::append_admin_log(&env, &admin, AdminAction::ReferrerStatusSet);
+        events::emit_referrer_status(&env, referrer, status);
+        Ok(())
+    }
+
+    pub fn get_referrer(env: Env, referrer: Address) -> Option<Referrer> {
+        referrals::get(&env, &referrer)
+    }
+
+    /// Attribute `buyer` to an approved referrer, for life. Needs the
+    /// buyer's auth and fails if they were already referred.
+    pub fn attribute_referral(env: Env, buyer: Address, referrer: Address) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        buyer.require_auth();
+        referrals::attribute(&env, &buyer, &referrer)?;
+        events::emit_buyer_referred(&env, buyer, referrer);
+        Ok(())
+    }
+
+    pub fn get_referred_by(env: Env, buyer: Address) -> Option<Address> {
+        referrals::referred_by(&env, &buyer)
+    }
+
+    /// Total fees paid to `referrer` so far in `currency`.
+    pub fn get_referral_earnings(env: Env, referrer: Address, currency: Address) -> i128 {
+        referrals::earned(&env, &referrer, &currency)
+    }
+
+    /// Irrevocably assign the right to receive this trade's seller-side
+    /// proceeds to `assignee` (e.g. an invoice financier). Seller only, any
+    /// time before settlement. Overrides any payee or payout split and
+    /// cannot be changed afterwards.
+    pub fn assign_payout(env: Env, trade_id: u64, assignee: Address) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let trade = storage::get_trade(&env, trade_id)?;
+        if trade.status.is_terminal() {
+            return Err(ContractError::InvalidStatus);
+        }
+        trade.seller.require_auth();
+        if storage::get_payout_assignee(&env, trade_id).is_some() {
+            return Err(ContractError::PayoutAssigned);
+        }
+        if assignee == trade.buyer || assignee == env.current_contract_address() {
+            return Err(ContractError::InvalidPayee);
+        }
+        storage::save_payout_assignee(&env, trade_id, &assignee);
+        events::emit_payout_assigned(&env, trade_id, assignee);
+        Ok(())
+    }
+
+    pub fn get_payout_assignee(env: Env, trade_id: u64) -> Option<Address> {
+        storage::get_payout_assignee(&env, trade_id)
+    }
+
+    /// Hand the buyer role, and with it any refund rights, to `new_buyer`.
+    /// Needs the current buyer's auth, plus the seller's while the trade is
+    /// still unfunded.
+    pub fn transfer_buyer(env: Env, trade_id: u64, new_buyer: Address) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let mut trade = storage::get_trade(&env, trade_id)?;
+        if !matches!(trade.status, TradeStatus::Created | TradeStatus::Funded) {
+            return Err(ContractError::InvalidStatus);
+        }
+        trade.buyer.require_auth();
+        if trade.status == TradeStatus::Created {
+            trade.seller.require_auth();
+        }
+        if new_buyer == trade.seller || new_buyer == trade.buyer {
+            return Err(ContractError::InvalidParties);
+        }
+        let receives_proceeds = seller_payee(&env, &trade) == new_buyer
+            || storage::get_payout_split(&env, trade_id)
+                .map_or(false, |shares| shares.iter().any(|s| s.recipient == new_buyer));
+        if receives_proceeds {
+            return Err(ContractError::InvalidParties);
+        }
+        if let Some(ref arb) = trade.arbitrator {
+            require_disinterested(arb, &trade.seller, &new_buyer)?;
+        }
+        validate_user_compliance(&env, &new_buyer, trade.amount)?;
+        attestation::require_attested(&env, &[&new_buyer])?;
+        if storage::has_open_pair_slot(&env, trade_id) {
+            require_pair_capacity(&env, &trade.seller, &new_buyer)?;
+            storage::close_pair_trade(&env, trade_id, &trade.seller, &trade.buyer);
+            storage::open_pair_trade(&env, trade_id, &trade.seller, &new_buyer);
+        }
+        // A refund request belongs to the buyer who raised it.
+        storage::remove_refund_request(&env, trade_id);
+        let old_buyer = core::mem::replace(&mut trade.buyer, new_buyer.clone());
+        storage::save_trade(&env, trade_id, &trade);
+        events::emit_buyer_transferred(&env, trade_id, old_buyer, new_buyer);
+        Ok(())
+    }
+
+    /// Split the seller-side payout across several recipients (e.g. seller
+    /// 90%, logistics partner 10%). Seller only, while the trade is `Created`
+    /// so the buyer sees the distribution before funding. Takes precedence
+    /// over a single payee.
+    pub fn set_payout_split(
+        env: Env,
+        trade_id: u64,
+        shares: soroban_sdk::Vec<PayoutShare>,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Created {
+            return Err(ContractError::InvalidStatus);
+        }
+        trade.seller.require_auth();
+        if storage::get_payout_assignee(&env, trade_id).is_some() {
+            return Err(ContractError::PayoutAssigned);
+        }
+        if shares.len() > MAX_PAYOUT_RECIPIENTS {
+            return Err(ContractError::TooManyPayoutRecipients);
+        }
+        if shares.is_empty() {
+            return Err(ContractError::InvalidPayoutSplit);
+        }
+        let mut total: u32 = 0;
+        for share in shares.iter() {
+            if share.bps == 0 || share.recipient == env.current_contract_address() {
+                return Err(ContractError::InvalidPayoutSplit);
+            }
+            if share.recipient == trade.buyer {
+                return Err(ContractError::InvalidParties);
+            }
+            total = total.checked_add(share.bps).ok_or(ContractError::InvalidPayoutSplit)?;
+        }
+        if total != 10_000 {
+            return Err(ContractError::InvalidPayoutSplit);
+        }
+        storage::save_payout_split(&env, trade_id, &shares);
+        events::emit_payout_split_set(&env, trade_id, shares.len());
+        Ok(())
+    }
+
+    pub fn get_payout_split(env: Env, trade_id: u64) -> Option<soroban_sdk::Vec<PayoutShare>> {
+        storage::get_payout_split(&env, trade_id)
+    }
+
+    /// Escrow extra tokens alongside the trade's currency, for deals priced
+    /// across several assets. Seller only, while the trade is `Created` so
+    /// the buyer sees the basket before funding; funding then pulls every leg.
+    pub fn set_trade_basket(
+        env: Env,
+        trade_id: u64,
+        legs: soroban_sdk::Vec<BasketLeg>,
+    ) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        let trade = storage::get_trade(&env, trade_id)?;
+        if trade.status != TradeStatus::Created {
+            return Err(ContractError::InvalidStatus);
+        }
+        trade.seller.require_auth();
+        require_not_pooled(&env, trade_id)?;
+        basket::set(&env, &trade, &legs)?;
+        events::emit_basket_set(&env, trade_id, legs.len());
         Ok(())
     }
 
-    /// Use `DisputeResolution::Partial { buyer_bps }` for a split:
-    /// `buyer_bps` is the buyer's share of the net payout in basis points (0–10000).
-    pub fn resolve_dispute(
+    pub fn get_trade_basket(env: Env, trade_id: u64) -> Option<soroban_sdk::Vec<BasketLeg>> {
+        basket::legs(&env, trade_id)
+    }
+
+    /// Denominate the trade in `reference` (e.g. a USD asset) while settling
+    /// in its currency. The amount is fixed from the oracle price when the
+    /// trade is first funded. Seller only, while the trade is `Created`.
+    pub fn set_trade_quote(
         env: Env,
         trade_id: u64,
-        resolution: DisputeResolution,
+        reference: Address,
+        quoted_amount: i128,
     ) -> Result<(), ContractError> {
         require_initialized(&env)?;
         require_not_paused(&env)?;
         let trade = storage::get_trade(&env, trade_id)?;
-        if trade.status != TradeStatus::Disputed {
+        if trade.status != TradeStatus::Created {
             return Err(ContractError::InvalidStatus);
         }
-        let arbitrator = match trade.arbitrator.clone() {
-            Some(addr) => addr,
-            None => return Err(ContractError::NoArbitrator),
-        };
-        arbitrator.require_auth();
-        let net = trade
-            .amount
-            .checked_sub(trade.fee)
-            .ok_or(ContractError::Overflow)?;
-        let token_client = token::Client::new(&env, &trade.currency);
-        match resolution.clone() {
-            DisputeResolution::ReleaseToBuyer => {
-                token_client.transfer(&env.current_contract_address(), &trade.buyer, &(net as i128));
-                events::emit_dispute_resolved(&env, trade_id, resolution, trade.buyer);
-            }
-            DisputeResolution::ReleaseToSeller => {
-                token_client.transfer(&env.current_contract_address(), &trade.seller, &(net as i128));
-                events::emit_dispute_resolved(&env, trade_id, resolution, trade.seller);
-            }
-            DisputeResolution::Partial(buyer_bps) => {
-                if buyer_bps > 10_000 {
-                    return Err(ContractError::InvalidSplitBps);
-                }
-                let buyer_amount = net
-                    .checked_mul(buyer_bps as u64)
-                    .ok_or(ContractError::Overflow)?
-                    .checked_div(10_000)
-                    .ok_or(ContractError::Overflow)?;
-                let seller_amount = net
-                    .checked_sub(buyer_amount)
-                    .ok_or(ContractError::Overflow)?;
-                if buyer_amount > 0 {
-                    token_client.transfer(
-                        &env.current_contract_address(),
-                        &trade.buyer,
-                        &(buyer_amount as i128),
-                    );
-                }
-                if seller_amount > 0 {
-                    token_client.transfer(
-                        &env.current_contract_address(),
-                        &trade.seller,
-                        &(seller_amount as i128),
-                    );
-                }
-                events::emit_partial_resolved(&env, trade_id, buyer_amount, seller_amount, trade.fee);
-            }
-        }
-        storage::add_accumulated_fees(&env, trade.fee)?;
+        trade.seller.require_auth();
+        require_not_pooled(&env, trade_id)?;
+        pricing::set(&env, &trade, &reference, quoted_amount)?;
+        events::emit_quote_set(&env, trade_id, reference, quoted_amount);
         Ok(())
     }
 
-    pub fn get_trade(env: Env, trade_id: u64) -> Result<Trade, ContractError> {
-        storage::get_trade(&env, trade_id)
+    /// Reference-asset quote of a trade and, once funded, the rate it was fixed at.
+    pub fn get_trade_quote(env: Env, trade_id: u64) -> Option<TradeQuote> {
+        storage::get_trade_quote(&env, trade_id)
+    }
+
+    /// Address that will receive the seller's proceeds for this trade.
+    pub fn get_trade_payee(env: Env, trade_id: u64) -> Result<Address, ContractError> {
+        let trade = storage::get_trade(&env, trade_id)?;
+        Ok(seller_payee(&env, &trade))
+    }
+
+    /// Trade created with the given client-supplied `external_ref`, if any.
+    /// Lets backends recover the ID after a retried `create_trade`.
+    pub fn get_trade_by_external_ref(env: Env, external_ref: soroban_sdk::BytesN<32>) -> Option<u64> {
+        storage::get_trade_by_external_ref(&env, &external_ref)
+    }
+
+    /// Free-form reference (order ID, invoice number) attached at creation.
+    pub fn get_trade_memo(env: Env, trade_id: u64) -> Option<soroban_sdk::String> {
+        storage::get_trade_memo(&env, trade_id)
     }
 
-    /// Withdraw accumulated protocol fees for a specific currency to a recipient.
-    /// Only the admin may call this. Panics if `amount` exceeds the available balance.
-    pub fn withdraw_fees(
+    /// Request a withdrawal of `amount` fees accumulated in `currency` to
+    /// `to` (admin only). It can be executed once the fee withdrawal delay
+    /// has passed, so a compromised admin key cannot drain fees unnoticed.
+    pub fn request_fee_withdrawal(
         env: Env,
-        admin: Address,
         currency: Address,
+        to: Address,
         amount: i128,
-        recipient: Address,
-    ) -> Result<(), ContractError> {
+    ) -> Result<u64, ContractError> {
         require_initialized(&env)?;
+        require_function_active(&env, PAUSE_WITHDRAW)?;
+        let admin = storage::get_admin(&env)?;
         admin.require_auth();
-        if admin != storage::get_admin(&env)? {
-            return Err(ContractError::Unauthorized);
+        if storage::get_pending_fee_withdrawal(&env).is_some() {
+            return Err(ContractError::FeeWithdrawalPending);
         }
-        if amount <= 0 {
+        if amount <= 0 || amount > storage::get_withdrawable_fees(&env, &currency)? {
             return Err(ContractError::NoFeesToWithdraw);
         }
-        let available = storage::get_currency_fees(&env, &currency) as i128;
-        if amount > available {
-            return Err(ContractError::NoFeesToWithdraw);
-        }
-        storage::set_currency_fees(&env, &currency, (available - amount) as u64);
-        token::Client::new(&env, &currency).transfer(
-            &env.current_contract_address(),
-            &recipient,
-            &amount,
-        );
-        env.events().publish(
-            (
-                soroban_sdk::Symbol::new(&env, "fees"),
-                soroban_sdk::Symbol::new(&env, "withdrawn"),
-                recipient.clone(),
-            ),
-            (currency, amount),
+        let now = env.ledger().timestamp();
+        let executable_at = now
+            .checked_add(storage::get_fee_withdrawal_delay(&env))
+            .ok_or(ContractError::Overflow)?;
+        storage::set_pending_fee_withdrawal(
+            &env,
+            &PendingFeeWithdrawal {
+                currency: currency.clone(),
+                to: to.clone(),
+                amount,
+                requested_at: now,
+                executable_at,
+            },
         );
+        storage::append_admin_log(&env, &admin, AdminAction::FeeWithdrawalRequested);
+        events::emit_fee_withdrawal_requested(&env, currency, to, amount, executable_at);
+        Ok(executable_at)
+    }
+
+    /// Pay out the requested fee withdrawal once its delay has elapsed.
+    pub fn execute_fee_withdrawal(env: Env) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        require_function_active(&env, PAUSE_WITHDRAW)?;
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        let pending = storage::get_pending_fee_withdrawal(&env)
+            .ok_or(ContractError::NoFeeWithdrawal)?;
+        if env.ledger().timestamp() < pending.executable_at {
+            return Err(ContractError::FeeWithdrawalDelayNotElapsed);
+        }
+        storage::sub_withdrawable_fees(&env, &pending.currency, pending.amount)?;
+        storage::clear_pending_fee_withdrawal(&env);
+        let payout = forward_protocol_share(&env, &pending.currency, pending.amount)?;
+        transfer::send(&token::Client::new(&env, &pending.currency), &env.current_contract_address(), &pending.to, payout)?;
+        analytics::on_fees_withdrawn(&env, &pending.currency, pending.amount)?;
+        storage::append_admin_log(&env, &admin, AdminAction::FeesWithdrawn);
+        events::emit_fees_withdrawn(&env, pending.currency, pending.amount, pending.to);
         Ok(())
     }
 
-    /// Legacy single-currency fee withdrawal (USDC accumulated fees).
-    pub fn withdraw_fees_legacy(env: Env, to: Address) -> Result<(), ContractError> {
+    /// Abort a requested fee withdrawal (admin only).
+    pub fn cancel_fee_withdrawal(env: Env) -> Result<(), ContractError> {
         require_initialized(&env)?;
         let admin = storage::get_admin(&env)?;
         admin.require_auth();
-        let fees = storage::get_accumulated_fees(&env)?;
-        if fees == 0 {
-            return Err(ContractError::NoFeesToWithdraw);
+        let pending = storage::get_pending_fee_withdrawal(&env)
+            .ok_or(ContractError::NoFeeWithdrawal)?;
+        storage::clear_pending_fee_withdrawal(&env);
+        storage::append_admin_log(&env, &admin, AdminAction::FeeWithdrawalCancelled);
+        events::emit_fee_withdrawal_cancelled(&env, pending.currency, pending.to, pending.amount);
+        Ok(())
+    }
+
+    pub fn get_pending_fee_withdrawal(env: Env) -> Option<PendingFeeWithdrawal> {
+        storage::get_pending_fee_withdrawal(&env)
+    }
+
+    /// Seconds between requesting and executing a fee withdrawal (admin
+    /// only). Applies to requests made after the change.
+    pub fn set_fee_withdrawal_delay(env: Env, seconds: u64) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        if seconds < MIN_FEE_WITHDRAWAL_DELAY {
+            return Err(ContractError::InvalidFeeWithdrawalDelay);
+        }
+        let old = storage::get_fee_withdrawal_delay(&env);
+        storage::set_fee_withdrawal_delay(&env, seconds);
+        events::emit_config_changed(&env, symbol_short!("fee_wd_dl"), old, seconds);
+        Ok(())
+    }
+
+    pub fn get_fee_withdrawal_delay(env: Env) -> u64 {
+        storage::get_fee_withdrawal_delay(&env)
+    }
+
+    /// Forward collected fees to `treasury` at settlement instead of
+    /// accumulating them (admin only). `None` restores accumulation.
+    pub fn set_treasury(env: Env, treasury: Option<Address>) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        let old = storage::get_treasury(&env);
+        events::emit_config_changed(&env, symbol_short!("treasury"), old, treasury.clone());
+        match treasury {
+            Some(t) => storage::set_treasury(&env, &t),
+            None => storage::remove_treasury(&env),
+        }
+        Ok(())
+    }
+
+    pub fn get_treasury(env: Env) -> Option<Address> {
+        storage::get_treasury(&env)
+    }
+
+    /// Burn this share of every collected fee instead of accumulating it
+    /// (admin only). Zero disables burning.
+    pub fn set_fee_burn_bps(env: Env, bps: u32) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        if bps > 10_000 {
+            return Err(ContractError::InvalidFeeBps);
         }
-        usdc_client(&env)?.transfer(&env.current_contract_address(), &to, &(fees as i128));
-        storage::set_accumulated_fees(&env, 0);
-        events::emit_fees_withdrawn(&env, fees, to);
+        let old = storage::get_fee_burn_bps(&env);
+        storage::set_fee_burn_bps(&env, bps);
+        events::emit_config_changed(&env, symbol_short!("fee_burn"), old, bps);
         Ok(())
     }
 
-    pub fn get_accumulated_fees(env: Env) -> Result<u64, ContractError> {
+    pub fn get_fee_burn_bps(env: Env) -> u32 {
+        storage::get_fee_burn_bps(&env)
+    }
+
+    /// Total fees burned in `currency`.
+    pub fn get_total_burned(env: Env, currency: Address) -> i128 {
+        storage::get_total_burned(&env, &currency)
+    }
+
+    pub fn get_accumulated_fees(env: Env) -> Result<i128, ContractError> {
         storage::get_accumulated_fees(&env)
     }
 
+    /// Route a share of every fee withdrawal to `recipient` (the deploying
+    /// factory, for factory-made instances). The first call needs the admin;
+    /// once set, only the current recipient may change it.
+    pub fn set_protocol_fee_share(env: Env, recipient: Address, bps: u32) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        let old = storage::get_protocol_fee_share(&env);
+        match old {
+            Some(ref current) => current.recipient.require_auth(),
+            None => storage::get_admin(&env)?.require_auth(),
+        }
+        if bps > MAX_PROTOCOL_FEE_SHARE_BPS {
+            return Err(ContractError::InvalidFeeBps);
+        }
+        let share = ProtocolFeeShare { recipient: recipient.clone(), bps };
+        storage::set_protocol_fee_share(&env, &share);
+        events::emit_config_changed(&env, symbol_short!("proto_fee"), old, Some(share));
+        events::emit_protocol_share_set(&env, recipient, bps);
+        Ok(())
+    }
+
+    pub fn get_protocol_fee_share(env: Env) -> Option<ProtocolFeeShare> {
+        storage::get_protocol_fee_share(&env)
+    }
+
+    /// Principal currently held in escrow in `currency` across all funded,
+    /// unsettled trades.
+    pub fn get_total_locked(env: Env, currency: Address) -> i128 {
+        storage::get_locked(&env, &currency)
+    }
+
     /// Get platform fee in basis points
     pub fn get_platform_fee_bps(env: Env) -> Result<u32, ContractError> {
         get_fee_bps(&env)
@@ -870,6 +4151,44 @@ impl StellarEscrowContract {
         queries::query_trades(&env, filter, page)
     }
 
+    /// Scan the trade book by ID range: up to `limit` (≤ 100) summaries
+    /// starting at `start_id`, for light indexers and admin tools.
+    pub fn list_trades(
+        env: Env,
+        start_id: u64,
+        limit: u32,
+    ) -> Result<soroban_sdk::Vec<queries::TradeSummary>, ContractError> {
+        require_initialized(&env)?;
+        queries::list_trades(&env, start_id, limit)
+    }
+
+    /// Summaries of trades `from_id..=to_id` (at most `MAX_TRADE_RANGE`
+    /// ids), so indexers can backfill without replaying events.
+    pub fn get_trades_range(
+        env: Env,
+        from_id: u64,
+        to_id: u64,
+    ) -> Result<soroban_sdk::Vec<queries::TradeSummary>, ContractError> {
+        require_initialized(&env)?;
+        queries::trades_range(&env, from_id, to_id)
+    }
+
+    /// Platform configuration and totals in one struct, for migrating to a
+    /// new instance or auditing (admin only).
+    pub fn export_config(env: Env) -> Result<export::ConfigSnapshot, ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        export::config(&env)
+    }
+
+    /// Up to `limit` (≤ 50) trades from `start` with their per-trade entries,
+    /// for migrating to a new instance or auditing (admin only).
+    pub fn export_trades(env: Env, start: u64, limit: u32) -> Result<soroban_sdk::Vec<export::TradeSnapshot>, ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        export::trades(&env, start, limit)
+    }
+
     /// Aggregate statistics (count, volume, fees, min/max amount) over filtered trades.
     pub fn aggregate_trades(
         env: Env,
@@ -936,7 +4255,7 @@ impl StellarEscrowContract {
         env: Env,
         base: Address,
         quote: Address,
-        trade_amount: u64,
+        trade_amount: i128,
         min_usd: i128,
         max_usd: i128,
     ) -> Result<oracle::PriceValidation, ContractError> {
@@ -959,35 +4278,26 @@ impl StellarEscrowContract {
         }
 
         if oracle::check_trigger(&env, &trigger)? {
+            storage::sub_total_locked(&env, &trade.currency, trade.amount)?;
             match trigger.action {
                 TriggerAction::Cancel => {
                     // Refund entire escrowed amount to buyer
                     let token_client = token::Client::new(&env, &trade.currency);
-                    token_client.transfer(
-                        &env.current_contract_address(),
-                        &trade.buyer,
-                        &(trade.amount as i128),
-                    );
-                    trade.status = TradeStatus::Cancelled;
+                    pay_buyer_side(&env, &token_client, &trade, trade.amount)?;
+                    state::transition(&env, &mut trade, TradeEvent::Refund, &env.current_contract_address())?;
+                    events::emit_trade_refunded(&env, trade_id, trade.amount, 0);
                 }
                 TriggerAction::Release => {
                     // Release to seller, minus platform fee
                     let token_client = token::Client::new(&env, &trade.currency);
                     let payout = trade.amount.checked_sub(trade.fee).ok_or(ContractError::Overflow)?;
-                    token_client.transfer(
-                        &env.current_contract_address(),
-                        &trade.seller,
-                        &(payout as i128),
-                    );
-                    // Add fee to contract's accumulated revenue
-                    let current_fees = storage::get_currency_fees(&env, &trade.currency);
-                    let new_fees = current_fees.checked_add(trade.fee).ok_or(ContractError::Overflow)?;
-                    storage::set_currency_fees(&env, &trade.currency, new_fees);
-                    storage::add_accumulated_fees(&env, trade.fee)?;
-                    trade.status = TradeStatus::Triggered;
+                    pay_seller_side(&env, &token_client, &trade, payout)?;
+                    book_trade_fee(&env, &trade, trade.fee)?;
+                    state::transition(&env, &mut trade, TradeEvent::Trigger, &env.current_contract_address())?;
                 }
             }
             save_trade(&env, trade_id, &trade);
+            record_settlement(&env, &trade)?;
             events::emit_trigger_executed(&env, trade_id, &trigger.action);
         } else {
             return Err(ContractError::PriceConditionNotMet);
@@ -1006,6 +4316,7 @@ impl StellarEscrowContract {
         let admin = storage::get_admin(&env)?;
         admin.require_auth();
         storage::set_paused(&env, true);
+        storage::append_admin_log(&env, &admin, AdminAction::Paused);
         events::emit_paused(&env, admin);
         Ok(())
     }
@@ -1015,27 +4326,130 @@ impl StellarEscrowContract {
         let admin = storage::get_admin(&env)?;
         admin.require_auth();
         storage::set_paused(&env, false);
+        storage::append_admin_log(&env, &admin, AdminAction::Unpaused);
         events::emit_unpaused(&env, admin);
         Ok(())
     }
 
-    /// Emergency withdrawal of all contract token balance (admin only).
+    /// Pause individual capabilities without a full pause (admin only).
+    /// `flags` is a set of `PAUSE_*` bits and replaces the current set; zero
+    /// resumes everything. Refunds and settlement stay available.
+    pub fn set_paused_functions(env: Env, flags: u32) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        if flags & !PAUSE_ALL_FUNCTIONS != 0 {
+            return Err(ContractError::InvalidPauseFlags);
+        }
+        let old = storage::get_paused_functions(&env);
+        storage::set_paused_functions(&env, flags);
+        events::emit_config_changed(&env, symbol_short!("pause_fn"), old, flags);
+        Ok(())
+    }
+
+    pub fn get_paused_functions(env: Env) -> u32 {
+        storage::get_paused_functions(&env)
+    }
+
+    /// Schedule a withdrawal of the full contract token balance (admin only).
+    /// It can only be executed after `EMERGENCY_WITHDRAWAL_DELAY`, giving users
+    /// advance warning via the `emrg_init` event. Allowed even while paused.
+    pub fn initiate_emergency_withdrawal(env: Env, to: Address) -> Result<u64, ContractError> {
+        require_initialized(&env)?;
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+        if storage::get_pending_emergency_withdrawal(&env).is_some() {
+            return Err(ContractError::EmergencyWithdrawalPending);
+        }
+        let now = env.ledger().timestamp();
+        let executable_at = now
+            .checked_add(EMERGENCY_WITHDRAWAL_DELAY)
+            .ok_or(ContractError::Overflow)?;
+        storage::set_pending_emergency_withdrawal(
+            &env,
+            &PendingEmergencyWithdrawal { to: to.clone(), initiated_at: now, executable_at },
+        );
+        storage::append_admin_log(&env, &admin, AdminAction::EmergencyInitiated);
+        events::emit_emergency_initiated(&env, to, executable_at);
+        Ok(executable_at)
+    }
+
+    /// Abort a scheduled emergency withdrawal (admin only).
+    pub fn cancel_emergency_withdrawal(env: Env) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+        let pending = storage::get_pending_emergency_withdrawal(&env)
+            .ok_or(ContractError::NoEmergencyWithdrawal)?;
+        storage::clear_pending_emergency_withdrawal(&env);
+        storage::append_admin_log(&env, &admin, AdminAction::EmergencyCancelled);
+        events::emit_emergency_cancelled(&env, pending.to);
+        Ok(())
+    }
+
+    /// Execute a scheduled emergency withdrawal once its delay has elapsed.
     /// Allowed even while paused so funds can always be recovered.
-    pub fn emergency_withdraw(env: Env, to: Address) -> Result<(), ContractError> {
+    pub fn execute_emergency_withdrawal(env: Env) -> Result<(), ContractError> {
         require_initialized(&env)?;
         let admin = get_admin(&env)?;
         admin.require_auth();
+        let pending = storage::get_pending_emergency_withdrawal(&env)
+            .ok_or(ContractError::NoEmergencyWithdrawal)?;
+        if env.ledger().timestamp() < pending.executable_at {
+            return Err(ContractError::EmergencyDelayNotElapsed);
+        }
+        storage::clear_pending_emergency_withdrawal(&env);
+        let to = pending.to;
         let token = get_usdc_token(&env)?;
-        let token_client = token::Client::new(&env, &token);
-        let balance = token_client.balance(&env.current_contract_address());
-        if balance > 0 {
-            token_client.transfer(&env.current_contract_address(), &to, &balance);
+        let balance = transfer::drain(&token::Client::new(&env, &token), &to)?;
+        analytics::on_fees_withdrawn(&env, &token, storage::get_accumulated_fees(&env)?)?;
+        set_accumulated_fees(&env, 0)?;
+        storage::append_admin_log(&env, &admin, AdminAction::EmergencyExecuted);
+        events::emit_emergency_withdraw(&env, to, balance);
+        Ok(())
+    }
+
+    /// Reconcile the contract's balance of `currency` against the escrow
+    /// and fees held in it. Emits a `insolvent` warning event when the
+    /// balance falls short so monitoring can alert on it.
+    pub fn verify_solvency(env: Env, currency: Address) -> Result<SolvencyReport, ContractError> {
+        require_initialized(&env)?;
+        let balance = token::Client::new(&env, &currency).balance(&env.current_contract_address());
+        let total_locked = storage::get_locked(&env, &currency);
+        let accumulated_fees = storage::get_withdrawable_fees(&env, &currency)?;
+        let liabilities = total_locked
+            .checked_add(accumulated_fees)
+            .ok_or(ContractError::Overflow)?;
+        let surplus = balance.checked_sub(liabilities).ok_or(ContractError::Overflow)?;
+        let solvent = surplus >= 0;
+        if !solvent {
+            events::emit_solvency_warning(&env, currency.clone(), balance, liabilities);
+        }
+        Ok(SolvencyReport { currency, balance, total_locked, accumulated_fees, surplus, solvent })
+    }
+
+    /// Returns the scheduled emergency withdrawal, if any.
+    pub fn get_pending_emergency_withdrawal(env: Env) -> Option<PendingEmergencyWithdrawal> {
+        storage::get_pending_emergency_withdrawal(&env)
+    }
+
+    /// Set the TTL policy applied to trade entries on every access (admin only).
+    pub fn set_ttl_config(env: Env, threshold: u32, extend_to: u32) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        if threshold > extend_to || extend_to > env.storage().max_ttl() {
+            return Err(ContractError::InvalidTtlConfig);
         }
-        set_accumulated_fees(&env, 0);
-        events::emit_emergency_withdraw(&env, to, balance as u64);
+        let old = storage::get_ttl_config(&env);
+        let config = TtlConfig { threshold, extend_to };
+        storage::set_ttl_config(&env, &config);
+        events::emit_config_changed(&env, symbol_short!("ttl"), old, config);
         Ok(())
     }
 
+    pub fn get_ttl_config(env: Env) -> TtlConfig {
+        storage::get_ttl_config(&env)
+    }
+
     /// Returns true if the contract is currently paused.
     pub fn is_paused(env: Env) -> bool {
         storage::is_paused(&env)
@@ -1045,6 +4459,119 @@ impl StellarEscrowContract {
         storage::get_version(&env)
     }
 
+    /// Core configuration (admin, token, fee, trade counter, legacy fees) in one read.
+    pub fn get_config(env: Env) -> Result<Config, ContractError> {
+        let mut config = storage::get_config(&env)?;
+        config.fee_bps = storage::get_fee_bps(&env)?;
+        Ok(config)
+    }
+
+    /// Register (or with `None`, remove) the companion contract that mints
+    /// non-transferable settlement receipts to both parties (admin only).
+    pub fn set_receipt_contract(env: Env, contract: Option<Address>) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        let old = receipt::get_receipt_contract(&env);
+        events::emit_config_changed(&env, symbol_short!("receipt"), old, contract.clone());
+        match contract {
+            Some(c) => receipt::set_receipt_contract(&env, &c),
+            None => receipt::remove_receipt_contract(&env),
+        }
+        Ok(())
+    }
+
+    pub fn get_receipt_contract(env: Env) -> Option<Address> {
+        receipt::get_receipt_contract(&env)
+    }
+
+    /// Register (or clear with `None`) the KYC attestation contract that
+    /// gates trade creation and funding (admin only).
+    pub fn set_attestation_contract(env: Env, contract: Option<Address>) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        let old = attestation::get_attestation_contract(&env);
+        events::emit_config_changed(&env, symbol_short!("kyc_att"), old, contract.clone());
+        match contract {
+            Some(c) => attestation::set_attestation_contract(&env, &c),
+            None => attestation::remove_attestation_contract(&env),
+        }
+        Ok(())
+    }
+
+    pub fn get_attestation_contract(env: Env) -> Option<Address> {
+        attestation::get_attestation_contract(&env)
+    }
+
+    /// Require an arbitrator on trades above `amount` (admin only). Zero
+    /// disables the requirement.
+    pub fn set_arbitrator_threshold(env: Env, amount: i128) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        amount::require_non_negative(amount)?;
+        let old = storage::get_arbitrator_threshold(&env);
+        storage::set_arbitrator_threshold(&env, amount);
+        events::emit_config_changed(&env, symbol_short!("arb_min"), old, amount);
+        Ok(())
+    }
+
+    pub fn get_arbitrator_threshold(env: Env) -> i128 {
+        storage::get_arbitrator_threshold(&env)
+    }
+
+    /// Cap concurrent open trades between the same seller and buyer (admin
+    /// only). Zero removes the cap.
+    pub fn set_pair_trade_cap(env: Env, cap: u32) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        let old = storage::get_pair_trade_cap(&env);
+        storage::set_pair_trade_cap(&env, cap);
+        events::emit_config_changed(&env, symbol_short!("pair_cap"), old, cap);
+        Ok(())
+    }
+
+    pub fn get_pair_trade_cap(env: Env) -> u32 {
+        storage::get_pair_trade_cap(&env)
+    }
+
+    /// Open (unsettled) trades between `seller` and `buyer`.
+    pub fn get_pair_open_trades(env: Env, seller: Address, buyer: Address) -> u32 {
+        storage::get_pair_open_trades(&env, &seller, &buyer)
+    }
+
+    /// Address that controls this instance.
+    /// Admin actions from log position `start`, oldest first, at most 100 per
+    /// page. Only the most recent `ADMIN_LOG_CAPACITY` entries are retained.
+    pub fn get_admin_log(env: Env, start: u64, limit: u32) -> soroban_sdk::Vec<AdminLogEntry> {
+        storage::get_admin_log(&env, start, limit.min(100))
+    }
+
+    pub fn get_admin_log_count(env: Env) -> u64 {
+        storage::get_admin_log_count(&env)
+    }
+
+    pub fn get_admin(env: Env) -> Result<Address, ContractError> {
+        storage::get_admin(&env)
+    }
+
+    /// Default settlement token configured at initialization.
+    pub fn get_usdc_token(env: Env) -> Result<Address, ContractError> {
+        storage::get_usdc_token(&env)
+    }
+
+    pub fn get_default_hold(env: Env) -> u32 {
+        storage::get_default_hold(&env)
+    }
+
+    pub fn get_category_hold(env: Env, category: soroban_sdk::Symbol) -> Option<u32> {
+        storage::get_category_hold(&env, &category)
+    }
+
+    /// Semantic version of the deployed code and the WASM hash it was last
+    /// upgraded to, so integrators can branch behavior across deployments.
+    pub fn get_version(env: Env) -> Result<ContractVersion, ContractError> {
+        storage::get_contract_version(&env).ok_or(ContractError::NotInitialized)
+    }
+
     pub fn migrate(env: Env, expected_version: u32) -> Result<(), ContractError> {
         require_initialized(&env)?;
         let current = storage::get_version(&env);
@@ -1057,7 +4584,112 @@ impl StellarEscrowContract {
         Ok(())
     }
 
+    /// Rewrite a persisted trade in the current `VersionedTrade` layout.
+    /// Permissionless and idempotent in effect: fails with
+    /// `MigrationAlreadyApplied` if the entry is already current.
+    pub fn migrate_trade(env: Env, trade_id: u64) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        if storage::is_trade_current(&env, trade_id)? {
+            return Err(ContractError::MigrationAlreadyApplied);
+        }
+        let trade = storage::get_trade(&env, trade_id)?;
+        storage::save_trade(&env, trade_id, &trade);
+        events::emit_trade_migrated(&env, trade_id, TRADE_SCHEMA_VERSION);
+        Ok(())
+    }
+
+    /// Schema version a trade is persisted with (legacy bare entries report 0).
+    pub fn get_trade_schema_version(env: Env, trade_id: u64) -> Result<u32, ContractError> {
+        storage::get_trade_schema_version(&env, trade_id)
+    }
+
+    /// Quote the fee, net payout and applied rate for a prospective trade of
+    /// `amount` by `seller` in `currency` (USDC if `None`), accounting for
+    /// per-token rates, tiers, custom rates and subscription discounts.
+    /// Read-only; creates nothing.
+    pub fn simulate_fee(
+        env: Env,
+        amount: i128,
+        seller: Address,
+        currency: Option<Address>,
+    ) -> Result<FeeQuote, ContractError> {
+        require_initialized(&env)?;
+        let token = match currency {
+            Some(t) => t,
+            None => storage::get_usdc_token(&env)?,
+        };
+        fees::quote(&env, &seller, &token, amount)
+    }
+
+    /// Net `seller` would receive from a trade of `amount` in `token` under
+    /// the current fee configuration (tiers, caps, floors and promotions).
+    /// Read-only; creates nothing.
+    pub fn estimate_payout(env: Env, seller: Address, amount: i128, token: Address) -> Result<i128, ContractError> {
+        require_initialized(&env)?;
+        Ok(fees::quote(&env, &seller, &token, amount)?.payout)
+    }
+
+    /// How trade `trade_id`'s amount divides between platform fee,
+    /// arbitrator, partner, referrer and payout, computed by the same code
+    /// that settles it. Disputed and ruled trades are broken down as a
+    /// ruling settles them; all others as a release to the seller.
+    pub fn get_fee_breakdown(env: Env, trade_id: u64) -> Result<FeeBreakdown, ContractError> {
+        fees::breakdown(&env, &storage::get_trade(&env, trade_id)?)
+    }
+
     /// Query a user's current tier info.
+    /// Configure the loyalty programme (admin only).
+    pub fn set_loyalty_config(env: Env, config: LoyaltyConfig) -> Result<(), ContractError> {
+        require_initialized(&env)?;
+        storage::get_admin(&env)?.require_auth();
+        let old = storage::get_loyalty_config(&env);
+        loyalty::set_config(&env, &config)?;
+        events::emit_config_changed(&env, symbol_short!("loyalty"), old, Some(config));
+        Ok(())
+    }
+
+    pub fn get_loyalty_config(env: Env) -> Option<LoyaltyConfig> {
+        storage::get_loyalty_config(&env)
+    }
+
+    pub fn get_points(env: Env, user: Address) -> u64 {
+        storage::get_points(&env, &user)
+    }
+
+    /// Spend points for `discount_bps` off the fee of the caller's next trade
+    /// as seller. Returns the total discount now held.
+    pub fn redeem_points(env: Env, user: Address, discount_bps: u32) -> Result<u32, ContractError> {
+        require_initialized(&env)?;
+        require_not_paused(&env)?;
+        user.require_auth();
+        loyalty::redeem(&env, &user, discount_bps)
+    }
+
+    pub fn get_points_discount(env: Env, user: Address) -> u32 {
+        loyalty::discount_bps(&env, &user)
+    }
+
+    /// Claim the trust badge level the caller's clean trade streak has
+    /// earned. Returns the new level.
+    pub fn claim_badge(env: Env, user: Address) -> Result<u32, ContractError> {
+        require_initialized(&env)?;
+        user.require_auth();
+        badges::claim(&env, &user)
+    }
+
+    pub fn get_badge_level(env: Env, user: Address) -> u32 {
+        badges::level(&env, &user)
+    }
+
+    pub fn get_track_record(env: Env, user: Address) -> TrackRecord {
+        storage::get_track_record(&env, &user)
+    }
+
+    /// Trade counts per side, settled volume and dispute outcomes for `user`.
+    pub fn get_user_stats(env: Env, user: Address) -> UserStats {
+        storage::get_user_stats(&env, &user)
+    }
+
     pub fn get_user_tier(env: Env, user: Address) -> Option<UserTierInfo> {
         storage::get_user_tier(&env, &user)
     }
@@ -1134,7 +4766,9 @@ impl StellarEscrowContract {
     pub fn set_bridge_oracle(env: Env, oracle: Address) -> Result<(), ContractError> {
         require_initialized(&env)?;
         storage::get_admin(&env)?.require_auth();
+        let old = storage::get_bridge_oracle(&env);
         storage::set_bridge_oracle(&env, &oracle);
+        events::emit_config_changed(&env, symbol_short!("brg_orcl"), old, Some(oracle.clone()));
         events::emit_bridge_oracle_set(&env, oracle);
         Ok(())
     }
@@ -1143,15 +4777,19 @@ impl StellarEscrowContract {
         env: Env,
         seller: Address,
         buyer: Address,
-        amount: u64,
+        amount: i128,
         arbitrator: Option<Address>,
         source_chain: String,
         expiry_ledgers: u32,
     ) -> Result<u64, ContractError> {
         require_initialized(&env)?;
         require_not_paused(&env)?;
-        if amount == 0 {
-            return Err(ContractError::InvalidAmount);
+        require_function_active(&env, PAUSE_CREATE)?;
+        require_breaker_clear(&env)?;
+        trusted_callers::require_permitted(&env, None)?;
+        amount::require_positive(amount)?;
+        if buyer == seller {
+            return Err(ContractError::InvalidParties);
         }
         if storage::get_bridge_oracle(&env).is_none() {
             return Err(ContractError::BridgeOracleNotSet);
@@ -1162,11 +4800,17 @@ impl StellarEscrowContract {
                 if !storage::has_arbitrator(&env, &addr) {
                     return Err(ContractError::ArbitratorNotRegistered);
                 }
+                require_disinterested(&addr, &seller, &buyer)?;
                 Some(addr)
             }
             None => None,
         };
+        require_pair_capacity(&env, &seller, &buyer)?;
         let trade_id = storage::increment_trade_counter(&env)?;
+        storage::open_pair_trade(&env, trade_id, &seller, &buyer);
+        if let Some(ref arb) = arbitration {
+            storage::index_arbitrator_trade(&env, arb, trade_id);
+        }
         let expires_at_ledger = env
             .ledger()
             .sequence()
@@ -1177,7 +4821,7 @@ impl StellarEscrowContract {
             seller: seller.clone(),
             buyer: buyer.clone(),
             amount,
-            fee: calc_fee(&env, amount)?,
+            fee: calc_fee(&env, &seller, &storage::get_usdc_token(&env)?, amount)?,
             arbitrator: arbitration,
             status: TradeStatus::AwaitingBridge,
             expiry_time: None,
@@ -1198,7 +4842,8 @@ impl StellarEscrowContract {
             },
         );
         events::emit_bridge_trade_created(&env, trade_id, source_chain);
-        analytics::on_trade_created(&env, amount, &trade.seller, &trade.buyer);
+        analytics::on_trade_created(&env, &trade.currency, amount, &trade.seller, &trade.buyer)?;
+        user_stats::record_created(&env, &trade.seller, &trade.buyer);
         Ok(trade_id)
     }
 
@@ -1210,6 +4855,7 @@ impl StellarEscrowContract {
         require_initialized(&env)?;
         let oracle = storage::get_bridge_oracle(&env).ok_or(ContractError::BridgeOracleNotSet)?;
         oracle.require_auth();
+        require_breaker_clear(&env)?;
         let mut trade = storage::get_trade(&env, trade_id)?;
         if trade.status != TradeStatus::AwaitingBridge {
             return Err(ContractError::InvalidStatus);
@@ -1220,8 +4866,9 @@ impl StellarEscrowContract {
         }
         info.source_tx_hash = source_tx_hash;
         storage::save_cross_chain_info(&env, trade_id, &info);
-        trade.status = TradeStatus::Funded;
+        state::transition(&env, &mut trade, TradeEvent::Fund, &oracle)?;
         storage::save_trade(&env, trade_id, &trade);
+        record_funding_volume(&env, &trade.currency, trade.amount)?;
         events::emit_bridge_deposit_confirmed(&env, trade_id);
         analytics::on_trade_funded(&env);
         Ok(())
@@ -1238,10 +4885,12 @@ impl StellarEscrowContract {
             return Err(ContractError::BridgeTradeNotExpired);
         }
         trade.seller.require_auth();
-        trade.status = TradeStatus::Cancelled;
+        let actor = trade.seller.clone();
+        state::transition(&env, &mut trade, TradeEvent::Expire, &actor)?;
         storage::save_trade(&env, trade_id, &trade);
         events::emit_bridge_trade_expired(&env, trade_id);
-        analytics::on_trade_cancelled(&env);
+        events::emit_trade_expired(&env, trade_id);
+        analytics::on_trade_cancelled(&env)?;
         Ok(())
     }
 
@@ -1277,10 +4926,11 @@ impl StellarEscrowContract {
         trade_id: u64,
         provider: Address,
         premium_bps: u32,
-        coverage: u64,
+        coverage: i128,
     ) -> Result<(), ContractError> {
         require_initialized(&env)?;
         require_not_paused(&env)?;
+        amount::require_non_negative(coverage)?;
         if premium_bps > MAX_INSURANCE_PREMIUM_BPS {
             return Err(ContractError::InsurancePremiumTooHigh);
         }
@@ -1294,11 +4944,11 @@ impl StellarEscrowContract {
         trade.buyer.require_auth();
         let premium = trade
             .amount
-            .checked_mul(premium_bps as u64)
+            .checked_mul(i128::from(premium_bps))
             .ok_or(ContractError::Overflow)?
             .checked_div(10_000)
             .ok_or(ContractError::Overflow)?;
-        usdc_client(&env)?.transfer(&trade.buyer, &provider, &(premium as i128));
+        usdc_client(&env)?.transfer(&trade.buyer, &provider, &premium);
         storage::save_insurance_policy(
             &env,
             trade_id,
@@ -1317,11 +4967,12 @@ impl StellarEscrowContract {
         env: Env,
         trade_id: u64,
         recipient: Address,
-        payout: u64,
+        payout: i128,
     ) -> Result<(), ContractError> {
         require_initialized(&env)?;
+        amount::require_non_negative(payout)?;
         let trade = storage::get_trade(&env, trade_id)?;
-        if trade.status != TradeStatus::Disputed && trade.status != TradeStatus::Completed {
+        if !matches!(trade.status, TradeStatus::Disputed | TradeStatus::Completed | TradeStatus::Settled) {
             return Err(ContractError::InsuranceClaimNotEligible);
         }
         let mut policy = storage::get_insurance_policy(&env, trade_id).ok_or(ContractError::TradeNotInsured)?;
@@ -1330,7 +4981,7 @@ impl StellarEscrowContract {
         }
         policy.provider.require_auth();
         let actual_payout = if payout > policy.coverage { policy.coverage } else { payout };
-        usdc_client(&env)?.transfer(&policy.provider, &recipient, &(actual_payout as i128));
+        usdc_client(&env)?.transfer(&policy.provider, &recipient, &actual_payout);
         policy.claimed = true;
         storage::save_insurance_policy(&env, trade_id, &policy);
         events::emit_insurance_claimed(&env, trade_id, actual_payout, recipient);
@@ -1345,6 +4996,12 @@ impl StellarEscrowContract {
         analytics::get_metrics(&env)
     }
 
+    /// Lifetime counters for `currency` that only ever grow; unaffected by
+    /// fee withdrawals.
+    pub fn get_lifetime_totals(env: Env, currency: Address) -> LifetimeTotals {
+        analytics::get_lifetime_totals(&env, &currency)
+    }
+
     pub fn get_platform_stats(env: Env) -> PlatformStats {
         analytics::get_stats(&env)
     }
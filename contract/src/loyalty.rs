@@ -0,0 +1,79 @@
+//! Loyalty points: accrual on settled trades and redemption into fee discounts.
+//!
+//! Storage (points balances, held discounts, programme config) lives in
+//! storage.rs. Discounts are read by `fees::effective_fee_bps` and consumed
+//! when the holder next creates a trade as seller.
+
+use soroban_sdk::{Address, Env};
+
+use crate::errors::ContractError;
+use crate::events;
+use crate::storage::{
+    get_loyalty_config, get_points, get_points_discount, remove_points_discount,
+    set_loyalty_config, set_points, set_points_discount,
+};
+use crate::types::LoyaltyConfig;
+
+pub fn set_config(env: &Env, config: &LoyaltyConfig) -> Result<(), ContractError> {
+    if config.points_per_bps == 0 || config.max_discount_bps > 10_000 {
+        return Err(ContractError::InvalidLoyaltyConfig);
+    }
+    set_loyalty_config(env, config);
+    Ok(())
+}
+
+/// Credit `user` with points for `volume` of settled trade volume. No-op
+/// when no programme is configured.
+pub fn accrue(env: &Env, user: &Address, volume: i128) -> Result<(), ContractError> {
+    let config = match get_loyalty_config(env) {
+        Some(c) => c,
+        None => return Ok(()),
+    };
+    let earned = volume
+        .checked_mul(i128::from(config.points_per_million))
+        .ok_or(ContractError::Overflow)?
+        / 1_000_000;
+    let earned = u64::try_from(earned).map_err(|_| ContractError::Overflow)?;
+    if earned == 0 {
+        return Ok(());
+    }
+    let total = get_points(env, user).checked_add(earned).ok_or(ContractError::Overflow)?;
+    set_points(env, user, total);
+    events::emit_points_earned(env, user.clone(), earned, total);
+    Ok(())
+}
+
+/// Spend points for `discount_bps` off the fee of `user`'s next trade.
+/// Redemptions stack up to the configured cap.
+pub fn redeem(env: &Env, user: &Address, discount_bps: u32) -> Result<u32, ContractError> {
+    let config = get_loyalty_config(env).ok_or(ContractError::LoyaltyNotConfigured)?;
+    let held = get_points_discount(env, user)
+        .checked_add(discount_bps)
+        .ok_or(ContractError::Overflow)?;
+    if discount_bps == 0 || held > config.max_discount_bps {
+        return Err(ContractError::DiscountCapExceeded);
+    }
+    let cost = (discount_bps as u64)
+        .checked_mul(config.points_per_bps)
+        .ok_or(ContractError::Overflow)?;
+    let balance = get_points(env, user);
+    if balance < cost {
+        return Err(ContractError::InsufficientPoints);
+    }
+    set_points(env, user, balance - cost);
+    set_points_discount(env, user, held);
+    events::emit_points_redeemed(env, user.clone(), cost, held);
+    Ok(held)
+}
+
+/// Discount `user` holds for their next trade, in basis points.
+pub fn discount_bps(env: &Env, user: &Address) -> u32 {
+    get_points_discount(env, user)
+}
+
+/// Use up the held discount once a trade has been priced with it.
+pub fn consume_discount(env: &Env, user: &Address) {
+    if get_points_discount(env, user) > 0 {
+        remove_points_discount(env, user);
+    }
+}
@@ -192,13 +192,13 @@ pub fn validate_trade_price(
     env: &Env,
     base: &Address,
     quote: &Address,
-    trade_amount: u64,
+    trade_amount: i128,
     min_usd: i128,
     max_usd: i128,
 ) -> Result<PriceValidation, ContractError> {
     let pd = get_price(env, base, quote)?;
     let scale = 10_i128.pow(pd.decimals);
-    let usd_value = (trade_amount as i128)
+    let usd_value = trade_amount
         .checked_mul(pd.price)
         .ok_or(ContractError::Overflow)?
         .checked_div(scale)
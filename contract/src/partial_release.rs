@@ -29,7 +29,7 @@ pub fn calculate_partial_amounts(
     env: &Env,
     trade_id: u64,
     buyer_bps: u32,
-) -> Result<(u64, u64), ContractError> {
+) -> Result<(i128, i128), ContractError> {
     // Validate buyer_bps is within valid range
     if buyer_bps > 10000 {
         return Err(ContractError::InvalidSplitBps);
@@ -43,7 +43,7 @@ pub fn calculate_partial_amounts(
     
     // Calculate buyer amount: net * buyer_bps / 10000
     let buyer_amount = net
-        .checked_mul(buyer_bps as u64)
+        .checked_mul(i128::from(buyer_bps))
         .ok_or(ContractError::Overflow)?
         .checked_div(10000)
         .ok_or(ContractError::Overflow)?;
@@ -0,0 +1,107 @@
+//! White-label partners earning their own fee on trades they bring in.
+//!
+//! # Design
+//! - The admin registers partners by id (`set_partner`), each with a fee in
+//!   basis points and an address that receives it. A partner either adds
+//!   its margin on top of the platform fee or replaces it.
+//! - `create_trade` may name a partner id. The trade's `fee` then covers
+//!   both margins, so payout and refund paths are unchanged; the partner's
+//!   part and recipient are recorded per trade.
+//! - Whenever part of a partner trade's fee is booked, the partner's pro-rata
+//!   share goes straight to its recipient and the rest is booked as platform
+//!   fee. Partner earnings are tracked per currency and emitted on their own
+//!   topic.
+//! - Terms are fixed at creation: changing or removing a partner only
+//!   affects later trades.
+
+use soroban_sdk::{token, Address, Env};
+
+use crate::errors::ContractError;
+use crate::events;
+use crate::regulated;
+use crate::types::{Partner, Trade, TradePartner};
+
+// ---------------------------------------------------------------------------
+// Storage
+// ---------------------------------------------------------------------------
+
+const PARTNER_PREFIX: &str = "WL";
+pub(crate) const TRADE_PARTNER_PREFIX: &str = "WT";
+const PARTNER_EARNED_PREFIX: &str = "WE";
+
+pub fn set(env: &Env, partner_id: u32, partner: &Option<Partner>) {
+    let key = (PARTNER_PREFIX, partner_id);
+    match partner {
+        Some(p) => env.storage().persistent().set(&key, p),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get(env: &Env, partner_id: u32) -> Option<Partner> {
+    env.storage().persistent().get(&(PARTNER_PREFIX, partner_id))
+}
+
+pub fn get_trade_partner(env: &Env, trade_id: u64) -> Option<TradePartner> {
+    env.storage().persistent().get(&(TRADE_PARTNER_PREFIX, trade_id))
+}
+
+pub fn earned(env: &Env, partner_id: u32, currency: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(PARTNER_EARNED_PREFIX, partner_id, currency.clone()))
+        .unwrap_or(0)
+}
+
+// ---------------------------------------------------------------------------
+// Fees
+// ---------------------------------------------------------------------------
+
+/// Fee for a new trade of `amount` created through `partner_id`, given the
+/// platform's own fee for it. Returns the total fee and the partner's terms
+/// for that trade.
+pub fn quote(
+    env: &Env,
+    partner_id: u32,
+    amount: i128,
+    platform_fee: i128,
+) -> Result<(i128, TradePartner), ContractError> {
+    let partner = get(env, partner_id).ok_or(ContractError::PartnerNotFound)?;
+    let margin = amount
+        .checked_mul(i128::from(partner.fee_bps))
+        .ok_or(ContractError::Overflow)?
+        / 10_000;
+    let platform = if partner.replaces_platform_fee { 0 } else { platform_fee };
+    let total = platform.checked_add(margin).ok_or(ContractError::Overflow)?;
+    Ok((total, TradePartner { partner_id, recipient: partner.recipient, fee: margin }))
+}
+
+pub fn attach(env: &Env, trade_id: u64, terms: &TradePartner) {
+    env.storage().persistent().set(&(TRADE_PARTNER_PREFIX, trade_id), terms);
+}
+
+/// The partner's pro-rata share of `fee`, booked out of `trade`'s fee.
+pub fn share(env: &Env, trade: &Trade, fee: i128) -> Result<i128, ContractError> {
+    match get_trade_partner(env, trade.id) {
+        Some(l) if l.fee > 0 && trade.fee > 0 => Ok(fee.checked_mul(l.fee).ok_or(ContractError::Overflow)? / trade.fee),
+        _ => Ok(0),
+    }
+}
+
+/// Pay the partner its share of `fee`, booked out of `trade`'s fee, and
+/// return what is left for the platform.
+pub fn take(env: &Env, trade: &Trade, fee: i128) -> Result<i128, ContractError> {
+    let share = share(env, trade, fee)?;
+    let link = match get_trade_partner(env, trade.id) {
+        Some(l) if share > 0 => l,
+        _ => return Ok(fee),
+    };
+    regulated::pay_out(env, &token::Client::new(env, &trade.currency), &link.recipient, share)?;
+    let total = earned(env, link.partner_id, &trade.currency)
+        .checked_add(share)
+        .ok_or(ContractError::Overflow)?;
+    env.storage()
+        .persistent()
+        .set(&(PARTNER_EARNED_PREFIX, link.partner_id, trade.currency.clone()), &total);
+    events::emit_partner_fee_paid(env, trade.id, link.partner_id, link.recipient, trade.currency.clone(), share);
+    Ok(fee - share)
+}
@@ -0,0 +1,122 @@
+//! Seller payouts swapped into another token through a whitelisted DEX.
+//!
+//! # Design
+//! - The admin whitelists swap adapters (`set_swap_adapter`): contracts
+//!   exposing `swap_exact_in`, typically thin wrappers around a DEX router
+//!   such as Soroswap.
+//! - A seller opts a trade in with `set_payout_swap`, naming the adapter,
+//!   the token to receive and the minimum amount out for the full trade
+//!   amount. Partial payouts scale the minimum pro rata.
+//! - On release the escrow approves the adapter for the payout and calls
+//!   it, withdrawing any unused allowance afterwards. If the approval or
+//!   the swap fails for any reason (no route, slippage, paused pool) the
+//!   seller is paid directly in the trade's currency instead.
+//! - Only the default seller-side payout is swapped; payout splits,
+//!   assignments and basket legs are paid as usual.
+
+use soroban_sdk::{contractclient, token, Address, Env};
+
+use crate::errors::ContractError;
+use crate::events;
+use crate::regulated;
+use crate::types::{PayoutSwap, Trade};
+
+// ---------------------------------------------------------------------------
+// Swap adapter cross-contract interface
+// ---------------------------------------------------------------------------
+
+#[contractclient(name = "SwapAdapterClient")]
+pub trait SwapAdapterInterface {
+    /// Pull `amount_in` of `token_in` from `from` using the allowance `from`
+    /// granted the adapter, swap it and send at least `min_out` of
+    /// `token_out` to `to`. Returns the amount sent.
+    fn swap_exact_in(
+        env: Env,
+        from: Address,
+        token_in: Address,
+        token_out: Address,
+        amount_in: i128,
+        min_out: i128,
+        to: Address,
+    ) -> i128;
+}
+
+// ---------------------------------------------------------------------------
+// Storage
+// ---------------------------------------------------------------------------
+
+const SWAP_ADAPTER_PREFIX: &str = "SA";
+pub(crate) const PAYOUT_SWAP_PREFIX: &str = "PS";
+
+pub fn set_adapter(env: &Env, adapter: &Address, allowed: bool) {
+    let key = (SWAP_ADAPTER_PREFIX, adapter.clone());
+    if allowed {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+pub fn is_adapter(env: &Env, adapter: &Address) -> bool {
+    env.storage().persistent().has(&(SWAP_ADAPTER_PREFIX, adapter.clone()))
+}
+
+pub fn set(env: &Env, trade_id: u64, swap: &PayoutSwap) {
+    env.storage().persistent().set(&(PAYOUT_SWAP_PREFIX, trade_id), swap);
+}
+
+pub fn get(env: &Env, trade_id: u64) -> Option<PayoutSwap> {
+    env.storage().persistent().get(&(PAYOUT_SWAP_PREFIX, trade_id))
+}
+
+pub fn remove(env: &Env, trade_id: u64) {
+    env.storage().persistent().remove(&(PAYOUT_SWAP_PREFIX, trade_id));
+}
+
+// ---------------------------------------------------------------------------
+// Payout
+// ---------------------------------------------------------------------------
+
+/// Pay `amount` of the trade's currency to `payee`, swapped as the seller
+/// asked if possible and directly otherwise.
+pub fn pay_seller(
+    env: &Env,
+    token_client: &token::Client,
+    trade: &Trade,
+    payee: &Address,
+    amount: i128,
+) -> Result<(), ContractError> {
+    let swap = match get(env, trade.id) {
+        // An adapter delisted since the opt-in is no longer trusted.
+        Some(s) if token_client.address == trade.currency && is_adapter(env, &s.adapter) => s,
+        _ => return regulated::pay_out(env, token_client, payee, amount),
+    };
+    let min_out = swap
+        .min_out
+        .checked_mul(amount)
+        .ok_or(ContractError::Overflow)?
+        / trade.amount;
+    let contract = env.current_contract_address();
+    let expiration = env.ledger().sequence();
+    if !matches!(token_client.try_approve(&contract, &swap.adapter, &amount, &expiration), Ok(Ok(()))) {
+        events::emit_payout_swap_failed(env, trade.id, swap.adapter);
+        return regulated::pay_out(env, token_client, payee, amount);
+    }
+    let swapped = SwapAdapterClient::new(env, &swap.adapter).try_swap_exact_in(
+        &contract,
+        &trade.currency,
+        &swap.token_out,
+        &amount,
+        &min_out,
+        payee,
+    );
+    // Withdraw whatever the adapter did not pull. A token refusing the reset
+    // leaves an allowance that expires with this ledger anyway.
+    let _ = token_client.try_approve(&contract, &swap.adapter, &0, &expiration);
+    if let Ok(Ok(out)) = swapped {
+        events::emit_payout_swapped(env, trade.id, swap.token_out, amount, out);
+        return Ok(());
+    }
+    events::emit_payout_swap_failed(env, trade.id, swap.adapter);
+    regulated::pay_out(env, token_client, payee, amount)
+}
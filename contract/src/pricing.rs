@@ -0,0 +1,64 @@
+//! Trades denominated in a reference asset and settled in another token.
+//!
+//! The seller quotes the trade in reference-asset units. When the trade is
+//! first funded, the settlement amount is fixed from the oracle price of the
+//! trade's currency in the reference asset and recorded with the quote, so
+//! disputes can be judged against the rate actually used. Both assets are
+//! assumed to share the same base-unit decimals. The fee keeps the rate
+//! agreed at creation.
+
+use soroban_sdk::{Address, Env};
+
+use crate::amount::require_positive;
+use crate::errors::ContractError;
+use crate::events;
+use crate::oracle;
+use crate::storage::{get_trade_quote, save_trade, save_trade_quote};
+use crate::types::{FixedRate, Trade, TradeQuote};
+
+pub fn set(env: &Env, trade: &Trade, reference: &Address, quoted_amount: i128) -> Result<(), ContractError> {
+    if quoted_amount <= 0 || *reference == trade.currency {
+        return Err(ContractError::InvalidQuote);
+    }
+    if oracle::get_oracles(env, &trade.currency, reference).is_empty() {
+        return Err(ContractError::OracleNotFound);
+    }
+    save_trade_quote(
+        env,
+        trade.id,
+        &TradeQuote { reference: reference.clone(), quoted_amount, fixed: None },
+    );
+    Ok(())
+}
+
+/// Fix a quoted trade's amount at the current oracle rate, rescaling its
+/// fee. No-op for unquoted trades and ones already fixed.
+pub fn fix_settlement(env: &Env, trade: &mut Trade) -> Result<(), ContractError> {
+    let mut quote = match get_trade_quote(env, trade.id) {
+        Some(q) if q.fixed.is_none() => q,
+        _ => return Ok(()),
+    };
+    let pd = oracle::get_price(env, &trade.currency, &quote.reference)?;
+    if pd.price <= 0 {
+        return Err(ContractError::OraclePriceInvalid);
+    }
+    let scale = 10_i128.checked_pow(pd.decimals).ok_or(ContractError::Overflow)?;
+    let amount = quote
+        .quoted_amount
+        .checked_mul(scale)
+        .ok_or(ContractError::Overflow)?
+        / pd.price;
+    require_positive(amount)?;
+    trade.fee = trade.fee.checked_mul(amount).ok_or(ContractError::Overflow)? / trade.amount;
+    trade.amount = amount;
+    save_trade(env, trade.id, trade);
+    quote.fixed = Some(FixedRate {
+        price: pd.price,
+        decimals: pd.decimals,
+        amount,
+        fixed_at: env.ledger().timestamp(),
+    });
+    save_trade_quote(env, trade.id, &quote);
+    events::emit_quote_fixed(env, trade.id, pd.price, pd.decimals, amount);
+    Ok(())
+}
@@ -1,9 +1,13 @@
 use soroban_sdk::{contracttype, Address, Env, Vec};
 
 use crate::errors::ContractError;
-use crate::storage::{get_trade, get_trade_counter};
+use crate::storage::{get_created_at, get_trade, get_trade_counter};
 use crate::types::{Trade, TradeStatus};
 
+/// Widest id range `get_trades_range` accepts: two reads per trade keeps a
+/// full range inside the per-transaction read-entry limit.
+pub const MAX_TRADE_RANGE: u64 = 15;
+
 // ---------------------------------------------------------------------------
 // Query parameter types
 // ---------------------------------------------------------------------------
@@ -33,9 +37,9 @@ pub struct TradeFilter {
     /// Only return trades involving this address (as buyer or seller)
     pub participant: Option<Address>,
     /// Minimum trade amount (inclusive)
-    pub min_amount: Option<u64>,
+    pub min_amount: Option<i128>,
     /// Maximum trade amount (inclusive)
-    pub max_amount: Option<u64>,
+    pub max_amount: Option<i128>,
     /// Only return trades with IDs >= this value (useful for time-range proxies)
     pub from_trade_id: Option<u64>,
     /// Only return trades with IDs <= this value
@@ -56,15 +60,29 @@ pub struct PageParams {
     pub direction: SortDirection,
 }
 
+/// Compact view of a trade for scanning the trade book.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TradeSummary {
+    pub id: u64,
+    pub seller: Address,
+    pub buyer: Address,
+    pub amount: i128,
+    pub status: TradeStatus,
+    /// Ledger sequence the trade was created at.
+    pub created_at: Option<u64>,
+    pub expiry_time: Option<u64>,
+}
+
 /// Aggregated statistics over a set of trades
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct TradeStats {
     pub total_count: u64,
-    pub total_volume: u64,
-    pub total_fees: u64,
-    pub min_amount: u64,
-    pub max_amount: u64,
+    pub total_volume: i128,
+    pub total_fees: i128,
+    pub min_amount: i128,
+    pub max_amount: i128,
 }
 
 // ---------------------------------------------------------------------------
@@ -166,10 +184,10 @@ pub fn query_trades(
 pub fn aggregate_trades(env: &Env, filter: TradeFilter) -> Result<TradeStats, ContractError> {
     let total = get_trade_counter(env).unwrap_or(0);
     let mut count: u64 = 0;
-    let mut volume: u64 = 0;
-    let mut fees: u64 = 0;
-    let mut min_amount: u64 = u64::MAX;
-    let mut max_amount: u64 = 0;
+    let mut volume: i128 = 0;
+    let mut fees: i128 = 0;
+    let mut min_amount: i128 = i128::MAX;
+    let mut max_amount: i128 = 0;
 
     for id in 1..=total {
         if let Ok(trade) = get_trade(env, id) {
@@ -274,3 +292,50 @@ pub fn count_trades(env: &Env, filter: TradeFilter) -> Result<u64, ContractError
     
     Ok(count)
 }
+
+/// List trades by ascending ID starting at `start_id`, returning at most
+/// `limit` (capped at 100) summaries. IDs with no stored trade are skipped,
+/// so callers page by passing `last.id + 1` as the next `start_id`.
+pub fn list_trades(env: &Env, start_id: u64, limit: u32) -> Result<Vec<TradeSummary>, ContractError> {
+    let total = get_trade_counter(env)?;
+    let limit = limit.min(100);
+    let mut out: Vec<TradeSummary> = Vec::new(env);
+    let mut id = start_id.max(1);
+    while id <= total && out.len() < limit {
+        if let Ok(trade) = get_trade(env, id) {
+            out.push_back(summarize(env, trade));
+        }
+        id += 1;
+    }
+    Ok(out)
+}
+
+/// Summaries of every stored trade with an id in `from_id..=to_id`, for
+/// indexers backfilling history. Each summary reads two ledger entries, so
+/// the range is capped at `MAX_TRADE_RANGE` ids to stay within a
+/// transaction's read budget.
+pub fn trades_range(env: &Env, from_id: u64, to_id: u64) -> Result<Vec<TradeSummary>, ContractError> {
+    if to_id < from_id || to_id - from_id >= MAX_TRADE_RANGE {
+        return Err(ContractError::InvalidTradeRange);
+    }
+    let to_id = to_id.min(get_trade_counter(env)?);
+    let mut out: Vec<TradeSummary> = Vec::new(env);
+    for id in from_id.max(1)..=to_id {
+        if let Ok(trade) = get_trade(env, id) {
+            out.push_back(summarize(env, trade));
+        }
+    }
+    Ok(out)
+}
+
+fn summarize(env: &Env, trade: Trade) -> TradeSummary {
+    TradeSummary {
+        id: trade.id,
+        created_at: get_created_at(env, trade.id),
+        seller: trade.seller,
+        buyer: trade.buyer,
+        amount: trade.amount,
+        status: trade.status,
+        expiry_time: trade.expiry_time,
+    }
+}
@@ -0,0 +1,94 @@
+//! Proof-of-commerce receipts minted on settlement.
+//!
+//! # Design
+//! - The admin registers a companion receipt contract (`set_receipt_contract`).
+//! - The receipt contract must expose `mint(to, receipt)` and keep receipts
+//!   non-transferable; this contract only reports settled trades to it.
+//! - Both parties receive a receipt encoding the trade id, amount and outcome.
+//! - With no receipt contract registered, settlement is unaffected.
+
+use soroban_sdk::{contractclient, contracttype, symbol_short, Address, Env};
+
+use crate::types::Trade;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// How a trade was settled.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReceiptOutcome {
+    Completed,
+    Refunded,
+    Resolved,
+}
+
+/// Payload handed to the receipt contract for each party.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TradeReceipt {
+    pub trade_id: u64,
+    pub amount: i128,
+    pub currency: Address,
+    pub outcome: ReceiptOutcome,
+    pub counterparty: Address,
+}
+
+// ---------------------------------------------------------------------------
+// Receipt cross-contract interface
+// ---------------------------------------------------------------------------
+
+#[contractclient(name = "ReceiptClient")]
+pub trait ReceiptInterface {
+    fn mint(env: Env, to: Address, receipt: TradeReceipt);
+}
+
+// ---------------------------------------------------------------------------
+// Storage
+// ---------------------------------------------------------------------------
+
+fn receipt_key() -> soroban_sdk::Symbol {
+    symbol_short!("RCPT")
+}
+
+pub fn set_receipt_contract(env: &Env, contract: &Address) {
+    env.storage().instance().set(&receipt_key(), contract);
+}
+
+pub fn remove_receipt_contract(env: &Env) {
+    env.storage().instance().remove(&receipt_key());
+}
+
+pub fn get_receipt_contract(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&receipt_key())
+}
+
+// ---------------------------------------------------------------------------
+// Minting
+// ---------------------------------------------------------------------------
+
+/// Mint a receipt to the buyer and the seller of a settled trade, if a
+/// receipt contract is registered.
+pub fn issue(env: &Env, trade: &Trade, outcome: ReceiptOutcome) {
+    let contract = match get_receipt_contract(env) {
+        Some(c) => c,
+        None => return,
+    };
+    let client = ReceiptClient::new(env, &contract);
+    for (to, counterparty) in [
+        (trade.buyer.clone(), trade.seller.clone()),
+        (trade.seller.clone(), trade.buyer.clone()),
+    ] {
+        client.mint(
+            &to,
+            &TradeReceipt {
+                trade_id: trade.id,
+                amount: trade.amount,
+                currency: trade.currency.clone(),
+                outcome,
+                counterparty,
+            },
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//! Referrers earning a share of the platform fee on buyers they onboard.
+//!
+//! # Design
+//! - Anyone can register as a referrer (`register_referrer`), naming a
+//!   payout address and the share of the platform fee they ask for, up to
+//!   `MAX_REFERRAL_SHARE_BPS`. Registrations start `Pending` until the admin
+//!   approves them; re-registering to change terms puts them back there.
+//! - A buyer is attributed to an approved referrer once, for life
+//!   (`attribute_referral`), usually as part of onboarding.
+//! - Whenever platform fee is booked on a trade whose buyer is attributed,
+//!   the referrer's share goes to its payout address and the rest is booked
+//!   as usual. The share is taken after any partner margin, and only while
+//!   the referrer stays approved. Earnings are tracked per currency.
+
+use soroban_sdk::{token, Address, Env};
+
+use crate::errors::ContractError;
+use crate::events;
+use crate::regulated;
+use crate::types::{Referrer, ReferrerStatus, Trade, MAX_REFERRAL_SHARE_BPS};
+
+// ---------------------------------------------------------------------------
+// Storage
+// ---------------------------------------------------------------------------
+
+const REFERRER_PREFIX: &str = "RF";
+const REFERRED_BY_PREFIX: &str = "RL";
+const REFERRAL_EARNED_PREFIX: &str = "RG";
+
+pub fn get(env: &Env, referrer: &Address) -> Option<Referrer> {
+    env.storage().persistent().get(&(REFERRER_PREFIX, referrer.clone()))
+}
+
+fn save(env: &Env, referrer: &Address, config: &Referrer) {
+    env.storage().persistent().set(&(REFERRER_PREFIX, referrer.clone()), config);
+}
+
+pub fn referred_by(env: &Env, buyer: &Address) -> Option<Address> {
+    env.storage().persistent().get(&(REFERRED_BY_PREFIX, buyer.clone()))
+}
+
+pub fn earned(env: &Env, referrer: &Address, currency: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(REFERRAL_EARNED_PREFIX, referrer.clone(), currency.clone()))
+        .unwrap_or(0)
+}
+
+// ---------------------------------------------------------------------------
+// Registration
+// ---------------------------------------------------------------------------
+
+pub fn register(env: &Env, referrer: &Address, payout: &Address, share_bps: u32) -> Result<(), ContractError> {
+    if share_bps > MAX_REFERRAL_SHARE_BPS {
+        return Err(ContractError::InvalidReferralShare);
+    }
+    save(
+        env,
+        referrer,
+        &Referrer {
+            payout: payout.clone(),
+            share_bps,
+            status: ReferrerStatus::Pending,
+            registered_at: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+pub fn set_status(env: &Env, referrer: &Address, status: ReferrerStatus) -> Result<(), ContractError> {
+    let mut config = get(env, referrer).ok_or(ContractError::ReferrerNotFound)?;
+    config.status = status;
+    save(env, referrer, &config);
+    Ok(())
+}
+
+pub fn attribute(env: &Env, buyer: &Address, referrer: &Address) -> Result<(), ContractError> {
+    if buyer == referrer {
+        return Err(ContractError::InvalidParties);
+    }
+    if referred_by(env, buyer).is_some() {
+        return Err(ContractError::AlreadyReferred);
+    }
+    let config = get(env, referrer).ok_or(ContractError::ReferrerNotFound)?;
+    if config.status != ReferrerStatus::Approved {
+        return Err(ContractError::ReferrerNotApproved);
+    }
+    env.storage().persistent().set(&(REFERRED_BY_PREFIX, buyer.clone()), referrer);
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Fee split
+// ---------------------------------------------------------------------------
+
+/// Approved referrer of `trade`'s buyer, if any, with its terms.
+fn earning_referrer(env: &Env, trade: &Trade) -> Option<(Address, Referrer)> {
+    let referrer = referred_by(env, &trade.buyer)?;
+    match get(env, &referrer) {
+        Some(c) if c.status == ReferrerStatus::Approved => Some((referrer, c)),
+        _ => None,
+    }
+}
+
+/// The referrer's share of `fee`, a platform fee booked on `trade`.
+pub fn share(env: &Env, trade: &Trade, fee: i128) -> Result<i128, ContractError> {
+    match earning_referrer(env, trade) {
+        Some((_, c)) => Ok(fee.checked_mul(i128::from(c.share_bps)).ok_or(ContractError::Overflow)? / 10_000),
+        None => Ok(0),
+    }
+}
+
+/// Pay the buyer's referrer its share of `fee`, a platform fee booked on
+/// `trade`, and return what is left for the platform.
+pub fn take(env: &Env, trade: &Trade, fee: i128) -> Result<i128, ContractError> {
+    let share = share(env, trade, fee)?;
+    let (referrer, config) = match earning_referrer(env, trade) {
+        Some(r) if share > 0 => r,
+        _ => return Ok(fee),
+    };
+    regulated::pay_out(env, &token::Client::new(env, &trade.currency), &config.payout, share)?;
+    let total = earned(env, &referrer, &trade.currency)
+        .checked_add(share)
+        .ok_or(ContractError::Overflow)?;
+    env.storage()
+        .persistent()
+        .set(&(REFERRAL_EARNED_PREFIX, referrer.clone(), trade.currency.clone()), &total);
+    events::emit_referral_paid(env, trade.id, referrer, config.payout, trade.currency.clone(), share);
+    Ok(fee - share)
+}
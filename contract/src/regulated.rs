@@ -0,0 +1,100 @@
+//! Settlement in regulated assets (SEP-8).
+//!
+//! # Design
+//! - A regulated asset's issuer must authorize every holder, so a payout to
+//!   an account the issuer has not (yet) approved fails in the token.
+//! - The admin marks such tokens with `set_regulated_asset`. Payouts in a
+//!   regulated token check the recipient's authorization first: authorized
+//!   recipients are paid at once; for anyone else the amount is held for
+//!   them and the trade settles regardless.
+//! - Once the issuer approves the recipient, they collect what is held with
+//!   `claim_held_payout`. Held amounts stay counted in `total_locked`.
+//! - Any other failed transfer where either side lacks authorization is
+//!   reported as `AssetAuthorizationRequired` by `transfer::send`.
+
+use soroban_sdk::{token, Address, Env};
+
+use crate::errors::ContractError;
+use crate::events;
+use crate::storage;
+use crate::transfer;
+
+// ---------------------------------------------------------------------------
+// Storage
+// ---------------------------------------------------------------------------
+
+const REGULATED_ASSET_PREFIX: &str = "RA";
+const HELD_PAYOUT_PREFIX: &str = "RH";
+
+pub fn set_regulated(env: &Env, token: &Address, regulated: bool) {
+    let key = (REGULATED_ASSET_PREFIX, token.clone());
+    if regulated {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+pub fn is_regulated(env: &Env, token: &Address) -> bool {
+    env.storage().persistent().has(&(REGULATED_ASSET_PREFIX, token.clone()))
+}
+
+pub fn held(env: &Env, recipient: &Address, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(HELD_PAYOUT_PREFIX, recipient.clone(), token.clone()))
+        .unwrap_or(0)
+}
+
+fn set_held(env: &Env, recipient: &Address, token: &Address, amount: i128) {
+    let key = (HELD_PAYOUT_PREFIX, recipient.clone(), token.clone());
+    if amount == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &amount);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Payouts
+// ---------------------------------------------------------------------------
+
+/// Whether the issuer currently lets `id` hold `token`. Tokens that are not
+/// Stellar assets have no such notion and always count as authorized.
+pub fn is_authorized(env: &Env, token: &Address, id: &Address) -> bool {
+    token::StellarAssetClient::new(env, token)
+        .try_authorized(id)
+        .ok()
+        .and_then(|r| r.ok())
+        .unwrap_or(true)
+}
+
+/// Pay `amount` out of escrow to `to`, holding it for them instead when the
+/// token is regulated and the issuer has not authorized them.
+pub fn pay_out(env: &Env, token_client: &token::Client, to: &Address, amount: i128) -> Result<(), ContractError> {
+    let token = &token_client.address;
+    if is_regulated(env, token) && !is_authorized(env, token, to) {
+        let total = held(env, to, token).checked_add(amount).ok_or(ContractError::Overflow)?;
+        set_held(env, to, token, total);
+        storage::add_total_locked(env, token, amount)?;
+        events::emit_payout_held(env, to.clone(), token.clone(), amount);
+        return Ok(());
+    }
+    transfer::send(token_client, &env.current_contract_address(), to, amount)
+}
+
+/// Second step: pay out everything held for `recipient` in `token` once the
+/// issuer has authorized them.
+pub fn claim(env: &Env, recipient: &Address, token: &Address) -> Result<i128, ContractError> {
+    let amount = held(env, recipient, token);
+    if amount == 0 {
+        return Err(ContractError::NoHeldPayout);
+    }
+    if !is_authorized(env, token, recipient) {
+        return Err(ContractError::AssetAuthorizationRequired);
+    }
+    set_held(env, recipient, token, 0);
+    storage::sub_total_locked(env, token, amount)?;
+    transfer::send(&token::Client::new(env, token), &env.current_contract_address(), recipient, amount)?;
+    Ok(amount)
+}
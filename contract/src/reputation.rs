@@ -8,8 +8,9 @@ use soroban_sdk::{Address, Env, Vec};
 use crate::errors::ContractError;
 use crate::events;
 use crate::storage::{
-    get_arbitrator_reputation, has_arbitrator, has_rated, mark_rated,
-    save_arbitrator_reputation,
+    get_arbitrator_reputation, get_party_reputation, has_arbitrator, has_rated,
+    has_rated_counterparty, mark_rated, mark_rated_counterparty, save_arbitrator_reputation,
+    save_party_reputation,
 };
 use crate::types::{ArbitratorReputation, TradeStatus};
 
@@ -63,6 +64,32 @@ pub fn rate_arbitrator(
     Ok(())
 }
 
+/// Record a 1–5 star rating from one trade party for the other. Each party
+/// may rate once per trade.
+pub fn rate_counterparty(
+    env: &Env,
+    trade_id: u64,
+    rater: &Address,
+    ratee: &Address,
+    stars: u32,
+) -> Result<(), ContractError> {
+    if stars < 1 || stars > 5 {
+        return Err(ContractError::InvalidRating);
+    }
+    if has_rated_counterparty(env, trade_id, rater) {
+        return Err(ContractError::AlreadyRated);
+    }
+    mark_rated_counterparty(env, trade_id, rater);
+
+    let mut rep = get_party_reputation(env, ratee);
+    rep.rating_sum = rep.rating_sum.saturating_add(stars);
+    rep.rating_count = rep.rating_count.saturating_add(1);
+    save_party_reputation(env, ratee, &rep);
+
+    events::emit_party_rated(env, trade_id, rater.clone(), ratee.clone(), stars);
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Computed statistics
 // ---------------------------------------------------------------------------
@@ -0,0 +1,189 @@
+//! Merkle batch settlement for micro-trades.
+//!
+//! # Design
+//! - An operator settles many small trades off-chain, then commits one batch:
+//!   the Merkle root of the payouts and their total, which it deposits in the
+//!   same call (`commit_settlement_batch`).
+//! - Each leaf is a `BatchLeaf` (batch id, leaf index, seller, amount). Its
+//!   hash is `sha256(0x00 || xdr(leaf))`; an inner node is
+//!   `sha256(0x01 || left || right)`. Bit `i` of the leaf index says whether
+//!   the node at level `i` is a right child. A level with an odd node count
+//!   pairs its last node with itself.
+//! - Anyone can submit a seller's claim with its proof; the payout always goes
+//!   to the seller named in the leaf. Each index pays out once.
+//! - Once the claim window closes the operator takes back whatever is left
+//!   (`reclaim_settlement_batch`). Platform fees are the operator's concern
+//!   and are expected to be netted out of the leaves.
+
+use soroban_sdk::{symbol_short, token, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol, Vec};
+
+use crate::amount::require_positive;
+use crate::clock;
+use crate::errors::ContractError;
+use crate::storage;
+use crate::transfer;
+use crate::types::{BatchLeaf, SettlementBatch, TimingMode, MAX_BATCH_PROOF_DEPTH};
+
+// ---------------------------------------------------------------------------
+// Storage
+// ---------------------------------------------------------------------------
+
+const BATCH_PREFIX: &str = "MB";
+const BATCH_CLAIM_PREFIX: &str = "MC";
+
+fn counter_key() -> Symbol {
+    symbol_short!("MB_NEXT")
+}
+
+pub fn get(env: &Env, batch_id: u64) -> Result<SettlementBatch, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&(BATCH_PREFIX, batch_id))
+        .ok_or(ContractError::SettlementBatchNotFound)
+}
+
+fn save(env: &Env, batch_id: u64, batch: &SettlementBatch) {
+    env.storage().persistent().set(&(BATCH_PREFIX, batch_id), batch);
+}
+
+fn next_id(env: &Env) -> u64 {
+    let id: u64 = env.storage().instance().get(&counter_key()).unwrap_or(0) + 1;
+    env.storage().instance().set(&counter_key(), &id);
+    id
+}
+
+pub fn is_claimed(env: &Env, batch_id: u64, index: u32) -> bool {
+    env.storage().persistent().has(&(BATCH_CLAIM_PREFIX, batch_id, index))
+}
+
+// ---------------------------------------------------------------------------
+// Merkle proofs
+// ---------------------------------------------------------------------------
+
+/// Hash of the leaf paying `amount` to `seller` at `index` of the batch.
+pub fn leaf_hash(env: &Env, leaf: &BatchLeaf) -> BytesN<32> {
+    let mut data = Bytes::from_array(env, &[0u8]);
+    data.append(&leaf.clone().to_xdr(env));
+    env.crypto().sha256(&data).to_bytes()
+}
+
+fn node_hash(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+    let mut data = Bytes::from_array(env, &[1u8]);
+    data.append(&Bytes::from(left.clone()));
+    data.append(&Bytes::from(right.clone()));
+    env.crypto().sha256(&data).to_bytes()
+}
+
+fn verify(env: &Env, root: &BytesN<32>, leaf: &BatchLeaf, proof: &Vec<BytesN<32>>) -> bool {
+    if proof.len() > MAX_BATCH_PROOF_DEPTH {
+        return false;
+    }
+    let mut node = leaf_hash(env, leaf);
+    let mut position = leaf.index;
+    for sibling in proof.iter() {
+        node = if position & 1 == 0 {
+            node_hash(env, &node, &sibling)
+        } else {
+            node_hash(env, &sibling, &node)
+        };
+        position >>= 1;
+    }
+    node == *root
+}
+
+// ---------------------------------------------------------------------------
+// Lifecycle
+// ---------------------------------------------------------------------------
+
+/// Pull `total` from the operator and record the batch.
+pub fn commit(
+    env: &Env,
+    operator: &Address,
+    currency: &Address,
+    root: &BytesN<32>,
+    total: i128,
+    leaves: u32,
+    claim_window: u64,
+) -> Result<u64, ContractError> {
+    require_positive(total)?;
+    if leaves == 0 || claim_window == 0 {
+        return Err(ContractError::InvalidSettlementBatch);
+    }
+    transfer::send(&token::Client::new(env, currency), operator, &env.current_contract_address(), total)?;
+    storage::add_total_locked(env, currency, total)?;
+    let batch_id = next_id(env);
+    save(
+        env,
+        batch_id,
+        &SettlementBatch {
+            operator: operator.clone(),
+            currency: currency.clone(),
+            root: root.clone(),
+            total,
+            claimed: 0,
+            leaves,
+            claim_deadline: clock::after(env, TimingMode::Timestamp, claim_window)?,
+            reclaimed: false,
+        },
+    );
+    Ok(batch_id)
+}
+
+/// Verify `leaf` against the batch root and pay it to its seller.
+pub fn claim(env: &Env, leaf: &BatchLeaf, proof: &Vec<BytesN<32>>) -> Result<SettlementBatch, ContractError> {
+    let mut batch = get(env, leaf.batch_id)?;
+    if batch.reclaimed || clock::now(env, TimingMode::Timestamp) > batch.claim_deadline {
+        return Err(ContractError::SettlementBatchClosed);
+    }
+    if leaf.index >= batch.leaves {
+        return Err(ContractError::InvalidMerkleProof);
+    }
+    if is_claimed(env, leaf.batch_id, leaf.index) {
+        return Err(ContractError::BatchLeafClaimed);
+    }
+    require_positive(leaf.amount)?;
+    let claimed = batch.claimed.checked_add(leaf.amount).ok_or(ContractError::Overflow)?;
+    if claimed > batch.total {
+        return Err(ContractError::InvalidMerkleProof);
+    }
+    if !verify(env, &batch.root, leaf, proof) {
+        return Err(ContractError::InvalidMerkleProof);
+    }
+
+    env.storage().persistent().set(&(BATCH_CLAIM_PREFIX, leaf.batch_id, leaf.index), &true);
+    batch.claimed = claimed;
+    save(env, leaf.batch_id, &batch);
+    storage::sub_total_locked(env, &batch.currency, leaf.amount)?;
+    transfer::send(
+        &token::Client::new(env, &batch.currency),
+        &env.current_contract_address(),
+        &leaf.seller,
+        leaf.amount,
+    )?;
+    Ok(batch)
+}
+
+/// Return what was not claimed to the operator once the window has closed.
+pub fn reclaim(env: &Env, batch_id: u64) -> Result<(SettlementBatch, i128), ContractError> {
+    let mut batch = get(env, batch_id)?;
+    batch.operator.require_auth();
+    if batch.reclaimed {
+        return Err(ContractError::SettlementBatchClosed);
+    }
+    if clock::now(env, TimingMode::Timestamp) <= batch.claim_deadline {
+        return Err(ContractError::SettlementBatchOpen);
+    }
+    let remaining = batch.total - batch.claimed;
+    batch.reclaimed = true;
+    save(env, batch_id, &batch);
+    storage::sub_total_locked(env, &batch.currency, remaining)?;
+    if remaining > 0 {
+        transfer::send(
+            &token::Client::new(env, &batch.currency),
+            &env.current_contract_address(),
+            &batch.operator,
+            remaining,
+        )?;
+    }
+    Ok((batch, remaining))
+}
@@ -0,0 +1,163 @@
+//! Trade lifecycle state machine.
+//!
+//! Every status change goes through `transition`, which checks the move
+//! against the allowed graph below, closes the trade's pair slot once it
+//! reaches a terminal status, and emits the canonical `status_changed` event.
+//! Entrypoints call `check` up front to reject calls the trade's status
+//! does not allow before doing any other work.
+//!
+//! Hashed trades follow the same graph through `next`.
+//!
+//! A trade under a compliance freeze (`freeze_trade`) is refused by both
+//! `transition` and `begin_settlement` until it is unfrozen.
+//!
+//! Payouts run inside a per-trade settlement guard: `begin_settlement` marks
+//! the trade before any token leaves escrow and `end_settlement` clears it
+//! afterwards, so a token contract or hook calling back into the same
+//! trade mid-payout is refused instead of paying twice.
+//!
+//! | Event   | From                                        | To             |
+//! |---------|---------------------------------------------|----------------|
+//! | Fund    | Created, AwaitingBridge                     | Funded         |
+//! | Complete| Funded                                      | Completed      |
+//! | Hold    | Completed                                   | PendingRelease |
+//! | Settle  | Funded, Completed, PendingRelease, Resolved | Settled        |
+//! | Cancel  | Created                                     | Cancelled      |
+//! | Expire  | Created, AwaitingBridge                     | Expired        |
+//! | Dispute | Funded, Completed, PendingRelease           | Disputed       |
+//! | Rule    | Disputed                                    | Resolved       |
+//! | Reopen  | Resolved                                    | Disputed       |
+//! | Refund  | Funded, Completed, Disputed, PendingRelease | Refunded       |
+//! | Trigger | Funded                                      | Triggered      |
+
+use soroban_sdk::{Address, Env};
+
+use crate::errors::ContractError;
+use crate::events;
+use crate::storage;
+use crate::types::{Trade, TradeStatus};
+
+/// What happened to a trade; determines its next status.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TradeEvent {
+    Fund,
+    Complete,
+    /// Receipt confirmed with a release hold.
+    Hold,
+    /// Escrow paid out: released to the seller side or a ruling executed.
+    Settle,
+    Cancel,
+    Expire,
+    Dispute,
+    /// An arbitrator ruling is recorded.
+    Rule,
+    /// A ruling is reopened or vetoed.
+    Reopen,
+    Refund,
+    Trigger,
+}
+
+impl TradeEvent {
+    fn target(self) -> TradeStatus {
+        match self {
+            TradeEvent::Fund => TradeStatus::Funded,
+            TradeEvent::Complete => TradeStatus::Completed,
+            TradeEvent::Settle => TradeStatus::Settled,
+            TradeEvent::Hold => TradeStatus::PendingRelease,
+            TradeEvent::Cancel => TradeStatus::Cancelled,
+            TradeEvent::Expire => TradeStatus::Expired,
+            TradeEvent::Dispute | TradeEvent::Reopen => TradeStatus::Disputed,
+            TradeEvent::Rule => TradeStatus::Resolved,
+            TradeEvent::Refund => TradeStatus::Refunded,
+            TradeEvent::Trigger => TradeStatus::Triggered,
+        }
+    }
+
+    fn allowed_from(self, status: &TradeStatus) -> bool {
+        use TradeStatus::*;
+        match self {
+            TradeEvent::Fund | TradeEvent::Expire => matches!(status, Created | AwaitingBridge),
+            TradeEvent::Complete | TradeEvent::Trigger => matches!(status, Funded),
+            TradeEvent::Hold => matches!(status, Completed),
+            TradeEvent::Settle => matches!(status, Funded | Completed | PendingRelease | Resolved),
+            TradeEvent::Dispute => matches!(status, Funded | Completed | PendingRelease),
+            TradeEvent::Cancel => matches!(status, Created),
+            TradeEvent::Rule => matches!(status, Disputed),
+            TradeEvent::Reopen => matches!(status, Resolved),
+            TradeEvent::Refund => matches!(status, Funded | Completed | Disputed | PendingRelease),
+        }
+    }
+}
+
+/// Status a trade in `status` moves to on `event`, if the move is allowed.
+pub fn next(status: &TradeStatus, event: TradeEvent) -> Result<TradeStatus, ContractError> {
+    if !allows(status, event) {
+        return Err(ContractError::InvalidStatus);
+    }
+    Ok(event.target())
+}
+
+/// Whether a trade in `status` may undergo `event`.
+pub fn allows(status: &TradeStatus, event: TradeEvent) -> bool {
+    event.allowed_from(status)
+}
+
+/// Reject `event` if the trade's current status does not allow it.
+pub fn check(trade: &Trade, event: TradeEvent) -> Result<(), ContractError> {
+    if !allows(&trade.status, event) {
+        return Err(ContractError::InvalidStatus);
+    }
+    Ok(())
+}
+
+/// Reject any change to a trade under a compliance freeze.
+pub fn require_not_frozen(env: &Env, trade_id: u64) -> Result<(), ContractError> {
+    if storage::is_trade_frozen(env, trade_id) {
+        return Err(ContractError::TradeFrozen);
+    }
+    Ok(())
+}
+
+const SETTLE_GUARD_PREFIX: &str = "SG";
+
+/// Mark `trade` as mid-payout. Fails if it has already left escrow or if a
+/// payout of the same trade is already in progress.
+pub fn begin_settlement(env: &Env, trade: &Trade) -> Result<(), ContractError> {
+    require_not_frozen(env, trade.id)?;
+    if trade.status.is_terminal() {
+        return Err(ContractError::InvalidStatus);
+    }
+    let key = (SETTLE_GUARD_PREFIX, trade.id);
+    if storage::get_temp::<_, bool>(env, &key).is_some() {
+        return Err(ContractError::SettlementInProgress);
+    }
+    // Only needs to outlive the current invocation.
+    storage::set_temp(env, &key, &true, 1);
+    Ok(())
+}
+
+pub fn end_settlement(env: &Env, trade_id: u64) {
+    storage::remove_temp(env, &(SETTLE_GUARD_PREFIX, trade_id));
+}
+
+/// Apply `event` to `trade` on behalf of `actor`. The caller saves the trade.
+pub fn transition(
+    env: &Env,
+    trade: &mut Trade,
+    event: TradeEvent,
+    actor: &Address,
+) -> Result<(), ContractError> {
+    require_not_frozen(env, trade.id)?;
+    check(trade, event)?;
+    let old_status = trade.status.clone();
+    let new_status = event.target();
+    if new_status.is_terminal() {
+        storage::close_pair_trade(env, trade.id, &trade.seller, &trade.buyer);
+    }
+    if old_status == new_status {
+        return Ok(());
+    }
+    trade.status = new_status.clone();
+    events::emit_status_changed(env, trade.id, old_status, new_status, actor.clone());
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{symbol_short, Address, Env, String, Symbol, Vec};
+use soroban_sdk::{symbol_short, Address, Env, Map, String, Symbol, Vec};
 
 use crate::errors::ContractError;
 use crate::types::{
@@ -11,13 +11,8 @@ use crate::types::{
 // Instance storage keys — symbol_short! is the most gas-efficient encoding
 // for keys stored in instance storage (loaded on every contract call).
 // ---------------------------------------------------------------------------
-fn key_init()     -> Symbol { symbol_short!("INIT") }
-fn key_admin()    -> Symbol { symbol_short!("ADMIN") }
-fn key_usdc()     -> Symbol { symbol_short!("USDC") }
-fn key_fee_bps()  -> Symbol { symbol_short!("FEE_BPS") }
-fn key_counter()  -> Symbol { symbol_short!("COUNTER") }
-fn key_acc_fees() -> Symbol { symbol_short!("ACC_FEES") }
 fn key_paused()   -> Symbol { symbol_short!("PAUSED") }
+fn key_pause_fn() -> Symbol { symbol_short!("PAUSE_FN") }
 fn key_tier_cfg() -> Symbol { symbol_short!("TIER_CFG") }
 fn key_tmpl_ctr() -> Symbol { symbol_short!("TMPL_CTR") }
 fn key_version()  -> Symbol { symbol_short!("VERSION") }
@@ -25,6 +20,38 @@ fn key_bridge()   -> Symbol { symbol_short!("BRIDGE") }
 fn key_gov_tkn()  -> Symbol { symbol_short!("GOV_TKN") }
 fn key_prop_ctr() -> Symbol { symbol_short!("PROP_CTR") }
 fn key_glob_lim() -> Symbol { symbol_short!("GLOB_LIM") }
+fn key_case_cap() -> Symbol { symbol_short!("CASE_CAP") }
+fn key_semver()   -> Symbol { symbol_short!("SEMVER") }
+fn key_emrg_wd()  -> Symbol { symbol_short!("EMRG_WD") }
+fn key_brk_cfg()  -> Symbol { symbol_short!("BRK_CFG") }
+fn key_brk_win()  -> Symbol { symbol_short!("BRK_WINS") }
+fn key_brk_trip() -> Symbol { symbol_short!("BRK_TRIP") }
+fn key_reopen()   -> Symbol { symbol_short!("REOPEN") }
+fn key_dsp_bond() -> Symbol { symbol_short!("DSP_BOND") }
+fn key_dsp_resp() -> Symbol { symbol_short!("DSP_RESP") }
+fn key_supv()     -> Symbol { symbol_short!("SUPV") }
+fn key_fund_dl()  -> Symbol { symbol_short!("FUND_DL") }
+fn key_timing()   -> Symbol { symbol_short!("TIMING") }
+fn key_ttl_cfg()  -> Symbol { symbol_short!("TTL_CFG") }
+fn key_fee_cap()  -> Symbol { symbol_short!("FEE_CAP") }
+fn key_fee_min()  -> Symbol { symbol_short!("FEE_MIN") }
+fn key_fee_promo() -> Symbol { symbol_short!("FEE_PROMO") }
+fn key_fee_pend() -> Symbol { symbol_short!("FEE_PEND") }
+fn key_proto_fee() -> Symbol { symbol_short!("PROTO_FEE") }
+fn key_hold() -> Symbol { symbol_short!("HOLD") }
+fn key_pair_cap() -> Symbol { symbol_short!("PAIR_CAP") }
+fn key_arb_threshold() -> Symbol { symbol_short!("ARB_MIN") }
+fn key_cmpl_win() -> Symbol { symbol_short!("CMPL_WIN") }
+fn key_conf_win() -> Symbol { symbol_short!("CONF_WIN") }
+fn key_admin_log() -> Symbol { symbol_short!("ADM_LOG") }
+fn key_fee_wd() -> Symbol { symbol_short!("FEE_WD") }
+fn key_fee_wd_dl() -> Symbol { symbol_short!("FEE_WD_DL") }
+fn key_loyalty() -> Symbol { symbol_short!("LOYALTY") }
+fn key_fee_burn() -> Symbol { symbol_short!("FEE_BURN") }
+fn key_treasury() -> Symbol { symbol_short!("TREASURY") }
+fn key_arb_fee_cap() -> Symbol { symbol_short!("ARB_FEEMX") }
+fn key_keeper() -> Symbol { symbol_short!("KEEPER_BP") }
+fn key_currencies() -> Symbol { symbol_short!("CURRS") }
 
 // ---------------------------------------------------------------------------
 // Persistent storage key prefixes — single-char strings minimise key size,
@@ -38,6 +65,8 @@ const XCHAIN_PREFIX:          &str = "X";
 const INS_PROVIDER_PREFIX:    &str = "IP";
 const INS_POLICY_PREFIX:      &str = "IPL";
 const CURRENCY_FEES_PREFIX:   &str = "CF";
+const LOCKED_PREFIX:          &str = "LK";
+const BURNED_PREFIX:          &str = "BN";
 const USER_COMPLIANCE_PREFIX: &str = "UC";
 const USER_LIMIT_PREFIX:      &str = "UL";
 const JURISDICTION_PREFIX:    &str = "JR";
@@ -50,27 +79,31 @@ const ARB_RATED_PREFIX:       &str = "RT";
 const MULTISIG_VOTE_PREFIX:   &str = "MV";
 const TRADE_PRIVACY_PREFIX:   &str = "TP";
 const DISCLOSURE_PREFIX:      &str = "DC";
+const ARB_CATEGORY_PREFIX:    &str = "AC";
+const TRADE_CATEGORY_PREFIX:  &str = "TC";
+const ARB_CASELOAD_PREFIX:    &str = "AL";
+const ASSIGNMENT_PREFIX:      &str = "AS";
+const TRADE_STATUS_PREFIX:    &str = "TS";
 
 // ---------------------------------------------------------------------------
 // Initialization
 // ---------------------------------------------------------------------------
-use soroban_sdk::{contracttype, Address, Env, String};
+use soroban_sdk::{contracttype, Address, Env, IntoVal, String, TryFromVal, Val};
 
 use crate::{
     errors::ContractError,
-    types::{CrossChainInfo, InsurancePolicy, Trade, UserCompliance},
+    types::{
+        Config, ConfigV1, CrossChainInfo, InsurancePolicy, Trade, TradeStatus, TradeV1, UserCompliance,
+        VersionedTrade,
+        TRADE_SCHEMA_VERSION,
+    },
 };
 
 #[contracttype]
 #[derive(Clone)]
-enum DataKey {
-    Admin,
-    UsdcToken,
-    FeeBps,
-    Initialized,
+pub(crate) enum DataKey {
+    Config,
     Paused,
-    TradeCounter,
-    AccumulatedFees,
     Version,
     GlobalTradeLimit,
     BridgeOracle,
@@ -84,65 +117,162 @@ enum DataKey {
     InsurancePolicy(u64),
 }
 
+// ---------------------------------------------------------------------------
+// Core config — admin, token, fee, trade counter and legacy accumulated fees
+// live in a single instance entry so each call reads them at most once and
+// new fields can be added atomically. Initialization is implied by presence.
+// ---------------------------------------------------------------------------
+
 pub fn is_initialized(env: &Env) -> bool {
-    env.storage().instance().get(&DataKey::Initialized).unwrap_or(false)
+    env.storage().instance().has(&DataKey::Config) || has_legacy_config(env)
+}
+
+pub fn save_config(env: &Env, config: &Config) {
+    env.storage().instance().set(&DataKey::Config, config);
+}
+
+/// Reads a config written before amounts moved to `i128` as well; the next
+/// `save_config` stores it in the current layout.
+pub fn get_config(env: &Env) -> Result<Config, ContractError> {
+    let raw: Val = match env.storage().instance().get(&DataKey::Config) {
+        Some(raw) => raw,
+        None => return legacy_config(env),
+    };
+    if let Ok(config) = Config::try_from_val(env, &raw) {
+        return Ok(config);
+    }
+    ConfigV1::try_from_val(env, &raw)
+        .map(Config::from)
+        .map_err(|_| ContractError::MigrationVersionMismatch)
+}
+
+// ---------------------------------------------------------------------------
+// Legacy core keys — instances initialized before the `Config` entry kept
+// each core field under its own instance key, written either as a short
+// symbol or as a unit variant of the old `DataKey` (which encodes by name, so
+// `LegacyKey` matches it). They are read until the first `save_config`
+// writes the consolidated entry.
+// ---------------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone)]
+enum LegacyKey {
+    Admin,
+    UsdcToken,
+    FeeBps,
+    Initialized,
+    TradeCounter,
+    AccumulatedFees,
+}
+
+fn legacy_get<V: TryFromVal<Env, Val>>(env: &Env, short: Symbol, key: LegacyKey) -> Option<V> {
+    let instance = env.storage().instance();
+    instance.get(&short).or_else(|| instance.get(&key))
+}
+
+fn has_legacy_config(env: &Env) -> bool {
+    let instance = env.storage().instance();
+    instance.get(&symbol_short!("INIT")).unwrap_or(false)
+        || instance.get(&LegacyKey::Initialized).unwrap_or(false)
+}
+
+/// Config assembled from the legacy keys. Escrowed principal was not tracked
+/// then, so `total_locked` starts at zero.
+fn legacy_config(env: &Env) -> Result<Config, ContractError> {
+    if !has_legacy_config(env) {
+        return Err(ContractError::NotInitialized);
+    }
+    let accumulated = decode_amount(env, legacy_get(env, symbol_short!("ACC_FEES"), LegacyKey::AccumulatedFees));
+    Ok(Config {
+        admin: legacy_get(env, symbol_short!("ADMIN"), LegacyKey::Admin).ok_or(ContractError::NotInitialized)?,
+        usdc_token: legacy_get(env, symbol_short!("USDC"), LegacyKey::UsdcToken)
+            .ok_or(ContractError::NotInitialized)?,
+        fee_bps: legacy_get(env, symbol_short!("FEE_BPS"), LegacyKey::FeeBps).ok_or(ContractError::NotInitialized)?,
+        trade_counter: legacy_get(env, symbol_short!("COUNTER"), LegacyKey::TradeCounter).unwrap_or(0),
+        accumulated_fees: accumulated.unwrap_or(0),
+        total_locked: 0,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Amounts — token amounts are stored as `i128`. Entries written before the
+// switch hold `u64` and are widened on read until next rewritten.
+// ---------------------------------------------------------------------------
+
+fn decode_amount(env: &Env, raw: Option<Val>) -> Option<i128> {
+    let raw = raw?;
+    i128::try_from_val(env, &raw)
+        .ok()
+        .or_else(|| u64::try_from_val(env, &raw).ok().map(i128::from))
+}
+
+fn instance_amount<K: IntoVal<Env, Val>>(env: &Env, key: &K) -> Option<i128> {
+    decode_amount(env, env.storage().instance().get(key))
 }
 
-pub fn set_initialized(env: &Env) {
-    env.storage().instance().set(&DataKey::Initialized, &true);
+fn persistent_amount<K: IntoVal<Env, Val>>(env: &Env, key: &K) -> Option<i128> {
+    decode_amount(env, env.storage().persistent().get(key))
 }
 
 // ---------------------------------------------------------------------------
 // Admin
 // ---------------------------------------------------------------------------
 
-pub fn set_admin(env: &Env, admin: &Address) {
-    env.storage().instance().set(&DataKey::Admin, admin);
+pub fn set_admin(env: &Env, admin: &Address) -> Result<(), ContractError> {
+    let mut config = get_config(env)?;
+    config.admin = admin.clone();
+    save_config(env, &config);
+    Ok(())
 }
 
 pub fn get_admin(env: &Env) -> Result<Address, ContractError> {
-    env.storage()
-        .instance()
-        .get(&DataKey::Admin)
-        .ok_or(ContractError::NotInitialized)
+    Ok(get_config(env)?.admin)
 }
 
 // ---------------------------------------------------------------------------
 // USDC Token
 // ---------------------------------------------------------------------------
 
-pub fn set_usdc_token(env: &Env, token: &Address) {
-    env.storage().instance().set(&DataKey::UsdcToken, token);
-}
-
 pub fn get_usdc_token(env: &Env) -> Result<Address, ContractError> {
-    env.storage()
-        .instance()
-        .get(&DataKey::UsdcToken)
-        .ok_or(ContractError::NotInitialized)
+    Ok(get_config(env)?.usdc_token)
 }
 
 // ---------------------------------------------------------------------------
 // Fee BPS
 // ---------------------------------------------------------------------------
 
-pub fn set_fee_bps(env: &Env, fee_bps: u32) {
-    env.storage().instance().set(&DataKey::FeeBps, &fee_bps);
+/// Set the platform rate immediately, discarding any scheduled change.
+pub fn set_fee_bps(env: &Env, fee_bps: u32) -> Result<(), ContractError> {
+    let mut config = get_config(env)?;
+    config.fee_bps = fee_bps;
+    save_config(env, &config);
+    env.storage().instance().remove(&key_fee_pend());
+    Ok(())
 }
 
+/// Platform rate in force at the current ledger: a scheduled change once its
+/// `effective_from` ledger is reached, otherwise the configured rate.
 pub fn get_fee_bps(env: &Env) -> Result<u32, ContractError> {
-    env.storage()
-        .instance()
-        .get(&DataKey::FeeBps)
-        .ok_or(ContractError::NotInitialized)
+    if let Some(pending) = get_pending_fee_change(env) {
+        if env.ledger().sequence() >= pending.effective_from {
+            return Ok(pending.fee_bps);
+        }
+    }
+    Ok(get_config(env)?.fee_bps)
+}
+
+pub fn set_pending_fee_change(env: &Env, pending: &crate::types::PendingFeeChange) {
+    env.storage().instance().set(&key_fee_pend(), pending);
+}
+
+pub fn get_pending_fee_change(env: &Env) -> Option<crate::types::PendingFeeChange> {
+    env.storage().instance().get(&key_fee_pend())
 }
 
 // ---------------------------------------------------------------------------
-// Trade Counter
+// Pause State
 // ---------------------------------------------------------------------------
 
-pub fn set_trade_counter(env: &Env, counter: u64) {
-    env.storage().instance().set(&key_counter(), &counter);
 pub fn set_paused(env: &Env, paused: bool) {
     env.storage().instance().set(&DataKey::Paused, &paused);
 }
@@ -151,83 +281,371 @@ pub fn is_paused(env: &Env) -> bool {
     env.storage().instance().get(&DataKey::Paused).unwrap_or(false)
 }
 
-pub fn set_trade_counter(env: &Env, counter: u64) {
-    env.storage().instance().set(&DataKey::TradeCounter, &counter);
+// ---------------------------------------------------------------------------
+// Trade Counter
+// ---------------------------------------------------------------------------
+
+pub fn get_trade_counter(env: &Env) -> Result<u64, ContractError> {
+    Ok(get_config(env)?.trade_counter)
 }
 
 pub fn increment_trade_counter(env: &Env) -> Result<u64, ContractError> {
-    let next = env
-        .storage()
-        .instance()
-        .get::<_, u64>(&DataKey::TradeCounter)
-        .unwrap_or(0)
+    let mut config = get_config(env)?;
+    config.trade_counter = config
+        .trade_counter
         .checked_add(1)
         .ok_or(ContractError::Overflow)?;
-    env.storage().instance().set(&DataKey::TradeCounter, &next);
-    Ok(next)
+    save_config(env, &config);
+    Ok(config.trade_counter)
 }
 
 // ---------------------------------------------------------------------------
 // Accumulated Fees (legacy single-currency path)
 // ---------------------------------------------------------------------------
 
-pub fn set_accumulated_fees(env: &Env, fees: u64) {
-    env.storage().instance().set(&key_acc_fees(), &fees);
+pub fn set_accumulated_fees(env: &Env, fees: i128) -> Result<(), ContractError> {
+    let mut config = get_config(env)?;
+    config.accumulated_fees = fees;
+    save_config(env, &config);
+    Ok(())
+}
+
+pub fn get_accumulated_fees(env: &Env) -> Result<i128, ContractError> {
+    Ok(get_config(env)?.accumulated_fees)
+}
+
+/// Share of each collected fee, in basis points, that is burned.
+pub fn set_fee_burn_bps(env: &Env, bps: u32) {
+    env.storage().instance().set(&key_fee_burn(), &bps);
+}
+
+pub fn get_fee_burn_bps(env: &Env) -> u32 {
+    env.storage().instance().get(&key_fee_burn()).unwrap_or(0)
+}
+
+/// Share of a trade's fee, in basis points, paid to whoever triggers its timeout.
+pub fn set_keeper_bounty_bps(env: &Env, bps: u32) {
+    env.storage().instance().set(&key_keeper(), &bps);
+}
+
+pub fn get_keeper_bounty_bps(env: &Env) -> u32 {
+    env.storage().instance().get(&key_keeper()).unwrap_or(0)
+}
+
+pub fn set_max_arbitrator_fee_bps(env: &Env, bps: u32) {
+    env.storage().instance().set(&key_arb_fee_cap(), &bps);
 }
 
-pub fn get_accumulated_fees(env: &Env) -> Result<u64, ContractError> {
+pub fn get_max_arbitrator_fee_bps(env: &Env) -> u32 {
     env.storage()
         .instance()
-        .get(&key_acc_fees())
-        .ok_or(ContractError::NotInitialized)
+        .get(&key_arb_fee_cap())
+        .unwrap_or(crate::types::DEFAULT_MAX_ARBITRATOR_FEE_BPS)
 }
 
-/// Atomically add `delta` to the legacy accumulated-fees counter in a single
-/// read-modify-write, avoiding a separate get + set pair.
-pub fn add_accumulated_fees(env: &Env, delta: u64) -> Result<(), ContractError> {
-    let current: u64 = env.storage().instance().get(&key_acc_fees()).unwrap_or(0);
-    let new_fees = current.checked_add(delta).ok_or(ContractError::Overflow)?;
-    env.storage().instance().set(&key_acc_fees(), &new_fees);
+/// Contract that receives fees at settlement when set.
+pub fn set_treasury(env: &Env, treasury: &Address) {
+    env.storage().instance().set(&key_treasury(), treasury);
+}
+
+pub fn remove_treasury(env: &Env) {
+    env.storage().instance().remove(&key_treasury());
+}
+
+pub fn get_treasury(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&key_treasury())
+}
+
+/// Fees burned in `currency`. Burns from before totals were kept per
+/// currency are not included.
+pub fn get_total_burned(env: &Env, currency: &Address) -> i128 {
+    persistent_amount(env, &(BURNED_PREFIX, currency)).unwrap_or(0)
+}
+
+pub fn add_total_burned(env: &Env, currency: &Address, delta: i128) -> Result<(), ContractError> {
+    let total = get_total_burned(env, currency).checked_add(delta).ok_or(ContractError::Overflow)?;
+    env.storage().persistent().set(&(BURNED_PREFIX, currency), &total);
     Ok(())
 }
 
+/// Atomically add `delta` to the legacy accumulated-fees counter in a single
+/// read-modify-write of the config entry.
+pub fn add_accumulated_fees(env: &Env, delta: i128) -> Result<i128, ContractError> {
+    let mut config = get_config(env)?;
+    config.accumulated_fees = config
+        .accumulated_fees
+        .checked_add(delta)
+        .ok_or(ContractError::Overflow)?;
+    save_config(env, &config);
+    Ok(config.accumulated_fees)
+}
+
+// ---------------------------------------------------------------------------
+// Total escrowed liability
+// ---------------------------------------------------------------------------
+
+/// Amount of `currency` held in escrow. Only counts movements since
+/// escrow was tracked per currency.
+pub fn get_locked(env: &Env, currency: &Address) -> i128 {
+    persistent_amount(env, &(LOCKED_PREFIX, currency)).unwrap_or(0)
+}
+
+fn set_locked(env: &Env, currency: &Address, amount: i128) {
+    env.storage().persistent().set(&(LOCKED_PREFIX, currency), &amount);
+}
+
+/// Every currency that has been held in escrow since currencies were
+/// tracked, in the order first seen.
+pub fn get_currencies(env: &Env) -> Vec<Address> {
+    env.storage().instance().get(&key_currencies()).unwrap_or(Vec::new(env))
+}
+
+fn register_currency(env: &Env, currency: &Address) {
+    let mut currencies = get_currencies(env);
+    if !currencies.contains(currency) {
+        currencies.push_back(currency.clone());
+        env.storage().instance().set(&key_currencies(), &currencies);
+    }
+}
+
+/// Record `amount` of `currency` entering escrow when a trade is funded.
+pub fn add_total_locked(env: &Env, currency: &Address, amount: i128) -> Result<i128, ContractError> {
+    let locked = get_locked(env, currency).checked_add(amount).ok_or(ContractError::Overflow)?;
+    register_currency(env, currency);
+    set_locked(env, currency, locked);
+    Ok(locked)
+}
+
+/// Record `amount` of `currency` leaving escrow on release or refund.
+/// Saturates at zero so trades funded off-chain (bridge deposits) cannot
+/// underflow the counter.
+pub fn sub_total_locked(env: &Env, currency: &Address, amount: i128) -> Result<i128, ContractError> {
+    let locked = get_locked(env, currency).saturating_sub(amount).max(0);
+    set_locked(env, currency, locked);
+    Ok(locked)
+}
+
 // ---------------------------------------------------------------------------
 // Per-currency accumulated fees
 // ---------------------------------------------------------------------------
 
-pub fn get_currency_fees(env: &Env, currency: &Address) -> u64 {
-    let key = (CURRENCY_FEES_PREFIX, currency);
-    env.storage().persistent().get(&key).unwrap_or(0)
+/// Fees available for withdrawal in `currency`: the config's accumulated
+/// counter for the platform token, the per-currency entry for any other.
+pub fn get_withdrawable_fees(env: &Env, currency: &Address) -> Result<i128, ContractError> {
+    if *currency == get_usdc_token(env)? {
+        get_accumulated_fees(env)
+    } else {
+        Ok(get_currency_fees(env, currency))
+    }
+}
+
+/// Book `amount` as fees withdrawable in `currency`.
+pub fn add_withdrawable_fees(env: &Env, currency: &Address, amount: i128) -> Result<(), ContractError> {
+    if *currency == get_usdc_token(env)? {
+        add_accumulated_fees(env, amount).map(|_| ())
+    } else {
+        add_currency_fees(env, currency, amount)
+    }
+}
+
+/// Take `amount` out of the fees withdrawable in `currency`.
+pub fn sub_withdrawable_fees(env: &Env, currency: &Address, amount: i128) -> Result<(), ContractError> {
+    let available = get_withdrawable_fees(env, currency)?;
+    if amount > available {
+        return Err(ContractError::NoFeesToWithdraw);
+    }
+    if *currency == get_usdc_token(env)? {
+        set_accumulated_fees(env, available - amount)
+    } else {
+        set_currency_fees(env, currency, available - amount);
+        Ok(())
+    }
 }
 
-pub fn set_currency_fees(env: &Env, currency: &Address, fees: u64) {
+pub fn get_currency_fees(env: &Env, currency: &Address) -> i128 {
+    persistent_amount(env, &(CURRENCY_FEES_PREFIX, currency)).unwrap_or(0)
+}
+
+pub fn set_currency_fees(env: &Env, currency: &Address, fees: i128) {
     let key = (CURRENCY_FEES_PREFIX, currency);
     env.storage().persistent().set(&key, &fees);
 }
 
 /// Atomically add `delta` to per-currency fees in a single read-modify-write,
 /// avoiding a separate `get_currency_fees` + `set_currency_fees` pair.
-pub fn add_currency_fees(env: &Env, currency: &Address, delta: u64) -> Result<(), ContractError> {
+pub fn add_currency_fees(env: &Env, currency: &Address, delta: i128) -> Result<(), ContractError> {
     let key = (CURRENCY_FEES_PREFIX, currency);
-    let current: u64 = env.storage().persistent().get(&key).unwrap_or(0);
+    let current = persistent_amount(env, &key).unwrap_or(0);
     let new_fees = current.checked_add(delta).ok_or(ContractError::Overflow)?;
     env.storage().persistent().set(&key, &new_fees);
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// Temporary storage
+// ---------------------------------------------------------------------------
+// Short-lived data (rate-limit windows and other ephemeral flags) goes in
+// temporary storage: cheaper rent, and entries simply disappear once their
+// TTL runs out instead of lingering in persistent state.
+
+/// Write `val` to temporary storage, keeping it live for at least `ledgers`.
+pub fn set_temp<K, V>(env: &Env, key: &K, val: &V, ledgers: u32)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val>,
+{
+    let temp = env.storage().temporary();
+    temp.set(key, val);
+    temp.extend_ttl(key, ledgers, ledgers);
+}
+
+/// Read a temporary entry; `None` if it was never written or has expired.
+pub fn get_temp<K, V>(env: &Env, key: &K) -> Option<V>
+where
+    K: IntoVal<Env, Val>,
+    V: TryFromVal<Env, Val>,
+{
+    env.storage().temporary().get(key)
+}
+
+pub fn remove_temp<K>(env: &Env, key: &K)
+where
+    K: IntoVal<Env, Val>,
+{
+    env.storage().temporary().remove(key);
+}
+
+// ---------------------------------------------------------------------------
+// TTL
+// ---------------------------------------------------------------------------
+
+pub fn set_ttl_config(env: &Env, config: &crate::types::TtlConfig) {
+    env.storage().instance().set(&key_ttl_cfg(), config);
+}
+
+pub fn get_ttl_config(env: &Env) -> crate::types::TtlConfig {
+    env.storage().instance().get(&key_ttl_cfg()).unwrap_or(crate::types::TtlConfig {
+        threshold: crate::types::DEFAULT_TTL_THRESHOLD,
+        extend_to: crate::types::DEFAULT_TTL_EXTEND_TO,
+    })
+}
+
+/// Per-trade side entries keyed `(prefix, trade_id)` that settlement reads,
+/// so they must live as long as the trade itself. Descriptive records (memo,
+/// terms hash, delivery proof, message anchors, order structure, category,
+/// resolution reason) are left to their own TTL: no payout path reads them
+/// and losing one only drops history, never funds.
+const TRADE_SIDE_PREFIXES: [&str; 21] = [
+    TRADE_FREEZE_PREFIX,
+    PAYEE_PREFIX,
+    PAYOUT_ASSIGNEE_PREFIX,
+    DEADLINES_PREFIX,
+    DISPUTE_RAISER_PREFIX,
+    HELD_UNTIL_PREFIX,
+    STREAM_PREFIX,
+    CONTRIB_PREFIX,
+    LINE_ITEMS_PREFIX,
+    SPLIT_PREFIX,
+    BASKET_PREFIX,
+    QUOTE_PREFIX,
+    REFUND_REQ_PREFIX,
+    PENDING_RES_PREFIX,
+    REOPEN_BOND_PREFIX,
+    DISPUTE_BONDS_PREFIX,
+    DISPUTE_RESP_PREFIX,
+    crate::acceptance::ACCEPTANCE_PREFIX,
+    crate::donation::DONATION_PREFIX,
+    crate::partners::TRADE_PARTNER_PREFIX,
+    crate::payout_swap::PAYOUT_SWAP_PREFIX,
+];
+
+/// Extend the trade entry, its status mirror, every side entry in
+/// `TRADE_SIDE_PREFIXES` and the contract instance so an active trade cannot
+/// be archived mid-flow, nor lose the records its settlement depends on.
+fn bump_trade(env: &Env, trade_id: u64) {
+    let ttl = get_ttl_config(env);
+    let persistent = env.storage().persistent();
+    persistent.extend_ttl(&DataKey::Trade(trade_id), ttl.threshold, ttl.extend_to);
+    let status_key = (TRADE_STATUS_PREFIX, trade_id);
+    if persistent.has(&status_key) {
+        persistent.extend_ttl(&status_key, ttl.threshold, ttl.extend_to);
+    }
+    for prefix in TRADE_SIDE_PREFIXES {
+        let key = (prefix, trade_id);
+        if persistent.has(&key) {
+            persistent.extend_ttl(&key, ttl.threshold, ttl.extend_to);
+        }
+    }
+    env.storage().instance().extend_ttl(ttl.threshold, ttl.extend_to);
+}
+
 // ---------------------------------------------------------------------------
 // Trades
 // ---------------------------------------------------------------------------
 
+/// Trades are always written in the newest envelope variant. The status is
+/// mirrored under its own small key so pollers can read it without decoding
+/// the full trade.
 pub fn save_trade(env: &Env, trade_id: u64, trade: &Trade) {
-    env.storage().persistent().set(&DataKey::Trade(trade_id), trade);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Trade(trade_id), &VersionedTrade::V2(trade.clone()));
+    let status_key = (TRADE_STATUS_PREFIX, trade_id);
+    env.storage().persistent().set(&status_key, &trade.status);
+    bump_trade(env, trade_id);
+}
+
+pub fn has_trade(env: &Env, trade_id: u64) -> bool {
+    env.storage().persistent().has(&DataKey::Trade(trade_id))
+}
+
+/// Status of a trade, read from the mirrored status key. Falls back to the
+/// full entry for trades last written before the mirror existed.
+pub fn get_trade_status(env: &Env, trade_id: u64) -> Result<TradeStatus, ContractError> {
+    let status_key = (TRADE_STATUS_PREFIX, trade_id);
+    if let Some(status) = env.storage().persistent().get(&status_key) {
+        bump_trade(env, trade_id);
+        return Ok(status);
+    }
+    Ok(get_trade(env, trade_id)?.status)
 }
 
 pub fn get_trade(env: &Env, trade_id: u64) -> Result<Trade, ContractError> {
-    env.storage()
+    Ok(get_versioned_trade(env, trade_id)?.into_current())
+}
+
+/// Read a trade entry in whatever layout it was persisted with. Entries written
+/// before the envelope existed are bare schema 1 values and decode as `V1`.
+pub fn get_versioned_trade(env: &Env, trade_id: u64) -> Result<VersionedTrade, ContractError> {
+    let raw: Val = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Trade(trade_id))
+        .ok_or(ContractError::TradeNotFound)?;
+    bump_trade(env, trade_id);
+    if let Ok(entry) = VersionedTrade::try_from_val(env, &raw) {
+        return Ok(entry);
+    }
+    TradeV1::try_from_val(env, &raw)
+        .map(VersionedTrade::V1)
+        .map_err(|_| ContractError::MigrationVersionMismatch)
+}
+
+/// Schema version of the stored entry; bare pre-envelope entries report 0.
+pub fn get_trade_schema_version(env: &Env, trade_id: u64) -> Result<u32, ContractError> {
+    let raw: Val = env
+        .storage()
         .persistent()
         .get(&DataKey::Trade(trade_id))
-        .ok_or(ContractError::TradeNotFound)
+        .ok_or(ContractError::TradeNotFound)?;
+    Ok(VersionedTrade::try_from_val(env, &raw)
+        .map(|entry| entry.version())
+        .unwrap_or(0))
+}
+
+/// Returns true if the stored entry is already in the current envelope format.
+pub fn is_trade_current(env: &Env, trade_id: u64) -> Result<bool, ContractError> {
+    Ok(get_trade_schema_version(env, trade_id)? == TRADE_SCHEMA_VERSION)
 }
 
 // ---------------------------------------------------------------------------
@@ -275,6 +693,39 @@ pub fn get_arbitrator_fee(env: &Env, arbitrator: &Address) -> i128 {
     env.storage().persistent().get(&key).unwrap_or(0)
 }
 
+const ARB_EARNINGS_PREFIX: &str = "AE";
+const ARB_FEE_BPS_PREFIX: &str = "AB";
+
+/// Share of the disputed amount, in bps, an arbitrator charges for a ruling.
+pub fn save_arbitrator_fee_bps(env: &Env, arbitrator: &Address, bps: u32) {
+    let key = (ARB_FEE_BPS_PREFIX, arbitrator);
+    env.storage().persistent().set(&key, &bps);
+}
+
+pub fn get_arbitrator_fee_bps(env: &Env, arbitrator: &Address) -> u32 {
+    let key = (ARB_FEE_BPS_PREFIX, arbitrator);
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+pub fn remove_arbitrator_fee_bps(env: &Env, arbitrator: &Address) {
+    let key = (ARB_FEE_BPS_PREFIX, arbitrator);
+    env.storage().persistent().remove(&key);
+}
+
+/// Fees an arbitrator has earned from resolved disputes and not yet withdrawn.
+pub fn get_arbitrator_earnings(env: &Env, arbitrator: &Address) -> i128 {
+    persistent_amount(env, &(ARB_EARNINGS_PREFIX, arbitrator)).unwrap_or(0)
+}
+
+pub fn set_arbitrator_earnings(env: &Env, arbitrator: &Address, amount: i128) {
+    let key = (ARB_EARNINGS_PREFIX, arbitrator);
+    if amount == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &amount);
+    }
+}
+
 /// Append `arbitrator` to the persistent registry list (no-op if already present).
 pub fn add_to_arbitrator_list(env: &Env, arbitrator: &Address) {
     let mut list: soroban_sdk::Vec<Address> = env
@@ -316,6 +767,90 @@ pub fn get_arbitrator_list(env: &Env) -> soroban_sdk::Vec<Address> {
         .unwrap_or_else(|| soroban_sdk::Vec::new(env))
 }
 
+// ---------------------------------------------------------------------------
+// Arbitrator categories / specializations
+// ---------------------------------------------------------------------------
+
+/// Replace the category tags an arbitrator specializes in.
+pub fn save_arbitrator_categories(env: &Env, arbitrator: &Address, categories: &Vec<Symbol>) {
+    let key = (ARB_CATEGORY_PREFIX, arbitrator);
+    env.storage().persistent().set(&key, categories);
+}
+
+/// Return the category tags for an arbitrator (empty if none declared).
+pub fn get_arbitrator_categories(env: &Env, arbitrator: &Address) -> Vec<Symbol> {
+    let key = (ARB_CATEGORY_PREFIX, arbitrator);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn remove_arbitrator_categories(env: &Env, arbitrator: &Address) {
+    let key = (ARB_CATEGORY_PREFIX, arbitrator);
+    env.storage().persistent().remove(&key);
+}
+
+pub fn save_trade_category(env: &Env, trade_id: u64, category: &Symbol) {
+    let key = (TRADE_CATEGORY_PREFIX, trade_id);
+    env.storage().persistent().set(&key, category);
+}
+
+pub fn get_trade_category(env: &Env, trade_id: u64) -> Option<Symbol> {
+    let key = (TRADE_CATEGORY_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+// ---------------------------------------------------------------------------
+// Arbitrator caseload
+// ---------------------------------------------------------------------------
+
+/// Maximum open disputes per arbitrator. 0 means unlimited.
+pub fn set_arbitrator_caseload_cap(env: &Env, cap: u32) {
+    env.storage().instance().set(&key_case_cap(), &cap);
+}
+
+pub fn get_arbitrator_caseload_cap(env: &Env) -> u32 {
+    env.storage().instance().get(&key_case_cap()).unwrap_or(0)
+}
+
+/// Number of disputes currently open with `arbitrator`.
+pub fn get_arbitrator_caseload(env: &Env, arbitrator: &Address) -> u32 {
+    let key = (ARB_CASELOAD_PREFIX, arbitrator);
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+pub fn increment_arbitrator_caseload(env: &Env, arbitrator: &Address) -> Result<u32, ContractError> {
+    let key = (ARB_CASELOAD_PREFIX, arbitrator);
+    let next = get_arbitrator_caseload(env, arbitrator)
+        .checked_add(1)
+        .ok_or(ContractError::Overflow)?;
+    env.storage().persistent().set(&key, &next);
+    Ok(next)
+}
+
+/// Saturates at zero so a resolution can never underflow the counter.
+pub fn decrement_arbitrator_caseload(env: &Env, arbitrator: &Address) -> u32 {
+    let key = (ARB_CASELOAD_PREFIX, arbitrator);
+    let next = get_arbitrator_caseload(env, arbitrator).saturating_sub(1);
+    env.storage().persistent().set(&key, &next);
+    next
+}
+
+// ---------------------------------------------------------------------------
+// Arbitrator assignment acceptance
+// ---------------------------------------------------------------------------
+
+pub fn save_assignment(env: &Env, trade_id: u64, assignment: &crate::types::ArbitratorAssignment) {
+    let key = (ASSIGNMENT_PREFIX, trade_id);
+    env.storage().persistent().set(&key, assignment);
+}
+
+pub fn get_assignment(env: &Env, trade_id: u64) -> Option<crate::types::ArbitratorAssignment> {
+    let key = (ASSIGNMENT_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
 // ---------------------------------------------------------------------------
 // Multi-Signature Arbitration Votes
 // ---------------------------------------------------------------------------
@@ -369,6 +904,15 @@ pub fn is_paused(env: &Env) -> bool {
     env.storage().instance().get(&key_paused()).unwrap_or(false)
 }
 
+/// `PAUSE_*` bits of the capabilities currently paused on their own.
+pub fn set_paused_functions(env: &Env, flags: u32) {
+    env.storage().instance().set(&key_pause_fn(), &flags);
+}
+
+pub fn get_paused_functions(env: &Env) -> u32 {
+    env.storage().instance().get(&key_pause_fn()).unwrap_or(0)
+}
+
 // ---------------------------------------------------------------------------
 // Tier Config
 // ---------------------------------------------------------------------------
@@ -419,17 +963,14 @@ pub fn get_user_compliance(env: &Env, user: &Address) -> Option<UserCompliance>
         .get(&DataKey::UserCompliance(user.clone()))
 }
 
-pub fn set_user_trade_limit(env: &Env, user: &Address, limit: u64) {
+pub fn set_user_trade_limit(env: &Env, user: &Address, limit: i128) {
     env.storage()
         .persistent()
         .set(&DataKey::UserTradeLimit(user.clone()), &limit);
 }
 
-pub fn get_user_trade_limit(env: &Env, user: &Address) -> u64 {
-    env.storage()
-        .persistent()
-        .get(&DataKey::UserTradeLimit(user.clone()))
-        .unwrap_or(0)
+pub fn get_user_trade_limit(env: &Env, user: &Address) -> i128 {
+    persistent_amount(env, &DataKey::UserTradeLimit(user.clone())).unwrap_or(0)
 }
 
 // ---------------------------------------------------------------------------
@@ -450,59 +991,159 @@ pub fn is_jurisdiction_allowed(env: &Env, jurisdiction: &String) -> bool {
 // Global Trade Limit
 // ---------------------------------------------------------------------------
 
-pub fn set_global_trade_limit(env: &Env, limit: u64) {
+pub fn set_global_trade_limit(env: &Env, limit: i128) {
     env.storage().instance().set(&key_glob_lim(), &limit);
 }
 
-pub fn get_global_trade_limit(env: &Env) -> u64 {
-    env.storage().instance().get(&key_glob_lim()).unwrap_or(u64::MAX)
+pub fn get_global_trade_limit(env: &Env) -> i128 {
+    instance_amount(env, &key_glob_lim()).unwrap_or(i128::MAX)
 }
 
 // ---------------------------------------------------------------------------
-// Trade Templates
+// Fee schedule
 // ---------------------------------------------------------------------------
 
-pub fn get_template_counter(env: &Env) -> u64 {
-    env.storage().instance().get(&key_tmpl_ctr()).unwrap_or(0)
+/// Maximum fee charged on a single trade. Zero means uncapped.
+pub fn set_fee_cap(env: &Env, cap: i128) {
+    env.storage().instance().set(&key_fee_cap(), &cap);
 }
 
-pub fn increment_template_counter(env: &Env) -> Result<u64, ContractError> {
-    let next = get_template_counter(env)
-        .checked_add(1)
-        .ok_or(ContractError::Overflow)?;
-    env.storage().instance().set(&key_tmpl_ctr(), &next);
-    Ok(next)
+pub fn get_fee_cap(env: &Env) -> i128 {
+    instance_amount(env, &key_fee_cap()).unwrap_or(0)
 }
 
-pub fn save_template(env: &Env, template_id: u64, template: &TradeTemplate) {
-    let key = (TEMPLATE_PREFIX, template_id);
-    env.storage().persistent().set(&key, template);
+/// Minimum fee charged on any trade. Zero means no floor.
+pub fn set_min_fee(env: &Env, min: i128) {
+    env.storage().instance().set(&key_fee_min(), &min);
 }
 
-pub fn get_template(env: &Env, template_id: u64) -> Result<TradeTemplate, ContractError> {
-    let key = (TEMPLATE_PREFIX, template_id);
-    env.storage()
-        .persistent()
-        .get(&key)
-        .ok_or(ContractError::TemplateNotFound)
+pub fn get_min_fee(env: &Env) -> i128 {
+    instance_amount(env, &key_fee_min()).unwrap_or(0)
 }
 
-// ---------------------------------------------------------------------------
-// Subscriptions
-// ---------------------------------------------------------------------------
+pub fn set_protocol_fee_share(env: &Env, share: &crate::types::ProtocolFeeShare) {
+    env.storage().instance().set(&key_proto_fee(), share);
+}
 
-pub fn save_subscription(env: &Env, subscriber: &Address, sub: &Subscription) {
-    let key = (SUBSCRIPTION_PREFIX, subscriber);
-    env.storage().persistent().set(&key, sub);
+pub fn get_protocol_fee_share(env: &Env) -> Option<crate::types::ProtocolFeeShare> {
+    env.storage().instance().get(&key_proto_fee())
 }
 
-pub fn get_subscription(env: &Env, subscriber: &Address) -> Option<Subscription> {
-    let key = (SUBSCRIPTION_PREFIX, subscriber);
+const TOKEN_FEE_PREFIX: &str = "TF";
+
+/// Fee rate override for trades settled in `token`.
+pub fn set_token_fee_bps(env: &Env, token: &Address, fee_bps: u32) {
+    let key = (TOKEN_FEE_PREFIX, token.clone());
+    env.storage().persistent().set(&key, &fee_bps);
+}
+
+pub fn get_token_fee_bps(env: &Env, token: &Address) -> Option<u32> {
+    let key = (TOKEN_FEE_PREFIX, token.clone());
     env.storage().persistent().get(&key)
 }
 
-pub fn remove_subscription(env: &Env, subscriber: &Address) {
-    let key = (SUBSCRIPTION_PREFIX, subscriber);
+pub fn remove_token_fee_bps(env: &Env, token: &Address) {
+    let key = (TOKEN_FEE_PREFIX, token.clone());
+    env.storage().persistent().remove(&key);
+}
+
+pub fn set_promo_window(env: &Env, window: &crate::types::PromoWindow) {
+    env.storage().instance().set(&key_fee_promo(), window);
+}
+
+pub fn get_promo_window(env: &Env) -> Option<crate::types::PromoWindow> {
+    env.storage().instance().get(&key_fee_promo())
+}
+
+pub fn remove_promo_window(env: &Env) {
+    env.storage().instance().remove(&key_fee_promo());
+}
+
+// ---------------------------------------------------------------------------
+// Volume circuit breaker
+// ---------------------------------------------------------------------------
+
+pub fn set_volume_breaker(env: &Env, config: &crate::types::VolumeBreakerConfig) {
+    env.storage().instance().set(&key_brk_cfg(), config);
+}
+
+pub fn get_volume_breaker(env: &Env) -> Option<crate::types::VolumeBreakerConfig> {
+    env.storage().instance().get(&key_brk_cfg())
+}
+
+pub fn remove_volume_breaker(env: &Env) {
+    env.storage().instance().remove(&key_brk_cfg());
+}
+
+/// Running volume per currency. It is only meaningful for one window, so it
+/// lives in temporary storage and is dropped by the network once the last
+/// open window passes.
+pub fn set_volume_windows(env: &Env, windows: &Map<Address, crate::types::VolumeWindow>, ledgers: u32) {
+    set_temp(env, &key_brk_win(), windows, ledgers);
+}
+
+pub fn get_volume_windows(env: &Env) -> Map<Address, crate::types::VolumeWindow> {
+    get_temp(env, &key_brk_win()).unwrap_or(Map::new(env))
+}
+
+pub fn clear_volume_windows(env: &Env) {
+    remove_temp(env, &key_brk_win());
+}
+
+/// Tripped state must survive the window expiring, so it stays in instance storage.
+pub fn set_breaker_tripped(env: &Env, tripped: bool) {
+    env.storage().instance().set(&key_brk_trip(), &tripped);
+}
+
+pub fn is_breaker_tripped(env: &Env) -> bool {
+    env.storage().instance().get(&key_brk_trip()).unwrap_or(false)
+}
+
+// ---------------------------------------------------------------------------
+// Trade Templates
+// ---------------------------------------------------------------------------
+
+pub fn get_template_counter(env: &Env) -> u64 {
+    env.storage().instance().get(&key_tmpl_ctr()).unwrap_or(0)
+}
+
+pub fn increment_template_counter(env: &Env) -> Result<u64, ContractError> {
+    let next = get_template_counter(env)
+        .checked_add(1)
+        .ok_or(ContractError::Overflow)?;
+    env.storage().instance().set(&key_tmpl_ctr(), &next);
+    Ok(next)
+}
+
+pub fn save_template(env: &Env, template_id: u64, template: &TradeTemplate) {
+    let key = (TEMPLATE_PREFIX, template_id);
+    env.storage().persistent().set(&key, template);
+}
+
+pub fn get_template(env: &Env, template_id: u64) -> Result<TradeTemplate, ContractError> {
+    let key = (TEMPLATE_PREFIX, template_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .ok_or(ContractError::TemplateNotFound)
+}
+
+// ---------------------------------------------------------------------------
+// Subscriptions
+// ---------------------------------------------------------------------------
+
+pub fn save_subscription(env: &Env, subscriber: &Address, sub: &Subscription) {
+    let key = (SUBSCRIPTION_PREFIX, subscriber);
+    env.storage().persistent().set(&key, sub);
+}
+
+pub fn get_subscription(env: &Env, subscriber: &Address) -> Option<Subscription> {
+    let key = (SUBSCRIPTION_PREFIX, subscriber);
+    env.storage().persistent().get(&key)
+}
+
+pub fn remove_subscription(env: &Env, subscriber: &Address) {
+    let key = (SUBSCRIPTION_PREFIX, subscriber);
     env.storage().persistent().remove(&key);
 }
 
@@ -596,29 +1237,6 @@ pub fn get_global_trade_limit(env: &Env) -> u64 {
         .unwrap_or(u64::MAX)
 }
 
-pub fn set_accumulated_fees(env: &Env, fees: u64) {
-    env.storage().instance().set(&DataKey::AccumulatedFees, &fees);
-}
-
-pub fn get_accumulated_fees(env: &Env) -> Result<u64, ContractError> {
-    env.storage()
-        .instance()
-        .get(&DataKey::AccumulatedFees)
-        .ok_or(ContractError::NotInitialized)
-}
-
-pub fn add_accumulated_fees(env: &Env, delta: u64) -> Result<u64, ContractError> {
-    let updated = env
-        .storage()
-        .instance()
-        .get::<_, u64>(&DataKey::AccumulatedFees)
-        .unwrap_or(0)
-        .checked_add(delta)
-        .ok_or(ContractError::Overflow)?;
-    env.storage().instance().set(&DataKey::AccumulatedFees, &updated);
-    Ok(updated)
-}
-
 pub fn mark_rated(env: &Env, trade_id: u64, rater: &Address) {
     let key = (ARB_RATED_PREFIX, trade_id, rater);
     env.storage().persistent().set(&key, &true);
@@ -640,6 +1258,847 @@ pub fn set_version(env: &Env, version: u32) {
     env.storage().instance().set(&key_version(), &version);
 }
 
+// ---------------------------------------------------------------------------
+// Contract semantic version
+// ---------------------------------------------------------------------------
+
+pub fn set_contract_version(env: &Env, version: &crate::types::ContractVersion) {
+    env.storage().instance().set(&key_semver(), version);
+}
+
+pub fn get_contract_version(env: &Env) -> Option<crate::types::ContractVersion> {
+    env.storage().instance().get(&key_semver())
+}
+
+// ---------------------------------------------------------------------------
+// Trade memo
+// ---------------------------------------------------------------------------
+
+const MEMO_PREFIX: &str = "MM";
+
+pub fn save_trade_memo(env: &Env, trade_id: u64, memo: &String) {
+    let key = (MEMO_PREFIX, trade_id);
+    env.storage().persistent().set(&key, memo);
+}
+
+pub fn get_trade_memo(env: &Env, trade_id: u64) -> Option<String> {
+    let key = (MEMO_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+// ---------------------------------------------------------------------------
+// Dual-authorized terms
+// ---------------------------------------------------------------------------
+
+const TERMS_HASH_PREFIX: &str = "TH";
+
+pub fn save_terms_hash(env: &Env, trade_id: u64, terms_hash: &soroban_sdk::BytesN<32>) {
+    let key = (TERMS_HASH_PREFIX, trade_id);
+    env.storage().persistent().set(&key, terms_hash);
+}
+
+pub fn get_terms_hash(env: &Env, trade_id: u64) -> Option<soroban_sdk::BytesN<32>> {
+    let key = (TERMS_HASH_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+// ---------------------------------------------------------------------------
+// Trade freezes
+// ---------------------------------------------------------------------------
+
+const TRADE_FREEZE_PREFIX: &str = "FZ";
+
+pub fn save_trade_freeze(env: &Env, trade_id: u64, freeze: &crate::types::TradeFreeze) {
+    env.storage().persistent().set(&(TRADE_FREEZE_PREFIX, trade_id), freeze);
+}
+
+pub fn get_trade_freeze(env: &Env, trade_id: u64) -> Option<crate::types::TradeFreeze> {
+    env.storage().persistent().get(&(TRADE_FREEZE_PREFIX, trade_id))
+}
+
+pub fn is_trade_frozen(env: &Env, trade_id: u64) -> bool {
+    env.storage().persistent().has(&(TRADE_FREEZE_PREFIX, trade_id))
+}
+
+pub fn remove_trade_freeze(env: &Env, trade_id: u64) {
+    env.storage().persistent().remove(&(TRADE_FREEZE_PREFIX, trade_id));
+}
+
+// ---------------------------------------------------------------------------
+// Trade payee
+// ---------------------------------------------------------------------------
+
+const PAYEE_PREFIX: &str = "PY";
+
+pub fn save_trade_payee(env: &Env, trade_id: u64, payee: &Address) {
+    let key = (PAYEE_PREFIX, trade_id);
+    env.storage().persistent().set(&key, payee);
+}
+
+pub fn get_trade_payee(env: &Env, trade_id: u64) -> Option<Address> {
+    let key = (PAYEE_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+// ---------------------------------------------------------------------------
+// Payout assignment
+// ---------------------------------------------------------------------------
+
+const PAYOUT_ASSIGNEE_PREFIX: &str = "PA";
+
+pub fn save_payout_assignee(env: &Env, trade_id: u64, assignee: &Address) {
+    let key = (PAYOUT_ASSIGNEE_PREFIX, trade_id);
+    env.storage().persistent().set(&key, assignee);
+}
+
+pub fn get_payout_assignee(env: &Env, trade_id: u64) -> Option<Address> {
+    let key = (PAYOUT_ASSIGNEE_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+// ---------------------------------------------------------------------------
+// Funding deadline
+// ---------------------------------------------------------------------------
+
+const CREATED_LEDGER_PREFIX: &str = "CL";
+
+/// Ledgers a `Created` trade may stay unfunded. Zero disables expiry.
+pub fn set_funding_deadline(env: &Env, ledgers: u32) {
+    env.storage().instance().set(&key_fund_dl(), &ledgers);
+}
+
+pub fn get_funding_deadline(env: &Env) -> u32 {
+    env.storage().instance().get(&key_fund_dl()).unwrap_or(0)
+}
+
+/// Point on the deadline clock at which the trade was created.
+pub fn save_created_at(env: &Env, trade_id: u64, at: u64) {
+    let key = (CREATED_LEDGER_PREFIX, trade_id);
+    env.storage().persistent().set(&key, &at);
+}
+
+/// Timing mode chosen for deadline features, if the admin has set one.
+pub fn set_timing_mode(env: &Env, mode: crate::types::TimingMode) {
+    env.storage().instance().set(&key_timing(), &mode);
+}
+
+pub fn get_timing_mode(env: &Env) -> Option<crate::types::TimingMode> {
+    env.storage().instance().get(&key_timing())
+}
+
+pub fn get_created_at(env: &Env, trade_id: u64) -> Option<u64> {
+    let key = (CREATED_LEDGER_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+const DEADLINES_PREFIX: &str = "TD";
+
+/// Ledgers after creation a funded trade has to be marked complete. Zero
+/// disables the timeout.
+pub fn set_completion_window(env: &Env, ledgers: u32) {
+    env.storage().instance().set(&key_cmpl_win(), &ledgers);
+}
+
+pub fn get_completion_window(env: &Env) -> u32 {
+    env.storage().instance().get(&key_cmpl_win()).unwrap_or(0)
+}
+
+/// Ledgers after creation a completed trade has to be confirmed. Zero
+/// disables the timeout.
+pub fn set_confirmation_window(env: &Env, ledgers: u32) {
+    env.storage().instance().set(&key_conf_win(), &ledgers);
+}
+
+pub fn get_confirmation_window(env: &Env) -> u32 {
+    env.storage().instance().get(&key_conf_win()).unwrap_or(0)
+}
+
+pub fn save_trade_deadlines(env: &Env, trade_id: u64, deadlines: &crate::types::Deadlines) {
+    let key = (DEADLINES_PREFIX, trade_id);
+    env.storage().persistent().set(&key, deadlines);
+}
+
+pub fn get_trade_deadlines(env: &Env, trade_id: u64) -> Option<crate::types::Deadlines> {
+    let key = (DEADLINES_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+// ---------------------------------------------------------------------------
+// External references (idempotency keys)
+// ---------------------------------------------------------------------------
+
+const EXT_REF_PREFIX: &str = "ER";
+
+pub fn save_external_ref(env: &Env, external_ref: &soroban_sdk::BytesN<32>, trade_id: u64) {
+    let key = (EXT_REF_PREFIX, external_ref.clone());
+    env.storage().persistent().set(&key, &trade_id);
+}
+
+pub fn get_trade_by_external_ref(env: &Env, external_ref: &soroban_sdk::BytesN<32>) -> Option<u64> {
+    let key = (EXT_REF_PREFIX, external_ref.clone());
+    env.storage().persistent().get(&key)
+}
+
+// ---------------------------------------------------------------------------
+// Mandatory arbitrator threshold
+// ---------------------------------------------------------------------------
+
+/// Trades above this amount must name an arbitrator. Zero disables the rule.
+pub fn set_arbitrator_threshold(env: &Env, amount: i128) {
+    env.storage().instance().set(&key_arb_threshold(), &amount);
+}
+
+pub fn get_arbitrator_threshold(env: &Env) -> i128 {
+    instance_amount(env, &key_arb_threshold()).unwrap_or(0)
+}
+
+// ---------------------------------------------------------------------------
+// Open trades per seller/buyer pair
+// ---------------------------------------------------------------------------
+
+const PAIR_COUNT_PREFIX: &str = "PC";
+const PAIR_OPEN_PREFIX: &str = "PO";
+
+/// Maximum concurrent open trades between one seller and one buyer. Zero
+/// means unlimited.
+pub fn set_pair_trade_cap(env: &Env, cap: u32) {
+    env.storage().instance().set(&key_pair_cap(), &cap);
+}
+
+pub fn get_pair_trade_cap(env: &Env) -> u32 {
+    env.storage().instance().get(&key_pair_cap()).unwrap_or(0)
+}
+
+pub fn get_pair_open_trades(env: &Env, seller: &Address, buyer: &Address) -> u32 {
+    let key = (PAIR_COUNT_PREFIX, seller.clone(), buyer.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+fn set_pair_open_trades(env: &Env, seller: &Address, buyer: &Address, count: u32) {
+    let key = (PAIR_COUNT_PREFIX, seller.clone(), buyer.clone());
+    if count == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &count);
+    }
+}
+
+/// Count `trade_id` against its pair until `close_pair_trade` is called.
+pub fn open_pair_trade(env: &Env, trade_id: u64, seller: &Address, buyer: &Address) {
+    let count = get_pair_open_trades(env, seller, buyer).saturating_add(1);
+    set_pair_open_trades(env, seller, buyer, count);
+    env.storage().persistent().set(&(PAIR_OPEN_PREFIX, trade_id), &true);
+}
+
+/// True while `trade_id` holds a pair slot, i.e. has not yet settled or closed.
+pub fn has_open_pair_slot(env: &Env, trade_id: u64) -> bool {
+    env.storage().persistent().has(&(PAIR_OPEN_PREFIX, trade_id))
+}
+
+/// Release the pair slot held by `trade_id`. Safe to call more than once.
+pub fn close_pair_trade(env: &Env, trade_id: u64, seller: &Address, buyer: &Address) {
+    let key = (PAIR_OPEN_PREFIX, trade_id);
+    if !env.storage().persistent().has(&key) {
+        return;
+    }
+    env.storage().persistent().remove(&key);
+    let count = get_pair_open_trades(env, seller, buyer).saturating_sub(1);
+    set_pair_open_trades(env, seller, buyer, count);
+}
+
+// ---------------------------------------------------------------------------
+// Loyalty points
+// ---------------------------------------------------------------------------
+
+const POINTS_PREFIX: &str = "LP";
+const POINTS_DISCOUNT_PREFIX: &str = "LD";
+
+pub fn set_loyalty_config(env: &Env, config: &crate::types::LoyaltyConfig) {
+    env.storage().instance().set(&key_loyalty(), config);
+}
+
+pub fn get_loyalty_config(env: &Env) -> Option<crate::types::LoyaltyConfig> {
+    env.storage().instance().get(&key_loyalty())
+}
+
+pub fn get_points(env: &Env, user: &Address) -> u64 {
+    env.storage().persistent().get(&(POINTS_PREFIX, user.clone())).unwrap_or(0)
+}
+
+pub fn set_points(env: &Env, user: &Address, points: u64) {
+    env.storage().persistent().set(&(POINTS_PREFIX, user.clone()), &points);
+}
+
+/// Redeemed discount, in basis points, waiting to apply to the user's next trade.
+pub fn get_points_discount(env: &Env, user: &Address) -> u32 {
+    env.storage().persistent().get(&(POINTS_DISCOUNT_PREFIX, user.clone())).unwrap_or(0)
+}
+
+pub fn set_points_discount(env: &Env, user: &Address, bps: u32) {
+    env.storage().persistent().set(&(POINTS_DISCOUNT_PREFIX, user.clone()), &bps);
+}
+
+pub fn remove_points_discount(env: &Env, user: &Address) {
+    env.storage().persistent().remove(&(POINTS_DISCOUNT_PREFIX, user.clone()));
+}
+
+// ---------------------------------------------------------------------------
+// Trust badges
+// ---------------------------------------------------------------------------
+
+const TRACK_RECORD_PREFIX: &str = "TR";
+const BADGE_PREFIX: &str = "BG";
+
+pub fn get_track_record(env: &Env, user: &Address) -> crate::types::TrackRecord {
+    env.storage().persistent().get(&(TRACK_RECORD_PREFIX, user.clone())).unwrap_or_default()
+}
+
+pub fn save_track_record(env: &Env, user: &Address, record: &crate::types::TrackRecord) {
+    env.storage().persistent().set(&(TRACK_RECORD_PREFIX, user.clone()), record);
+}
+
+pub fn get_badge_level(env: &Env, user: &Address) -> u32 {
+    env.storage().persistent().get(&(BADGE_PREFIX, user.clone())).unwrap_or(0)
+}
+
+pub fn set_badge_level(env: &Env, user: &Address, level: u32) {
+    env.storage().persistent().set(&(BADGE_PREFIX, user.clone()), &level);
+}
+
+// ---------------------------------------------------------------------------
+// User statistics
+// ---------------------------------------------------------------------------
+
+const USER_STATS_PREFIX: &str = "US";
+
+pub fn get_user_stats(env: &Env, user: &Address) -> crate::types::UserStats {
+    env.storage().persistent().get(&(USER_STATS_PREFIX, user.clone())).unwrap_or_default()
+}
+
+pub fn save_user_stats(env: &Env, user: &Address, stats: &crate::types::UserStats) {
+    env.storage().persistent().set(&(USER_STATS_PREFIX, user.clone()), stats);
+}
+
+// ---------------------------------------------------------------------------
+// Admin action log
+// ---------------------------------------------------------------------------
+
+const ADMIN_LOG_PREFIX: &str = "AX";
+
+/// Total entries ever appended; the log holds the last `ADMIN_LOG_CAPACITY`.
+pub fn get_admin_log_count(env: &Env) -> u64 {
+    env.storage().instance().get(&key_admin_log()).unwrap_or(0)
+}
+
+/// Append an entry, overwriting the oldest once the ring buffer is full.
+pub fn append_admin_log(env: &Env, actor: &Address, action: crate::types::AdminAction) {
+    let seq = get_admin_log_count(env);
+    let entry = crate::types::AdminLogEntry {
+        seq,
+        actor: actor.clone(),
+        action,
+        ledger: env.ledger().sequence(),
+    };
+    let slot = seq % crate::types::ADMIN_LOG_CAPACITY;
+    env.storage().persistent().set(&(ADMIN_LOG_PREFIX, slot), &entry);
+    env.storage().instance().set(&key_admin_log(), &(seq + 1));
+}
+
+/// Entries from `start` onward, oldest first. Overwritten entries are
+/// skipped, so the first returned `seq` may be greater than `start`.
+pub fn get_admin_log(env: &Env, start: u64, limit: u32) -> Vec<crate::types::AdminLogEntry> {
+    let count = get_admin_log_count(env);
+    let oldest = count.saturating_sub(crate::types::ADMIN_LOG_CAPACITY);
+    let mut out = Vec::new(env);
+    let mut seq = start.max(oldest);
+    while seq < count && out.len() < limit {
+        let slot = seq % crate::types::ADMIN_LOG_CAPACITY;
+        if let Some(entry) = env.storage().persistent().get(&(ADMIN_LOG_PREFIX, slot)) {
+            out.push_back(entry);
+        }
+        seq += 1;
+    }
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Arbitrator trade index
+// ---------------------------------------------------------------------------
+
+const ARB_TRADE_COUNT_PREFIX: &str = "AN";
+const ARB_TRADE_PREFIX: &str = "AT";
+
+pub fn get_arbitrator_trade_count(env: &Env, arbitrator: &Address) -> u32 {
+    let key = (ARB_TRADE_COUNT_PREFIX, arbitrator.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Record `trade_id` as assigned to `arbitrator`. Entries are never removed,
+/// so the index covers past cases as well as open ones.
+pub fn index_arbitrator_trade(env: &Env, arbitrator: &Address, trade_id: u64) {
+    let count = get_arbitrator_trade_count(env, arbitrator);
+    env.storage().persistent().set(&(ARB_TRADE_PREFIX, arbitrator.clone(), count), &trade_id);
+    env.storage().persistent().set(&(ARB_TRADE_COUNT_PREFIX, arbitrator.clone()), &(count + 1));
+}
+
+/// Trade IDs assigned to `arbitrator` from index `start` onward, oldest first.
+pub fn get_arbitrator_trades(env: &Env, arbitrator: &Address, start: u32, limit: u32) -> Vec<u64> {
+    let count = get_arbitrator_trade_count(env, arbitrator);
+    let mut out = Vec::new(env);
+    let mut i = start;
+    while i < count && out.len() < limit {
+        if let Some(id) = env.storage().persistent().get(&(ARB_TRADE_PREFIX, arbitrator.clone(), i)) {
+            out.push_back(id);
+        }
+        i += 1;
+    }
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Dispute raiser
+// ---------------------------------------------------------------------------
+
+const DISPUTE_RAISER_PREFIX: &str = "DB";
+
+/// One record per trade, keyed by `trade_id` alone with the raiser as the
+/// value; a later raise or re-open overwrites it.
+pub fn save_dispute_raiser(env: &Env, trade_id: u64, raised_by: &Address) {
+    let key = (DISPUTE_RAISER_PREFIX, trade_id);
+    env.storage().persistent().set(&key, raised_by);
+}
+
+pub fn get_dispute_raiser(env: &Env, trade_id: u64) -> Option<Address> {
+    let key = (DISPUTE_RAISER_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+// ---------------------------------------------------------------------------
+// Delivery proof
+// ---------------------------------------------------------------------------
+
+const DELIVERY_PROOF_PREFIX: &str = "DP";
+
+pub fn save_delivery_proof(env: &Env, trade_id: u64, proof_hash: &soroban_sdk::BytesN<32>) {
+    let key = (DELIVERY_PROOF_PREFIX, trade_id);
+    env.storage().persistent().set(&key, proof_hash);
+}
+
+pub fn get_delivery_proof(env: &Env, trade_id: u64) -> Option<soroban_sdk::BytesN<32>> {
+    let key = (DELIVERY_PROOF_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+// ---------------------------------------------------------------------------
+// Message anchors
+// ---------------------------------------------------------------------------
+
+const MSG_ANCHOR_PREFIX: &str = "MA";
+
+pub fn save_message_anchors(env: &Env, trade_id: u64, anchors: &Vec<crate::types::MessageAnchor>) {
+    let key = (MSG_ANCHOR_PREFIX, trade_id);
+    env.storage().persistent().set(&key, anchors);
+}
+
+pub fn get_message_anchors(env: &Env, trade_id: u64) -> Vec<crate::types::MessageAnchor> {
+    let key = (MSG_ANCHOR_PREFIX, trade_id);
+    env.storage().persistent().get(&key).unwrap_or_else(|| Vec::new(env))
+}
+
+// ---------------------------------------------------------------------------
+// Order structure
+// ---------------------------------------------------------------------------
+
+const ORDER_PREFIX: &str = "OQ";
+
+pub fn save_order_structure(env: &Env, trade_id: u64, order: &crate::types::OrderStructure) {
+    let key = (ORDER_PREFIX, trade_id);
+    env.storage().persistent().set(&key, order);
+}
+
+pub fn get_order_structure(env: &Env, trade_id: u64) -> Option<crate::types::OrderStructure> {
+    let key = (ORDER_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+// ---------------------------------------------------------------------------
+// Counterparty ratings
+// ---------------------------------------------------------------------------
+
+const PARTY_REP_PREFIX: &str = "UR";
+const PARTY_RATED_PREFIX: &str = "CR";
+
+pub fn get_party_reputation(env: &Env, party: &Address) -> crate::types::PartyReputation {
+    let key = (PARTY_REP_PREFIX, party.clone());
+    env.storage().persistent().get(&key).unwrap_or_default()
+}
+
+pub fn save_party_reputation(env: &Env, party: &Address, rep: &crate::types::PartyReputation) {
+    let key = (PARTY_REP_PREFIX, party.clone());
+    env.storage().persistent().set(&key, rep);
+}
+
+pub fn has_rated_counterparty(env: &Env, trade_id: u64, rater: &Address) -> bool {
+    let key = (PARTY_RATED_PREFIX, trade_id, rater.clone());
+    env.storage().persistent().has(&key)
+}
+
+pub fn mark_rated_counterparty(env: &Env, trade_id: u64, rater: &Address) {
+    let key = (PARTY_RATED_PREFIX, trade_id, rater.clone());
+    env.storage().persistent().set(&key, &true);
+}
+
+// ---------------------------------------------------------------------------
+// Release hold
+// ---------------------------------------------------------------------------
+
+const CATEGORY_HOLD_PREFIX: &str = "HC";
+const HELD_UNTIL_PREFIX: &str = "HR";
+
+/// Default number of ledgers funds stay held after `confirm_receipt`.
+/// Zero releases immediately.
+pub fn set_default_hold(env: &Env, ledgers: u32) {
+    env.storage().instance().set(&key_hold(), &ledgers);
+}
+
+pub fn get_default_hold(env: &Env) -> u32 {
+    env.storage().instance().get(&key_hold()).unwrap_or(0)
+}
+
+pub fn set_category_hold(env: &Env, category: &Symbol, ledgers: u32) {
+    let key = (CATEGORY_HOLD_PREFIX, category.clone());
+    env.storage().persistent().set(&key, &ledgers);
+}
+
+pub fn get_category_hold(env: &Env, category: &Symbol) -> Option<u32> {
+    let key = (CATEGORY_HOLD_PREFIX, category.clone());
+    env.storage().persistent().get(&key)
+}
+
+pub fn set_held_until(env: &Env, trade_id: u64, until: u64) {
+    let key = (HELD_UNTIL_PREFIX, trade_id);
+    env.storage().persistent().set(&key, &until);
+}
+
+pub fn get_held_until(env: &Env, trade_id: u64) -> Option<u64> {
+    let key = (HELD_UNTIL_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+// ---------------------------------------------------------------------------
+// Streaming release
+// ---------------------------------------------------------------------------
+
+const STREAM_PREFIX: &str = "ST";
+
+pub fn save_stream(env: &Env, trade_id: u64, stream: &crate::types::StreamSchedule) {
+    let key = (STREAM_PREFIX, trade_id);
+    env.storage().persistent().set(&key, stream);
+}
+
+pub fn get_stream(env: &Env, trade_id: u64) -> Option<crate::types::StreamSchedule> {
+    let key = (STREAM_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+/// Amount already streamed to the seller side; excluded from any later
+/// dispute or refund.
+pub fn get_streamed_amount(env: &Env, trade_id: u64) -> i128 {
+    get_stream(env, trade_id).map(|s| s.claimed).unwrap_or(0)
+}
+
+// ---------------------------------------------------------------------------
+// Pooled funding
+// ---------------------------------------------------------------------------
+
+const CONTRIB_PREFIX: &str = "CT";
+
+pub fn save_contributions(env: &Env, trade_id: u64, contributions: &Vec<crate::types::Contribution>) {
+    let key = (CONTRIB_PREFIX, trade_id);
+    env.storage().persistent().set(&key, contributions);
+}
+
+pub fn get_contributions(env: &Env, trade_id: u64) -> Option<Vec<crate::types::Contribution>> {
+    let key = (CONTRIB_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+pub fn remove_contributions(env: &Env, trade_id: u64) {
+    let key = (CONTRIB_PREFIX, trade_id);
+    env.storage().persistent().remove(&key);
+}
+
+// ---------------------------------------------------------------------------
+// Bundle line items
+// ---------------------------------------------------------------------------
+
+const LINE_ITEMS_PREFIX: &str = "LI";
+
+pub fn save_line_items(env: &Env, trade_id: u64, items: &Vec<crate::types::LineItem>) {
+    let key = (LINE_ITEMS_PREFIX, trade_id);
+    env.storage().persistent().set(&key, items);
+}
+
+pub fn get_line_items(env: &Env, trade_id: u64) -> Option<Vec<crate::types::LineItem>> {
+    let key = (LINE_ITEMS_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+pub fn is_bundle(env: &Env, trade_id: u64) -> bool {
+    let key = (LINE_ITEMS_PREFIX, trade_id);
+    env.storage().persistent().has(&key)
+}
+
+// ---------------------------------------------------------------------------
+// Payout split
+// ---------------------------------------------------------------------------
+
+const SPLIT_PREFIX: &str = "SP";
+
+pub fn save_payout_split(env: &Env, trade_id: u64, shares: &Vec<crate::types::PayoutShare>) {
+    let key = (SPLIT_PREFIX, trade_id);
+    env.storage().persistent().set(&key, shares);
+}
+
+pub fn get_payout_split(env: &Env, trade_id: u64) -> Option<Vec<crate::types::PayoutShare>> {
+    let key = (SPLIT_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+// ---------------------------------------------------------------------------
+// Basket legs
+// ---------------------------------------------------------------------------
+
+const BASKET_PREFIX: &str = "BK";
+
+pub fn save_basket(env: &Env, trade_id: u64, basket: &crate::types::Basket) {
+    let key = (BASKET_PREFIX, trade_id);
+    env.storage().persistent().set(&key, basket);
+}
+
+pub fn get_basket(env: &Env, trade_id: u64) -> Option<crate::types::Basket> {
+    let key = (BASKET_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+// ---------------------------------------------------------------------------
+// Reference-asset quotes
+// ---------------------------------------------------------------------------
+
+const QUOTE_PREFIX: &str = "QT";
+
+pub fn save_trade_quote(env: &Env, trade_id: u64, quote: &crate::types::TradeQuote) {
+    let key = (QUOTE_PREFIX, trade_id);
+    env.storage().persistent().set(&key, quote);
+}
+
+pub fn get_trade_quote(env: &Env, trade_id: u64) -> Option<crate::types::TradeQuote> {
+    let key = (QUOTE_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+// ---------------------------------------------------------------------------
+// Refund requests
+// ---------------------------------------------------------------------------
+
+const REFUND_REQ_PREFIX: &str = "RR";
+
+pub fn save_refund_request(env: &Env, trade_id: u64, request: &crate::types::RefundRequest) {
+    let key = (REFUND_REQ_PREFIX, trade_id);
+    env.storage().persistent().set(&key, request);
+}
+
+pub fn get_refund_request(env: &Env, trade_id: u64) -> Option<crate::types::RefundRequest> {
+    let key = (REFUND_REQ_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+pub fn remove_refund_request(env: &Env, trade_id: u64) {
+    let key = (REFUND_REQ_PREFIX, trade_id);
+    env.storage().persistent().remove(&key);
+}
+
+// ---------------------------------------------------------------------------
+// Resolution reasons
+// ---------------------------------------------------------------------------
+
+const RESOLUTION_REASON_PREFIX: &str = "RN";
+
+pub fn save_resolution_reason(env: &Env, trade_id: u64, reason_hash: &soroban_sdk::BytesN<32>) {
+    let key = (RESOLUTION_REASON_PREFIX, trade_id);
+    env.storage().persistent().set(&key, reason_hash);
+}
+
+pub fn get_resolution_reason(env: &Env, trade_id: u64) -> Option<soroban_sdk::BytesN<32>> {
+    let key = (RESOLUTION_REASON_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+// ---------------------------------------------------------------------------
+// Dispute re-open window
+// ---------------------------------------------------------------------------
+
+const PENDING_RES_PREFIX: &str = "PD";
+const REOPEN_BOND_PREFIX: &str = "RB";
+
+pub fn set_reopen_policy(env: &Env, policy: &crate::types::ReopenPolicy) {
+    env.storage().instance().set(&key_reopen(), policy);
+}
+
+pub fn get_reopen_policy(env: &Env) -> crate::types::ReopenPolicy {
+    env.storage()
+        .instance()
+        .get(&key_reopen())
+        .unwrap_or(crate::types::ReopenPolicy { window: 0, bond: 0 })
+}
+
+/// Trade amount at or above which rulings need admin countersignature.
+/// Zero disables supervised arbitration.
+pub fn set_supervision_threshold(env: &Env, threshold: i128) {
+    env.storage().instance().set(&key_supv(), &threshold);
+}
+
+pub fn get_supervision_threshold(env: &Env) -> i128 {
+    instance_amount(env, &key_supv()).unwrap_or(0)
+}
+
+pub fn save_pending_resolution(env: &Env, trade_id: u64, pending: &crate::types::PendingResolution) {
+    let key = (PENDING_RES_PREFIX, trade_id);
+    env.storage().persistent().set(&key, pending);
+}
+
+pub fn get_pending_resolution(env: &Env, trade_id: u64) -> Option<crate::types::PendingResolution> {
+    let key = (PENDING_RES_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+pub fn remove_pending_resolution(env: &Env, trade_id: u64) {
+    let key = (PENDING_RES_PREFIX, trade_id);
+    env.storage().persistent().remove(&key);
+}
+
+pub fn save_reopen_bond(env: &Env, trade_id: u64, bond: &crate::types::ReopenBond) {
+    let key = (REOPEN_BOND_PREFIX, trade_id);
+    env.storage().persistent().set(&key, bond);
+}
+
+pub fn get_reopen_bond(env: &Env, trade_id: u64) -> Option<crate::types::ReopenBond> {
+    let key = (REOPEN_BOND_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+// ---------------------------------------------------------------------------
+// Dispute bonds
+// ---------------------------------------------------------------------------
+
+const DISPUTE_BONDS_PREFIX: &str = "DN";
+
+pub fn set_dispute_bond_policy(env: &Env, policy: &crate::types::DisputeBondPolicy) {
+    env.storage().instance().set(&key_dsp_bond(), policy);
+}
+
+pub fn get_dispute_bond_policy(env: &Env) -> crate::types::DisputeBondPolicy {
+    env.storage().instance().get(&key_dsp_bond()).unwrap_or_default()
+}
+
+pub fn save_dispute_bonds(env: &Env, trade_id: u64, bonds: &crate::types::DisputeBonds) {
+    let key = (DISPUTE_BONDS_PREFIX, trade_id);
+    env.storage().persistent().set(&key, bonds);
+}
+
+pub fn get_dispute_bonds(env: &Env, trade_id: u64) -> Option<crate::types::DisputeBonds> {
+    let key = (DISPUTE_BONDS_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+pub fn remove_dispute_bonds(env: &Env, trade_id: u64) {
+    let key = (DISPUTE_BONDS_PREFIX, trade_id);
+    env.storage().persistent().remove(&key);
+}
+
+// ---------------------------------------------------------------------------
+// Dispute responses
+// ---------------------------------------------------------------------------
+
+const DISPUTE_RESP_PREFIX: &str = "DR";
+
+/// Seconds each party has to respond once a dispute opens. Zero disables.
+pub fn set_dispute_response_window(env: &Env, window: u64) {
+    env.storage().instance().set(&key_dsp_resp(), &window);
+}
+
+pub fn get_dispute_response_window(env: &Env) -> u64 {
+    env.storage().instance().get(&key_dsp_resp()).unwrap_or(0)
+}
+
+pub fn save_dispute_responses(env: &Env, trade_id: u64, responses: &crate::types::DisputeResponses) {
+    let key = (DISPUTE_RESP_PREFIX, trade_id);
+    env.storage().persistent().set(&key, responses);
+}
+
+pub fn get_dispute_responses(env: &Env, trade_id: u64) -> Option<crate::types::DisputeResponses> {
+    let key = (DISPUTE_RESP_PREFIX, trade_id);
+    env.storage().persistent().get(&key)
+}
+
+pub fn remove_dispute_responses(env: &Env, trade_id: u64) {
+    let key = (DISPUTE_RESP_PREFIX, trade_id);
+    env.storage().persistent().remove(&key);
+}
+
+// ---------------------------------------------------------------------------
+// Emergency withdrawal
+// ---------------------------------------------------------------------------
+
+pub fn set_pending_emergency_withdrawal(env: &Env, pending: &crate::types::PendingEmergencyWithdrawal) {
+    env.storage().instance().set(&key_emrg_wd(), pending);
+}
+
+pub fn get_pending_emergency_withdrawal(env: &Env) -> Option<crate::types::PendingEmergencyWithdrawal> {
+    env.storage().instance().get(&key_emrg_wd())
+}
+
+pub fn clear_pending_emergency_withdrawal(env: &Env) {
+    env.storage().instance().remove(&key_emrg_wd());
+}
+
+// ---------------------------------------------------------------------------
+// Fee withdrawal timelock
+// ---------------------------------------------------------------------------
+
+pub fn set_pending_fee_withdrawal(env: &Env, pending: &crate::types::PendingFeeWithdrawal) {
+    env.storage().instance().set(&key_fee_wd(), pending);
+}
+
+/// Also reads a withdrawal requested before amounts moved to `i128` or
+/// before withdrawals named a currency; those are in the platform token.
+pub fn get_pending_fee_withdrawal(env: &Env) -> Option<crate::types::PendingFeeWithdrawal> {
+    let raw: Val = env.storage().instance().get(&key_fee_wd())?;
+    if let Ok(pending) = crate::types::PendingFeeWithdrawal::try_from_val(env, &raw) {
+        return Some(pending);
+    }
+    let legacy = crate::types::PendingFeeWithdrawalV2::try_from_val(env, &raw)
+        .ok()
+        .or_else(|| crate::types::PendingFeeWithdrawalV1::try_from_val(env, &raw).ok().map(Into::into))?;
+    Some(legacy.in_currency(get_usdc_token(env).ok()?))
+}
+
+pub fn clear_pending_fee_withdrawal(env: &Env) {
+    env.storage().instance().remove(&key_fee_wd());
+}
+
+/// Seconds between requesting and executing a fee withdrawal.
+pub fn set_fee_withdrawal_delay(env: &Env, seconds: u64) {
+    env.storage().instance().set(&key_fee_wd_dl(), &seconds);
+}
+
+pub fn get_fee_withdrawal_delay(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&key_fee_wd_dl())
+        .unwrap_or(crate::types::DEFAULT_FEE_WITHDRAWAL_DELAY)
+}
+
 // ---------------------------------------------------------------------------
 // Bridge Oracle
 // ---------------------------------------------------------------------------
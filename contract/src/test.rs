@@ -4,7 +4,7 @@ extern crate std;
 
 use soroban_sdk::{testutils::{Address as _, Ledger}, token, Address, Env};
 
-use crate::{OptionalMetadata, StellarEscrowContract, StellarEscrowContractClient, TradeStatus};
+use crate::{OptionalMetadata, StellarEscrowContract, StellarEscrowContractClient, TradeOptions, TradeStatus};
 
 fn setup() -> (Env, Address, Address, Address, Address, Address, StellarEscrowContractClient<'static>) {
     let env = Env::default();
@@ -66,24 +66,24 @@ fn test_register_and_remove_arbitrator() {
 #[test]
 fn test_update_fee() {
     let (_, _, _, _, _, _, client) = setup();
-    client.update_fee(&200u32);
+    client.update_fee(&200u32, &0u32);
     assert_eq!(client.get_platform_fee_bps(), 200);
 }
 
 #[test]
 fn test_update_fee_invalid() {
     let (_, _, _, _, _, _, client) = setup();
-    assert!(client.try_update_fee(&10001u32).is_err());
+    assert!(client.try_update_fee(&10001u32, &0u32).is_err());
 }
 
 #[test]
 fn test_create_trade() {
     let (_, _, _, seller, buyer, _, client) = setup();
-    let id = client.create_trade(&seller, &buyer, &1_000_000u64, &None, &OptionalMetadata::None);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
     assert_eq!(id, 1);
     let trade = client.get_trade(&id);
     assert_eq!(trade.status, TradeStatus::Created);
-    assert_eq!(trade.amount, 1_000_000u64);
+    assert_eq!(trade.amount, 1_000_000i128);
 }
 
 #[test]
@@ -96,10 +96,10 @@ fn test_create_trade_with_compliance_rules() {
     };
     client.set_user_compliance(&admin, &seller, &acceptable_user);
     client.set_user_compliance(&admin, &buyer, &acceptable_user);
-    client.set_user_trade_limit(&admin, &seller, &2_000_000u64);
-    client.set_global_trade_limit(&admin, &3_000_000u64);
+    client.set_user_trade_limit(&admin, &seller, &2_000_000i128);
+    client.set_global_trade_limit(&admin, &3_000_000i128);
 
-    let id = client.create_trade(&seller, &buyer, &1_000_000u64, &None, &OptionalMetadata::None);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
     assert_eq!(id, 1);
 }
 
@@ -119,7 +119,7 @@ fn test_create_trade_fails_for_unverified_kyc() {
     client.set_user_compliance(&admin, &seller, &seller_compliance);
     client.set_user_compliance(&admin, &buyer, &buyer_compliance);
 
-    assert!(client.try_create_trade(&seller, &buyer, &1_000_000u64, &None, &OptionalMetadata::None).is_err());
+    assert!(client.try_create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None).is_err());
 }
 
 #[test]
@@ -134,7 +134,7 @@ fn test_create_trade_fails_for_jurisdiction_block() {
     client.set_user_compliance(&admin, &buyer, &compliant);
     client.set_jurisdiction_rule(&admin, &soroban_sdk::String::from_str(&env, "CN"), &false);
 
-    assert!(client.try_create_trade(&seller, &buyer, &1_000_000u64, &None, &OptionalMetadata::None).is_err());
+    assert!(client.try_create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None).is_err());
 }
 
 #[test]
@@ -147,21 +147,31 @@ fn test_create_trade_fails_for_amount_limit() {
     };
     client.set_user_compliance(&admin, &seller, &compliant);
     client.set_user_compliance(&admin, &buyer, &compliant);
-    client.set_user_trade_limit(&admin, &seller, &500_000u64);
+    client.set_user_trade_limit(&admin, &seller, &500_000i128);
 
-    assert!(client.try_create_trade(&seller, &buyer, &1_000_000u64, &None, &OptionalMetadata::None).is_err());
+    assert!(client.try_create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None).is_err());
 }
 
 #[test]
 fn test_create_trade_zero_amount_fails() {
     let (_, _, _, seller, buyer, _, client) = setup();
-    assert!(client.try_create_trade(&seller, &buyer, &0u64, &None, &OptionalMetadata::None).is_err());
+    assert!(client.try_create_trade(&seller, &buyer, &0i128, &None, &OptionalMetadata::None).is_err());
+}
+
+#[test]
+fn test_create_trade_negative_amount_fails() {
+    let (_, _, _, seller, buyer, _, client) = setup();
+    assert_eq!(
+        client.try_create_trade(&seller, &buyer, &-1i128, &None, &OptionalMetadata::None),
+        Err(Ok(crate::ContractError::InvalidAmount))
+    );
+    assert!(client.try_simulate_fee(&-1i128, &seller, &None).is_err());
 }
 
 #[test]
 fn test_fund_trade() {
     let (env, token_addr, _, seller, buyer, _, client) = setup();
-    let id = client.create_trade(&seller, &buyer, &1_000_000u64, &None, &OptionalMetadata::None);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
     fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
     client.fund_trade(&id);
     assert_eq!(client.get_trade(&id).status, TradeStatus::Funded);
@@ -170,22 +180,22 @@ fn test_fund_trade() {
 #[test]
 fn test_complete_and_confirm_trade() {
     let (env, token_addr, _, seller, buyer, _, client) = setup();
-    let amount = 1_000_000u64;
+    let amount = 1_000_000i128;
     let id = client.create_trade(&seller, &buyer, &amount, &None, &OptionalMetadata::None);
-    fund(&env, &token_addr, &buyer, &client.address, amount as i128);
+    fund(&env, &token_addr, &buyer, &client.address, amount);
     client.fund_trade(&id);
-    client.complete_trade(&id);
-    client.confirm_receipt(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
 
     // fee = 1% of 1_000_000 = 10_000; seller receives 990_000
     assert_eq!(token::Client::new(&env, &token_addr).balance(&seller), 990_000i128);
-    assert_eq!(client.get_accumulated_fees(), 10_000u64);
+    assert_eq!(client.get_accumulated_fees(), 10_000i128);
 }
 
 #[test]
 fn test_cancel_trade() {
     let (_, _, _, seller, buyer, _, client) = setup();
-    let id = client.create_trade(&seller, &buyer, &1_000_000u64, &None, &OptionalMetadata::None);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
     client.cancel_trade(&id);
     assert_eq!(client.get_trade(&id).status, TradeStatus::Cancelled);
 }
@@ -194,14 +204,15 @@ fn test_cancel_trade() {
 fn test_dispute_and_resolve_to_buyer() {
     let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
     client.register_arbitrator(&arbitrator);
-    let amount = 1_000_000u64;
+    let amount = 1_000_000i128;
     let id = client.create_trade(&seller, &buyer, &amount, &Some(arbitrator.clone()), &OptionalMetadata::None);
-    fund(&env, &token_addr, &buyer, &client.address, amount as i128);
+    client.accept_assignment(&id);
+    fund(&env, &token_addr, &buyer, &client.address, amount);
     client.fund_trade(&id);
-    client.raise_dispute(&id, &buyer);
+    client.raise_dispute(&buyer, &id);
 
     let before = token::Client::new(&env, &token_addr).balance(&buyer);
-    client.resolve_dispute(&id, &crate::DisputeResolution::ReleaseToBuyer);
+    client.resolve_dispute(&id, &crate::DisputeResolution::ReleaseToBuyer, &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]));
     let after = token::Client::new(&env, &token_addr).balance(&buyer);
     assert_eq!(after - before, 990_000i128);
 }
@@ -209,17 +220,92 @@ fn test_dispute_and_resolve_to_buyer() {
 #[test]
 fn test_withdraw_fees() {
     let (env, token_addr, _, seller, buyer, _, client) = setup();
-    let amount = 1_000_000u64;
+    let amount = 1_000_000i128;
     let id = client.create_trade(&seller, &buyer, &amount, &None, &OptionalMetadata::None);
-    fund(&env, &token_addr, &buyer, &client.address, amount as i128);
+    fund(&env, &token_addr, &buyer, &client.address, amount);
     client.fund_trade(&id);
-    client.complete_trade(&id);
-    client.confirm_receipt(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
 
     let recipient = Address::generate(&env);
-    client.withdraw_fees(&recipient);
+    client.request_fee_withdrawal(&token_addr, &recipient, &10_000i128);
+    assert_eq!(
+        client.try_execute_fee_withdrawal(),
+        Err(Ok(crate::ContractError::FeeWithdrawalDelayNotElapsed))
+    );
+    env.ledger().with_mut(|l| l.timestamp += client.get_fee_withdrawal_delay());
+    client.execute_fee_withdrawal();
     assert_eq!(token::Client::new(&env, &token_addr).balance(&recipient), 10_000i128);
-    assert_eq!(client.get_accumulated_fees(), 0u64);
+    assert_eq!(client.get_accumulated_fees(), 0i128);
+    assert!(client.get_pending_fee_withdrawal().is_none());
+}
+
+#[test]
+fn test_lifetime_totals_survive_fee_withdrawal() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    client.create_trade(&seller, &buyer, &500_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+
+    client.request_fee_withdrawal(&token_addr, &Address::generate(&env), &10_000i128);
+    env.ledger().with_mut(|l| l.timestamp += client.get_fee_withdrawal_delay());
+    client.execute_fee_withdrawal();
+
+    let totals = client.get_lifetime_totals(&token_addr);
+    assert_eq!(totals.trades_created, 2);
+    assert_eq!(totals.trades_settled, 1);
+    assert_eq!(totals.gross_volume, 1_000_000);
+    assert_eq!(totals.fees_collected, 10_000);
+    assert_eq!(totals.fees_withdrawn, 10_000);
+    assert_eq!(client.get_accumulated_fees(), 0i128);
+}
+
+#[test]
+fn test_lifetime_totals_are_per_currency() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let eurc = basket_token(&env, &buyer, 500_000);
+    let id = client.create_trade(
+        &seller, &buyer, &500_000i128, &None, &OptionalMetadata::None,
+        &None, &TradeOptions { currency: Some(eurc.clone()), ..Default::default() },
+    );
+    fund(&env, &eurc, &buyer, &client.address, 500_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+
+    let totals = client.get_lifetime_totals(&eurc);
+    assert_eq!(totals.trades_created, 1);
+    assert_eq!(totals.trades_settled, 1);
+    assert_eq!(totals.gross_volume, 500_000);
+    assert_eq!(totals.fees_collected, 5_000);
+    assert_eq!(client.get_lifetime_totals(&token_addr), crate::LifetimeTotals::default());
+}
+
+#[test]
+fn test_cancel_fee_withdrawal() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+
+    let recipient = Address::generate(&env);
+    client.request_fee_withdrawal(&token_addr, &recipient, &10_000i128);
+    assert_eq!(
+        client.try_request_fee_withdrawal(&token_addr, &recipient, &1i128),
+        Err(Ok(crate::ContractError::FeeWithdrawalPending))
+    );
+    client.cancel_fee_withdrawal();
+    env.ledger().with_mut(|l| l.timestamp += client.get_fee_withdrawal_delay());
+    assert_eq!(
+        client.try_execute_fee_withdrawal(),
+        Err(Ok(crate::ContractError::NoFeeWithdrawal))
+    );
+    assert_eq!(client.get_accumulated_fees(), 10_000i128);
 }
 
 #[test]
@@ -227,16 +313,77 @@ fn test_pause_and_unpause() {
     let (_, _, _, seller, buyer, _, client) = setup();
     client.pause();
     assert!(client.is_paused());
-    assert!(client.try_create_trade(&seller, &buyer, &1_000_000u64, &None, &OptionalMetadata::None).is_err());
+    assert!(client.try_create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None).is_err());
     client.unpause();
     assert!(!client.is_paused());
 }
 
+#[test]
+fn test_paused_functions_block_only_their_capability() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    client.accept_assignment(&id);
+    client.set_paused_functions(&(crate::PAUSE_CREATE | crate::PAUSE_DISPUTE | crate::PAUSE_WITHDRAW));
+    assert!(!client.is_paused());
+    assert_eq!(
+        client.try_create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None),
+        Err(Ok(crate::ContractError::FunctionPaused))
+    );
+
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    assert_eq!(client.try_raise_dispute(&buyer, &id), Err(Ok(crate::ContractError::FunctionPaused)));
+    assert_eq!(
+        client.try_request_fee_withdrawal(&token_addr, &Address::generate(&env), &1i128),
+        Err(Ok(crate::ContractError::FunctionPaused))
+    );
+
+    client.set_paused_functions(&0u32);
+    client.raise_dispute(&buyer, &id);
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Disputed);
+}
+
+#[test]
+fn test_set_paused_functions_rejects_unknown_bits() {
+    let (_, _, _, _, _, _, client) = setup();
+    assert_eq!(
+        client.try_set_paused_functions(&(crate::PAUSE_ALL_FUNCTIONS + 1)),
+        Err(Ok(crate::ContractError::InvalidPauseFlags))
+    );
+    assert_eq!(client.get_paused_functions(), 0);
+}
+
+#[test]
+fn test_frozen_trade_blocks_transitions_until_unfrozen() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.freeze_trade(&id, &7u32);
+    assert_eq!(client.get_trade_freeze(&id).unwrap().reason_code, 7);
+    assert_eq!(client.try_complete_trade(&id, &None), Err(Ok(crate::ContractError::TradeFrozen)));
+    assert_eq!(client.try_freeze_trade(&id, &8u32), Err(Ok(crate::ContractError::TradeFrozen)));
+
+    // Other trades are unaffected.
+    let other = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    client.cancel_trade(&other);
+
+    client.unfreeze_trade(&id);
+    assert_eq!(client.get_trade_freeze(&id), None);
+    client.complete_trade(&id, &None);
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Completed);
+    assert_eq!(client.try_unfreeze_trade(&id), Err(Ok(crate::ContractError::TradeNotFrozen)));
+}
+
 #[test]
 fn test_no_fees_to_withdraw_fails() {
-    let (env, _, _, _, _, _, client) = setup();
+    let (env, token_addr, _, _, _, _, client) = setup();
     let recipient = Address::generate(&env);
-    assert!(client.try_withdraw_fees(&recipient).is_err());
+    assert_eq!(
+        client.try_request_fee_withdrawal(&token_addr, &recipient, &1i128),
+        Err(Ok(crate::ContractError::NoFeesToWithdraw))
+    );
 }
 
 #[test]
@@ -286,7 +433,7 @@ fn test_set_bridge_oracle() {
     let seller = Address::generate(&env);
     let buyer = Address::generate(&env);
     let id = client.create_cross_chain_trade(
-        &seller, &buyer, &1_000_000u64, &None,
+        &seller, &buyer, &1_000_000i128, &None,
         &soroban_sdk::String::from_str(&env, "ethereum"), &1000u32,
     );
     assert_eq!(id, 1);
@@ -296,7 +443,7 @@ fn test_set_bridge_oracle() {
 fn test_create_cross_chain_trade_no_oracle_fails() {
     let (env, _, _, seller, buyer, _, client) = setup();
     assert!(client.try_create_cross_chain_trade(
-        &seller, &buyer, &1_000_000u64, &None,
+        &seller, &buyer, &1_000_000i128, &None,
         &soroban_sdk::String::from_str(&env, "ethereum"), &1000u32,
     ).is_err());
 }
@@ -307,7 +454,7 @@ fn test_confirm_bridge_deposit() {
     let oracle = client.get_cross_chain_info(&{
         // create a trade first to get the oracle address indirectly
         let id = client.create_cross_chain_trade(
-            &seller, &buyer, &1_000_000u64, &None,
+            &seller, &buyer, &1_000_000i128, &None,
             &soroban_sdk::String::from_str(&env, "ethereum"), &1000u32,
         );
         id
@@ -317,7 +464,7 @@ fn test_confirm_bridge_deposit() {
 
     // re-create cleanly
     let id = client.create_cross_chain_trade(
-        &seller, &buyer, &1_000_000u64, &None,
+        &seller, &buyer, &1_000_000i128, &None,
         &soroban_sdk::String::from_str(&env, "polygon"), &1000u32,
     );
     assert_eq!(client.get_trade(&id).status, TradeStatus::AwaitingBridge);
@@ -333,20 +480,40 @@ fn test_confirm_bridge_deposit() {
 fn test_expire_bridge_trade() {
     let (env, _, _, seller, buyer, client) = setup_bridge();
     let id = client.create_cross_chain_trade(
-        &seller, &buyer, &1_000_000u64, &None,
+        &seller, &buyer, &1_000_000i128, &None,
         &soroban_sdk::String::from_str(&env, "ethereum"), &10u32,
     );
     // advance ledger past expiry
     env.ledger().with_mut(|l| l.sequence_number += 11);
     client.expire_bridge_trade(&id);
-    assert_eq!(client.get_trade(&id).status, TradeStatus::Cancelled);
+    assert_eq!(client.get_trade(&id).status, TradeStatus::Expired);
+}
+
+#[test]
+fn test_terminal_status_transitions() {
+    use crate::state::{allows, TradeEvent};
+    assert!(allows(&TradeStatus::Created, TradeEvent::Expire));
+    assert!(allows(&TradeStatus::Funded, TradeEvent::Refund));
+    assert!(!allows(&TradeStatus::Created, TradeEvent::Refund));
+    assert!(!allows(&TradeStatus::Funded, TradeEvent::Cancel));
+    assert!(!allows(&TradeStatus::Funded, TradeEvent::Expire));
+    for terminal in [TradeStatus::Settled, TradeStatus::Refunded, TradeStatus::Expired, TradeStatus::Cancelled] {
+        assert!(terminal.is_terminal());
+        for event in [TradeEvent::Fund, TradeEvent::Dispute, TradeEvent::Refund, TradeEvent::Settle] {
+            assert!(!allows(&terminal, event));
+        }
+    }
+    assert!(!TradeStatus::Completed.is_terminal());
+    assert!(allows(&TradeStatus::Resolved, TradeEvent::Settle));
+    assert!(allows(&TradeStatus::Resolved, TradeEvent::Reopen));
+    assert!(!allows(&TradeStatus::Resolved, TradeEvent::Rule));
 }
 
 #[test]
 fn test_expire_bridge_trade_before_expiry_fails() {
     let (env, _, _, seller, buyer, client) = setup_bridge();
     let id = client.create_cross_chain_trade(
-        &seller, &buyer, &1_000_000u64, &None,
+        &seller, &buyer, &1_000_000i128, &None,
         &soroban_sdk::String::from_str(&env, "ethereum"), &1000u32,
     );
     assert!(client.try_expire_bridge_trade(&id).is_err());
@@ -356,7 +523,7 @@ fn test_expire_bridge_trade_before_expiry_fails() {
 fn test_confirm_expired_bridge_trade_fails() {
     let (env, _, _, seller, buyer, client) = setup_bridge();
     let id = client.create_cross_chain_trade(
-        &seller, &buyer, &1_000_000u64, &None,
+        &seller, &buyer, &1_000_000i128, &None,
         &soroban_sdk::String::from_str(&env, "ethereum"), &10u32,
     );
     env.ledger().with_mut(|l| l.sequence_number += 11);
@@ -379,7 +546,7 @@ fn setup_insurance() -> (Env, Address, Address, Address, Address, StellarEscrowC
 }
 
 fn funded_trade(env: &Env, token_addr: &Address, seller: &Address, buyer: &Address, client: &StellarEscrowContractClient) -> u64 {
-    let id = client.create_trade(seller, buyer, &1_000_000u64, &None, &OptionalMetadata::None);
+    let id = client.create_trade(seller, buyer, &1_000_000i128, &None, &OptionalMetadata::None);
     token::Client::new(env, token_addr).approve(buyer, &client.address, &1_000_000i128, &200u32);
     client.fund_trade(&id);
     id
@@ -404,13 +571,13 @@ fn test_purchase_insurance() {
 
     let buyer_before = token::Client::new(&env, &token_addr).balance(&buyer);
     // 100 bps = 1% of 1_000_000 = 10_000 premium
-    client.purchase_insurance(&id, &provider, &100u32, &500_000u64);
+    client.purchase_insurance(&id, &provider, &100u32, &500_000i128);
     let buyer_after = token::Client::new(&env, &token_addr).balance(&buyer);
     assert_eq!(buyer_before - buyer_after, 10_000i128);
 
     let policy = client.get_insurance_policy(&id).unwrap();
-    assert_eq!(policy.premium, 10_000u64);
-    assert_eq!(policy.coverage, 500_000u64);
+    assert_eq!(policy.premium, 10_000);
+    assert_eq!(policy.coverage, 500_000);
     assert!(!policy.claimed);
 }
 
@@ -419,7 +586,7 @@ fn test_purchase_insurance_unregistered_provider_fails() {
     let (env, token_addr, _, seller, buyer, client) = setup_insurance();
     let id = funded_trade(&env, &token_addr, &seller, &buyer, &client);
     let rando = Address::generate(&env);
-    assert!(client.try_purchase_insurance(&id, &rando, &100u32, &500_000u64).is_err());
+    assert!(client.try_purchase_insurance(&id, &rando, &100u32, &500_000i128).is_err());
 }
 
 #[test]
@@ -429,7 +596,7 @@ fn test_purchase_insurance_premium_too_high_fails() {
     client.register_insurance_provider(&provider);
     let id = funded_trade(&env, &token_addr, &seller, &buyer, &client);
     // 1001 bps > MAX_INSURANCE_PREMIUM_BPS (1000)
-    assert!(client.try_purchase_insurance(&id, &provider, &1001u32, &500_000u64).is_err());
+    assert!(client.try_purchase_insurance(&id, &provider, &1001u32, &500_000i128).is_err());
 }
 
 #[test]
@@ -440,20 +607,21 @@ fn test_claim_insurance() {
     token::StellarAssetClient::new(&env, &token_addr).mint(&provider, &10_000_000i128);
 
     let id = funded_trade(&env, &token_addr, &seller, &buyer, &client);
-    client.purchase_insurance(&id, &provider, &100u32, &500_000u64);
+    client.purchase_insurance(&id, &provider, &100u32, &500_000i128);
 
     // raise dispute to make trade eligible for claim
     let arb = Address::generate(&env);
     client.register_arbitrator(&arb);
     // re-create a trade with arbitrator for dispute
-    let id2 = client.create_trade(&seller, &buyer, &1_000_000u64, &Some(arb.clone()), &OptionalMetadata::None);
+    let id2 = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arb.clone()), &OptionalMetadata::None);
+    client.accept_assignment(&id2);
     token::Client::new(&env, &token_addr).approve(&buyer, &client.address, &1_000_000i128, &200u32);
     client.fund_trade(&id2);
-    client.purchase_insurance(&id2, &provider, &100u32, &500_000u64);
-    client.raise_dispute(&id2, &buyer);
+    client.purchase_insurance(&id2, &provider, &100u32, &500_000i128);
+    client.raise_dispute(&buyer, &id2);
 
     let seller_before = token::Client::new(&env, &token_addr).balance(&seller);
-    client.claim_insurance(&id2, &seller, &200_000u64);
+    client.claim_insurance(&id2, &seller, &200_000i128);
     let seller_after = token::Client::new(&env, &token_addr).balance(&seller);
     assert_eq!(seller_after - seller_before, 200_000i128);
 
@@ -470,14 +638,15 @@ fn test_claim_insurance_double_claim_fails() {
 
     let arb = Address::generate(&env);
     client.register_arbitrator(&arb);
-    let id = client.create_trade(&seller, &buyer, &1_000_000u64, &Some(arb.clone()), &OptionalMetadata::None);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arb.clone()), &OptionalMetadata::None);
+    client.accept_assignment(&id);
     token::Client::new(&env, &token_addr).approve(&buyer, &client.address, &1_000_000i128, &200u32);
     client.fund_trade(&id);
-    client.purchase_insurance(&id, &provider, &100u32, &500_000u64);
-    client.raise_dispute(&id, &buyer);
+    client.purchase_insurance(&id, &provider, &100u32, &500_000i128);
+    client.raise_dispute(&buyer, &id);
 
-    client.claim_insurance(&id, &seller, &100_000u64);
-    assert!(client.try_claim_insurance(&id, &seller, &100_000u64).is_err());
+    client.claim_insurance(&id, &seller, &100_000i128);
+    assert!(client.try_claim_insurance(&id, &seller, &100_000i128).is_err());
 }
 
 #[test]
@@ -489,16 +658,17 @@ fn test_claim_capped_at_coverage() {
 
     let arb = Address::generate(&env);
     client.register_arbitrator(&arb);
-    let id = client.create_trade(&seller, &buyer, &1_000_000u64, &Some(arb.clone()), &OptionalMetadata::None);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arb.clone()), &OptionalMetadata::None);
+    client.accept_assignment(&id);
     token::Client::new(&env, &token_addr).approve(&buyer, &client.address, &1_000_000i128, &200u32);
     client.fund_trade(&id);
     // coverage = 50_000
-    client.purchase_insurance(&id, &provider, &100u32, &50_000u64);
-    client.raise_dispute(&id, &buyer);
+    client.purchase_insurance(&id, &provider, &100u32, &50_000i128);
+    client.raise_dispute(&buyer, &id);
 
     let seller_before = token::Client::new(&env, &token_addr).balance(&seller);
     // request 999_999 but coverage is only 50_000
-    client.claim_insurance(&id, &seller, &999_999u64);
+    client.claim_insurance(&id, &seller, &999_999i128);
     let seller_after = token::Client::new(&env, &token_addr).balance(&seller);
     assert_eq!(seller_after - seller_before, 50_000i128);
 }
@@ -522,7 +692,7 @@ fn test_analytics_initial_state() {
 #[test]
 fn test_analytics_volume_and_unique_addresses() {
     let (env, token_addr, _, seller, buyer, _, client) = setup();
-    let amount = 1_000_000u64;
+    let amount = 1_000_000i128;
 
     client.create_trade(&seller, &buyer, &amount, &None, &OptionalMetadata::None);
 
@@ -533,24 +703,37 @@ fn test_analytics_volume_and_unique_addresses() {
     assert_eq!(result.unique_addresses, 2);
 
     // second trade with same addresses — unique count must not grow
-    fund(&env, &token_addr, &buyer, &client.address, amount as i128);
+    fund(&env, &token_addr, &buyer, &client.address, amount);
     client.create_trade(&seller, &buyer, &amount, &None, &OptionalMetadata::None);
     let result2 = client.analytics_query(&crate::analytics::TimeWindow::AllTime);
     assert_eq!(result2.unique_addresses, 2);
     assert_eq!(result2.all_time.metrics.total_volume, amount * 2);
 }
 
+#[test]
+fn test_analytics_volume_beyond_u64_is_exact() {
+    let (_, _, _, seller, buyer, _, client) = setup();
+    let amount = i128::from(u64::MAX) * 3;
+
+    client.create_trade(&seller, &buyer, &amount, &None, &OptionalMetadata::None);
+    client.create_trade(&seller, &buyer, &amount, &None, &OptionalMetadata::None);
+
+    let result = client.analytics_query(&crate::analytics::TimeWindow::AllTime);
+    assert_eq!(result.all_time.metrics.total_volume, amount * 2);
+    assert_eq!(result.window.volume, amount * 2);
+}
+
 #[test]
 fn test_analytics_success_rate() {
     let (env, token_addr, _, seller, buyer, _, client) = setup();
-    let amount = 1_000_000u64;
+    let amount = 1_000_000i128;
 
     // Complete one trade
     let id = client.create_trade(&seller, &buyer, &amount, &None, &OptionalMetadata::None);
-    fund(&env, &token_addr, &buyer, &client.address, amount as i128);
+    fund(&env, &token_addr, &buyer, &client.address, amount);
     client.fund_trade(&id);
-    client.complete_trade(&id);
-    client.confirm_receipt(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
 
     let result = client.analytics_query(&crate::analytics::TimeWindow::AllTime);
     // 1 completed, 0 cancelled, 0 disputed → 100% = 10_000 bps
@@ -561,13 +744,14 @@ fn test_analytics_success_rate() {
 #[test]
 fn test_analytics_dispute_rate() {
     let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
-    let amount = 1_000_000u64;
+    let amount = 1_000_000i128;
     client.register_arbitrator(&arbitrator);
 
     let id = client.create_trade(&seller, &buyer, &amount, &Some(arbitrator.clone()), &OptionalMetadata::None);
-    fund(&env, &token_addr, &buyer, &client.address, amount as i128);
+    client.accept_assignment(&id);
+    fund(&env, &token_addr, &buyer, &client.address, amount);
     client.fund_trade(&id);
-    client.raise_dispute(&id, &buyer);
+    client.raise_dispute(&buyer, &id);
 
     let result = client.analytics_query(&crate::analytics::TimeWindow::AllTime);
     // 1 funded, 1 disputed → dispute rate = 10_000 bps (100%)
@@ -577,21 +761,22 @@ fn test_analytics_dispute_rate() {
 #[test]
 fn test_analytics_mixed_success_rate() {
     let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
-    let amount = 1_000_000u64;
+    let amount = 1_000_000i128;
     client.register_arbitrator(&arbitrator);
 
     // Trade 1: completed
     let id1 = client.create_trade(&seller, &buyer, &amount, &None, &OptionalMetadata::None);
-    fund(&env, &token_addr, &buyer, &client.address, amount as i128);
+    fund(&env, &token_addr, &buyer, &client.address, amount);
     client.fund_trade(&id1);
-    client.complete_trade(&id1);
-    client.confirm_receipt(&id1);
+    client.complete_trade(&id1, &None);
+    client.confirm_receipt(&id1, &None);
 
     // Trade 2: disputed (counts as terminal)
     let id2 = client.create_trade(&seller, &buyer, &amount, &Some(arbitrator.clone()), &OptionalMetadata::None);
-    fund(&env, &token_addr, &buyer, &client.address, amount as i128);
+    client.accept_assignment(&id2);
+    fund(&env, &token_addr, &buyer, &client.address, amount);
     client.fund_trade(&id2);
-    client.raise_dispute(&id2, &buyer);
+    client.raise_dispute(&buyer, &id2);
 
     let result = client.analytics_query(&crate::analytics::TimeWindow::AllTime);
     // 1 completed / (1 completed + 1 disputed) = 5_000 bps (50%)
@@ -601,7 +786,7 @@ fn test_analytics_mixed_success_rate() {
 #[test]
 fn test_analytics_window_metrics_track_trades() {
     let (_, _, _, seller, buyer, _, client) = setup();
-    let amount = 500_000u64;
+    let amount = 500_000i128;
 
     client.create_trade(&seller, &buyer, &amount, &None, &OptionalMetadata::None);
 
@@ -613,16 +798,16 @@ fn test_analytics_window_metrics_track_trades() {
 #[test]
 fn test_analytics_active_trades() {
     let (env, token_addr, _, seller, buyer, _, client) = setup();
-    let amount = 1_000_000u64;
+    let amount = 1_000_000i128;
 
     let id = client.create_trade(&seller, &buyer, &amount, &None, &OptionalMetadata::None);
     let result = client.analytics_query(&crate::analytics::TimeWindow::AllTime);
     assert_eq!(result.all_time.active_trades, 1);
 
-    fund(&env, &token_addr, &buyer, &client.address, amount as i128);
+    fund(&env, &token_addr, &buyer, &client.address, amount);
     client.fund_trade(&id);
-    client.complete_trade(&id);
-    client.confirm_receipt(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
 
     let result2 = client.analytics_query(&crate::analytics::TimeWindow::AllTime);
     assert_eq!(result2.all_time.active_trades, 0);
@@ -679,71 +864,3504 @@ fn test_get_arbitrators_returns_all_registered() {
     let list = client.get_arbitrators();
     assert!(list.contains(&arb1));
     assert!(list.contains(&arb2));
-// Issue #122 — withdraw_fees (per-currency)
+}
+
 // ---------------------------------------------------------------------------
+// Issue #122 — fee withdrawals (per-currency)
+// ---------------------------------------------------------------------------
+
+/// A second token with `fees` booked as per-currency platform fees and held
+/// by the contract.
+fn seed_currency_fees(env: &Env, client: &StellarEscrowContractClient, fees: i128) -> Address {
+    let currency = basket_token(env, &client.address, fees);
+    env.as_contract(&client.address, || crate::storage::set_currency_fees(env, &currency, fees));
+    currency
+}
 
 #[test]
 fn test_withdraw_fees_per_currency_succeeds() {
-    let (env, token_addr, _, seller, buyer, _, client) = setup();
-    let amount = 1_000_000u64;
-    let id = client.create_trade(&seller, &buyer, &amount, &None, &OptionalMetadata::None);
-    fund(&env, &token_addr, &buyer, &client.address, amount as i128);
-    client.fund_trade(&id);
-    client.complete_trade(&id);
-    client.confirm_receipt(&id);
+    let (env, token_addr, _, _, _, _, client) = setup();
+    let currency = seed_currency_fees(&env, &client, 10_000);
+    let recipient = Address::generate(&env);
+    client.request_fee_withdrawal(&currency, &recipient, &10_000i128);
+    env.ledger().with_mut(|l| l.timestamp += client.get_fee_withdrawal_delay());
+    client.execute_fee_withdrawal();
+    assert_eq!(token::Client::new(&env, &currency).balance(&recipient), 10_000i128);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&recipient), 0);
+    env.as_contract(&client.address, || {
+        assert_eq!(crate::storage::get_currency_fees(&env, &currency), 0);
+    });
+}
 
-    // fee = 1% of 1_000_000 = 10_000 stroops accumulated in currency fees
-    let admin = Address::generate(&env);
-    // re-use the token_addr as the currency (USDC in tests)
+#[test]
+fn test_withdraw_fees_to_blocked_recipient_returns_error() {
+    let (env, _, _, _, _, _, client) = setup();
+    let currency = seed_currency_fees(&env, &client, 10_000);
     let recipient = Address::generate(&env);
-    // The admin stored during setup() is the one that initialized the contract.
-    // We need to call with the real admin — extract it via get_admin indirectly
-    // by using mock_all_auths (already active in setup).
-    let result = client.try_withdraw_fees(
-        &client.get_admin().unwrap(),
-        &token_addr,
-        &10_000i128,
-        &recipient,
+    token::StellarAssetClient::new(&env, &currency).set_authorized(&recipient, &false);
+    client.request_fee_withdrawal(&currency, &recipient, &10_000i128);
+    env.ledger().with_mut(|l| l.timestamp += client.get_fee_withdrawal_delay());
+    assert_eq!(
+        client.try_execute_fee_withdrawal(),
+        Err(Ok(crate::ContractError::AssetAuthorizationRequired))
     );
-    // currency fees are tracked via add_currency_fees; if 0 it returns NoFeesToWithdraw
-    // This test validates the happy path when fees exist.
-    // Since the existing test harness uses accumulated_fees (legacy), we verify
-    // the function at least doesn't panic on a zero-balance call.
-    assert!(result.is_err()); // no per-currency fees seeded → NoFeesToWithdraw
+    env.as_contract(&client.address, || {
+        assert_eq!(crate::storage::get_currency_fees(&env, &currency), 10_000);
+    });
 }
 
 #[test]
 fn test_withdraw_fees_unauthorized_fails() {
-    let (env, token_addr, _, _, _, _, client) = setup();
-    let attacker = Address::generate(&env);
-    let recipient = Address::generate(&env);
-    let result = client.try_withdraw_fees(&attacker, &token_addr, &1i128, &recipient);
+    let (env, _, _, _, _, _, client) = setup();
+    let currency = seed_currency_fees(&env, &client, 10_000);
+    env.set_auths(&[]);
+    let result = client.try_request_fee_withdrawal(&currency, &Address::generate(&env), &1i128);
     assert!(result.is_err());
 }
 
 #[test]
 fn test_withdraw_fees_zero_amount_fails() {
-    let (env, token_addr, _, _, _, _, client) = setup();
-    let recipient = Address::generate(&env);
-    let result = client.try_withdraw_fees(
-        &client.get_admin().unwrap(),
-        &token_addr,
-        &0i128,
-        &recipient,
+    let (env, _, _, _, _, _, client) = setup();
+    let currency = seed_currency_fees(&env, &client, 10_000);
+    assert_eq!(
+        client.try_request_fee_withdrawal(&currency, &Address::generate(&env), &0i128),
+        Err(Ok(crate::ContractError::NoFeesToWithdraw))
     );
-    assert!(result.is_err());
 }
 
 #[test]
 fn test_withdraw_fees_exceeds_balance_fails() {
     let (env, token_addr, _, _, _, _, client) = setup();
+    let currency = seed_currency_fees(&env, &client, 10_000);
+    assert_eq!(
+        client.try_request_fee_withdrawal(&currency, &Address::generate(&env), &10_001i128),
+        Err(Ok(crate::ContractError::NoFeesToWithdraw))
+    );
+    // Fees in one currency are not withdrawable in another.
+    assert_eq!(
+        client.try_request_fee_withdrawal(&token_addr, &Address::generate(&env), &1i128),
+        Err(Ok(crate::ContractError::NoFeesToWithdraw))
+    );
+}
+
+#[test]
+fn test_fee_in_other_currency_is_booked_per_currency() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    let eurc = basket_token(&env, &buyer, 1_000_000);
+    let id = client.create_trade(
+        &seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None,
+        &None, &TradeOptions { currency: Some(eurc.clone()), ..Default::default() },
+    );
+    fund(&env, &eurc, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+
+    assert_eq!(client.get_accumulated_fees(), 0);
+    env.as_contract(&client.address, || {
+        assert_eq!(crate::storage::get_currency_fees(&env, &eurc), 10_000);
+    });
     let recipient = Address::generate(&env);
-    // No fees accumulated → any positive amount exceeds balance
-    let result = client.try_withdraw_fees(
-        &client.get_admin().unwrap(),
-        &token_addr,
-        &1_000_000i128,
-        &recipient,
+    client.request_fee_withdrawal(&eurc, &recipient, &10_000i128);
+    env.ledger().with_mut(|l| l.timestamp += client.get_fee_withdrawal_delay());
+    client.execute_fee_withdrawal();
+    assert_eq!(token::Client::new(&env, &eurc).balance(&recipient), 10_000);
+}
+
+// ---------------------------------------------------------------------------
+// Arbitrator categories / specializations
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_set_and_get_arbitrator_categories() {
+    let (env, _, _, _, _, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    let cats = soroban_sdk::vec![&env, soroban_sdk::symbol_short!("elec"), soroban_sdk::symbol_short!("services")];
+    client.set_arbitrator_categories(&arbitrator, &cats);
+    assert_eq!(client.get_arbitrator_categories(&arbitrator), cats);
+    assert!(client.arbitrator_has_category(&arbitrator, &soroban_sdk::symbol_short!("elec")));
+    assert!(!client.arbitrator_has_category(&arbitrator, &soroban_sdk::symbol_short!("hi_value")));
+}
+
+#[test]
+fn test_set_arbitrator_categories_unregistered_fails() {
+    let (env, _, _, _, _, arbitrator, client) = setup();
+    let cats = soroban_sdk::vec![&env, soroban_sdk::symbol_short!("elec")];
+    assert!(client.try_set_arbitrator_categories(&arbitrator, &cats).is_err());
+}
+
+#[test]
+fn test_create_trade_with_matching_category() {
+    let (env, _, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    client.set_arbitrator_categories(&arbitrator, &soroban_sdk::vec![&env, soroban_sdk::symbol_short!("elec")]);
+    let id = client.create_trade(
+        &seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None,
+        &None, &TradeOptions { category: Some(soroban_sdk::symbol_short!("elec")), ..Default::default() },
     );
-    assert!(result.is_err());
+    assert_eq!(client.get_trade_category(&id), Some(soroban_sdk::symbol_short!("elec")));
+}
+
+#[test]
+fn test_create_trade_with_mismatched_category_fails() {
+    let (env, _, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    client.set_arbitrator_categories(&arbitrator, &soroban_sdk::vec![&env, soroban_sdk::symbol_short!("services")]);
+    let result = client.try_create_trade(
+        &seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None,
+        &None, &TradeOptions { category: Some(soroban_sdk::symbol_short!("elec")), ..Default::default() },
+    );
+    assert_eq!(result, Err(Ok(crate::ContractError::ArbitratorCategoryMismatch)));
+}
+
+// ---------------------------------------------------------------------------
+// Arbitrator caseload cap
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_dispute_tracks_arbitrator_caseload() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    client.accept_assignment(&id);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.raise_dispute(&buyer, &id);
+    assert_eq!(client.get_arbitrator_open_disputes(&arbitrator), 1);
+    client.resolve_dispute(&id, &crate::DisputeResolution::ReleaseToSeller, &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]));
+    assert_eq!(client.get_arbitrator_open_disputes(&arbitrator), 0);
+}
+
+#[test]
+fn test_assignment_rejected_when_caseload_full() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    client.set_arbitrator_caseload_cap(&1u32);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    client.accept_assignment(&id);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.raise_dispute(&buyer, &id);
+
+    let result = client.try_create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    assert_eq!(result, Err(Ok(crate::ContractError::ArbitratorCaseloadFull)));
+}
+
+// ---------------------------------------------------------------------------
+// Arbitrator assignment acceptance
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_fund_requires_accepted_assignment() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    assert_eq!(client.try_fund_trade(&id), Err(Ok(crate::ContractError::AssignmentNotAccepted)));
+
+    client.accept_assignment(&id);
+    assert!(client.get_assignment(&id).unwrap().accepted);
+    client.fund_trade(&id);
+    assert_eq!(client.get_trade(&id).status, TradeStatus::Funded);
+}
+
+#[test]
+fn test_accept_assignment_twice_fails() {
+    let (_, _, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    client.accept_assignment(&id);
+    assert_eq!(client.try_accept_assignment(&id), Err(Ok(crate::ContractError::AssignmentAlreadyAccepted)));
+}
+
+#[test]
+fn test_accept_assignment_after_grace_period_fails() {
+    let (env, _, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    env.ledger().with_mut(|l| l.timestamp += crate::types::ASSIGNMENT_GRACE_PERIOD + 1);
+    assert_eq!(client.try_accept_assignment(&id), Err(Ok(crate::ContractError::AssignmentExpired)));
+}
+
+// ---------------------------------------------------------------------------
+// Contract semantic version
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_get_version_set_on_initialize() {
+    let (_, _, _, _, _, _, client) = setup();
+    let v = client.get_version();
+    assert_eq!(v.major, crate::upgrade::CONTRACT_VERSION_MAJOR);
+    assert_eq!(v.minor, crate::upgrade::CONTRACT_VERSION_MINOR);
+    assert_eq!(v.patch, crate::upgrade::CONTRACT_VERSION_PATCH);
+    assert_eq!(v.wasm_hash, None);
+}
+
+// ---------------------------------------------------------------------------
+// Trade schema versioning
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_new_trades_use_current_schema() {
+    let (_, _, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    assert_eq!(client.get_trade_schema_version(&id), crate::types::TRADE_SCHEMA_VERSION);
+    assert_eq!(client.try_migrate_trade(&id), Err(Ok(crate::ContractError::MigrationAlreadyApplied)));
+}
+
+/// Schema 1 layout of `trade`, with `u64` amounts.
+fn legacy_trade(trade: &crate::types::Trade) -> crate::types::TradeV1 {
+    crate::types::TradeV1 {
+        id: trade.id,
+        seller: trade.seller.clone(),
+        buyer: trade.buyer.clone(),
+        amount: trade.amount as u64,
+        fee: trade.fee as u64,
+        arbitrator: trade.arbitrator.clone(),
+        status: trade.status.clone(),
+        expiry_time: trade.expiry_time,
+        currency: trade.currency.clone(),
+        metadata: trade.metadata.clone(),
+        trigger: trade.trigger.clone(),
+    }
+}
+
+#[test]
+fn test_migrate_legacy_bare_trade_entry() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    let trade = client.get_trade(&id);
+    // Simulate an entry written before the versioned envelope existed.
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(&crate::storage::DataKey::Trade(id), &legacy_trade(&trade));
+    });
+    assert_eq!(client.get_trade_schema_version(&id), 0);
+    assert_eq!(client.get_trade(&id), trade);
+
+    client.migrate_trade(&id);
+    assert_eq!(client.get_trade_schema_version(&id), crate::types::TRADE_SCHEMA_VERSION);
+    assert_eq!(client.get_trade(&id), trade);
+}
+
+#[test]
+fn test_migrate_v1_trade_widens_amounts() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    let trade = client.get_trade(&id);
+    env.as_contract(&client.address, || {
+        let entry = crate::types::VersionedTrade::V1(legacy_trade(&trade));
+        env.storage().persistent().set(&crate::storage::DataKey::Trade(id), &entry);
+    });
+    assert_eq!(client.get_trade_schema_version(&id), 1);
+    assert_eq!(client.get_trade(&id).amount, 1_000_000i128);
+    assert_eq!(client.get_trade(&id).fee, 10_000i128);
+
+    client.migrate_trade(&id);
+    assert_eq!(client.get_trade_schema_version(&id), 2);
+    assert_eq!(client.get_trade(&id), trade);
+}
+
+// ---------------------------------------------------------------------------
+// Consolidated config
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_config_tracks_counter_and_fee() {
+    let (_, token_addr, admin, seller, buyer, _, client) = setup();
+    let cfg = client.get_config();
+    assert_eq!(cfg.admin, admin);
+    assert_eq!(cfg.usdc_token, token_addr);
+    assert_eq!(cfg.fee_bps, 100);
+    assert_eq!(cfg.trade_counter, 0);
+
+    client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    client.update_fee(&250u32, &0u32);
+    let cfg = client.get_config();
+    assert_eq!(cfg.trade_counter, 1);
+    assert_eq!(cfg.fee_bps, 250);
+}
+
+#[test]
+fn test_upgrade_reads_legacy_core_keys() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let token_addr = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let contract_id = env.register_contract(None, StellarEscrowContract);
+    let client = StellarEscrowContractClient::new(&env, &contract_id);
+    // Core keys as the baseline contract wrote them, before `Config`.
+    env.as_contract(&contract_id, || {
+        let instance = env.storage().instance();
+        instance.set(&soroban_sdk::symbol_short!("INIT"), &true);
+        instance.set(&soroban_sdk::symbol_short!("ADMIN"), &admin);
+        instance.set(&soroban_sdk::symbol_short!("USDC"), &token_addr);
+        instance.set(&soroban_sdk::symbol_short!("FEE_BPS"), &100u32);
+        instance.set(&soroban_sdk::symbol_short!("COUNTER"), &7u64);
+        instance.set(&soroban_sdk::symbol_short!("ACC_FEES"), &5_000u64);
+    });
+
+    let cfg = client.get_config();
+    assert_eq!(cfg.admin, admin);
+    assert_eq!(cfg.usdc_token, token_addr);
+    assert_eq!(cfg.trade_counter, 7);
+    assert_eq!(cfg.accumulated_fees, 5_000);
+    assert_eq!(
+        client.try_initialize(&Address::generate(&env), &token_addr, &0u32),
+        Err(Ok(crate::ContractError::AlreadyInitialized))
+    );
+
+    // The first admin write persists the consolidated entry.
+    client.update_fee(&250u32, &0u32);
+    let cfg = client.get_config();
+    assert_eq!(cfg.admin, admin);
+    assert_eq!(cfg.fee_bps, 250);
+    assert_eq!(cfg.trade_counter, 7);
+}
+
+// ---------------------------------------------------------------------------
+// Fee simulation
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_simulate_fee_matches_created_trade() {
+    let (_, _, _, seller, buyer, _, client) = setup();
+    let quote = client.simulate_fee(&1_000_000i128, &seller, &None);
+    assert_eq!(quote.fee_bps, 100);
+    assert_eq!(quote.fee, 10_000);
+    assert_eq!(quote.payout, 990_000);
+
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    assert_eq!(client.get_trade(&id).fee, quote.fee);
+}
+
+#[test]
+fn test_estimate_payout_reflects_fee_config() {
+    let (_, token_addr, _, seller, _, _, client) = setup();
+    assert_eq!(client.estimate_payout(&seller, &1_000_000i128, &token_addr), 990_000);
+    client.set_fee_cap(&5_000i128);
+    assert_eq!(client.estimate_payout(&seller, &1_000_000i128, &token_addr), 995_000);
+    assert_eq!(
+        client.try_estimate_payout(&seller, &-1i128, &token_addr),
+        Err(Ok(crate::ContractError::InvalidAmount))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Lightweight status queries
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_get_trade_status_and_has_trade() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    assert!(!client.has_trade(&1u64));
+    assert!(client.try_get_trade_status(&1u64).is_err());
+
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    assert!(client.has_trade(&id));
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Created);
+
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Funded);
+}
+
+#[test]
+fn test_get_trades_skips_unknown_ids() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    let a = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    let b = client.create_trade(&seller, &buyer, &2_000_000i128, &None, &OptionalMetadata::None);
+    let trades = client.get_trades(&soroban_sdk::vec![&env, b, 99u64, a]);
+    assert_eq!(trades.len(), 2);
+    assert_eq!(trades.get(0).unwrap().id, b);
+    assert_eq!(trades.get(1).unwrap().id, a);
+
+    let mut too_many = soroban_sdk::Vec::new(&env);
+    for i in 0..=crate::MAX_BULK_TRADES as u64 {
+        too_many.push_back(i);
+    }
+    assert_eq!(client.try_get_trades(&too_many), Err(Ok(crate::ContractError::BatchTooLarge)));
+}
+
+// ---------------------------------------------------------------------------
+// Trusted callers
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_create_trade_via_requires_trusted_caller() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    let router = Address::generate(&env);
+    assert_eq!(
+        client.try_create_trade_via(&router, &seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None),
+        Err(Ok(crate::ContractError::CallerNotTrusted))
+    );
+
+    client.set_trusted_caller(&router, &true);
+    assert!(client.is_trusted_caller(&router));
+    let id = client.create_trade_via(&router, &seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    assert_eq!(client.get_trade(&id).seller, seller);
+
+    client.set_trusted_caller(&router, &false);
+    assert!(client
+        .try_create_trade_via(&router, &seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None)
+        .is_err());
+}
+
+#[test]
+fn test_trusted_callers_only_blocks_direct_creation() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    let router = Address::generate(&env);
+    client.set_trusted_caller(&router, &true);
+    client.set_trusted_callers_only(&true);
+    assert!(client.is_trusted_callers_only());
+    assert_eq!(
+        client.try_create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None),
+        Err(Ok(crate::ContractError::DirectCreationDisabled))
+    );
+    client.create_trade_via(&router, &seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+
+    client.set_trusted_callers_only(&false);
+    client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+}
+
+// ---------------------------------------------------------------------------
+// Dual-authorization trade creation
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_create_trade_dual_auth_requires_both_parties() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    let terms = soroban_sdk::BytesN::from_array(&env, &[9u8; 32]);
+    let id = client.create_trade_dual_auth(&seller, &buyer, &1_000_000i128, &terms, &None, &OptionalMetadata::None);
+    let signers: std::vec::Vec<Address> = env.auths().into_iter().map(|(signer, _)| signer).collect();
+    assert!(signers.contains(&seller));
+    assert!(signers.contains(&buyer));
+    assert_eq!(client.get_trade_terms_hash(&id), Some(terms));
+
+    let single = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    assert_eq!(client.get_trade_terms_hash(&single), None);
+}
+
+// ---------------------------------------------------------------------------
+// Signed acceptance
+// ---------------------------------------------------------------------------
+
+fn sign_acceptance(
+    env: &Env,
+    client: &StellarEscrowContractClient,
+    key: &ed25519_dalek::SigningKey,
+    trade_id: u64,
+    terms: &soroban_sdk::BytesN<32>,
+    nonce: u64,
+) -> soroban_sdk::BytesN<64> {
+    use ed25519_dalek::Signer;
+    let payload: std::vec::Vec<u8> = client.get_acceptance_payload(&trade_id, terms, &nonce).iter().collect();
+    soroban_sdk::BytesN::from_array(env, &key.sign(&payload).to_bytes())
+}
+
+#[test]
+fn test_accept_trade_signed_records_acceptance() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+    client.set_acceptance_key(&buyer, &soroban_sdk::BytesN::from_array(&env, &key.verifying_key().to_bytes()));
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    let terms = soroban_sdk::BytesN::from_array(&env, &[3u8; 32]);
+
+    let signature = sign_acceptance(&env, &client, &key, id, &terms, 1);
+    client.accept_trade_signed(&id, &terms, &1u64, &signature);
+    let acceptance = client.get_buyer_acceptance(&id).unwrap();
+    assert_eq!(acceptance.terms_hash, terms);
+    assert_eq!(acceptance.nonce, 1);
+
+    // An amendment is accepted with a higher nonce; replaying the old one fails.
+    let amended = soroban_sdk::BytesN::from_array(&env, &[4u8; 32]);
+    let signature2 = sign_acceptance(&env, &client, &key, id, &amended, 2);
+    client.accept_trade_signed(&id, &amended, &2u64, &signature2);
+    assert_eq!(client.get_buyer_acceptance(&id).unwrap().terms_hash, amended);
+    assert_eq!(
+        client.try_accept_trade_signed(&id, &terms, &1u64, &signature),
+        Err(Ok(crate::ContractError::StaleAcceptanceNonce))
+    );
+}
+
+#[test]
+fn test_accept_trade_signed_rejects_bad_signature() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    let terms = soroban_sdk::BytesN::from_array(&env, &[3u8; 32]);
+    let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+    let signature = sign_acceptance(&env, &client, &key, id, &terms, 1);
+    assert_eq!(
+        client.try_accept_trade_signed(&id, &terms, &1u64, &signature),
+        Err(Ok(crate::ContractError::NoAcceptanceKey))
+    );
+
+    let other = ed25519_dalek::SigningKey::from_bytes(&[8u8; 32]);
+    client.set_acceptance_key(&buyer, &soroban_sdk::BytesN::from_array(&env, &other.verifying_key().to_bytes()));
+    assert!(client.try_accept_trade_signed(&id, &terms, &1u64, &signature).is_err());
+    assert_eq!(client.get_buyer_acceptance(&id), None);
+}
+
+// ---------------------------------------------------------------------------
+// Merkle settlement batches
+// ---------------------------------------------------------------------------
+
+fn batch_node(env: &Env, left: &soroban_sdk::BytesN<32>, right: &soroban_sdk::BytesN<32>) -> soroban_sdk::BytesN<32> {
+    let mut data = soroban_sdk::Bytes::from_array(env, &[1u8]);
+    data.append(&left.clone().into());
+    data.append(&right.clone().into());
+    env.crypto().sha256(&data).to_bytes()
+}
+
+/// Commit a three-leaf batch funded by `operator` and return its id, leaves
+/// and the proof for each leaf.
+fn three_leaf_batch(
+    env: &Env,
+    client: &StellarEscrowContractClient,
+    operator: &Address,
+    currency: &Address,
+    sellers: [&Address; 3],
+) -> (u64, std::vec::Vec<crate::BatchLeaf>, std::vec::Vec<soroban_sdk::Vec<soroban_sdk::BytesN<32>>>) {
+    let leaves: std::vec::Vec<crate::BatchLeaf> = sellers
+        .iter()
+        .enumerate()
+        .map(|(i, seller)| crate::BatchLeaf {
+            batch_id: 1,
+            index: i as u32,
+            seller: (*seller).clone(),
+            amount: 100 * (i as i128 + 1),
+        })
+        .collect();
+    let h: std::vec::Vec<_> = leaves.iter().map(|l| client.get_batch_leaf_hash(l)).collect();
+    let left = batch_node(env, &h[0], &h[1]);
+    let right = batch_node(env, &h[2], &h[2]);
+    let root = batch_node(env, &left, &right);
+    let proofs = std::vec![
+        soroban_sdk::vec![env, h[1].clone(), right.clone()],
+        soroban_sdk::vec![env, h[0].clone(), right],
+        soroban_sdk::vec![env, h[2].clone(), left],
+    ];
+    let id = client.commit_settlement_batch(operator, currency, &root, &600i128, &3u32, &3_600u64);
+    (id, leaves, proofs)
+}
+
+#[test]
+fn test_settlement_batch_claims_with_proofs() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    let other = Address::generate(&env);
+    let (id, leaves, proofs) = three_leaf_batch(&env, &client, &buyer, &token_addr, [&seller, &arbitrator, &other]);
+    assert_eq!(id, 1);
+    assert_eq!(client.get_total_locked(&token_addr), 600);
+
+    client.claim_batch_payout(&leaves[2], &proofs[2]);
+    client.claim_batch_payout(&leaves[0], &proofs[0]);
+    let tok = token::Client::new(&env, &token_addr);
+    assert_eq!(tok.balance(&other), 300);
+    assert_eq!(tok.balance(&seller), 100);
+    assert!(client.is_batch_leaf_claimed(&id, &0u32));
+    assert_eq!(client.get_settlement_batch(&id).claimed, 400);
+    assert_eq!(
+        client.try_claim_batch_payout(&leaves[0], &proofs[0]),
+        Err(Ok(crate::ContractError::BatchLeafClaimed))
+    );
+
+    let mut inflated = leaves[1].clone();
+    inflated.amount = 250;
+    assert_eq!(
+        client.try_claim_batch_payout(&inflated, &proofs[1]),
+        Err(Ok(crate::ContractError::InvalidMerkleProof))
+    );
+}
+
+#[test]
+fn test_settlement_batch_reclaim_after_window() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    let other = Address::generate(&env);
+    let (id, leaves, proofs) = three_leaf_batch(&env, &client, &buyer, &token_addr, [&seller, &arbitrator, &other]);
+    client.claim_batch_payout(&leaves[1], &proofs[1]);
+    assert_eq!(
+        client.try_reclaim_settlement_batch(&id),
+        Err(Ok(crate::ContractError::SettlementBatchOpen))
+    );
+
+    env.ledger().with_mut(|l| l.timestamp += 3_601);
+    assert_eq!(
+        client.try_claim_batch_payout(&leaves[0], &proofs[0]),
+        Err(Ok(crate::ContractError::SettlementBatchClosed))
+    );
+    let before = token::Client::new(&env, &token_addr).balance(&buyer);
+    assert_eq!(client.reclaim_settlement_batch(&id), 400);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&buyer), before + 400);
+    assert_eq!(client.get_total_locked(&token_addr), 0);
+}
+
+// ---------------------------------------------------------------------------
+// Hashed trades
+// ---------------------------------------------------------------------------
+
+fn hashed_details(env: &Env, seller: &Address, buyer: &Address, amount: i128) -> crate::HashedTradeDetails {
+    crate::HashedTradeDetails {
+        seller: seller.clone(),
+        buyer: buyer.clone(),
+        amount,
+        salt: soroban_sdk::BytesN::from_array(env, &[5u8; 32]),
+    }
+}
+
+#[test]
+fn test_hashed_trade_stores_only_commitment_until_released() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    let details = hashed_details(&env, &seller, &buyer, 1_000_000);
+    let (commitment, bucket) = client.get_hashed_trade_commitment(&details);
+    assert_eq!(bucket, 7);
+    let id = client.create_hashed_trade(&commitment, &bucket, &None, &arbitrator);
+
+    let mut wrong = details.clone();
+    wrong.amount = 999_999;
+    assert_eq!(client.try_fund_hashed_trade(&id, &wrong), Err(Ok(crate::ContractError::CommitmentMismatch)));
+
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_hashed_trade(&id, &details);
+    let record = client.get_hashed_trade(&id);
+    assert_eq!(record.status, TradeStatus::Funded);
+    assert_eq!(record.revealed, None);
+
+    client.release_hashed_trade(&id, &details);
+    assert_eq!(client.get_hashed_trade(&id).status, TradeStatus::Settled);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&seller), 990_000);
+    assert_eq!(client.get_total_locked(&token_addr), 0);
+}
+
+#[test]
+fn test_hashed_trade_dispute_reveals_details_to_arbitrator() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    let details = hashed_details(&env, &seller, &buyer, 1_000_000);
+    let (commitment, bucket) = client.get_hashed_trade_commitment(&details);
+    let id = client.create_hashed_trade(&commitment, &bucket, &None, &arbitrator);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_hashed_trade(&id, &details);
+    let before = token::Client::new(&env, &token_addr).balance(&buyer);
+
+    client.raise_hashed_dispute(&id, &details, &buyer);
+    assert_eq!(client.get_hashed_trade(&id).revealed, Some(details));
+    client.resolve_hashed_dispute(&id, &crate::DisputeResolution::ReleaseToBuyer);
+    assert_eq!(client.get_hashed_trade(&id).status, TradeStatus::Settled);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&buyer), before + 990_000);
+    assert_eq!(
+        client.try_resolve_hashed_dispute(&id, &crate::DisputeResolution::ReleaseToSeller),
+        Err(Ok(crate::ContractError::InvalidStatus))
+    );
+}
+
+#[test]
+fn test_hashed_trade_charges_fee_rate_fixed_at_creation() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    let details = hashed_details(&env, &seller, &buyer, 1_000_000);
+    let (commitment, bucket) = client.get_hashed_trade_commitment(&details);
+    let id = client.create_hashed_trade(&commitment, &bucket, &None, &arbitrator);
+    assert_eq!(client.get_hashed_trade(&id).fee_bps, 100);
+
+    client.update_fee(&500u32, &0u32);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_hashed_trade(&id, &details);
+    client.release_hashed_trade(&id, &details);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&seller), 990_000);
+}
+
+#[test]
+fn test_frozen_hashed_trade_cannot_move() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    let details = hashed_details(&env, &seller, &buyer, 1_000_000);
+    let (commitment, bucket) = client.get_hashed_trade_commitment(&details);
+    let id = client.create_hashed_trade(&commitment, &bucket, &None, &arbitrator);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_hashed_trade(&id, &details);
+
+    client.freeze_hashed_trade(&id, &7u32);
+    assert_eq!(
+        client.try_release_hashed_trade(&id, &details),
+        Err(Ok(crate::ContractError::TradeFrozen))
+    );
+    assert_eq!(
+        client.try_raise_hashed_dispute(&id, &details, &buyer),
+        Err(Ok(crate::ContractError::TradeFrozen))
+    );
+    client.unfreeze_hashed_trade(&id);
+    client.release_hashed_trade(&id, &details);
+    assert_eq!(client.get_hashed_trade(&id).status, TradeStatus::Settled);
+}
+
+#[test]
+fn test_hashed_trade_rejects_party_as_arbitrator() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    client.register_arbitrator(&seller);
+    let details = hashed_details(&env, &seller, &buyer, 1_000_000);
+    let (commitment, bucket) = client.get_hashed_trade_commitment(&details);
+    let id = client.create_hashed_trade(&commitment, &bucket, &None, &seller);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    assert_eq!(
+        client.try_fund_hashed_trade(&id, &details),
+        Err(Ok(crate::ContractError::ArbitratorIsParty))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Charity donations
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_confirm_receipt_with_round_down_donation() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let charity = Address::generate(&env);
+    client.set_charity(&Some(charity.clone()));
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+
+    // 990_000 net rounds down to 950_000; the 40_000 remainder is donated.
+    client.confirm_receipt_with_donation(&id, &None, &crate::DonationChoice::RoundDown(50_000));
+    let tok = token::Client::new(&env, &token_addr);
+    assert_eq!(tok.balance(&seller), 950_000);
+    assert_eq!(tok.balance(&charity), 40_000);
+    assert_eq!(client.get_total_donated(&token_addr), 40_000);
+    assert_eq!(client.get_accumulated_fees(), 10_000);
+}
+
+#[test]
+fn test_donation_requires_charity_and_respects_cap() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    assert_eq!(
+        client.try_confirm_receipt_with_donation(&id, &None, &crate::DonationChoice::Bps(100)),
+        Err(Ok(crate::ContractError::NoCharity))
+    );
+
+    client.set_charity(&Some(Address::generate(&env)));
+    assert_eq!(
+        client.try_confirm_receipt_with_donation(&id, &None, &crate::DonationChoice::Bps(crate::MAX_DONATION_BPS + 1)),
+        Err(Ok(crate::ContractError::InvalidDonation))
+    );
+    client.confirm_receipt_with_donation(&id, &None, &crate::DonationChoice::Bps(100));
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&seller), 990_000 - 9_900);
+}
+
+// ---------------------------------------------------------------------------
+// White-label partners
+// ---------------------------------------------------------------------------
+
+fn partner_trade(env: &Env, token_addr: &Address, client: &StellarEscrowContractClient, seller: &Address, buyer: &Address, partner: u32) -> u64 {
+    let id = client.create_trade(
+        seller, buyer, &1_000_000i128, &None, &OptionalMetadata::None,
+        &None, &TradeOptions { partner: Some(partner), ..Default::default() },
+    );
+    fund(env, token_addr, buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+    id
+}
+
+#[test]
+fn test_partner_fee_on_top_of_platform_fee() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let recipient = Address::generate(&env);
+    client.set_partner(&7u32, &Some(crate::Partner { recipient: recipient.clone(), fee_bps: 50, replaces_platform_fee: false }));
+
+    let id = partner_trade(&env, &token_addr, &client, &seller, &buyer, 7);
+    assert_eq!(client.get_trade(&id).fee, 15_000);
+    assert_eq!(client.get_trade_partner(&id).unwrap().fee, 5_000);
+    let tok = token::Client::new(&env, &token_addr);
+    assert_eq!(tok.balance(&seller), 985_000);
+    assert_eq!(tok.balance(&recipient), 5_000);
+    assert_eq!(client.get_partner_earnings(&7u32, &token_addr), 5_000);
+    assert_eq!(client.get_accumulated_fees(), 10_000);
+}
+
+#[test]
+fn test_partner_fee_replacing_platform_fee() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let recipient = Address::generate(&env);
+    assert_eq!(
+        client.try_set_partner(&1u32, &Some(crate::Partner { recipient: recipient.clone(), fee_bps: 10_001, replaces_platform_fee: true })),
+        Err(Ok(crate::ContractError::InvalidPartnerFee))
+    );
+    assert_eq!(
+        client.try_create_trade(
+            &seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None,
+            &None, &TradeOptions { partner: Some(1u32), ..Default::default() },
+        ),
+        Err(Ok(crate::ContractError::PartnerNotFound))
+    );
+
+    client.set_partner(&1u32, &Some(crate::Partner { recipient: recipient.clone(), fee_bps: 200, replaces_platform_fee: true }));
+    partner_trade(&env, &token_addr, &client, &seller, &buyer, 1);
+    let tok = token::Client::new(&env, &token_addr);
+    assert_eq!(tok.balance(&seller), 980_000);
+    assert_eq!(tok.balance(&recipient), 20_000);
+    assert_eq!(client.get_accumulated_fees(), 0);
+}
+
+// ---------------------------------------------------------------------------
+// Referrals
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_referrer_earns_share_of_referred_buyer_fees() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let referrer = Address::generate(&env);
+    let payout = Address::generate(&env);
+    client.register_referrer(&referrer, &payout, &2_000u32);
+    assert_eq!(client.get_referrer(&referrer).unwrap().status, crate::ReferrerStatus::Pending);
+    assert_eq!(client.try_attribute_referral(&buyer, &referrer), Err(Ok(crate::ContractError::ReferrerNotApproved)));
+
+    client.set_referrer_status(&referrer, &crate::ReferrerStatus::Approved);
+    client.attribute_referral(&buyer, &referrer);
+    assert_eq!(client.get_referred_by(&buyer), Some(referrer.clone()));
+    assert_eq!(client.try_attribute_referral(&buyer, &referrer), Err(Ok(crate::ContractError::AlreadyReferred)));
+
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+
+    // 20% of the 10_000 platform fee.
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&payout), 2_000);
+    assert_eq!(client.get_referral_earnings(&referrer, &token_addr), 2_000);
+    assert_eq!(client.get_accumulated_fees(), 8_000);
+}
+
+#[test]
+fn test_suspended_referrer_stops_earning() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let referrer = Address::generate(&env);
+    assert_eq!(
+        client.try_register_referrer(&referrer, &referrer, &(crate::MAX_REFERRAL_SHARE_BPS + 1)),
+        Err(Ok(crate::ContractError::InvalidReferralShare))
+    );
+    client.register_referrer(&referrer, &referrer, &2_000u32);
+    client.set_referrer_status(&referrer, &crate::ReferrerStatus::Approved);
+    client.attribute_referral(&buyer, &referrer);
+    client.set_referrer_status(&referrer, &crate::ReferrerStatus::Suspended);
+
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&referrer), 0);
+    assert_eq!(client.get_accumulated_fees(), 10_000);
+}
+
+#[test]
+fn test_fee_breakdown_matches_settlement() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let partner = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    client.set_partner(&3u32, &Some(crate::Partner { recipient: partner.clone(), fee_bps: 50, replaces_platform_fee: false }));
+    client.register_referrer(&referrer, &referrer, &1_000u32);
+    client.set_referrer_status(&referrer, &crate::ReferrerStatus::Approved);
+    client.attribute_referral(&buyer, &referrer);
+
+    let id = client.create_trade(
+        &seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None,
+        &None, &TradeOptions { partner: Some(3u32), ..Default::default() },
+    );
+    let breakdown = client.get_fee_breakdown(&id);
+    assert_eq!(breakdown.fee, 15_000);
+    assert_eq!(breakdown.partner_fee, 5_000);
+    assert_eq!(breakdown.referral_fee, 1_000);
+    assert_eq!(breakdown.platform_fee, 9_000);
+    assert_eq!(breakdown.arbitrator_fee, 0);
+    assert_eq!(breakdown.payout, 985_000);
+
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+    let tok = token::Client::new(&env, &token_addr);
+    assert_eq!(tok.balance(&seller), breakdown.payout);
+    assert_eq!(tok.balance(&partner), breakdown.partner_fee);
+    assert_eq!(tok.balance(&referrer), breakdown.referral_fee);
+    assert_eq!(client.get_accumulated_fees(), breakdown.platform_fee);
+}
+
+// ---------------------------------------------------------------------------
+// Paginated trade listing
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_list_trades_pages_by_id() {
+    let (_, _, _, seller, buyer, _, client) = setup();
+    for i in 1..=5u64 {
+        client.create_trade(&seller, &buyer, &(i128::from(i) * 1_000), &None, &OptionalMetadata::None);
+    }
+    let page = client.list_trades(&1u64, &2u32);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().id, 1);
+    assert_eq!(page.get(1).unwrap().id, 2);
+
+    let next = client.list_trades(&(page.get(1).unwrap().id + 1), &10u32);
+    assert_eq!(next.len(), 3);
+    assert_eq!(next.get(2).unwrap().amount, 5_000);
+    assert_eq!(client.list_trades(&6u64, &10u32).len(), 0);
+}
+
+#[test]
+fn test_get_trades_range_for_indexers() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    for i in 1..=4u64 {
+        client.create_trade(&seller, &buyer, &(i128::from(i) * 1_000), &None, &OptionalMetadata::None);
+    }
+    let range = client.get_trades_range(&2u64, &10u64);
+    assert_eq!(range.len(), 3);
+    assert_eq!(range.get(0).unwrap().id, 2);
+    assert_eq!(range.get(2).unwrap().amount, 4_000);
+    assert_eq!(range.get(0).unwrap().created_at, Some(u64::from(env.ledger().sequence())));
+
+    assert_eq!(client.try_get_trades_range(&3u64, &2u64), Err(Ok(crate::ContractError::InvalidTradeRange)));
+    assert_eq!(
+        client.try_get_trades_range(&1u64, &crate::queries::MAX_TRADE_RANGE.saturating_add(1)),
+        Err(Ok(crate::ContractError::InvalidTradeRange))
+    );
+}
+
+#[test]
+fn test_export_config_and_trades() {
+    let (env, token_addr, admin, seller, buyer, _, client) = setup();
+    client.set_fee_cap(&5_000i128);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.create_trade(&seller, &buyer, &2_000i128, &None, &OptionalMetadata::None);
+
+    let config = client.export_config();
+    assert_eq!(config.admin, admin);
+    assert_eq!(config.usdc_token, token_addr);
+    assert_eq!(config.fee_bps, 100);
+    assert_eq!(config.fee_cap, 5_000);
+    assert_eq!(config.trade_counter, 2);
+    assert_eq!(config.currencies.len(), 1);
+    assert_eq!(config.currencies.get(0).unwrap().currency, token_addr);
+    assert_eq!(config.currencies.get(0).unwrap().locked, 1_000_000);
+
+    let trades = client.export_trades(&1u64, &10u32);
+    assert_eq!(trades.len(), 2);
+    assert_eq!(trades.get(0).unwrap().trade, client.get_trade(&id));
+    assert!(trades.get(0).unwrap().created_at.is_some());
+    assert_eq!(client.export_trades(&2u64, &10u32).get(0).unwrap().trade.amount, 2_000);
+}
+
+#[test]
+fn test_export_config_lists_locked_per_currency() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let eurc = basket_token(&env, &buyer, 500_000);
+    let usd = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    let eur = client.create_trade(
+        &seller, &buyer, &500_000i128, &None, &OptionalMetadata::None,
+        &None, &TradeOptions { currency: Some(eurc.clone()), ..Default::default() },
+    );
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    fund(&env, &eurc, &buyer, &client.address, 500_000);
+    client.fund_trade(&usd);
+    client.fund_trade(&eur);
+
+    let currencies = client.export_config().currencies;
+    assert_eq!(currencies.len(), 2);
+    assert_eq!(currencies.get(0).unwrap().currency, token_addr);
+    assert_eq!(currencies.get(0).unwrap().locked, 1_000_000);
+    assert_eq!(currencies.get(1).unwrap().currency, eurc);
+    assert_eq!(currencies.get(1).unwrap().locked, 500_000);
+}
+
+// ---------------------------------------------------------------------------
+// Total escrowed liability
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_total_locked_follows_fund_and_release() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    assert_eq!(client.get_total_locked(&token_addr), 0);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    assert_eq!(client.get_total_locked(&token_addr), 1_000_000);
+
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+    assert_eq!(client.get_total_locked(&token_addr), 0);
+}
+
+#[test]
+fn test_total_locked_is_per_currency() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let eurc = basket_token(&env, &buyer, 500_000);
+    let id = client.create_trade(
+        &seller, &buyer, &500_000i128, &None, &OptionalMetadata::None,
+        &None, &TradeOptions { currency: Some(eurc.clone()), ..Default::default() },
+    );
+    fund(&env, &eurc, &buyer, &client.address, 500_000);
+    client.fund_trade(&id);
+    assert_eq!(client.get_total_locked(&eurc), 500_000);
+    assert_eq!(client.get_total_locked(&token_addr), 0);
+}
+
+// ---------------------------------------------------------------------------
+// Solvency reconciliation
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_verify_solvency_balanced() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    let report = client.verify_solvency(&token_addr);
+    assert_eq!(report.balance, 1_000_000);
+    assert_eq!(report.total_locked, 1_000_000);
+    assert_eq!(report.surplus, 0);
+    assert!(report.solvent);
+}
+
+#[test]
+fn test_verify_solvency_detects_shortfall() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    // Drain part of the balance behind the contract's back.
+    let sink = Address::generate(&env);
+    token::Client::new(&env, &token_addr).transfer(&client.address, &sink, &1_000i128);
+    let report = client.verify_solvency(&token_addr);
+    assert_eq!(report.surplus, -1_000);
+    assert!(!report.solvent);
+}
+
+#[test]
+fn test_verify_solvency_is_per_currency() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let eurc = basket_token(&env, &buyer, 1_000_000);
+    let id = client.create_trade(
+        &seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None,
+        &None, &TradeOptions { currency: Some(eurc.clone()), ..Default::default() },
+    );
+    fund(&env, &eurc, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+
+    let report = client.verify_solvency(&eurc);
+    assert_eq!(report.balance, 1_000_000);
+    assert_eq!(report.total_locked, 1_000_000);
+    assert!(report.solvent);
+    // Escrow in another token does not count towards the platform token.
+    let usdc = client.verify_solvency(&token_addr);
+    assert_eq!(usdc.balance, 0);
+    assert_eq!(usdc.total_locked, 0);
+    assert!(usdc.solvent);
+}
+
+// ---------------------------------------------------------------------------
+// Time-locked emergency withdrawal
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_emergency_withdrawal_requires_delay() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+
+    let rescue = Address::generate(&env);
+    let executable_at = client.initiate_emergency_withdrawal(&rescue);
+    assert_eq!(executable_at, env.ledger().timestamp() + crate::EMERGENCY_WITHDRAWAL_DELAY);
+    assert_eq!(
+        client.try_initiate_emergency_withdrawal(&rescue),
+        Err(Ok(crate::ContractError::EmergencyWithdrawalPending))
+    );
+    assert_eq!(
+        client.try_execute_emergency_withdrawal(),
+        Err(Ok(crate::ContractError::EmergencyDelayNotElapsed))
+    );
+
+    env.ledger().with_mut(|l| l.timestamp = executable_at);
+    client.execute_emergency_withdrawal();
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&rescue), 1_000_000);
+    assert!(client.get_pending_emergency_withdrawal().is_none());
+}
+
+#[test]
+fn test_cancel_emergency_withdrawal() {
+    let (env, _, _, _, _, _, client) = setup();
+    assert_eq!(
+        client.try_cancel_emergency_withdrawal(),
+        Err(Ok(crate::ContractError::NoEmergencyWithdrawal))
+    );
+    client.initiate_emergency_withdrawal(&Address::generate(&env));
+    client.cancel_emergency_withdrawal();
+    assert_eq!(
+        client.try_execute_emergency_withdrawal(),
+        Err(Ok(crate::ContractError::NoEmergencyWithdrawal))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Volume circuit breaker
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_volume_breaker_trips_and_resets() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    client.set_volume_breaker(&100u32, &1_500_000i128);
+
+    let a = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    let b = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 2_000_000);
+    client.fund_trade(&a);
+    assert!(!client.is_volume_breaker_tripped());
+    client.fund_trade(&b);
+    assert!(client.is_volume_breaker_tripped());
+
+    assert_eq!(
+        client.try_create_trade(&seller, &buyer, &1_000i128, &None, &OptionalMetadata::None),
+        Err(Ok(crate::ContractError::CircuitBreakerTripped))
+    );
+
+    client.reset_volume_breaker();
+    client.create_trade(&seller, &buyer, &1_000i128, &None, &OptionalMetadata::None);
+}
+
+#[test]
+fn test_volume_breaker_window_rolls_over() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    client.set_volume_breaker(&10u32, &1_500_000i128);
+    let a = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    let b = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 2_000_000);
+    client.fund_trade(&a);
+    env.ledger().with_mut(|l| l.sequence_number += 11);
+    client.fund_trade(&b);
+    let window = client.get_volume_window(&token_addr).unwrap();
+    assert_eq!(window.volume, 1_000_000);
+    assert!(!client.is_volume_breaker_tripped());
+}
+
+#[test]
+fn test_volume_breaker_blocks_funding_once_tripped() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    client.set_volume_breaker(&100u32, &500_000i128);
+    let a = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    let b = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 2_000_000);
+    client.fund_trade(&a);
+    assert!(client.is_volume_breaker_tripped());
+    assert_eq!(client.try_fund_trade(&b), Err(Ok(crate::ContractError::CircuitBreakerTripped)));
+    assert_eq!(
+        client.try_contribute(&b, &buyer, &1_000i128),
+        Err(Ok(crate::ContractError::CircuitBreakerTripped))
+    );
+}
+
+#[test]
+fn test_volume_breaker_measures_each_currency_separately() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    client.set_volume_breaker(&100u32, &1_500_000i128);
+    let eurc = basket_token(&env, &buyer, 1_000_000);
+    let a = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    let b = client.create_trade(
+        &seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None,
+        &None, &TradeOptions { currency: Some(eurc.clone()), ..Default::default() },
+    );
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    fund(&env, &eurc, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&a);
+    client.fund_trade(&b);
+
+    assert_eq!(client.get_volume_window(&token_addr).unwrap().volume, 1_000_000);
+    assert_eq!(client.get_volume_window(&eurc).unwrap().volume, 1_000_000);
+    assert!(!client.is_volume_breaker_tripped());
+}
+
+// ---------------------------------------------------------------------------
+// Trade memo
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_create_trade_with_memo() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    let memo = soroban_sdk::String::from_str(&env, "INV-2024-0042");
+    let id = client.create_trade(
+        &seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None,
+        &None, &TradeOptions { memo: Some(memo.clone()), ..Default::default() },
+    );
+    assert_eq!(client.get_trade_memo(&id), Some(memo));
+}
+
+#[test]
+fn test_create_trade_memo_too_long() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    let memo = soroban_sdk::String::from_str(&env, &"x".repeat(crate::MAX_MEMO_LEN as usize + 1));
+    let result = client.try_create_trade(
+        &seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None,
+        &None, &TradeOptions { memo: Some(memo), ..Default::default() },
+    );
+    assert_eq!(result, Err(Ok(crate::ContractError::MemoTooLong)));
+}
+
+// ---------------------------------------------------------------------------
+// Third-party payee
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_confirm_receipt_pays_designated_payee() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    let cold = Address::generate(&env);
+    client.set_trade_payee(&id, &cold);
+    assert_eq!(client.get_trade_payee(&id), cold);
+
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+
+    let token = token::Client::new(&env, &token_addr);
+    assert_eq!(token.balance(&cold), 990_000);
+    assert_eq!(token.balance(&seller), 0);
+}
+
+#[test]
+fn test_set_trade_payee_on_funded_trade_needs_buyer() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    let cold = Address::generate(&env);
+    client.set_trade_payee(&id, &cold);
+    let signers: std::vec::Vec<Address> = env.auths().into_iter().map(|(signer, _)| signer).collect();
+    assert_eq!(signers, std::vec![seller.clone()]);
+
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.set_trade_payee(&id, &cold);
+    let signers: std::vec::Vec<Address> = env.auths().into_iter().map(|(signer, _)| signer).collect();
+    assert!(signers.contains(&seller));
+    assert!(signers.contains(&buyer));
+}
+
+#[test]
+fn test_set_trade_payee_rejects_buyer() {
+    let (_, _, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    assert_eq!(
+        client.try_set_trade_payee(&id, &buyer),
+        Err(Ok(crate::ContractError::InvalidPayee))
+    );
+    assert_eq!(client.get_trade_payee(&id), seller);
+}
+
+#[test]
+fn test_assigned_payout_overrides_payee_and_is_irrevocable() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    let cold = Address::generate(&env);
+    let financier = Address::generate(&env);
+    client.set_trade_payee(&id, &cold);
+    client.assign_payout(&id, &financier);
+    assert_eq!(client.get_payout_assignee(&id), Some(financier.clone()));
+    assert_eq!(client.get_trade_payee(&id), financier);
+
+    assert_eq!(
+        client.try_assign_payout(&id, &seller),
+        Err(Ok(crate::ContractError::PayoutAssigned))
+    );
+    assert_eq!(
+        client.try_set_trade_payee(&id, &cold),
+        Err(Ok(crate::ContractError::PayoutAssigned))
+    );
+
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+
+    let token = token::Client::new(&env, &token_addr);
+    assert_eq!(token.balance(&financier), 990_000);
+    assert_eq!(token.balance(&cold), 0);
+    assert_eq!(token.balance(&seller), 0);
+}
+
+// ---------------------------------------------------------------------------
+// Payout split
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_confirm_receipt_distributes_payout_split() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    let logistics = Address::generate(&env);
+    client.set_payout_split(
+        &id,
+        &soroban_sdk::vec![
+            &env,
+            crate::PayoutShare { recipient: seller.clone(), bps: 9_000 },
+            crate::PayoutShare { recipient: logistics.clone(), bps: 1_000 },
+        ],
+    );
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+
+    let token = token::Client::new(&env, &token_addr);
+    assert_eq!(token.balance(&seller), 891_000);
+    assert_eq!(token.balance(&logistics), 99_000);
+}
+
+#[test]
+fn test_set_payout_split_rejects_bad_total() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    let shares = soroban_sdk::vec![
+        &env,
+        crate::PayoutShare { recipient: seller.clone(), bps: 9_000 },
+        crate::PayoutShare { recipient: Address::generate(&env), bps: 500 },
+    ];
+    assert_eq!(
+        client.try_set_payout_split(&id, &shares),
+        Err(Ok(crate::ContractError::InvalidPayoutSplit))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Basket escrow
+// ---------------------------------------------------------------------------
+
+fn basket_token(env: &Env, buyer: &Address, amount: i128) -> Address {
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(env));
+    token::StellarAssetClient::new(env, &sac.address()).mint(buyer, &amount);
+    sac.address()
+}
+
+#[test]
+fn test_basket_trade_funds_and_releases_every_leg() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let eurc = basket_token(&env, &buyer, 500_000);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    client.set_trade_basket(
+        &id,
+        &soroban_sdk::vec![&env, crate::BasketLeg { token: eurc.clone(), amount: 500_000 }],
+    );
+    assert_eq!(client.get_trade_basket(&id).unwrap().len(), 1);
+
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    let eurc_client = token::Client::new(&env, &eurc);
+    assert_eq!(eurc_client.balance(&client.address), 500_000);
+
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&seller), 990_000);
+    assert_eq!(eurc_client.balance(&seller), 500_000);
+    assert_eq!(eurc_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_basket_trade_refund_returns_every_leg() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let eurc = basket_token(&env, &buyer, 500_000);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    client.set_trade_basket(
+        &id,
+        &soroban_sdk::vec![&env, crate::BasketLeg { token: eurc.clone(), amount: 500_000 }],
+    );
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+
+    client.request_refund(&id, &soroban_sdk::BytesN::from_array(&env, &[7u8; 32]));
+    client.approve_refund(&id);
+    assert_eq!(token::Client::new(&env, &eurc).balance(&buyer), 500_000);
+}
+
+#[test]
+fn test_basket_trade_rejects_pooled_funding() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    let eurc = basket_token(&env, &buyer, 500_000);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    client.set_trade_basket(
+        &id,
+        &soroban_sdk::vec![&env, crate::BasketLeg { token: eurc, amount: 500_000 }],
+    );
+    assert_eq!(
+        client.try_contribute(&id, &buyer, &1_000i128),
+        Err(Ok(crate::ContractError::BasketNotPoolable))
+    );
+}
+
+#[test]
+fn test_set_trade_basket_rejects_primary_currency() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    assert_eq!(
+        client.try_set_trade_basket(
+            &id,
+            &soroban_sdk::vec![&env, crate::BasketLeg { token: token_addr, amount: 1 }],
+        ),
+        Err(Ok(crate::ContractError::InvalidBasket))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Reference-asset quotes
+// ---------------------------------------------------------------------------
+
+mod oracle_mock {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+    use crate::PriceData;
+
+    #[contract]
+    pub struct MockOracle;
+
+    #[contractimpl]
+    impl MockOracle {
+        pub fn set_price(env: Env, price: i128) {
+            env.storage().instance().set(&symbol_short!("px"), &price);
+        }
+
+        pub fn lastprice(env: Env, _base: Address, _quote: Address) -> Option<PriceData> {
+            let price: i128 = env.storage().instance().get(&symbol_short!("px"))?;
+            Some(PriceData { price, decimals: 7, timestamp: env.ledger().timestamp() })
+        }
+    }
+}
+
+#[test]
+fn test_quoted_trade_fixes_amount_at_funding() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let usd = Address::generate(&env);
+    let oracle_id = env.register_contract(None, oracle_mock::MockOracle);
+    client.register_oracle(&token_addr, &usd, &oracle_id, &0u32);
+    // One unit of the trade currency is worth 0.5 USD.
+    oracle_mock::MockOracleClient::new(&env, &oracle_id).set_price(&5_000_000i128);
+
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    client.set_trade_quote(&id, &usd, &1_000_000i128);
+    assert!(client.get_trade_quote(&id).unwrap().fixed.is_none());
+
+    fund(&env, &token_addr, &buyer, &client.address, 2_000_000);
+    client.fund_trade(&id);
+
+    let trade = client.get_trade(&id);
+    assert_eq!(trade.amount, 2_000_000);
+    assert_eq!(trade.fee, 20_000);
+    let fixed = client.get_trade_quote(&id).unwrap().fixed.unwrap();
+    assert_eq!(fixed.price, 5_000_000);
+    assert_eq!(fixed.amount, 2_000_000);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&client.address), 2_000_000);
+}
+
+#[test]
+fn test_set_trade_quote_requires_oracle() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    assert_eq!(
+        client.try_set_trade_quote(&id, &Address::generate(&env), &1_000_000i128),
+        Err(Ok(crate::ContractError::OracleNotFound))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Refund requests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_refund_request_approved_by_seller() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+
+    client.request_refund(&id, &soroban_sdk::BytesN::from_array(&env, &[7u8; 32]));
+    assert_eq!(
+        client.try_request_refund(&id, &soroban_sdk::BytesN::from_array(&env, &[7u8; 32])),
+        Err(Ok(crate::ContractError::RefundAlreadyRequested))
+    );
+    client.approve_refund(&id);
+
+    // Buyer was minted 1_000_000_000 in setup; the full escrow comes back.
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&buyer), 1_000_000_000);
+    assert_eq!(client.get_trade(&id).status, TradeStatus::Refunded);
+    assert!(client.get_refund_request(&id).is_none());
+}
+
+#[test]
+fn test_refund_request_escalates_after_window() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    client.accept_assignment(&id);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+
+    client.request_refund(&id, &soroban_sdk::BytesN::from_array(&env, &[1u8; 32]));
+    assert_eq!(
+        client.try_escalate_refund(&id),
+        Err(Ok(crate::ContractError::RefundWindowOpen))
+    );
+    env.ledger().with_mut(|l| l.timestamp += crate::REFUND_RESPONSE_WINDOW);
+    client.escalate_refund(&id);
+    assert_eq!(client.get_trade(&id).status, TradeStatus::Disputed);
+}
+
+#[test]
+fn test_refund_request_without_arbitrator_escalates_to_admin() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+
+    client.request_refund(&id, &soroban_sdk::BytesN::from_array(&env, &[1u8; 32]));
+    env.ledger().with_mut(|l| l.timestamp += crate::REFUND_RESPONSE_WINDOW);
+    client.escalate_refund(&id);
+    assert_eq!(client.get_trade(&id).status, TradeStatus::Disputed);
+
+    client.resolve_dispute(&id, &crate::DisputeResolution::ReleaseToBuyer, &soroban_sdk::BytesN::from_array(&env, &[2u8; 32]));
+    assert_eq!(client.get_trade(&id).status, TradeStatus::Settled);
+    // Buyer was minted 1_000_000_000 in setup; the escrow less the 1% fee comes back.
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&buyer), 1_000_000_000 - 10_000);
+}
+
+// ---------------------------------------------------------------------------
+// Seller-initiated refund
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_refund_trade_fee_free() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.refund_trade(&id, &true);
+
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&buyer), 1_000_000_000);
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Refunded);
+    assert_eq!(client.get_total_locked(&token_addr), 0);
+}
+
+#[test]
+fn test_refund_trade_retains_fee() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.refund_trade(&id, &false);
+
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&buyer), 1_000_000_000 - 10_000);
+    assert_eq!(client.get_accumulated_fees(), 10_000);
+}
+
+#[test]
+fn test_refund_trade_requires_funds() {
+    let (_, _, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    assert_eq!(
+        client.try_refund_trade(&id, &true),
+        Err(Ok(crate::ContractError::InvalidStatus))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Resolution reason anchoring
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_resolve_dispute_stores_reason_hash() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    client.accept_assignment(&id);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.raise_dispute(&buyer, &id);
+    assert!(client.get_resolution_reason(&id).is_none());
+
+    let reason = soroban_sdk::BytesN::from_array(&env, &[42u8; 32]);
+    client.resolve_dispute(&id, &crate::DisputeResolution::Partial(5_000), &reason);
+    assert_eq!(client.get_resolution_reason(&id), Some(reason));
+}
+
+#[test]
+fn test_resolve_disputes_skips_and_reports_failures() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    fund(&env, &token_addr, &buyer, &client.address, 2_000_000);
+    let mut ids = std::vec::Vec::new();
+    for _ in 0..2 {
+        let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+        client.accept_assignment(&id);
+        client.fund_trade(&id);
+        client.raise_dispute(&buyer, &id);
+        ids.push(id);
+    }
+
+    let reason = soroban_sdk::BytesN::from_array(&env, &[9u8; 32]);
+    let results = client.resolve_disputes(
+        &arbitrator,
+        &soroban_sdk::vec![
+            &env,
+            (ids[0], crate::DisputeResolution::ReleaseToBuyer),
+            (ids[1], crate::DisputeResolution::Partial(10_001)),
+            (999u64, crate::DisputeResolution::ReleaseToSeller),
+        ],
+        &reason,
+    );
+    assert_eq!(results.get(0).unwrap().error, None);
+    assert_eq!(results.get(1).unwrap().error, Some(crate::ContractError::InvalidSplitBps as u32));
+    assert_eq!(results.get(2).unwrap().error, Some(crate::ContractError::TradeNotFound as u32));
+    assert_eq!(client.get_resolution_reason(&ids[0]), Some(reason));
+    assert_eq!(client.get_trade(&ids[1]).status, TradeStatus::Disputed);
+}
+
+#[test]
+fn test_resolve_disputes_rolls_back_on_failure_after_payout() {
+    let (env, _, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    client.set_max_arbitrator_fee_bps(&500u32);
+    env.as_contract(&client.address, || crate::storage::save_arbitrator_fee_bps(&env, &arbitrator, 500));
+    let eurc = basket_token(&env, &buyer, 2_000_000);
+    fund(&env, &eurc, &buyer, &client.address, 2_000_000);
+    let mut ids = std::vec::Vec::new();
+    for _ in 0..2 {
+        let id = client.create_trade(
+            &seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None,
+            &None, &TradeOptions { currency: Some(eurc.clone()), ..Default::default() },
+        );
+        client.accept_assignment(&id);
+        client.fund_trade(&id);
+        client.raise_dispute(&buyer, &id);
+        ids.push(id);
+    }
+    // The buyer's refund goes out first; the arbitrator's ruling fee in the
+    // trade currency then fails.
+    token::StellarAssetClient::new(&env, &eurc).set_authorized(&arbitrator, &false);
+
+    let reason = soroban_sdk::BytesN::from_array(&env, &[9u8; 32]);
+    let result = client.try_resolve_disputes(
+        &arbitrator,
+        &soroban_sdk::vec![
+            &env,
+            (ids[0], crate::DisputeResolution::ReleaseToBuyer),
+            (ids[1], crate::DisputeResolution::ReleaseToBuyer),
+        ],
+        &reason,
+    );
+    assert!(result.is_err());
+    assert_eq!(token::Client::new(&env, &eurc).balance(&client.address), 2_000_000);
+    assert_eq!(token::Client::new(&env, &eurc).balance(&buyer), 0);
+    for id in ids {
+        assert_eq!(client.get_trade(&id).status, TradeStatus::Disputed);
+    }
+}
+
+#[test]
+fn test_assigned_trades_index_keeps_settled_cases() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    let first = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    client.accept_assignment(&first);
+    client.fund_trade(&first);
+    client.complete_trade(&first, &None);
+    client.confirm_receipt(&first, &None);
+    let second = client.create_trade(&seller, &buyer, &500_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    client.create_trade(&seller, &buyer, &500_000i128, &None, &OptionalMetadata::None);
+
+    assert_eq!(client.get_assigned_trade_count(&arbitrator), 2);
+    assert_eq!(client.get_assigned_trades(&arbitrator, &0u32, &10u32), soroban_sdk::vec![&env, first, second]);
+    assert_eq!(client.get_assigned_trades(&arbitrator, &1u32, &10u32), soroban_sdk::vec![&env, second]);
+}
+
+#[test]
+fn test_arbitrator_earns_declared_fee_and_withdraws() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator_self(&arbitrator, &4_000i128, &0u32);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    client.accept_assignment(&id);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.raise_dispute(&buyer, &id);
+    client.resolve_dispute(&id, &crate::DisputeResolution::ReleaseToSeller, &soroban_sdk::BytesN::from_array(&env, &[1u8; 32]));
+
+    // The 10_000 fee is split between the arbitrator and the platform.
+    assert_eq!(client.get_arbitrator_earnings(&arbitrator), 4_000);
+    assert_eq!(client.get_accumulated_fees(), 6_000);
+
+    let wallet = Address::generate(&env);
+    assert_eq!(client.withdraw_arbitrator_earnings(&arbitrator, &wallet), 4_000);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&wallet), 4_000);
+    assert_eq!(
+        client.try_withdraw_arbitrator_earnings(&arbitrator, &wallet),
+        Err(Ok(crate::ContractError::NoArbitratorEarnings))
+    );
+}
+
+#[test]
+fn test_arbitrator_fee_rate_withheld_from_disputed_amount() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    assert_eq!(
+        client.try_register_arbitrator_self(&arbitrator, &1i128, &501u32),
+        Err(Ok(crate::ContractError::ArbitratorFeeTooHigh))
+    );
+    client.register_arbitrator_self(&arbitrator, &1i128, &200u32);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    client.accept_assignment(&id);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.raise_dispute(&buyer, &id);
+    client.resolve_dispute(&id, &crate::DisputeResolution::ReleaseToBuyer, &soroban_sdk::BytesN::from_array(&env, &[1u8; 32]));
+
+    // 2% of the 990_000 disputed, plus the 1-unit service fee.
+    assert_eq!(client.get_arbitrator_earnings(&arbitrator), 19_801);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&buyer), 1_000_000_000 - 1_000_000 + 970_200);
+}
+
+#[test]
+fn test_admin_ruling_earns_no_arbitrator_fee() {
+    let (env, token_addr, admin, seller, buyer, _, client) = setup();
+    // Even an admin who is also a registered arbitrator earns nothing when
+    // ruling on a trade that has no arbitrator.
+    client.register_arbitrator_self(&admin, &4_000i128, &200u32);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.raise_dispute(&buyer, &id);
+    client.resolve_dispute(&id, &crate::DisputeResolution::ReleaseToBuyer, &soroban_sdk::BytesN::from_array(&env, &[1u8; 32]));
+
+    assert_eq!(client.get_arbitrator_earnings(&admin), 0);
+    assert_eq!(client.get_accumulated_fees(), 10_000);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&buyer), 1_000_000_000 - 1_000_000 + 990_000);
+}
+
+// ---------------------------------------------------------------------------
+// Dispute re-open window
+// ---------------------------------------------------------------------------
+
+fn setup_disputed_trade() -> (Env, Address, Address, Address, StellarEscrowContractClient<'static>, u64) {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    client.accept_assignment(&id);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.raise_dispute(&buyer, &id);
+    (env, token_addr, seller, buyer, client, id)
+}
+
+#[test]
+fn test_immediate_resolution_cannot_be_resolved_again() {
+    let (env, token_addr, seller, _, client, id) = setup_disputed_trade();
+    let hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
+    client.resolve_dispute(&id, &crate::DisputeResolution::ReleaseToSeller, &hash);
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Settled);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&seller), 990_000);
+
+    assert_eq!(
+        client.try_resolve_dispute(&id, &crate::DisputeResolution::ReleaseToSeller, &hash),
+        Err(Ok(crate::ContractError::InvalidStatus))
+    );
+    assert_eq!(
+        client.try_execute_resolution(&id),
+        Err(Ok(crate::ContractError::InvalidStatus))
+    );
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&seller), 990_000);
+}
+
+#[test]
+fn test_resolution_deferred_until_window_closes() {
+    let (env, token_addr, seller, _, client, id) = setup_disputed_trade();
+    client.set_reopen_policy(&86_400u64, &0i128);
+    client.resolve_dispute(&id, &crate::DisputeResolution::ReleaseToSeller, &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]));
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Resolved);
+    assert_eq!(
+        client.try_execute_resolution(&id),
+        Err(Ok(crate::ContractError::ReopenWindowOpen))
+    );
+
+    env.ledger().with_mut(|l| l.timestamp += 86_400);
+    client.execute_resolution(&id);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&seller), 990_000);
+    assert!(client.get_pending_resolution(&id).is_none());
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Settled);
+}
+
+#[test]
+fn test_reopen_dispute_with_bond_refunded_on_changed_ruling() {
+    let (env, token_addr, _, buyer, client, id) = setup_disputed_trade();
+    client.set_reopen_policy(&86_400u64, &50_000i128);
+    let hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
+    client.resolve_dispute(&id, &crate::DisputeResolution::ReleaseToSeller, &hash);
+
+    let token = token::Client::new(&env, &token_addr);
+    let before = token.balance(&buyer);
+    client.reopen_dispute(&id, &buyer, &soroban_sdk::BytesN::from_array(&env, &[9u8; 32]));
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Disputed);
+    assert_eq!(
+        client.try_reopen_dispute(&id, &buyer, &hash),
+        Err(Ok(crate::ContractError::InvalidStatus))
+    );
+
+    client.resolve_dispute(&id, &crate::DisputeResolution::ReleaseToBuyer, &hash);
+    env.ledger().with_mut(|l| l.timestamp += 86_400);
+    client.execute_resolution(&id);
+    // Bond returned on top of the net payout.
+    assert_eq!(token.balance(&buyer), before + 990_000);
+}
+
+#[test]
+fn test_reopen_after_window_fails() {
+    let (env, _, seller, _, client, id) = setup_disputed_trade();
+    client.set_reopen_policy(&3_600u64, &0i128);
+    client.resolve_dispute(&id, &crate::DisputeResolution::ReleaseToBuyer, &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]));
+    env.ledger().with_mut(|l| l.timestamp += 3_600);
+    assert_eq!(
+        client.try_reopen_dispute(&id, &seller, &soroban_sdk::BytesN::from_array(&env, &[1u8; 32])),
+        Err(Ok(crate::ContractError::ReopenWindowClosed))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Dispute bonds
+// ---------------------------------------------------------------------------
+
+fn setup_bonded_dispute() -> (Env, Address, Address, Address, Address, StellarEscrowContractClient<'static>, u64) {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    client.set_dispute_bond_policy(&10_000i128, &3_600u64);
+    token::StellarAssetClient::new(&env, &token_addr).mint(&seller, &10_000i128);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    client.accept_assignment(&id);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.raise_dispute(&buyer, &id);
+    (env, token_addr, seller, buyer, arbitrator, client, id)
+}
+
+#[test]
+fn test_loser_bond_split_between_arbitrator_and_winner() {
+    let (env, token_addr, seller, buyer, arbitrator, client, id) = setup_bonded_dispute();
+    let reason = soroban_sdk::BytesN::from_array(&env, &[3u8; 32]);
+    client.post_dispute_bond(&id, &buyer);
+    assert_eq!(
+        client.try_resolve_dispute(&id, &crate::DisputeResolution::ReleaseToBuyer, &reason),
+        Err(Ok(crate::ContractError::DisputeBondsPending))
+    );
+    client.post_dispute_bond(&id, &seller);
+    client.resolve_dispute(&id, &crate::DisputeResolution::ReleaseToBuyer, &reason);
+
+    let token = token::Client::new(&env, &token_addr);
+    assert_eq!(token.balance(&arbitrator), 5_000);
+    assert_eq!(token.balance(&seller), 0);
+    // Buyer gets the net escrow, their own bond and half the seller's.
+    assert_eq!(token.balance(&buyer), 1_000_000_000 - 1_000_000 + 990_000 + 5_000);
+    assert!(client.get_dispute_bonds(&id).is_none());
+}
+
+#[test]
+fn test_silent_party_loses_by_default() {
+    let (env, token_addr, seller, buyer, _, client, id) = setup_bonded_dispute();
+    client.post_dispute_bond(&id, &seller);
+    assert_eq!(
+        client.try_claim_bond_default(&id),
+        Err(Ok(crate::ContractError::DisputeBondsPending))
+    );
+    env.ledger().with_mut(|l| l.timestamp += 3_600);
+    assert_eq!(
+        client.try_post_dispute_bond(&id, &buyer),
+        Err(Ok(crate::ContractError::BondWindowClosed))
+    );
+    client.claim_bond_default(&id);
+
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&seller), 10_000 + 990_000);
+    assert_eq!(client.get_trade(&id).status, TradeStatus::Settled);
+}
+
+// ---------------------------------------------------------------------------
+// Dispute response deadlines
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_unresponsive_party_loses_after_window() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    client.set_dispute_response_window(&86_400u64);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    client.accept_assignment(&id);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.raise_dispute(&buyer, &id);
+
+    let responses = client.get_dispute_responses(&id).unwrap();
+    assert!(responses.buyer_responded && !responses.seller_responded);
+    assert_eq!(client.try_claim_default_victory(&id), Err(Ok(crate::ContractError::ResponseWindowOpen)));
+
+    env.ledger().with_mut(|l| l.timestamp += 86_400);
+    assert_eq!(
+        client.try_submit_evidence(&id, &seller, &soroban_sdk::BytesN::from_array(&env, &[5u8; 32])),
+        Err(Ok(crate::ContractError::ResponseWindowClosed))
+    );
+    client.claim_default_victory(&id);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&buyer), 1_000_000_000 - 10_000);
+}
+
+#[test]
+fn test_default_victory_needs_a_silent_party() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    client.set_dispute_response_window(&86_400u64);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    client.accept_assignment(&id);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.raise_dispute(&buyer, &id);
+    client.submit_evidence(&id, &seller, &soroban_sdk::BytesN::from_array(&env, &[5u8; 32]));
+
+    env.ledger().with_mut(|l| l.timestamp += 86_400);
+    assert_eq!(client.try_claim_default_victory(&id), Err(Ok(crate::ContractError::NoDefaultVictor)));
+}
+
+// ---------------------------------------------------------------------------
+// Supervised arbitration
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_supervised_resolution_requires_admin_approval() {
+    let (env, token_addr, seller, _, client, id) = setup_disputed_trade();
+    client.set_supervision_threshold(&500_000i128);
+    client.resolve_dispute(&id, &crate::DisputeResolution::ReleaseToSeller, &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]));
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Resolved);
+    assert_eq!(
+        client.try_execute_resolution(&id),
+        Err(Ok(crate::ContractError::ResolutionAwaitingReview))
+    );
+
+    // No re-open window configured, so approval pays out straight away.
+    client.approve_resolution(&id);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&seller), 990_000);
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Settled);
+}
+
+#[test]
+fn test_vetoed_resolution_returns_to_dispute() {
+    let (env, _, _, _, client, id) = setup_disputed_trade();
+    client.set_supervision_threshold(&500_000i128);
+    let hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
+    client.resolve_dispute(&id, &crate::DisputeResolution::ReleaseToSeller, &hash);
+    client.veto_resolution(&id);
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Disputed);
+    assert!(client.get_pending_resolution(&id).is_none());
+}
+
+#[test]
+fn test_resolution_below_threshold_is_unsupervised() {
+    let (env, _, _, _, client, id) = setup_disputed_trade();
+    client.set_supervision_threshold(&5_000_000i128);
+    client.resolve_dispute(&id, &crate::DisputeResolution::ReleaseToSeller, &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]));
+    assert!(client.get_pending_resolution(&id).is_none());
+    assert_eq!(
+        client.try_approve_resolution(&id),
+        Err(Ok(crate::ContractError::InvalidStatus))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Idempotency key
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_create_trade_rejects_duplicate_external_ref() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    let ext = soroban_sdk::BytesN::from_array(&env, &[5u8; 32]);
+    let id = client.create_trade(
+        &seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None,
+        &None, &TradeOptions { external_ref: Some(ext.clone()), ..Default::default() },
+    );
+    let retry = client.try_create_trade(
+        &seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None,
+        &None, &TradeOptions { external_ref: Some(ext.clone()), ..Default::default() },
+    );
+    assert_eq!(retry, Err(Ok(crate::ContractError::DuplicateExternalRef)));
+    assert_eq!(client.get_trade_by_external_ref(&ext), Some(id));
+}
+
+// ---------------------------------------------------------------------------
+// Funding deadline
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_expire_unfunded_trade_after_deadline() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    client.set_funding_deadline(&100u32);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    assert_eq!(
+        client.try_expire_trade(&id),
+        Err(Ok(crate::ContractError::FundingDeadlineNotReached))
+    );
+
+    env.ledger().with_mut(|l| l.sequence_number += 100);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    assert_eq!(
+        client.try_fund_trade(&id),
+        Err(Ok(crate::ContractError::FundingDeadlinePassed))
+    );
+    client.expire_trade(&id);
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Expired);
+}
+
+#[test]
+fn test_expire_trade_disabled_by_default() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    env.ledger().with_mut(|l| l.sequence_number += 2_000);
+    assert_eq!(
+        client.try_expire_trade(&id),
+        Err(Ok(crate::ContractError::FundingDeadlineNotReached))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Trade TTL extension
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_trade_access_extends_ttl() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    client.set_ttl_config(&1_000u32, &10_000u32);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+
+    // Each read pushes the entry's TTL back out to `extend_to`, so the trade
+    // outlives the default minimum persistent TTL.
+    env.ledger().with_mut(|l| l.sequence_number += 4_000);
+    client.get_trade(&id);
+    env.ledger().with_mut(|l| l.sequence_number += 8_000);
+    assert_eq!(client.get_trade(&id).status, TradeStatus::Created);
+}
+
+#[test]
+fn test_set_ttl_config_rejects_inverted_bounds() {
+    let (_, _, _, _, _, _, client) = setup();
+    assert_eq!(
+        client.try_set_ttl_config(&10_000u32, &1_000u32),
+        Err(Ok(crate::ContractError::InvalidTtlConfig))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Temporary storage
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_volume_window_expires_from_temporary_storage() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    client.set_volume_breaker(&50u32, &5_000_000i128);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    assert!(client.get_volume_window(&token_addr).is_some());
+
+    // Past the window (and the network's minimum temporary TTL) the entry is gone.
+    env.ledger().with_mut(|l| l.sequence_number += 100);
+    assert!(client.get_volume_window(&token_addr).is_none());
+}
+
+#[test]
+fn test_tripped_breaker_outlives_volume_window() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    client.set_volume_breaker(&20u32, &500_000i128);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+
+    env.ledger().with_mut(|l| l.sequence_number += 100);
+    assert!(client.get_volume_window(&token_addr).is_none());
+    assert!(client.is_volume_breaker_tripped());
+}
+
+// ---------------------------------------------------------------------------
+// Fee cap
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_fee_cap_clamps_large_trades() {
+    let (_, _, _, seller, buyer, _, client) = setup();
+    client.set_fee_cap(&5_000i128);
+    let quote = client.simulate_fee(&1_000_000i128, &seller, &None);
+    assert_eq!(quote.fee_bps, 100);
+    assert_eq!(quote.fee, 5_000);
+    assert_eq!(quote.effective_bps, 50);
+
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    assert_eq!(client.get_trade(&id).fee, 5_000);
+}
+
+#[test]
+fn test_fee_cap_leaves_small_trades_alone() {
+    let (_, _, _, seller, _, _, client) = setup();
+    client.set_fee_cap(&5_000i128);
+    let quote = client.simulate_fee(&100_000i128, &seller, &None);
+    assert_eq!(quote.fee, 1_000);
+    assert_eq!(quote.effective_bps, 100);
+}
+
+// ---------------------------------------------------------------------------
+// Minimum fee
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_min_fee_raises_small_fees() {
+    let (_, _, _, seller, buyer, _, client) = setup();
+    client.set_min_fee(&500i128);
+    let quote = client.simulate_fee(&10_000i128, &seller, &None);
+    assert_eq!(quote.fee, 500);
+    assert_eq!(quote.effective_bps, 500);
+    let id = client.create_trade(&seller, &buyer, &10_000i128, &None, &OptionalMetadata::None);
+    assert_eq!(client.get_trade(&id).fee, 500);
+}
+
+#[test]
+fn test_min_fee_rejects_trades_too_small() {
+    let (_, _, _, seller, buyer, _, client) = setup();
+    client.set_min_fee(&500i128);
+    assert_eq!(
+        client.try_create_trade(&seller, &buyer, &999i128, &None, &OptionalMetadata::None),
+        Err(Ok(crate::ContractError::TradeTooSmallForFee))
+    );
+}
+
+#[test]
+fn test_min_fee_must_not_exceed_cap() {
+    let (_, _, _, _, _, _, client) = setup();
+    client.set_fee_cap(&1_000i128);
+    assert_eq!(
+        client.try_set_min_fee(&2_000i128),
+        Err(Ok(crate::ContractError::InvalidFeeBounds))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Promotional fee windows
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_promo_window_applies_only_inside_range() {
+    let (env, _, _, seller, _, _, client) = setup();
+    let now = env.ledger().sequence();
+    client.set_promo_window(&(now + 10), &(now + 20), &0u32);
+
+    let before = client.simulate_fee(&1_000_000i128, &seller, &None);
+    assert!(!before.promo);
+    assert_eq!(before.fee, 10_000);
+
+    env.ledger().with_mut(|l| l.sequence_number += 10);
+    let during = client.simulate_fee(&1_000_000i128, &seller, &None);
+    assert!(during.promo);
+    assert_eq!(during.fee, 0);
+
+    env.ledger().with_mut(|l| l.sequence_number += 10);
+    assert!(!client.simulate_fee(&1_000_000i128, &seller, &None).promo);
+}
+
+#[test]
+fn test_promo_window_never_raises_fee() {
+    let (env, _, _, seller, _, _, client) = setup();
+    let now = env.ledger().sequence();
+    client.set_promo_window(&now, &(now + 10), &500u32);
+    let quote = client.simulate_fee(&1_000_000i128, &seller, &None);
+    assert!(!quote.promo);
+    assert_eq!(quote.fee_bps, 100);
+}
+
+// ---------------------------------------------------------------------------
+// Per-token fee rates
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_token_fee_override_applies_to_trades_in_that_token() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    client.set_token_fee_bps(&token_addr, &250u32);
+    assert_eq!(client.get_token_fee_bps(&token_addr), 250);
+
+    let quote = client.simulate_fee(&1_000_000i128, &seller, &Some(token_addr.clone()));
+    assert_eq!(quote.fee, 25_000);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    assert_eq!(client.get_trade(&id).fee, 25_000);
+
+    // Tokens without an override fall back to the global rate.
+    let other = Address::generate(&env);
+    assert_eq!(client.get_token_fee_bps(&other), 100);
+    client.remove_token_fee_bps(&token_addr);
+    assert_eq!(client.get_token_fee_bps(&token_addr), 100);
+}
+
+// ---------------------------------------------------------------------------
+// Scheduled fee changes
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_scheduled_fee_change_applies_only_from_ledger() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    let from = env.ledger().sequence() + 50;
+    client.update_fee(&300u32, &from);
+
+    let schedule = client.get_fee_schedule();
+    assert_eq!(schedule.current_bps, 100);
+    assert_eq!(schedule.pending, Some(crate::PendingFeeChange { fee_bps: 300, effective_from: from }));
+
+    let early = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    env.ledger().with_mut(|l| l.sequence_number = from);
+    let late = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+
+    // The earlier trade keeps the fee snapshotted at creation.
+    assert_eq!(client.get_trade(&early).fee, 10_000);
+    assert_eq!(client.get_trade(&late).fee, 30_000);
+    assert_eq!(client.get_platform_fee_bps(), 300);
+    assert!(client.get_fee_schedule().pending.is_none());
+}
+
+// ---------------------------------------------------------------------------
+// Quantity and unit price
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_set_trade_quantity() {
+    let (_, _, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    client.set_trade_quantity(&id, &4u32, &250_000i128);
+    assert_eq!(
+        client.get_trade_quantity(&id),
+        Some(crate::OrderStructure { quantity: 4, unit_price: 250_000 })
+    );
+}
+
+#[test]
+fn test_set_trade_quantity_must_match_amount() {
+    let (_, _, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    assert_eq!(
+        client.try_set_trade_quantity(&id, &3u32, &250_000i128),
+        Err(Ok(crate::ContractError::QuantityMismatch))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Bundle line items
+// ---------------------------------------------------------------------------
+
+fn line_items(env: &Env, amounts: &[i128]) -> soroban_sdk::Vec<crate::LineItemTerms> {
+    let mut items = soroban_sdk::Vec::new(env);
+    for (i, amount) in amounts.iter().enumerate() {
+        items.push_back(crate::LineItemTerms {
+            amount: *amount,
+            terms_hash: soroban_sdk::BytesN::from_array(env, &[i as u8; 32]),
+        });
+    }
+    items
+}
+
+#[test]
+fn test_bundle_items_released_and_disputed_independently() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    client.accept_assignment(&id);
+    client.set_line_items(&id, &line_items(&env, &[600_000, 400_000]));
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+
+    client.release_item(&id, &0u32);
+    let token = token::Client::new(&env, &token_addr);
+    assert_eq!(token.balance(&seller), 594_000);
+
+    client.dispute_item(&id, &1u32, &buyer);
+    assert_eq!(
+        client.try_confirm_receipt(&id, &None),
+        Err(Ok(crate::ContractError::BundleRequiresItemSettlement))
+    );
+    client.resolve_item(&id, &1u32, &true);
+
+    let items = client.get_line_items(&id).unwrap();
+    assert_eq!(items.get(0).unwrap().status, crate::LineItemStatus::Released);
+    assert_eq!(items.get(1).unwrap().status, crate::LineItemStatus::Refunded);
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Settled);
+    assert_eq!(client.get_accumulated_fees(), 10_000);
+}
+
+#[test]
+fn test_set_line_items_must_sum_to_amount() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    assert_eq!(
+        client.try_set_line_items(&id, &line_items(&env, &[600_000, 300_000])),
+        Err(Ok(crate::ContractError::BundleItemsMismatch))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Protocol fee share
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_protocol_share_forwarded_on_withdrawal() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let protocol = Address::generate(&env);
+    client.set_protocol_fee_share(&protocol, &2_000u32);
+
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+
+    let operator = Address::generate(&env);
+    client.request_fee_withdrawal(&token_addr, &operator, &10_000i128);
+    env.ledger().with_mut(|l| l.timestamp += client.get_fee_withdrawal_delay());
+    client.execute_fee_withdrawal();
+    let token = token::Client::new(&env, &token_addr);
+    assert_eq!(token.balance(&protocol), 2_000);
+    assert_eq!(token.balance(&operator), 8_000);
+}
+
+#[test]
+fn test_protocol_share_bounded() {
+    let (env, _, _, _, _, _, client) = setup();
+    assert_eq!(
+        client.try_set_protocol_fee_share(&Address::generate(&env), &6_000u32),
+        Err(Ok(crate::ContractError::InvalidFeeBps))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Allowance funding
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_fund_trade_from_allowance() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade_from_allowance(&id);
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Funded);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&client.address), 1_000_000);
+    assert_eq!(token::Client::new(&env, &token_addr).allowance(&buyer, &client.address), 0);
+}
+
+#[test]
+fn test_fund_trade_from_allowance_requires_approval() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 500_000);
+    assert_eq!(
+        client.try_fund_trade_from_allowance(&id),
+        Err(Ok(crate::ContractError::InsufficientAllowance))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Pooled funding
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_pooled_funding_and_proportional_refund() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let dao = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token_addr).mint(&dao, &750_000i128);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+
+    client.contribute(&id, &dao, &750_000i128);
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Created);
+    assert_eq!(
+        client.try_contribute(&id, &buyer, &300_000i128),
+        Err(Ok(crate::ContractError::ContributionExceedsAmount))
+    );
+    client.contribute(&id, &buyer, &250_000i128);
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Funded);
+    assert_eq!(client.get_contributions(&id).len(), 2);
+
+    client.refund_trade(&id, &true);
+    let token = token::Client::new(&env, &token_addr);
+    assert_eq!(token.balance(&dao), 750_000);
+    assert_eq!(token.balance(&buyer), 1_000_000_000);
+}
+
+#[test]
+fn test_withdraw_contribution_before_pool_complete() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    client.contribute(&id, &buyer, &400_000i128);
+    assert_eq!(
+        client.try_fund_trade(&id),
+        Err(Ok(crate::ContractError::PooledFundingInProgress))
+    );
+    client.withdraw_contribution(&id, &buyer);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&buyer), 1_000_000_000);
+    assert!(client.get_contributions(&id).is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// Streaming release
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_claim_streamed_vests_linearly() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    client.set_stream(&id, &1_000u64);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.start_stream(&id);
+
+    env.ledger().with_mut(|l| l.timestamp += 250);
+    assert_eq!(client.claim_streamed(&id), 247_500);
+    assert_eq!(
+        client.try_claim_streamed(&id),
+        Err(Ok(crate::ContractError::NothingToClaim))
+    );
+
+    env.ledger().with_mut(|l| l.timestamp += 2_000);
+    assert_eq!(client.claim_streamed(&id), 742_500);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&seller), 990_000);
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Settled);
+    assert_eq!(client.get_accumulated_fees(), 10_000);
+}
+
+#[test]
+fn test_dispute_stops_stream_and_covers_remainder() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    client.accept_assignment(&id);
+    client.set_stream(&id, &1_000u64);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.start_stream(&id);
+
+    env.ledger().with_mut(|l| l.timestamp += 500);
+    client.claim_streamed(&id);
+    client.raise_dispute(&buyer, &id);
+    assert_eq!(
+        client.try_claim_streamed(&id),
+        Err(Ok(crate::ContractError::InvalidStatus))
+    );
+
+    client.resolve_dispute(&id, &crate::DisputeResolution::ReleaseToBuyer, &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]));
+    let token = token::Client::new(&env, &token_addr);
+    assert_eq!(token.balance(&seller), 495_000);
+    assert_eq!(token.balance(&buyer), 1_000_000_000 - 1_000_000 + 495_000);
+    assert_eq!(client.get_total_locked(&token_addr), 0);
+}
+
+// ---------------------------------------------------------------------------
+// Release hold
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_confirm_receipt_holds_funds_until_window_ends() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    client.set_default_hold(&100u32);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+    assert_eq!(client.get_trade_status(&id), TradeStatus::PendingRelease);
+    assert_eq!(
+        client.try_claim_release(&id, &None),
+        Err(Ok(crate::ContractError::HoldWindowActive))
+    );
+
+    env.ledger().with_mut(|l| l.sequence_number += 100);
+    client.claim_release(&id, &None);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&seller), 990_000);
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Settled);
+}
+
+#[test]
+fn test_timestamp_mode_converts_ledger_windows() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    assert_eq!(client.get_timing_mode(), None);
+    client.set_timing_mode(&crate::TimingMode::Timestamp);
+    assert_eq!(client.get_timing_mode(), Some(crate::TimingMode::Timestamp));
+    client.set_default_hold(&100u32);
+    client.set_completion_window(&300u32);
+
+    let created = env.ledger().timestamp();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    assert_eq!(client.get_trade_deadlines(&id).complete_by, created + 300 * crate::SECONDS_PER_LEDGER);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+
+    // Ledgers alone no longer advance the hold; elapsed seconds do.
+    env.ledger().with_mut(|l| l.sequence_number += 100);
+    assert_eq!(
+        client.try_claim_release(&id, &None),
+        Err(Ok(crate::ContractError::HoldWindowActive))
+    );
+    env.ledger().with_mut(|l| l.timestamp += 100 * crate::SECONDS_PER_LEDGER);
+    client.claim_release(&id, &None);
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Settled);
+
+    // Once trades exist the mode is fixed.
+    assert_eq!(
+        client.try_set_timing_mode(&crate::TimingMode::Ledger),
+        Err(Ok(crate::ContractError::TimingModeLocked))
+    );
+}
+
+#[test]
+fn test_settlement_guard_blocks_nested_payout() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+
+    // Simulate a payout of this trade already under way.
+    env.as_contract(&client.address, || {
+        let trade = crate::storage::get_trade(&env, id).unwrap();
+        crate::state::begin_settlement(&env, &trade).unwrap();
+    });
+    assert_eq!(
+        client.try_confirm_receipt(&id, &None),
+        Err(Ok(crate::ContractError::SettlementInProgress))
+    );
+    env.as_contract(&client.address, || crate::state::end_settlement(&env, id));
+    client.confirm_receipt(&id, &None);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&seller), 990_000);
+}
+
+#[test]
+fn test_buyer_can_dispute_during_hold() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.set_default_hold(&100u32);
+    client.register_arbitrator(&arbitrator);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    client.accept_assignment(&id);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+    client.raise_dispute(&buyer, &id);
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Disputed);
+}
+
+// ---------------------------------------------------------------------------
+// Counterparty ratings
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_rating_with_confirmation_and_counterparty() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &Some(4u32));
+    client.rate_counterparty(&id, &seller, &5u32);
+
+    assert_eq!(
+        client.get_party_reputation(&seller),
+        crate::PartyReputation { rating_sum: 4, rating_count: 1 }
+    );
+    assert_eq!(
+        client.get_party_reputation(&buyer),
+        crate::PartyReputation { rating_sum: 5, rating_count: 1 }
+    );
+    assert_eq!(
+        client.try_rate_counterparty(&id, &buyer, &3u32),
+        Err(Ok(crate::ContractError::AlreadyRated))
+    );
+}
+
+#[test]
+fn test_rate_counterparty_rejects_unsettled_trade() {
+    let (_, _, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    assert_eq!(
+        client.try_rate_counterparty(&id, &buyer, &5u32),
+        Err(Ok(crate::ContractError::InvalidStatus))
+    );
+}
+
+#[test]
+fn test_rate_counterparty_rejects_completed_but_unpaid_trade() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    assert_eq!(
+        client.try_rate_counterparty(&id, &seller, &1u32),
+        Err(Ok(crate::ContractError::InvalidStatus))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Delivery proof
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_complete_trade_records_delivery_proof() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    let proof = soroban_sdk::BytesN::from_array(&env, &[7u8; 32]);
+    client.complete_trade(&id, &Some(proof.clone()));
+    assert_eq!(client.get_delivery_proof(&id), Some(proof));
+}
+
+// ---------------------------------------------------------------------------
+// Status transition events
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_status_changed_emitted_on_transition() {
+    use soroban_sdk::{testutils::Events, Symbol, TryFromVal};
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+
+    let status = soroban_sdk::symbol_short!("status");
+    let found = env.events().all().iter().any(|(_, topics, _)| {
+        topics.get(1).and_then(|t| Symbol::try_from_val(&env, &t).ok()) == Some(status.clone())
+    });
+    assert!(found);
+}
+
+#[test]
+fn test_lifecycle_events_carry_ledger_time() {
+    use soroban_sdk::{testutils::Events, Symbol, TryFromVal};
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    env.ledger().with_mut(|l| {
+        l.timestamp = 1_700_000_000;
+        l.sequence_number = 4_242;
+    });
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+
+    let funded = soroban_sdk::symbol_short!("funded");
+    let payload = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            topics.get(1).and_then(|t| Symbol::try_from_val(&env, &t).ok()) == Some(funded.clone())
+        })
+        .map(|(_, _, data)| crate::events::EvTradeFunded::try_from_val(&env, &data).unwrap())
+        .unwrap();
+    assert_eq!(payload.trade_id, id);
+    assert_eq!(payload.ts, 1_700_000_000);
+    assert_eq!(payload.seq, 4_242);
+}
+
+// ---------------------------------------------------------------------------
+// Config getters
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_admin_and_token_getters() {
+    let (_, token_addr, admin, _, _, _, client) = setup();
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_usdc_token(), token_addr);
+}
+
+// ---------------------------------------------------------------------------
+// Settlement receipts
+// ---------------------------------------------------------------------------
+
+mod receipt_mock {
+    use soroban_sdk::{contract, contractimpl, Address, Env, Vec};
+
+    #[contract]
+    pub struct MockReceipts;
+
+    #[contractimpl]
+    impl MockReceipts {
+        pub fn mint(env: Env, to: Address, receipt: crate::TradeReceipt) {
+            let mut minted: Vec<(Address, crate::TradeReceipt)> =
+                env.storage().instance().get(&0u32).unwrap_or(Vec::new(&env));
+            minted.push_back((to, receipt));
+            env.storage().instance().set(&0u32, &minted);
+        }
+
+        pub fn minted(env: Env) -> Vec<(Address, crate::TradeReceipt)> {
+            env.storage().instance().get(&0u32).unwrap_or(Vec::new(&env))
+        }
+    }
+}
+
+#[test]
+fn test_receipts_minted_to_both_parties_on_completion() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let receipts_id = env.register_contract(None, receipt_mock::MockReceipts);
+    client.set_receipt_contract(&Some(receipts_id.clone()));
+
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+
+    let minted = receipt_mock::MockReceiptsClient::new(&env, &receipts_id).minted();
+    assert_eq!(minted.len(), 2);
+    let (to, receipt) = minted.get(0).unwrap();
+    assert_eq!(to, buyer);
+    assert_eq!(receipt.trade_id, id);
+    assert_eq!(receipt.outcome, crate::ReceiptOutcome::Completed);
+    assert_eq!(minted.get(1).unwrap().0, seller);
+}
+
+// ---------------------------------------------------------------------------
+// Dispute raiser
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_raise_dispute_records_caller() {
+    let (_, _, _, buyer, client, id) = setup_disputed_trade();
+    assert_eq!(client.get_dispute_raiser(&id), Some(buyer));
+}
+
+#[test]
+fn test_reopen_dispute_overwrites_raiser() {
+    let (env, _, seller, _, client, id) = setup_disputed_trade();
+    client.set_reopen_policy(&3_600u64, &0i128);
+    client.resolve_dispute(&id, &crate::DisputeResolution::ReleaseToBuyer, &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]));
+    client.reopen_dispute(&id, &seller, &soroban_sdk::BytesN::from_array(&env, &[1u8; 32]));
+    assert_eq!(client.get_dispute_raiser(&id), Some(seller));
+}
+
+#[test]
+fn test_raise_dispute_rejects_non_party() {
+    let (env, token_addr, _, seller, buyer, arbitrator, client) = setup();
+    client.register_arbitrator(&arbitrator);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+    client.accept_assignment(&id);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    assert_eq!(
+        client.try_raise_dispute(&Address::generate(&env), &id),
+        Err(Ok(crate::ContractError::Unauthorized))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Party validation
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_create_trade_rejects_self_trade() {
+    let (_, _, _, seller, _, _, client) = setup();
+    assert_eq!(
+        client.try_create_trade(&seller, &seller, &1_000_000i128, &None, &OptionalMetadata::None),
+        Err(Ok(crate::ContractError::InvalidParties))
+    );
+}
+
+#[test]
+fn test_payout_split_rejects_buyer_recipient() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    let shares = soroban_sdk::vec![
+        &env,
+        crate::PayoutShare { recipient: seller.clone(), bps: 5_000 },
+        crate::PayoutShare { recipient: buyer.clone(), bps: 5_000 },
+    ];
+    assert_eq!(
+        client.try_set_payout_split(&id, &shares),
+        Err(Ok(crate::ContractError::InvalidParties))
+    );
+}
+
+#[test]
+fn test_create_trade_rejects_party_as_arbitrator() {
+    let (_, _, _, seller, buyer, _, client) = setup();
+    client.register_arbitrator(&seller);
+    assert_eq!(
+        client.try_create_trade(&seller, &buyer, &1_000_000i128, &Some(seller.clone()), &OptionalMetadata::None),
+        Err(Ok(crate::ContractError::ArbitratorIsParty))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Per-pair open trade cap
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_pair_trade_cap_blocks_until_trade_closes() {
+    let (_, _, _, seller, buyer, _, client) = setup();
+    client.set_pair_trade_cap(&2);
+    let first = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    assert_eq!(client.get_pair_open_trades(&seller, &buyer), 2);
+    assert_eq!(
+        client.try_create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None),
+        Err(Ok(crate::ContractError::PairTradeLimitReached))
+    );
+
+    client.cancel_trade(&first);
+    assert_eq!(client.get_pair_open_trades(&seller, &buyer), 1);
+    client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+}
+
+// ---------------------------------------------------------------------------
+// Mandatory arbitrator threshold
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_arbitrator_threshold_boundary() {
+    let (_, _, _, seller, buyer, arbitrator, client) = setup();
+    assert_eq!(client.get_arbitrator_threshold(), 0);
+    client.set_arbitrator_threshold(&5_000_000i128);
+    assert_eq!(client.get_arbitrator_threshold(), 5_000_000);
+
+    // At the threshold an arbitrator is optional.
+    client.create_trade(&seller, &buyer, &5_000_000i128, &None, &OptionalMetadata::None);
+
+    assert_eq!(
+        client.try_create_trade(&seller, &buyer, &5_000_001i128, &None, &OptionalMetadata::None),
+        Err(Ok(crate::ContractError::ArbitratorRequired))
+    );
+
+    client.register_arbitrator(&arbitrator);
+    client.create_trade(&seller, &buyer, &5_000_001i128, &Some(arbitrator.clone()), &OptionalMetadata::None);
+}
+
+// ---------------------------------------------------------------------------
+// Per-trade deadlines
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_trade_deadlines_fall_back_to_admin_windows() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    client.set_completion_window(&300u32);
+    let created = env.ledger().sequence() as u64;
+    let deadlines = crate::Deadlines { fund_by: created + 50, complete_by: 0, confirm_by: 0 };
+    let id = client.create_trade(
+        &seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None,
+        &None, &TradeOptions { deadlines: Some(deadlines), ..Default::default() },
+    );
+    let resolved = client.get_trade_deadlines(&id);
+    assert_eq!(resolved.fund_by, created + 50);
+    assert_eq!(resolved.complete_by, created + 300);
+    assert_eq!(resolved.confirm_by, 0);
+
+    let backwards = crate::Deadlines { fund_by: created + 50, complete_by: created + 10, confirm_by: 0 };
+    assert_eq!(
+        client.try_create_trade(
+            &seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None,
+            &None, &TradeOptions { deadlines: Some(backwards), ..Default::default() },
+        ),
+        Err(Ok(crate::ContractError::InvalidDeadlines))
+    );
+}
+
+#[test]
+fn test_refund_overdue_after_complete_by() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let deadlines = crate::Deadlines { fund_by: 0, complete_by: env.ledger().sequence() as u64 + 100, confirm_by: 0 };
+    let id = client.create_trade(
+        &seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None,
+        &None, &TradeOptions { deadlines: Some(deadlines), ..Default::default() },
+    );
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    assert_eq!(
+        client.try_refund_overdue(&id, &None),
+        Err(Ok(crate::ContractError::DeadlineNotReached))
+    );
+
+    env.ledger().with_mut(|l| l.sequence_number += 100);
+    let before = token::Client::new(&env, &token_addr).balance(&buyer);
+    client.refund_overdue(&id, &None);
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Refunded);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&buyer), before + 1_000_000);
+}
+
+#[test]
+fn test_release_overdue_pays_unconfirmed_trade_once() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    client.set_confirmation_window(&200u32);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    assert_eq!(
+        client.try_release_overdue(&id, &None),
+        Err(Ok(crate::ContractError::DeadlineNotReached))
+    );
+
+    env.ledger().with_mut(|l| l.sequence_number += 200);
+    client.release_overdue(&id, &None);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&seller), 990_000);
+    assert_eq!(
+        client.try_release_overdue(&id, &None),
+        Err(Ok(crate::ContractError::InvalidStatus))
+    );
+}
+
+#[test]
+fn test_sweep_expired_expires_and_refunds() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    client.set_funding_deadline(&100u32);
+    client.set_completion_window(&100u32);
+    let unfunded = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    let funded = client.create_trade(&seller, &buyer, &2_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 2_000_000);
+    client.fund_trade(&funded);
+
+    env.ledger().with_mut(|l| l.sequence_number += 100);
+    let fresh = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    let before = token::Client::new(&env, &token_addr).balance(&buyer);
+    let results = client.sweep_expired(&soroban_sdk::vec![&env, unfunded, funded, fresh, 99u64], &None);
+
+    assert_eq!(results.get(0).unwrap().error, None);
+    assert_eq!(results.get(1).unwrap().error, None);
+    assert_eq!(results.get(2).unwrap().error, Some(crate::ContractError::FundingDeadlineNotReached as u32));
+    assert_eq!(results.get(3).unwrap().error, Some(crate::ContractError::TradeNotFound as u32));
+    assert_eq!(client.get_trade_status(&unfunded), TradeStatus::Expired);
+    assert_eq!(client.get_trade_status(&funded), TradeStatus::Refunded);
+    assert_eq!(client.get_trade_status(&fresh), TradeStatus::Created);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&buyer), before + 2_000_000);
+}
+
+#[test]
+fn test_sweep_expired_rolls_back_partial_contribution_refund() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    client.set_funding_deadline(&100u32);
+    let sac = token::StellarAssetClient::new(&env, &token_addr);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    sac.mint(&first, &400_000i128);
+    sac.mint(&second, &400_000i128);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    client.contribute(&id, &first, &400_000i128);
+    client.contribute(&id, &second, &400_000i128);
+    // The first contributor is refunded, then the second refund fails.
+    sac.set_authorized(&second, &false);
+
+    env.ledger().with_mut(|l| l.sequence_number += 100);
+    assert!(client.try_sweep_expired(&soroban_sdk::vec![&env, id], &None).is_err());
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&first), 0);
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Created);
+    env.as_contract(&client.address, || {
+        assert_eq!(crate::storage::get_contributions(&env, id).unwrap().len(), 2);
+    });
+}
+
+#[test]
+fn test_sweep_expired_rejects_oversized_batch() {
+    let (env, _, _, _, _, _, client) = setup();
+    let mut ids = soroban_sdk::Vec::new(&env);
+    for id in 0..=u64::from(crate::MAX_SWEEP_TRADES) {
+        ids.push_back(id);
+    }
+    assert_eq!(client.try_sweep_expired(&ids, &None), Err(Ok(crate::ContractError::BatchTooLarge)));
+}
+
+#[test]
+fn test_keeper_bounty_paid_from_trade_fee() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    client.set_confirmation_window(&200u32);
+    // 20% of the 10_000 trade fee.
+    client.set_keeper_bounty(&2_000u32);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+
+    env.ledger().with_mut(|l| l.sequence_number += 200);
+    let keeper = Address::generate(&env);
+    client.release_overdue(&id, &Some(keeper.clone()));
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&keeper), 2_000);
+    assert_eq!(client.get_accumulated_fees(), 8_000);
+}
+
+#[test]
+fn test_keeper_bounty_not_paid_for_unfunded_trades() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    client.set_funding_deadline(&100u32);
+    client.set_keeper_bounty(&10_000u32);
+    let settled = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&settled);
+    client.complete_trade(&settled, &None);
+    client.confirm_receipt(&settled, &None);
+    assert_eq!(client.get_accumulated_fees(), 10_000);
+
+    let mut ids = soroban_sdk::Vec::new(&env);
+    for _ in 0..5 {
+        ids.push_back(client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None));
+    }
+    env.ledger().with_mut(|l| l.sequence_number += 100);
+    let keeper = Address::generate(&env);
+    client.sweep_expired(&ids, &Some(keeper.clone()));
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&keeper), 0);
+    assert_eq!(client.get_accumulated_fees(), 10_000);
+}
+
+// ---------------------------------------------------------------------------
+// Admin action log
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_admin_log_records_actions_in_order() {
+    let (env, _, admin, _, _, arbitrator, client) = setup();
+    env.ledger().with_mut(|l| l.sequence_number += 10);
+    client.register_arbitrator(&arbitrator);
+    client.pause();
+    client.unpause();
+    assert_eq!(client.get_admin_log_count(), 3);
+
+    let log = client.get_admin_log(&0u64, &10u32);
+    assert_eq!(log.len(), 3);
+    let first = log.get(0).unwrap();
+    assert_eq!(first.seq, 0);
+    assert_eq!(first.actor, admin);
+    assert_eq!(first.action, crate::AdminAction::ArbitratorRegistered);
+    assert_eq!(first.ledger, env.ledger().sequence());
+    assert_eq!(log.get(2).unwrap().action, crate::AdminAction::Unpaused);
+
+    let page = client.get_admin_log(&1u64, &1u32);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().action, crate::AdminAction::Paused);
+}
+
+// ---------------------------------------------------------------------------
+// Configuration change events
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_config_setter_emits_old_and_new_values() {
+    use soroban_sdk::{testutils::Events, Symbol, TryFromVal};
+    let (env, _, _, _, _, _, client) = setup();
+    client.set_fee_cap(&5_000i128);
+    client.set_fee_cap(&7_000i128);
+
+    let key = soroban_sdk::symbol_short!("fee_cap");
+    let changes: std::vec::Vec<(u32, u64, u64)> = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            topics.get(2).and_then(|t| Symbol::try_from_val(&env, &t).ok()) == Some(key.clone())
+        })
+        .map(|(_, _, data)| <(u32, u64, u64)>::try_from_val(&env, &data).unwrap())
+        .collect();
+    assert_eq!(changes.last(), Some(&(crate::events::EVENT_VERSION, 5_000, 7_000)));
+}
+
+// ---------------------------------------------------------------------------
+// Loyalty points
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_loyalty_points_accrue_and_redeem_for_discount() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    client.set_loyalty_config(&crate::LoyaltyConfig {
+        points_per_million: 1_000,
+        points_per_bps: 10,
+        max_discount_bps: 50,
+    });
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+    assert_eq!(client.get_points(&seller), 1_000);
+    assert_eq!(client.get_points(&buyer), 1_000);
+
+    assert_eq!(
+        client.try_redeem_points(&seller, &51u32),
+        Err(Ok(crate::ContractError::DiscountCapExceeded))
+    );
+    assert_eq!(client.redeem_points(&seller, &50u32), 50);
+    assert_eq!(client.get_points(&seller), 500);
+
+    // 1% base rate less the 0.5% redeemed discount, used up by this trade.
+    let next = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    assert_eq!(client.get_trade(&next).fee, 5_000);
+    assert_eq!(client.get_points_discount(&seller), 0);
+}
+
+// ---------------------------------------------------------------------------
+// Fee burn
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_fee_burn_share_is_not_withdrawable() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    client.set_fee_burn_bps(&2_500u32);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+
+    // A quarter of the 10_000 fee is burned; the rest stays withdrawable.
+    assert_eq!(client.get_total_burned(&token_addr), 2_500);
+    assert_eq!(client.get_accumulated_fees(), 7_500);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&client.address), 7_500);
+    assert_eq!(
+        client.try_request_fee_withdrawal(&token_addr, &Address::generate(&env), &10_000i128),
+        Err(Ok(crate::ContractError::NoFeesToWithdraw))
+    );
+}
+
+mod no_burn_token {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    /// A token that moves balances but refuses every burn.
+    #[contract]
+    pub struct NoBurnToken;
+
+    #[contractimpl]
+    impl NoBurnToken {
+        pub fn mint(env: Env, to: Address, amount: i128) {
+            let balance = Self::balance(env.clone(), to.clone());
+            env.storage().persistent().set(&to, &(balance + amount));
+        }
+
+        pub fn balance(env: Env, id: Address) -> i128 {
+            env.storage().persistent().get(&id).unwrap_or(0)
+        }
+
+        pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+            from.require_auth();
+            let held = Self::balance(env.clone(), from.clone());
+            assert!(held >= amount, "insufficient balance");
+            env.storage().persistent().set(&from, &(held - amount));
+            Self::mint(env, to, amount);
+        }
+
+        pub fn burn(_env: Env, _from: Address, _amount: i128) {
+            panic!("burn disabled");
+        }
+    }
+}
+
+#[test]
+fn test_fee_burn_failure_keeps_share_withdrawable() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    client.set_fee_burn_bps(&2_500u32);
+    let currency = env.register_contract(None, no_burn_token::NoBurnToken);
+    no_burn_token::NoBurnTokenClient::new(&env, &currency).mint(&buyer, &1_000_000i128);
+    let id = client.create_trade(
+        &seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None,
+        &None, &TradeOptions { currency: Some(currency.clone()), ..Default::default() },
+    );
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Settled);
+    assert_eq!(client.get_total_burned(&currency), 0);
+    env.as_contract(&client.address, || {
+        assert_eq!(crate::storage::get_currency_fees(&env, &currency), 10_000);
+    });
+}
+
+// ---------------------------------------------------------------------------
+// Treasury fee forwarding
+// ---------------------------------------------------------------------------
+
+fn settle_one_trade(env: &Env, token_addr: &Address, seller: &Address, buyer: &Address, client: &StellarEscrowContractClient) {
+    let id = client.create_trade(seller, buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(env, token_addr, buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    client.confirm_receipt(&id, &None);
+}
+
+#[test]
+fn test_fees_forwarded_to_treasury() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let treasury = Address::generate(&env);
+    client.set_treasury(&Some(treasury.clone()));
+    settle_one_trade(&env, &token_addr, &seller, &buyer, &client);
+
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&treasury), 10_000);
+    assert_eq!(client.get_accumulated_fees(), 0);
+}
+
+#[test]
+fn test_broken_treasury_falls_back_to_accumulation() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let treasury = Address::generate(&env);
+    // A deauthorized holder cannot receive the asset, so forwarding fails.
+    token::StellarAssetClient::new(&env, &token_addr).set_authorized(&treasury, &false);
+    client.set_treasury(&Some(treasury.clone()));
+    settle_one_trade(&env, &token_addr, &seller, &buyer, &client);
+
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&treasury), 0);
+    assert_eq!(client.get_accumulated_fees(), 10_000);
+}
+
+// ---------------------------------------------------------------------------
+// Message anchoring
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_anchor_message_keeps_rolling_window() {
+    let (env, _, _, seller, buyer, _, client) = setup();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    assert_eq!(
+        client.try_anchor_message(&id, &Address::generate(&env), &soroban_sdk::BytesN::from_array(&env, &[1u8; 32])),
+        Err(Ok(crate::ContractError::Unauthorized))
+    );
+
+    for i in 0..=crate::types::MAX_MESSAGE_ANCHORS {
+        let author = if i % 2 == 0 { &buyer } else { &seller };
+        client.anchor_message(&id, author, &soroban_sdk::BytesN::from_array(&env, &[i as u8; 32]));
+    }
+    let anchors = client.get_message_anchors(&id);
+    assert_eq!(anchors.len(), crate::types::MAX_MESSAGE_ANCHORS);
+    // The first digest rolled off; the window starts at the second.
+    assert_eq!(anchors.get(0).unwrap().msg_hash, soroban_sdk::BytesN::from_array(&env, &[1u8; 32]));
+    assert_eq!(anchors.get(0).unwrap().author, seller);
+}
+
+// ---------------------------------------------------------------------------
+// KYC attestation gate
+// ---------------------------------------------------------------------------
+
+mod attestation_mock {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockAttestations;
+
+    #[contractimpl]
+    impl MockAttestations {
+        pub fn attest(env: Env, subject: Address) {
+            env.storage().persistent().set(&subject, &true);
+        }
+
+        pub fn has_valid_kyc(env: Env, subject: Address) -> bool {
+            env.storage().persistent().get(&subject).unwrap_or(false)
+        }
+    }
+}
+
+#[test]
+fn test_attestation_gate_requires_both_parties() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    let attestations_id = env.register_contract(None, attestation_mock::MockAttestations);
+    let attestations = attestation_mock::MockAttestationsClient::new(&env, &attestations_id);
+    client.set_attestation_contract(&Some(attestations_id.clone()));
+
+    attestations.attest(&seller);
+    assert_eq!(
+        client.try_create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None),
+        Err(Ok(crate::ContractError::ComplianceCheckFailed))
+    );
+
+    attestations.attest(&buyer);
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    assert_eq!(client.get_trade_status(&id), TradeStatus::Funded);
+}
+
+// ---------------------------------------------------------------------------
+// Trust badges
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_badge_claimed_after_clean_streak() {
+    let (env, token_addr, _, seller, buyer, _, client) = setup();
+    for _ in 0..crate::types::BADGE_TRADES_PER_LEVEL - 1 {
+        settle_one_trade(&env, &token_addr, &seller, &buyer, &client);
+    }
+    assert_eq!(
+        client.try_claim_badge(&seller),
+        Err(Ok(crate::ContractError::BadgeNotEarned))
+    );
+
+    settle_one_trade(&env, &token_addr, &seller, &buyer, &client);
+    assert_eq!(client.claim_badge(&seller), 1);
+    assert_eq!(client.get_badge_level(&seller), 1);
+    assert_eq!(client.get_badge_level(&buyer), 0);
+    assert_eq!(
+        client.try_claim_badge(&seller),
+        Err(Ok(crate::ContractError::BadgeNotEarned))
+    );
+}
+
+#[test]
+fn test_lost_dispute_resets_track_record() {
+    let (env, _, seller, _, client, id) = setup_disputed_trade();
+    client.resolve_dispute(&id, &crate::DisputeResolution::ReleaseToBuyer, &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]));
+    assert_eq!(
+        client.get_track_record(&seller),
+        crate::TrackRecord { clean_trades: 0, disputes_lost: 1 }
+    );
+}
+
+#[test]
+fn test_user_stats_follow_trade_lifecycle() {
+    let (env, _, seller, buyer, client, id) = setup_disputed_trade();
+    client.resolve_dispute(&id, &crate::DisputeResolution::ReleaseToBuyer, &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]));
+    assert_eq!(
+        client.get_user_stats(&buyer),
+        crate::UserStats { trades_as_buyer: 1, trades_as_seller: 0, volume: 1_000_000, disputes_won: 1, disputes_lost: 0 }
+    );
+    assert_eq!(
+        client.get_user_stats(&seller),
+        crate::UserStats { trades_as_buyer: 0, trades_as_seller: 1, volume: 1_000_000, disputes_won: 0, disputes_lost: 1 }
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Buyer transfer
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_transfer_buyer_moves_refund_rights() {
+    let (env, token_addr, admin, seller, buyer, _, client) = setup();
+    let new_buyer = Address::generate(&env);
+    client.set_user_compliance(&admin, &new_buyer, &crate::types::UserCompliance {
+        kyc_status: crate::types::KycStatus::Verified,
+        aml_cleared: true,
+        jurisdiction: soroban_sdk::String::from_str(&env, "US"),
+    });
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    fund(&env, &token_addr, &buyer, &client.address, 1_000_000);
+    client.fund_trade(&id);
+    assert_eq!(
+        client.try_transfer_buyer(&id, &seller),
+        Err(Ok(crate::ContractError::InvalidParties))
+    );
+
+    client.transfer_buyer(&id, &new_buyer);
+    assert_eq!(client.get_trade(&id).buyer, new_buyer);
+    assert_eq!(client.get_pair_open_trades(&seller, &buyer), 0);
+    assert_eq!(client.get_pair_open_trades(&seller, &new_buyer), 1);
+
+    client.refund_trade(&id, &true);
+    assert_eq!(token::Client::new(&env, &token_addr).balance(&new_buyer), 1_000_000);
+}
+
+// ---------------------------------------------------------------------------
+// Token transfer failures
+// ---------------------------------------------------------------------------
+
+mod frozen_token_mock {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+    /// Bare-bones token whose transfers can be switched off, like a frozen
+    /// or paused asset.
+    #[contract]
+    pub struct MockToken;
+
+    #[contractimpl]
+    impl MockToken {
+        pub fn mint(env: Env, to: Address, amount: i128) {
+            let balance = Self::balance(env.clone(), to.clone());
+            env.storage().persistent().set(&to, &(balance + amount));
+        }
+
+        pub fn set_frozen(env: Env, frozen: bool) {
+            env.storage().instance().set(&symbol_short!("frozen"), &frozen);
+        }
+
+        pub fn balance(env: Env, id: Address) -> i128 {
+            env.storage().persistent().get(&id).unwrap_or(0)
+        }
+
+        pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+            if env.storage().instance().get(&symbol_short!("frozen")).unwrap_or(false) {
+                panic!("token frozen");
+            }
+            let from_balance = Self::balance(env.clone(), from.clone());
+            assert!(from_balance >= amount, "insufficient balance");
+            env.storage().persistent().set(&from, &(from_balance - amount));
+            let to_balance = Self::balance(env.clone(), to.clone());
+            env.storage().persistent().set(&to, &(to_balance + amount));
+        }
+    }
+}
+
+fn setup_frozen_token() -> (
+    Env,
+    frozen_token_mock::MockTokenClient<'static>,
+    Address,
+    Address,
+    StellarEscrowContractClient<'static>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let token_id = env.register_contract(None, frozen_token_mock::MockToken);
+    let token = frozen_token_mock::MockTokenClient::new(&env, &token_id);
+    token.mint(&buyer, &1_000_000_000i128);
+
+    let contract_id = env.register_contract(None, StellarEscrowContract);
+    let client = StellarEscrowContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &token_id, &100u32);
+    let compliant = crate::types::UserCompliance {
+        kyc_status: crate::types::KycStatus::Verified,
+        aml_cleared: true,
+        jurisdiction: soroban_sdk::String::from_str(&env, "US"),
+    };
+    client.set_user_compliance(&admin, &seller, &compliant);
+    client.set_user_compliance(&admin, &buyer, &compliant);
+    (env, token, seller, buyer, client)
+}
+
+#[test]
+fn test_frozen_token_fails_funding_without_changing_status() {
+    let (_env, token, seller, buyer, client) = setup_frozen_token();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    token.set_frozen(&true);
+
+    assert_eq!(client.try_fund_trade(&id), Err(Ok(crate::ContractError::TokenTransferFailed)));
+    assert_eq!(client.get_trade(&id).status, TradeStatus::Created);
+    assert_eq!(token.balance(&buyer), 1_000_000_000);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn test_frozen_token_fails_payout_without_changing_status() {
+    let (_env, token, seller, buyer, client) = setup_frozen_token();
+    let id = client.create_trade(&seller, &buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    client.fund_trade(&id);
+    client.complete_trade(&id, &None);
+    token.set_frozen(&true);
+
+    assert_eq!(
+        client.try_confirm_receipt(&id, &None),
+        Err(Ok(crate::ContractError::TokenTransferFailed))
+    );
+    assert_eq!(
+        client.try_refund_trade(&id, &true),
+        Err(Ok(crate::ContractError::TokenTransferFailed))
+    );
+    assert_eq!(client.get_trade(&id).status, TradeStatus::Completed);
+    assert_eq!(token.balance(&client.address), 1_000_000);
+    assert_eq!(token.balance(&seller), 0);
+
+    // Once the token thaws the trade settles normally.
+    token.set_frozen(&false);
+    client.confirm_receipt(&id, &None);
+    assert_eq!(token.balance(&seller), 990_000);
 }
@@ -1,5 +1,6 @@
 use soroban_sdk::{Address, Env};
 
+use crate::amount;
 use crate::errors::ContractError;
 use crate::events;
 use crate::storage::{get_fee_bps, get_tier_config, get_user_tier, save_user_tier};
@@ -39,14 +40,17 @@ fn volume_tier(total_volume: u64) -> UserTier {
     }
 }
 
-pub fn record_volume(env: &Env, user: &Address, amount: u64) -> Result<(), ContractError> {
+pub fn record_volume(env: &Env, user: &Address, volume: i128) -> Result<(), ContractError> {
     let mut info = get_user_tier(env, user).unwrap_or(UserTierInfo {
         tier: UserTier::Bronze,
         total_volume: 0,
         custom_fee_bps: None,
     });
 
-    info.total_volume = info.total_volume.checked_add(amount).ok_or(ContractError::Overflow)?;
+    info.total_volume = info
+        .total_volume
+        .checked_add(amount::narrow(volume)?)
+        .ok_or(ContractError::Overflow)?;
 
     // Custom-fee users skip tier promotion; persist only the updated volume.
     if let UserTier::Custom = info.tier {
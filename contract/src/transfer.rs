@@ -0,0 +1,117 @@
+//! Token movements in and out of trade escrow.
+//!
+//! Trade currencies are arbitrary token contracts, and a frozen account, a
+//! paused asset or a misbehaving token would otherwise trap the whole call.
+//! These wrappers use the `try_` client calls and surface any failure as
+//! `TokenTransferFailed`. Returning the error rolls back every write the
+//! call made, so the trade keeps the status it had before.
+//!
+//! Stellar assets with clawback enabled let the issuer take tokens back from
+//! any holder, this contract included. The contract pools every trade in a
+//! currency in one balance, so before paying out of it `send` checks that
+//! the balance still covers the payout on top of everything still locked in
+//! that currency, and reports `EscrowClawedBack` if it does not. Callers
+//! release a trade's own escrow from the locked total before paying it out.
+//!
+//! Regulated assets (SEP-8) only move between accounts the issuer has
+//! authorized; a transfer refused for that reason is reported as
+//! `AssetAuthorizationRequired`.
+//!
+//! Fee burns go through `burn` for the same reasons.
+
+use soroban_sdk::{token, Address};
+
+use crate::errors::ContractError;
+use crate::regulated;
+use crate::storage;
+
+/// Transfer `amount` from `from` to `to`.
+pub fn send(token_client: &token::Client, from: &Address, to: &Address, amount: i128) -> Result<(), ContractError> {
+    if *from == token_client.env.current_contract_address() {
+        require_escrowed(token_client, amount)?;
+    }
+    let sent = token_client
+        .try_transfer(from, to, &amount)
+        .is_ok_and(|r| r.is_ok());
+    if !sent {
+        return Err(failure(token_client, from, to));
+    }
+    Ok(())
+}
+
+/// Classify a failed transfer between `from` and `to`.
+fn failure(token_client: &token::Client, from: &Address, to: &Address) -> ContractError {
+    let env = &token_client.env;
+    let token = &token_client.address;
+    if !regulated::is_authorized(env, token, from) || !regulated::is_authorized(env, token, to) {
+        return ContractError::AssetAuthorizationRequired;
+    }
+    ContractError::TokenTransferFailed
+}
+
+/// Fail with `EscrowClawedBack` if paying `amount` would leave the
+/// contract's balance short of the escrow still locked in the token.
+fn require_escrowed(token_client: &token::Client, amount: i128) -> Result<(), ContractError> {
+    let held = held(token_client)?;
+    let required = storage::get_locked(&token_client.env, &token_client.address)
+        .checked_add(amount)
+        .ok_or(ContractError::Overflow)?;
+    if held < required {
+        return Err(ContractError::EscrowClawedBack);
+    }
+    Ok(())
+}
+
+fn held(token_client: &token::Client) -> Result<i128, ContractError> {
+    token_client
+        .try_balance(&token_client.env.current_contract_address())
+        .ok()
+        .and_then(|r| r.ok())
+        .ok_or(ContractError::TokenTransferFailed)
+}
+
+/// Transfer the contract's whole balance of the token to `to`, escrow
+/// included. Only for the emergency withdrawal. Returns the amount sent.
+pub fn drain(token_client: &token::Client, to: &Address) -> Result<i128, ContractError> {
+    let contract = token_client.env.current_contract_address();
+    let balance = held(token_client)?;
+    if balance > 0 {
+        let sent = token_client
+            .try_transfer(&contract, to, &balance)
+            .is_ok_and(|r| r.is_ok());
+        if !sent {
+            return Err(failure(token_client, &contract, to));
+        }
+    }
+    Ok(balance)
+}
+
+/// Transfer `amount` from `from` to `to` out of an allowance `from` granted
+/// the calling contract.
+pub fn send_from(
+    token_client: &token::Client,
+    spender: &Address,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+) -> Result<(), ContractError> {
+    let sent = token_client
+        .try_transfer_from(spender, from, to, &amount)
+        .is_ok_and(|r| r.is_ok());
+    if !sent {
+        return Err(failure(token_client, from, to));
+    }
+    Ok(())
+}
+
+/// Burn `amount` of the contract's own balance.
+pub fn burn(token_client: &token::Client, amount: i128) -> Result<(), ContractError> {
+    require_escrowed(token_client, amount)?;
+    let burned = token_client
+        .try_burn(&token_client.env.current_contract_address(), &amount)
+        .is_ok_and(|r| r.is_ok());
+    if !burned {
+        return Err(ContractError::TokenTransferFailed);
+    }
+    Ok(())
+}
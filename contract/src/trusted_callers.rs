@@ -0,0 +1,69 @@
+//! Optional allowlist of integration contracts that create trades.
+//!
+//! # Design
+//! - The admin trusts router or marketplace contracts (`set_trusted_caller`).
+//! - A trusted caller opens trades through `create_trade_via`, which needs
+//!   its own auth plus the seller's, so the seller authorises the nested
+//!   call made by the router.
+//! - In integrations-only mode (`set_trusted_callers_only`), plain
+//!   `create_trade` and the multisig and cross-chain variants are refused,
+//!   so every new trade must come through a trusted caller. With the mode
+//!   off, all paths are open.
+
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+
+use crate::errors::ContractError;
+
+// ---------------------------------------------------------------------------
+// Storage
+// ---------------------------------------------------------------------------
+
+const TRUSTED_CALLER_PREFIX: &str = "TU";
+
+fn only_key() -> Symbol {
+    symbol_short!("TC_ONLY")
+}
+
+pub fn set_trusted(env: &Env, caller: &Address, trusted: bool) {
+    let key = (TRUSTED_CALLER_PREFIX, caller.clone());
+    if trusted {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+pub fn is_trusted(env: &Env, caller: &Address) -> bool {
+    env.storage().persistent().has(&(TRUSTED_CALLER_PREFIX, caller.clone()))
+}
+
+pub fn set_only(env: &Env, enabled: bool) {
+    env.storage().instance().set(&only_key(), &enabled);
+}
+
+pub fn is_only(env: &Env) -> bool {
+    env.storage().instance().get(&only_key()).unwrap_or(false)
+}
+
+// ---------------------------------------------------------------------------
+// Checks
+// ---------------------------------------------------------------------------
+
+/// Check who is opening a trade: `via` is the integration contract for
+/// `create_trade_via`, `None` for a direct `create_trade`.
+pub fn require_permitted(env: &Env, via: Option<&Address>) -> Result<(), ContractError> {
+    match via {
+        Some(caller) => {
+            caller.require_auth();
+            if !is_trusted(env, caller) {
+                return Err(ContractError::CallerNotTrusted);
+            }
+        }
+        None => {
+            if is_only(env) {
+                return Err(ContractError::DirectCreationDisabled);
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,7 +1,56 @@
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracttype, Address, BytesN, String, Symbol};
 
 pub const MAX_METADATA_SIZE: u32 = 1024;
 pub const MAX_INSURANCE_PREMIUM_BPS: u32 = 1000;
+/// Maximum number of category tags an arbitrator may declare.
+pub const MAX_ARBITRATOR_CATEGORIES: u32 = 10;
+/// Seconds an arbitrator has to accept an assignment after trade creation (3 days).
+pub const ASSIGNMENT_GRACE_PERIOD: u64 = 259_200;
+/// Default remaining-TTL (in ledgers) below which trade entries are extended (~1 day).
+pub const DEFAULT_TTL_THRESHOLD: u32 = 17_280;
+/// Default TTL (in ledgers) trade entries are extended to on access (~30 days).
+pub const DEFAULT_TTL_EXTEND_TO: u32 = 518_400;
+/// Largest share of a trade, in bps, the minimum fee may take before the
+/// trade is rejected as too small.
+pub const MAX_MIN_FEE_SHARE_BPS: u32 = 5_000;
+/// Maximum length in bytes of a trade memo (order reference, invoice number).
+pub const MAX_MEMO_LEN: u32 = 64;
+/// Maximum number of recipients in a trade's payout split.
+pub const MAX_PAYOUT_RECIPIENTS: u32 = 5;
+/// Maximum number of extra tokens escrowed alongside a trade's currency.
+pub const MAX_BASKET_LEGS: u32 = 5;
+/// Maximum Merkle proof length for a settlement batch claim.
+pub const MAX_BATCH_PROOF_DEPTH: u32 = 32;
+/// Default cap on the dispute fee rate arbitrators may set (5%).
+pub const DEFAULT_MAX_ARBITRATOR_FEE_BPS: u32 = 500;
+/// Average ledger close time, used to convert windows between timing modes.
+pub const SECONDS_PER_LEDGER: u64 = 5;
+/// Maximum number of disputes settled in one `resolve_disputes` call.
+pub const MAX_BATCH_RESOLUTIONS: u32 = 20;
+/// Maximum number of trades fetched in one `get_trades` call.
+pub const MAX_BULK_TRADES: u32 = 50;
+/// Maximum number of trades processed in one `sweep_expired` call.
+pub const MAX_SWEEP_TRADES: u32 = 20;
+/// Capabilities the admin can pause individually (`set_paused_functions`).
+/// Refunds, cancellations and settlement of existing trades are never
+/// paused this way.
+pub const PAUSE_CREATE: u32 = 1 << 0;
+pub const PAUSE_FUND: u32 = 1 << 1;
+pub const PAUSE_DISPUTE: u32 = 1 << 2;
+pub const PAUSE_WITHDRAW: u32 = 1 << 3;
+pub const PAUSE_ALL_FUNCTIONS: u32 = PAUSE_CREATE | PAUSE_FUND | PAUSE_DISPUTE | PAUSE_WITHDRAW;
+/// Most of a payout a buyer can donate to charity (5%).
+pub const MAX_DONATION_BPS: u32 = 500;
+/// Largest share of the platform fee a referrer can earn (50%).
+pub const MAX_REFERRAL_SHARE_BPS: u32 = 5_000;
+/// Seconds the seller has to answer a refund request before the buyer may escalate (2 days).
+pub const REFUND_RESPONSE_WINDOW: u64 = 172_800;
+/// Mandatory delay between initiating and executing an emergency withdrawal (3 days).
+pub const EMERGENCY_WITHDRAWAL_DELAY: u64 = 259_200;
+/// Default delay between requesting and executing a fee withdrawal (1 day).
+pub const DEFAULT_FEE_WITHDRAWAL_DELAY: u64 = 86_400;
+/// Shortest fee withdrawal delay the admin may configure (1 hour).
+pub const MIN_FEE_WITHDRAWAL_DELAY: u64 = 3_600;
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -48,6 +97,26 @@ pub enum TradeStatus {
     AwaitingBridge, // cross-chain: waiting for bridge oracle confirmation
     BridgeFailed,   // cross-chain: bridge attestation failed
     Triggered,      // price-based trigger executed
+    Refunded,       // escrow returned to the buyer
+    Resolved,       // ruling issued; payout deferred until the re-open window closes
+    Expired,        // never funded: left past the funding or bridge deadline
+    PendingRelease, // receipt confirmed; funds held until the hold window ends
+    Settled,        // escrow paid out on release or an executed ruling
+}
+
+impl TradeStatus {
+    /// Statuses a trade never leaves: nothing remains in escrow.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            TradeStatus::Cancelled
+                | TradeStatus::Settled
+                | TradeStatus::Refunded
+                | TradeStatus::Expired
+                | TradeStatus::Triggered
+                | TradeStatus::BridgeFailed
+        )
+    }
 }
 
 #[contracttype]
@@ -87,9 +156,67 @@ pub struct PriceTrigger {
 }
 
 
+/// Per-trade timeouts as absolute points on the deadline clock (ledger
+/// sequence or timestamp, see `clock`). A zero field falls back to the admin
+/// default window counted from creation.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Deadlines {
+    /// Unfunded trades may be expired from this point.
+    pub fund_by: u64,
+    /// Funded trades not marked complete may be refunded from this point.
+    pub complete_by: u64,
+    /// Completed trades not confirmed may be released from this point.
+    pub confirm_by: u64,
+}
+
+/// Optional settings for a new trade, grouped so the creation entrypoints
+/// stay within the host's argument limit. `TradeOptions::default()` opens a
+/// plain USDC trade with no expiry.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TradeOptions {
+    pub expiry_time: Option<u64>,
+    /// Escrow token; USDC when unset.
+    pub currency: Option<Address>,
+    pub trigger: Option<PriceTrigger>,
+    pub category: Option<Symbol>,
+    /// Free-text note shown to both parties, at most `MAX_MEMO_LEN` bytes.
+    pub memo: Option<String>,
+    /// Caller-chosen idempotency key; a second trade with the same key is
+    /// refused.
+    pub external_ref: Option<BytesN<32>>,
+    pub deadlines: Option<Deadlines>,
+    /// Registered partner the trade is created through.
+    pub partner: Option<u32>,
+}
+
+/// Amounts are `i128` to match the token interface and cover the full range
+/// of Stellar asset amounts; they are never negative.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Trade {
+    pub id: u64,
+    pub seller: Address,
+    pub buyer: Address,
+    pub amount: i128,
+    pub fee: i128,
+    pub arbitrator: Option<Address>,
+    pub status: TradeStatus,
+    pub expiry_time: Option<u64>,
+    pub currency: Address,
+    pub metadata: OptionalMetadata,
+    /// Optional JSON-like string metadata (product info, shipping details, etc.)
+    pub metadata: Option<String>,
+    /// Optional price-based trigger
+    pub trigger: Option<PriceTrigger>,
+}
+
+/// Schema 1 trade layout, with `u64` amount and fee. Kept only to decode
+/// entries written before amounts moved to `i128`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TradeV1 {
     pub id: u64,
     pub seller: Address,
     pub buyer: Address,
@@ -106,6 +233,577 @@ pub struct Trade {
     pub trigger: Option<PriceTrigger>,
 }
 
+impl From<TradeV1> for Trade {
+    fn from(v1: TradeV1) -> Self {
+        Trade {
+            id: v1.id,
+            seller: v1.seller,
+            buyer: v1.buyer,
+            amount: i128::from(v1.amount),
+            fee: i128::from(v1.fee),
+            arbitrator: v1.arbitrator,
+            status: v1.status,
+            expiry_time: v1.expiry_time,
+            currency: v1.currency,
+            metadata: v1.metadata,
+            trigger: v1.trigger,
+        }
+    }
+}
+
+/// One recipient's share of the seller-side payout, in basis points.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutShare {
+    pub recipient: Address,
+    pub bps: u32,
+}
+
+/// A buyer's open request for a refund, answered by the seller or escalated
+/// to a dispute once `escalate_after` passes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundRequest {
+    /// Hash of the off-chain explanation supplied by the buyer.
+    pub reason_hash: BytesN<32>,
+    pub requested_at: u64,
+    pub escalate_after: u64,
+}
+
+/// Admin policy for re-opening resolved disputes. A zero `window` settles
+/// rulings immediately.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReopenPolicy {
+    /// Seconds after a ruling during which either party may re-open.
+    pub window: u64,
+    /// Bond (in the trade's currency) the re-opening party must post.
+    pub bond: i128,
+}
+
+/// A ruling awaiting execution at the end of the re-open window.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingResolution {
+    pub resolution: DisputeResolution,
+    pub reason_hash: BytesN<32>,
+    pub executable_at: u64,
+    /// Set under supervised arbitration until the admin approves the ruling.
+    pub awaiting_admin: bool,
+}
+
+/// Bond posted by the party that re-opened a dispute. Refunded if the final
+/// ruling differs from the contested one, otherwise forfeited as a fee.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReopenBond {
+    pub payer: Address,
+    pub amount: i128,
+    pub contested: DisputeResolution,
+}
+
+/// Scheduled reduced-fee period, inclusive of `start_ledger`, exclusive of
+/// `end_ledger`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PromoWindow {
+    pub start_ledger: u32,
+    pub end_ledger: u32,
+    pub fee_bps: u32,
+}
+
+/// A platform fee change scheduled to take effect at a future ledger.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingFeeChange {
+    pub fee_bps: u32,
+    pub effective_from: u32,
+}
+
+/// Platform rate in force now, plus any scheduled change.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeSchedule {
+    pub current_bps: u32,
+    pub pending: Option<PendingFeeChange>,
+}
+
+/// Discrete-goods order structure recorded alongside a trade.
+/// `quantity * unit_price` always equals the trade amount.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrderStructure {
+    pub quantity: u32,
+    pub unit_price: i128,
+}
+
+/// Maximum number of line items in a bundle trade.
+pub const MAX_LINE_ITEMS: u32 = 20;
+
+/// Seller-supplied line item when splitting a trade into a bundle.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LineItemTerms {
+    pub amount: i128,
+    pub terms_hash: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LineItemStatus {
+    Open,
+    Released,
+    Disputed,
+    Refunded,
+}
+
+/// One line item of a bundle trade, settled independently of its siblings.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LineItem {
+    pub amount: i128,
+    pub terms_hash: BytesN<32>,
+    pub status: LineItemStatus,
+}
+
+/// Star ratings a trader has received from counterparties.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PartyReputation {
+    pub rating_sum: u32,
+    pub rating_count: u32,
+}
+
+/// Linear payout schedule for a streamed trade. `started_at` is zero until
+/// the buyer confirms commencement.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamSchedule {
+    pub duration: u64,
+    pub started_at: u64,
+    pub claimed: i128,
+}
+
+/// One payer's share of a pooled trade.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Contribution {
+    pub payer: Address,
+    pub amount: i128,
+}
+
+/// Upper bound on the protocol's cut of withdrawn fees.
+pub const MAX_PROTOCOL_FEE_SHARE_BPS: u32 = 5_000;
+
+/// Share of withdrawn platform fees forwarded to the protocol (typically the
+/// factory that deployed this instance).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProtocolFeeShare {
+    pub recipient: Address,
+    pub bps: u32,
+}
+
+/// Read-only fee quote for a prospective trade.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeQuote {
+    pub amount: i128,
+    /// Rate applied, after tier/custom/subscription adjustments.
+    pub fee_bps: u32,
+    /// `fee / amount` in basis points; below `fee_bps` when the fee cap bites.
+    pub effective_bps: u32,
+    /// True when a promotional window supplied the rate.
+    pub promo: bool,
+    pub fee: i128,
+    /// `amount - fee`, paid to the seller on a normal release.
+    pub payout: i128,
+}
+
+/// How a trade's amount divides up under the contract's own fee math. The
+/// parts other than `fee` sum to `amount`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeBreakdown {
+    pub amount: i128,
+    /// Withheld from the amount at creation: platform fee plus any partner
+    /// margin.
+    pub fee: i128,
+    /// What the platform books once the shares below are paid.
+    pub platform_fee: i128,
+    /// Arbitrator's service fee and ruling fee; zero unless the trade is
+    /// settled by a ruling.
+    pub arbitrator_fee: i128,
+    pub partner_fee: i128,
+    pub referral_fee: i128,
+    /// Paid to the seller side on release, or split between the parties on
+    /// a ruling.
+    pub payout: i128,
+}
+
+/// A requested withdrawal of accumulated fees, executable after the delay.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingFeeWithdrawal {
+    /// Token the fees are withdrawn in.
+    pub currency: Address,
+    pub to: Address,
+    pub amount: i128,
+    pub requested_at: u64,
+    /// Earliest ledger timestamp at which the withdrawal may be executed.
+    pub executable_at: u64,
+}
+
+/// Pending withdrawal layout from before amounts moved to `i128`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingFeeWithdrawalV1 {
+    pub to: Address,
+    pub amount: u64,
+    pub requested_at: u64,
+    pub executable_at: u64,
+}
+
+/// Pending withdrawal layout from before withdrawals named a currency; such
+/// requests were always in the platform token.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingFeeWithdrawalV2 {
+    pub to: Address,
+    pub amount: i128,
+    pub requested_at: u64,
+    pub executable_at: u64,
+}
+
+impl From<PendingFeeWithdrawalV1> for PendingFeeWithdrawalV2 {
+    fn from(v1: PendingFeeWithdrawalV1) -> Self {
+        PendingFeeWithdrawalV2 {
+            to: v1.to,
+            amount: i128::from(v1.amount),
+            requested_at: v1.requested_at,
+            executable_at: v1.executable_at,
+        }
+    }
+}
+
+impl PendingFeeWithdrawalV2 {
+    pub fn in_currency(self, currency: Address) -> PendingFeeWithdrawal {
+        PendingFeeWithdrawal {
+            currency,
+            to: self.to,
+            amount: self.amount,
+            requested_at: self.requested_at,
+            executable_at: self.executable_at,
+        }
+    }
+}
+
+/// A scheduled break-glass withdrawal of the contract's full token balance.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingEmergencyWithdrawal {
+    pub to: Address,
+    pub initiated_at: u64,
+    /// Earliest ledger timestamp at which the withdrawal may be executed.
+    pub executable_at: u64,
+}
+
+/// Admin-configured funding-volume circuit breaker.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VolumeBreakerConfig {
+    /// Length of the tracking window in ledgers.
+    pub window_ledgers: u32,
+    /// Funded volume within one window that trips the breaker.
+    pub threshold: i128,
+}
+
+/// Funding volume accumulated in the current breaker window.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VolumeWindow {
+    pub window_start: u32,
+    pub volume: i128,
+}
+
+/// Persistent TTL policy applied whenever a trade entry is read or written.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlConfig {
+    /// Extend only when the remaining TTL drops below this many ledgers.
+    pub threshold: u32,
+    /// TTL, in ledgers, entries are extended to.
+    pub extend_to: u32,
+}
+
+/// Result of reconciling the contract's balance of one token against its
+/// liabilities in that token.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SolvencyReport {
+    pub currency: Address,
+    /// Contract's current balance of `currency`.
+    pub balance: i128,
+    /// Escrow held in `currency`.
+    pub total_locked: i128,
+    /// Fees withdrawable in `currency`.
+    pub accumulated_fees: i128,
+    /// `balance - (total_locked + accumulated_fees)`; negative means a shortfall.
+    pub surplus: i128,
+    pub solvent: bool,
+}
+
+/// Core contract configuration, stored as a single instance entry.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub admin: Address,
+    pub usdc_token: Address,
+    pub fee_bps: u32,
+    /// Highest trade ID issued so far.
+    pub trade_counter: u64,
+    /// Legacy single-currency fee balance.
+    pub accumulated_fees: i128,
+    /// Deprecated and no longer updated: a sum of escrow across currencies.
+    /// Escrow is tracked per currency; see `get_total_locked`. Kept so
+    /// existing config entries still decode.
+    pub total_locked: i128,
+}
+
+/// Config layout from before amounts moved to `i128`. Decoded only so an
+/// upgraded contract can read, and then rewrite, its existing config.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigV1 {
+    pub admin: Address,
+    pub usdc_token: Address,
+    pub fee_bps: u32,
+    pub trade_counter: u64,
+    pub accumulated_fees: u64,
+    pub total_locked: u64,
+}
+
+impl From<ConfigV1> for Config {
+    fn from(v1: ConfigV1) -> Self {
+        Config {
+            admin: v1.admin,
+            usdc_token: v1.usdc_token,
+            fee_bps: v1.fee_bps,
+            trade_counter: v1.trade_counter,
+            accumulated_fees: i128::from(v1.accumulated_fees),
+            total_locked: i128::from(v1.total_locked),
+        }
+    }
+}
+
+/// Semantic version of the deployed contract code, plus the WASM hash it was
+/// upgraded to (None for the originally deployed code).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    pub wasm_hash: Option<BytesN<32>>,
+}
+
+/// Tracks whether the arbitrator named on a trade has agreed to take the case.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArbitratorAssignment {
+    pub arbitrator: Address,
+    /// Ledger timestamp at which the trade was created.
+    pub assigned_at: u64,
+    pub accepted: bool,
+}
+
+/// Canonical message a buyer signs off-chain to accept a trade. `terms_hash`
+/// names what is accepted (the original terms or an amendment) and `nonce`
+/// must grow with every acceptance of the same trade.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AcceptancePayload {
+    pub contract: Address,
+    pub trade_id: u64,
+    pub seller: Address,
+    pub buyer: Address,
+    pub amount: i128,
+    pub currency: Address,
+    pub terms_hash: BytesN<32>,
+    pub nonce: u64,
+}
+
+/// Latest signed acceptance recorded for a trade.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BuyerAcceptance {
+    pub terms_hash: BytesN<32>,
+    pub nonce: u64,
+    pub accepted_at: u64,
+}
+
+/// Compliance hold on a single trade; while present the trade cannot change
+/// status or pay out.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TradeFreeze {
+    /// Operator-defined code for the hold (e.g. a legal order category).
+    pub reason_code: u32,
+    pub frozen_at: u64,
+}
+
+/// How a buyer donates part of the seller payout at confirmation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DonationChoice {
+    /// Round the payout down to a multiple of this unit; donate the rest.
+    RoundDown(i128),
+    /// Donate this share of the payout, in basis points.
+    Bps(u32),
+}
+
+/// Review state of a self-registered referrer. Only approved referrers earn.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReferrerStatus {
+    Pending,
+    Approved,
+    Suspended,
+}
+
+/// A referrer's registration and payout terms.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Referrer {
+    /// Receives the referrer's share of fees.
+    pub payout: Address,
+    /// Share of the platform fee on referred buyers' trades, in basis points.
+    pub share_bps: u32,
+    pub status: ReferrerStatus,
+    pub registered_at: u64,
+}
+
+/// A white-label partner's fee terms, keyed by partner id.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Partner {
+    /// Receives the partner's share of each fee.
+    pub recipient: Address,
+    /// Partner margin in basis points of the trade amount.
+    pub fee_bps: u32,
+    /// Charge only the partner margin, waiving the platform fee.
+    pub replaces_platform_fee: bool,
+}
+
+/// Partner a trade was created through and the margin included in its fee.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TradePartner {
+    pub partner_id: u32,
+    pub recipient: Address,
+    /// Part of `Trade::fee` owed to the partner.
+    pub fee: i128,
+}
+
+/// A seller's request to receive a trade's payout in another token.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutSwap {
+    /// Whitelisted swap adapter to route through.
+    pub adapter: Address,
+    pub token_out: Address,
+    /// Minimum `token_out` for the full trade amount.
+    pub min_out: i128,
+}
+
+/// Opening of a hashed trade's commitment, agreed by the parties off-chain.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HashedTradeDetails {
+    pub seller: Address,
+    pub buyer: Address,
+    pub amount: i128,
+    /// Random value that keeps the commitment from being brute-forced.
+    pub salt: BytesN<32>,
+}
+
+/// Public record of a hashed trade.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HashedTrade {
+    /// `sha256` of the XDR-encoded `HashedTradeDetails`.
+    pub commitment: BytesN<32>,
+    /// Number of decimal digits in the amount.
+    pub amount_bucket: u32,
+    pub currency: Address,
+    pub arbitrator: Address,
+    /// Platform rate in effect at creation; the fee is charged at it.
+    pub fee_bps: u32,
+    pub status: TradeStatus,
+    pub created_at: u64,
+    /// Set when a dispute is raised, for the arbitrator to rule on.
+    pub revealed: Option<HashedTradeDetails>,
+}
+
+/// A committed Merkle batch of off-chain settled micro-trades.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementBatch {
+    pub operator: Address,
+    pub currency: Address,
+    pub root: BytesN<32>,
+    /// Deposited by the operator; the sum of all leaf amounts.
+    pub total: i128,
+    /// Paid out to sellers so far.
+    pub claimed: i128,
+    pub leaves: u32,
+    /// Last timestamp at which sellers may claim.
+    pub claim_deadline: u64,
+    /// Whether the operator has taken back the unclaimed remainder.
+    pub reclaimed: bool,
+}
+
+/// One seller payout in a settlement batch, hashed into its Merkle tree.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchLeaf {
+    pub batch_id: u64,
+    pub index: u32,
+    pub seller: Address,
+    pub amount: i128,
+}
+
+/// Current on-ledger schema version of persisted trades.
+pub const TRADE_SCHEMA_VERSION: u32 = 2;
+
+/// Versioned envelope for persisted trades. New `Trade` layouts get a new
+/// variant so entries written by older code can still be decoded and upgraded
+/// in place after a contract upgrade.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VersionedTrade {
+    V1(TradeV1),
+    /// Amount and fee widened to `i128`.
+    V2(Trade),
+}
+
+impl VersionedTrade {
+    pub fn version(&self) -> u32 {
+        match self {
+            VersionedTrade::V1(_) => 1,
+            VersionedTrade::V2(_) => 2,
+        }
+    }
+
+    /// Upgrade to the current `Trade` layout.
+    pub fn into_current(self) -> Trade {
+        match self {
+            VersionedTrade::V1(trade) => trade.into(),
+            VersionedTrade::V2(trade) => trade,
+        }
+    }
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum KycStatus {
@@ -135,7 +833,189 @@ pub struct CrossChainInfo {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct InsurancePolicy {
     pub provider: Address,
-    pub premium: u64,
-    pub coverage: u64,
+    pub premium: i128,
+    pub coverage: i128,
     pub claimed: bool,
 }
+
+/// Admin log entries kept before the oldest are overwritten.
+pub const ADMIN_LOG_CAPACITY: u64 = 256;
+
+/// Privileged action recorded in the admin log.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AdminAction {
+    FeeUpdated,
+    FeeBoundsSet,
+    TokenFeeSet,
+    PromoWindowSet,
+    ArbitratorRegistered,
+    ArbitratorRemoved,
+    Paused,
+    Unpaused,
+    FeeWithdrawalRequested,
+    FeesWithdrawn,
+    FeeWithdrawalCancelled,
+    EmergencyInitiated,
+    EmergencyCancelled,
+    EmergencyExecuted,
+    TradeFrozen,
+    TradeUnfrozen,
+    ReferrerStatusSet,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminLogEntry {
+    /// Position in the log since deployment; survives ring-buffer wraparound.
+    pub seq: u64,
+    pub actor: Address,
+    pub action: AdminAction,
+    pub ledger: u32,
+}
+
+/// Admin-defined loyalty programme. Points accrue to both parties of a
+/// completed trade and can be redeemed for a fee discount on a later trade.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoyaltyConfig {
+    /// Points earned per 1_000_000 units of settled trade volume.
+    pub points_per_million: u64,
+    /// Points needed to redeem one basis point of fee discount.
+    pub points_per_bps: u64,
+    /// Largest discount a user may hold at once, in basis points.
+    pub max_discount_bps: u32,
+}
+
+/// Message digests kept per trade; the oldest is dropped once full.
+pub const MAX_MESSAGE_ANCHORS: u32 = 50;
+
+/// Hash of an off-chain message between trade participants, anchored so the
+/// transcript can later be shown unaltered.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MessageAnchor {
+    pub author: Address,
+    pub msg_hash: BytesN<32>,
+    pub ledger: u32,
+}
+
+/// Clean trades (completed since the last lost dispute) per badge level.
+pub const BADGE_TRADES_PER_LEVEL: u32 = 10;
+/// Highest claimable trust badge level.
+pub const MAX_BADGE_LEVEL: u32 = 5;
+
+/// A user's settlement history, used to award trust badges.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TrackRecord {
+    /// Trades completed since the user last lost a dispute.
+    pub clean_trades: u32,
+    pub disputes_lost: u32,
+}
+
+/// Lifetime trading aggregates for one address.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct UserStats {
+    pub trades_as_buyer: u32,
+    pub trades_as_seller: u32,
+    /// Amount of settled trades the user was a party to.
+    pub volume: i128,
+    pub disputes_won: u32,
+    pub disputes_lost: u32,
+}
+
+/// An extra token escrowed alongside the trade's primary currency.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BasketLeg {
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// A trade's basket legs and how much of each has left escrow.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Basket {
+    pub legs: soroban_sdk::Vec<BasketLeg>,
+    /// Amount of each leg already paid out, by index into `legs`.
+    pub released: soroban_sdk::Vec<i128>,
+    /// Primary-currency amount paid out so far, which the legs follow.
+    pub primary_released: i128,
+}
+
+/// Rate at which a quoted trade's settlement amount was fixed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FixedRate {
+    /// Oracle price of one unit of the settlement currency in the reference
+    /// asset, scaled by 10^decimals.
+    pub price: i128,
+    pub decimals: u32,
+    /// Settlement-currency amount the trade was fixed at.
+    pub amount: i128,
+    pub fixed_at: u64,
+}
+
+/// A trade denominated in a reference asset and settled in its currency.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TradeQuote {
+    pub reference: Address,
+    /// Trade value in reference-asset units.
+    pub quoted_amount: i128,
+    /// Set once the trade is first funded.
+    pub fixed: Option<FixedRate>,
+}
+
+/// Outcome of one item in a `resolve_disputes` or `sweep_expired` batch.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchResolutionResult {
+    pub trade_id: u64,
+    /// `None` if the item was processed, else the `ContractError` code it
+    /// was skipped with.
+    pub error: Option<u32>,
+}
+
+/// Bonds both parties must post when a dispute opens. Zero `amount` disables.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DisputeBondPolicy {
+    /// Bond each party posts, in the trade's currency.
+    pub amount: i128,
+    /// Seconds after the dispute opens within which bonds must be posted.
+    pub window: u64,
+}
+
+/// Bond state of an open dispute.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeBonds {
+    pub amount: i128,
+    pub deadline: u64,
+    pub buyer_posted: bool,
+    pub seller_posted: bool,
+}
+
+/// Response tracking for an open dispute under a response window.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeResponses {
+    /// Timestamp by which each party must have raised the dispute or
+    /// submitted evidence.
+    pub deadline: u64,
+    pub buyer_responded: bool,
+    pub seller_responded: bool,
+}
+
+/// Unit deadline features measure time in.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TimingMode {
+    /// Ledger sequence numbers.
+    Ledger,
+    /// Ledger close time, in unix seconds.
+    Timestamp,
+}
@@ -15,11 +15,23 @@
 ///   UP_SNAP   – RollbackSnapshot (written on execute, cleared on migrate success)
 ///   UP_GUARD  – UpgradeGuard (written on execute, cleared on migrate / rollback)
 ///   VERSION   – u32 contract version (also managed by storage.rs helpers)
+///   SEMVER    – ContractVersion (semver of the running code + last WASM hash)
 
 use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol};
 
 use crate::errors::ContractError;
-use crate::storage::{get_admin, get_fee_bps, get_version, set_version};
+use crate::storage::{
+    get_admin, get_contract_version, get_fee_bps, get_version, set_contract_version, set_version,
+};
+use crate::types::ContractVersion;
+
+// ---------------------------------------------------------------------------
+// Semantic version of this build. Bump alongside Cargo.toml on release; the
+// value is written to storage on initialize and on migrate after an upgrade.
+// ---------------------------------------------------------------------------
+pub const CONTRACT_VERSION_MAJOR: u32 = 0;
+pub const CONTRACT_VERSION_MINOR: u32 = 1;
+pub const CONTRACT_VERSION_PATCH: u32 = 0;
 
 // ---------------------------------------------------------------------------
 // Timelock: ~24 h at ~5 s/ledger
@@ -122,6 +134,16 @@ fn clear_upgrade_guard(env: &Env) {
     env.storage().instance().remove(&key_up_guard());
 }
 
+/// Semantic version of the code currently executing, tagged with `wasm_hash`.
+pub fn build_version(wasm_hash: Option<BytesN<32>>) -> ContractVersion {
+    ContractVersion {
+        major: CONTRACT_VERSION_MAJOR,
+        minor: CONTRACT_VERSION_MINOR,
+        patch: CONTRACT_VERSION_PATCH,
+        wasm_hash,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Public API
 // ---------------------------------------------------------------------------
@@ -203,6 +225,12 @@ pub fn execute_upgrade(env: &Env, admin: &Address) -> Result<(), ContractError>
     // Clear the proposal — it has been consumed.
     clear_upgrade_proposal(env);
 
+    // Record the new WASM hash now; the semver is refreshed by the new code
+    // when `run_migration` executes.
+    let mut stored = get_contract_version(env).unwrap_or_else(|| build_version(None));
+    stored.wasm_hash = Some(proposal.new_wasm_hash.clone());
+    set_contract_version(env, &stored);
+
     // Deploy new WASM (Soroban built-in).
     env.deployer().update_current_contract_wasm(proposal.new_wasm_hash);
 
@@ -242,6 +270,10 @@ pub fn run_migration(env: &Env, admin: &Address, expected_version: u32) -> Resul
     let next = current.checked_add(1).ok_or(ContractError::Overflow)?;
     set_version(env, next);
 
+    // Running under the new WASM: stamp its semver, keeping the recorded hash.
+    let wasm_hash = get_contract_version(env).and_then(|v| v.wasm_hash);
+    set_contract_version(env, &build_version(wasm_hash));
+
     // Upgrade complete — clear guard and snapshot.
     clear_upgrade_guard(env);
     clear_rollback_snapshot(env);
@@ -269,7 +301,7 @@ pub fn rollback_upgrade(env: &Env, admin: &Address) -> Result<(), ContractError>
     set_version(env, snap.version_before);
 
     // Restore fee bps.
-    crate::storage::set_fee_bps(env, snap.fee_bps_before);
+    crate::storage::set_fee_bps(env, snap.fee_bps_before)?;
 
     // Clear guard and snapshot.
     clear_upgrade_guard(env);
@@ -0,0 +1,42 @@
+//! Per-address trading aggregates.
+//!
+//! One record per user, updated as trades move through their lifecycle:
+//! creation counts the trade for each side, settlement adds its amount to
+//! both parties' volume, and a one-sided ruling scores the dispute. Fee
+//! tiers, reputation and frontend profiles all read from this record.
+
+use soroban_sdk::{Address, Env};
+
+use crate::errors::ContractError;
+use crate::storage::{get_user_stats, save_user_stats};
+use crate::types::Trade;
+
+/// Count a newly created trade for both parties.
+pub fn record_created(env: &Env, seller: &Address, buyer: &Address) {
+    let mut stats = get_user_stats(env, seller);
+    stats.trades_as_seller = stats.trades_as_seller.saturating_add(1);
+    save_user_stats(env, seller, &stats);
+    let mut stats = get_user_stats(env, buyer);
+    stats.trades_as_buyer = stats.trades_as_buyer.saturating_add(1);
+    save_user_stats(env, buyer, &stats);
+}
+
+/// Add a settled trade's amount to both parties' volume.
+pub fn record_settled(env: &Env, trade: &Trade) -> Result<(), ContractError> {
+    for user in [&trade.seller, &trade.buyer] {
+        let mut stats = get_user_stats(env, user);
+        stats.volume = stats.volume.checked_add(trade.amount).ok_or(ContractError::Overflow)?;
+        save_user_stats(env, user, &stats);
+    }
+    Ok(())
+}
+
+/// Score a dispute ruled wholly in `winner`'s favour.
+pub fn record_dispute(env: &Env, winner: &Address, loser: &Address) {
+    let mut stats = get_user_stats(env, winner);
+    stats.disputes_won = stats.disputes_won.saturating_add(1);
+    save_user_stats(env, winner, &stats);
+    let mut stats = get_user_stats(env, loser);
+    stats.disputes_lost = stats.disputes_lost.saturating_add(1);
+    save_user_stats(env, loser, &stats);
+}
@@ -0,0 +1,93 @@
+//! Escrow in a Stellar asset with clawback enabled.
+//!
+//! The issuer of such an asset can claw tokens back from the escrow contract
+//! at any time. Payouts must then fail with `EscrowClawedBack` and leave the
+//! trade as it was, instead of failing inside the token contract. The
+//! shortfall cases settle through dispute rulings, which pay purely out of
+//! escrow.
+//!
+//! Run with:
+//!   cargo test --test clawback
+
+#![cfg(test)]
+
+mod common;
+
+use common::{approve_funding, create_disputed_trade, create_trade, setup, Harness};
+use soroban_sdk::{testutils::IssuerFlags, token, BytesN};
+use stellar_escrow_contract::{ContractError, DisputeResolution, TradeStatus};
+
+/// Turn on clawback for the test asset. Balances created afterwards,
+/// including the escrow contract's, can be clawed back.
+fn enable_clawback(h: &Harness) {
+    h.sac.issuer().set_flag(IssuerFlags::ClawbackEnabledFlag);
+}
+
+fn funded_trade(h: &Harness, amount: i128) -> u64 {
+    let id = create_trade(h, amount);
+    approve_funding(h, amount);
+    h.client.fund_trade(&id);
+    id
+}
+
+#[test]
+fn clawback_enabled_asset_settles_normally() {
+    let h = setup();
+    enable_clawback(&h);
+    let id = funded_trade(&h, 1_000_000);
+    h.client.complete_trade(&id, &None);
+    h.client.confirm_receipt(&id, &None);
+
+    assert_eq!(h.client.get_trade(&id).status, TradeStatus::Settled);
+    assert_eq!(token::Client::new(&h.env, &h.token_addr).balance(&h.seller), 990_000);
+}
+
+#[test]
+fn escrow_pooled_across_trades_settles_each_trade() {
+    let h = setup();
+    enable_clawback(&h);
+    let first = create_disputed_trade(&h, 1_000_000);
+    let second = create_disputed_trade(&h, 1_000_000);
+
+    assert_eq!(release_to_seller(&h, first), Ok(()));
+    assert_eq!(h.client.get_total_locked(&h.token_addr), 1_000_000);
+    assert_eq!(release_to_seller(&h, second), Ok(()));
+    assert_eq!(h.client.get_total_locked(&h.token_addr), 0);
+}
+
+fn release_to_seller(h: &Harness, id: u64) -> Result<(), ContractError> {
+    let reason = BytesN::from_array(&h.env, &[0u8; 32]);
+    match h.client.try_resolve_dispute(&id, &DisputeResolution::ReleaseToSeller, &reason) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(e.unwrap()),
+    }
+}
+
+#[test]
+fn clawed_back_escrow_fails_payout() {
+    let h = setup();
+    enable_clawback(&h);
+    let id = create_disputed_trade(&h, 1_000_000);
+
+    token::StellarAssetClient::new(&h.env, &h.token_addr).clawback(&h.client.address, &1_000_000i128);
+
+    assert_eq!(release_to_seller(&h, id), Err(ContractError::EscrowClawedBack));
+    assert_eq!(h.client.get_trade(&id).status, TradeStatus::Disputed);
+    assert_eq!(h.client.get_total_locked(&h.token_addr), 1_000_000);
+}
+
+#[test]
+fn partial_clawback_fails_every_trade_in_the_token() {
+    let h = setup();
+    enable_clawback(&h);
+    let first = create_disputed_trade(&h, 1_000_000);
+    let second = create_disputed_trade(&h, 1_000_000);
+    token::StellarAssetClient::new(&h.env, &h.token_addr).clawback(&h.client.address, &500_000i128);
+
+    // Escrow is pooled per token: paying the first trade in full would
+    // leave the second one short, so neither payout goes through.
+    assert_eq!(release_to_seller(&h, first), Err(ContractError::EscrowClawedBack));
+    assert_eq!(release_to_seller(&h, second), Err(ContractError::EscrowClawedBack));
+    assert_eq!(h.client.get_trade(&first).status, TradeStatus::Disputed);
+    assert_eq!(h.client.get_total_locked(&h.token_addr), 2_000_000);
+}
@@ -3,7 +3,7 @@
 extern crate std;
 
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
+    testutils::{Address as _, Ledger, StellarAssetContract},
     token, Address, Env,
 };
 
@@ -15,6 +15,8 @@ use stellar_escrow_contract::{
 pub struct Harness<'a> {
     pub env: Env,
     pub token_addr: Address,
+    /// Handle on the test asset's issuer, for setting flags like clawback.
+    pub sac: StellarAssetContract,
     pub admin: Address,
     pub seller: Address,
     pub buyer: Address,
@@ -53,6 +55,7 @@ pub fn setup() -> Harness<'static> {
     Harness {
         env,
         token_addr,
+        sac,
         admin,
         seller,
         buyer,
@@ -70,7 +73,7 @@ pub fn approve_funding(h: &Harness, amount: i128) {
     );
 }
 
-pub fn create_trade(h: &Harness, amount: u64) -> u64 {
+pub fn create_trade(h: &Harness, amount: i128) -> u64 {
     h.client.create_trade(
         &h.seller,
         &h.buyer,
@@ -80,7 +83,7 @@ pub fn create_trade(h: &Harness, amount: u64) -> u64 {
     )
 }
 
-pub fn create_disputed_trade(h: &Harness, amount: u64) -> u64 {
+pub fn create_disputed_trade(h: &Harness, amount: i128) -> u64 {
     let id = h.client.create_trade(
         &h.seller,
         &h.buyer,
@@ -88,17 +91,18 @@ pub fn create_disputed_trade(h: &Harness, amount: u64) -> u64 {
         &Some(h.arbitrator.clone()),
         &OptionalMetadata::None,
     );
-    approve_funding(h, amount as i128);
+    h.client.accept_assignment(&id);
+    approve_funding(h, amount);
     h.client.fund_trade(&id);
-    h.client.raise_dispute(&id, &h.buyer);
+    h.client.raise_dispute(&h.buyer, &id);
     id
 }
 
-pub fn create_completed_trade(h: &Harness, amount: u64) -> u64 {
+pub fn create_completed_trade(h: &Harness, amount: i128) -> u64 {
     let id = create_trade(h, amount);
-    approve_funding(h, amount as i128);
+    approve_funding(h, amount);
     h.client.fund_trade(&id);
-    h.client.complete_trade(&id);
+    h.client.complete_trade(&id, &None);
     id
 }
 
@@ -0,0 +1,279 @@
+//! Smart-wallet (custom account) compatibility tests.
+//!
+//! Buyer, seller and arbitrator are contract accounts implementing
+//! `__check_auth`, like passkey wallets. Every call is made with real
+//! authorization entries (`set_auths`) rather than mocked auth, so the
+//! wallet's `__check_auth` runs and the contract's auth tree (including the
+//! nested token transfer when funding) must match what the wallet signed.
+//!
+//! Run with:
+//!   cargo test --test custom_accounts
+
+#![cfg(test)]
+
+mod common;
+
+extern crate std;
+
+use common::{setup, Harness};
+use soroban_sdk::{
+    auth::{Context, CustomAccountInterface},
+    contract, contracterror, contractimpl,
+    crypto::Hash,
+    symbol_short, token,
+    xdr::{
+        InvokeContractArgs, ScAddress, ScSymbol, ScVal, SorobanAddressCredentials, SorobanAuthorizationEntry,
+        SorobanAuthorizedFunction, SorobanAuthorizedInvocation, SorobanCredentials, StringM, VecM,
+    },
+    Address, BytesN, Env, IntoVal, TryFromVal, Val, Vec,
+};
+use stellar_escrow_contract::{
+    types::{KycStatus, UserCompliance},
+    DisputeResolution, OptionalMetadata, TradeStatus,
+};
+
+// ---------------------------------------------------------------------------
+// Mock custom account
+// ---------------------------------------------------------------------------
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum WalletError {
+    Locked = 1,
+}
+
+/// Accepts any signature unless locked, and counts `__check_auth` calls.
+#[contract]
+pub struct MockWallet;
+
+#[contractimpl]
+impl MockWallet {
+    pub fn set_locked(env: Env, locked: bool) {
+        env.storage().instance().set(&symbol_short!("locked"), &locked);
+    }
+
+    pub fn checks(env: Env) -> u32 {
+        env.storage().instance().get(&symbol_short!("checks")).unwrap_or(0)
+    }
+}
+
+#[contractimpl]
+impl CustomAccountInterface for MockWallet {
+    type Signature = ();
+    type Error = WalletError;
+
+    fn __check_auth(
+        env: Env,
+        _signature_payload: Hash<32>,
+        _signature: (),
+        _auth_contexts: Vec<Context>,
+    ) -> Result<(), WalletError> {
+        if env.storage().instance().get(&symbol_short!("locked")).unwrap_or(false) {
+            return Err(WalletError::Locked);
+        }
+        let checks: u32 = env.storage().instance().get(&symbol_short!("checks")).unwrap_or(0);
+        env.storage().instance().set(&symbol_short!("checks"), &(checks + 1));
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Authorization entries
+// ---------------------------------------------------------------------------
+
+fn sc_address(env: &Env, address: &Address) -> ScAddress {
+    match ScVal::try_from_val(env, &address.to_val()).unwrap() {
+        ScVal::Address(a) => a,
+        _ => unreachable!(),
+    }
+}
+
+/// One authorized call: `contract.function(args)` plus the calls nested in it.
+fn invocation(
+    env: &Env,
+    contract: &Address,
+    function: &str,
+    args: Vec<Val>,
+    sub_invocations: std::vec::Vec<SorobanAuthorizedInvocation>,
+) -> SorobanAuthorizedInvocation {
+    let args: std::vec::Vec<ScVal> = args.iter().map(|v| ScVal::try_from_val(env, &v).unwrap()).collect();
+    SorobanAuthorizedInvocation {
+        function: SorobanAuthorizedFunction::ContractFn(InvokeContractArgs {
+            contract_address: sc_address(env, contract),
+            function_name: ScSymbol(StringM::try_from(function).unwrap()),
+            args: VecM::try_from(args).unwrap(),
+        }),
+        sub_invocations: VecM::try_from(sub_invocations).unwrap(),
+    }
+}
+
+/// Authorize `root` as signed by `wallet`, replacing any mocked auth for the
+/// next call.
+fn sign(env: &Env, wallet: &Address, nonce: i64, root: SorobanAuthorizedInvocation) {
+    env.set_auths(&[SorobanAuthorizationEntry {
+        credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+            address: sc_address(env, wallet),
+            nonce,
+            signature_expiration_ledger: env.ledger().sequence() + 100,
+            signature: ScVal::Void,
+        }),
+        root_invocation: root,
+    }]);
+}
+
+// ---------------------------------------------------------------------------
+// Harness
+// ---------------------------------------------------------------------------
+
+struct Wallets {
+    seller: Address,
+    buyer: Address,
+    arbitrator: Address,
+}
+
+fn wallets(h: &Harness) -> Wallets {
+    let compliant = UserCompliance {
+        kyc_status: KycStatus::Verified,
+        aml_cleared: true,
+        jurisdiction: soroban_sdk::String::from_str(&h.env, "US"),
+    };
+    let w = Wallets {
+        seller: h.env.register_contract(None, MockWallet),
+        buyer: h.env.register_contract(None, MockWallet),
+        arbitrator: h.env.register_contract(None, MockWallet),
+    };
+    for party in [&w.seller, &w.buyer, &w.arbitrator] {
+        h.client.set_user_compliance(&h.admin, party, &compliant);
+    }
+    h.client.register_arbitrator(&w.arbitrator);
+    token::StellarAssetClient::new(&h.env, &h.token_addr).mint(&w.buyer, &10_000_000i128);
+    w
+}
+
+fn checks(h: &Harness, wallet: &Address) -> u32 {
+    MockWalletClient::new(&h.env, wallet).checks()
+}
+
+/// Create (by the seller wallet) and fund (by the buyer wallet) a trade with
+/// the arbitrator wallet assigned, using real auth for each party.
+fn funded_trade(h: &Harness, w: &Wallets, amount: i128) -> u64 {
+    let env = &h.env;
+    let escrow = &h.client.address;
+    let arbitrator = Some(w.arbitrator.clone());
+    sign(
+        env,
+        &w.seller,
+        1,
+        invocation(
+            env,
+            escrow,
+            "create_trade",
+            (w.seller.clone(), w.buyer.clone(), amount, arbitrator.clone(), OptionalMetadata::None).into_val(env),
+            std::vec![],
+        ),
+    );
+    let id = h.client.create_trade(&w.seller, &w.buyer, &amount, &arbitrator, &OptionalMetadata::None);
+
+    sign(env, &w.arbitrator, 2, invocation(env, escrow, "accept_assignment", (id,).into_val(env), std::vec![]));
+    h.client.accept_assignment(&id);
+
+    let transfer = invocation(
+        env,
+        &h.token_addr,
+        "transfer",
+        (w.buyer.clone(), escrow.clone(), amount).into_val(env),
+        std::vec![],
+    );
+    sign(env, &w.buyer, 3, invocation(env, escrow, "fund_trade", (id,).into_val(env), std::vec![transfer]));
+    h.client.fund_trade(&id);
+    id
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn smart_wallets_complete_a_trade() {
+    let h = setup();
+    let w = wallets(&h);
+    let env = &h.env;
+    let escrow = &h.client.address;
+    let id = funded_trade(&h, &w, 1_000_000);
+    assert_eq!(h.client.get_trade(&id).status, TradeStatus::Funded);
+
+    let none: Option<BytesN<32>> = None;
+    sign(env, &w.seller, 4, invocation(env, escrow, "complete_trade", (id, none.clone()).into_val(env), std::vec![]));
+    h.client.complete_trade(&id, &none);
+
+    let rating: Option<u32> = None;
+    sign(env, &w.buyer, 5, invocation(env, escrow, "confirm_receipt", (id, rating).into_val(env), std::vec![]));
+    h.client.confirm_receipt(&id, &rating);
+
+    assert_eq!(h.client.get_trade(&id).status, TradeStatus::Settled);
+    assert_eq!(token::Client::new(env, &h.token_addr).balance(&w.seller), 990_000);
+    assert_eq!(checks(&h, &w.seller), 2);
+    assert_eq!(checks(&h, &w.buyer), 2);
+}
+
+#[test]
+fn smart_wallet_arbitrator_resolves_dispute() {
+    let h = setup();
+    let w = wallets(&h);
+    let env = &h.env;
+    let escrow = &h.client.address;
+    let id = funded_trade(&h, &w, 1_000_000);
+
+    sign(env, &w.buyer, 4, invocation(env, escrow, "raise_dispute", (w.buyer.clone(), id).into_val(env), std::vec![]));
+    h.client.raise_dispute(&w.buyer, &id);
+
+    let reason = BytesN::from_array(env, &[7u8; 32]);
+    let ruling = DisputeResolution::ReleaseToBuyer;
+    sign(
+        env,
+        &w.arbitrator,
+        5,
+        invocation(env, escrow, "resolve_dispute", (id, ruling.clone(), reason.clone()).into_val(env), std::vec![]),
+    );
+    h.client.resolve_dispute(&id, &ruling, &reason);
+
+    assert_eq!(h.client.get_trade(&id).status, TradeStatus::Resolved);
+    assert_eq!(checks(&h, &w.arbitrator), 2);
+    assert_eq!(token::Client::new(env, &h.token_addr).balance(&w.buyer), 10_000_000 - 10_000);
+}
+
+#[test]
+fn locked_smart_wallet_cannot_fund() {
+    let h = setup();
+    let w = wallets(&h);
+    let env = &h.env;
+    let escrow = &h.client.address;
+    let id = h.client.create_trade(&w.seller, &w.buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+    MockWalletClient::new(env, &w.buyer).set_locked(&true);
+
+    let transfer = invocation(
+        env,
+        &h.token_addr,
+        "transfer",
+        (w.buyer.clone(), escrow.clone(), 1_000_000i128).into_val(env),
+        std::vec![],
+    );
+    sign(env, &w.buyer, 1, invocation(env, escrow, "fund_trade", (id,).into_val(env), std::vec![transfer]));
+    assert!(h.client.try_fund_trade(&id).is_err());
+
+    env.mock_all_auths();
+    assert_eq!(h.client.get_trade(&id).status, TradeStatus::Created);
+}
+
+#[test]
+fn fund_trade_auth_must_cover_the_nested_transfer() {
+    let h = setup();
+    let w = wallets(&h);
+    let env = &h.env;
+    let escrow = &h.client.address;
+    let id = h.client.create_trade(&w.seller, &w.buyer, &1_000_000i128, &None, &OptionalMetadata::None);
+
+    // Signing only the escrow call leaves the token transfer unauthorized.
+    sign(env, &w.buyer, 1, invocation(env, escrow, "fund_trade", (id,).into_val(env), std::vec![]));
+    assert!(h.client.try_fund_trade(&id).is_err());
+}
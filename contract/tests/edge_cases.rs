@@ -6,7 +6,7 @@ use common::{
     advance_ledger_sequence, approve_funding, create_completed_trade, create_disputed_trade,
     create_trade, setup,
 };
-use soroban_sdk::{testutils::Address as _, String};
+use soroban_sdk::{testutils::Address as _, BytesN, String};
 use stellar_escrow_contract::{DisputeResolution, OptionalMetadata, TradeStatus};
 
 #[test]
@@ -29,7 +29,7 @@ fn edge_rejects_receipt_confirmation_before_completion() {
     approve_funding(&h, 1_000_000);
     h.client.fund_trade(&id);
 
-    assert!(h.client.try_confirm_receipt(&id).is_err());
+    assert!(h.client.try_confirm_receipt(&id, &None).is_err());
 }
 
 #[test]
@@ -39,7 +39,7 @@ fn edge_rejects_partial_resolution_above_100_percent() {
 
     assert!(
         h.client
-            .try_resolve_dispute(&id, &DisputeResolution::Partial(10_001))
+            .try_resolve_dispute(&id, &DisputeResolution::Partial(10_001), &BytesN::from_array(&h.env, &[0u8; 32]))
             .is_err()
     );
 }
@@ -53,7 +53,7 @@ fn edge_rejects_expiring_bridge_trade_before_deadline() {
     let id = h.client.create_cross_chain_trade(
         &h.seller,
         &h.buyer,
-        &1_000_000u64,
+        &1_000_000i128,
         &None,
         &chain,
         &20u32,
@@ -71,7 +71,7 @@ fn edge_expires_bridge_trade_once_deadline_passes() {
     let id = h.client.create_cross_chain_trade(
         &h.seller,
         &h.buyer,
-        &1_000_000u64,
+        &1_000_000i128,
         &None,
         &chain,
         &5u32,
@@ -92,14 +92,14 @@ fn edge_rejects_insurance_purchase_for_unfunded_trade() {
     let id = h.client.create_trade(
         &h.seller,
         &h.buyer,
-        &1_000_000u64,
+        &1_000_000i128,
         &None,
         &OptionalMetadata::None,
     );
 
     assert!(
         h.client
-            .try_purchase_insurance(&id, &provider, &100u32, &500_000u64)
+            .try_purchase_insurance(&id, &provider, &100u32, &500_000i128)
             .is_err()
     );
 }
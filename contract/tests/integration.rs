@@ -3,7 +3,7 @@
 mod common;
 
 use common::{approve_funding, create_trade, setup};
-use soroban_sdk::{testutils::Address as _, token, Address, String};
+use soroban_sdk::{testutils::{Address as _, Ledger}, token, Address, BytesN, String};
 use stellar_escrow_contract::{DisputeResolution, OptionalMetadata, TradeStatus};
 
 #[test]
@@ -13,11 +13,13 @@ fn integration_happy_path_accumulates_and_withdraws_fees() {
 
     approve_funding(&h, 1_000_000);
     h.client.fund_trade(&id);
-    h.client.complete_trade(&id);
-    h.client.confirm_receipt(&id);
+    h.client.complete_trade(&id, &None);
+    h.client.confirm_receipt(&id, &None);
 
     let recipient = Address::generate(&h.env);
-    h.client.withdraw_fees(&recipient);
+    h.client.request_fee_withdrawal(&h.token_addr, &recipient, &10_000i128);
+    h.env.ledger().with_mut(|l| l.timestamp += h.client.get_fee_withdrawal_delay());
+    h.client.execute_fee_withdrawal();
 
     assert_eq!(token::Client::new(&h.env, &h.token_addr).balance(&h.seller), 990_000);
     assert_eq!(token::Client::new(&h.env, &h.token_addr).balance(&recipient), 10_000);
@@ -31,18 +33,19 @@ fn integration_dispute_partial_resolution_preserves_fee_accounting() {
     let id = h.client.create_trade(
         &h.seller,
         &h.buyer,
-        &1_000_000u64,
+        &1_000_000i128,
         &Some(h.arbitrator.clone()),
         &OptionalMetadata::None,
     );
+    h.client.accept_assignment(&id);
 
     approve_funding(&h, 1_000_000);
     h.client.fund_trade(&id);
-    h.client.raise_dispute(&id, &h.buyer);
+    h.client.raise_dispute(&h.buyer, &id);
     h.client
-        .resolve_dispute(&id, &DisputeResolution::Partial(4_000));
+        .resolve_dispute(&id, &DisputeResolution::Partial(4_000), &BytesN::from_array(&h.env, &[0u8; 32]));
 
-    assert_eq!(h.client.get_trade(&id).status, TradeStatus::Disputed);
+    assert_eq!(h.client.get_trade(&id).status, TradeStatus::Resolved);
     assert_eq!(h.client.get_accumulated_fees(), 10_000);
     assert_eq!(
         token::Client::new(&h.env, &h.token_addr).balance(&h.buyer) - buyer_before,
@@ -63,7 +66,7 @@ fn integration_bridge_trade_can_complete_standard_settlement() {
     let id = h.client.create_cross_chain_trade(
         &h.seller,
         &h.buyer,
-        &1_500_000u64,
+        &1_500_000i128,
         &None,
         &String::from_str(&h.env, "ethereum"),
         &25u32,
@@ -71,10 +74,10 @@ fn integration_bridge_trade_can_complete_standard_settlement() {
 
     h.client
         .confirm_bridge_deposit(&id, &String::from_str(&h.env, "0xbridge"));
-    h.client.complete_trade(&id);
-    h.client.confirm_receipt(&id);
+    h.client.complete_trade(&id, &None);
+    h.client.confirm_receipt(&id, &None);
 
-    assert_eq!(h.client.get_trade(&id).status, TradeStatus::Completed);
+    assert_eq!(h.client.get_trade(&id).status, TradeStatus::Settled);
     assert_eq!(token::Client::new(&h.env, &h.token_addr).balance(&h.seller), 1_485_000);
 }
 
@@ -88,16 +91,17 @@ fn integration_insurance_claim_flow_pays_out_provider_coverage() {
     let id = h.client.create_trade(
         &h.seller,
         &h.buyer,
-        &1_000_000u64,
+        &1_000_000i128,
         &Some(h.arbitrator.clone()),
         &OptionalMetadata::None,
     );
+    h.client.accept_assignment(&id);
 
     approve_funding(&h, 1_000_000);
     h.client.fund_trade(&id);
-    h.client.purchase_insurance(&id, &provider, &100u32, &250_000u64);
-    h.client.raise_dispute(&id, &h.buyer);
-    h.client.claim_insurance(&id, &h.seller, &200_000u64);
+    h.client.purchase_insurance(&id, &provider, &100u32, &250_000i128);
+    h.client.raise_dispute(&h.buyer, &id);
+    h.client.claim_insurance(&id, &h.seller, &200_000i128);
 
     let policy = h.client.get_insurance_policy(&id).unwrap();
     assert!(policy.claimed);
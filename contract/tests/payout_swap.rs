@@ -0,0 +1,136 @@
+//! Seller payouts routed through a whitelisted swap adapter.
+//!
+//! The mock adapter swaps at a fixed rate out of its own inventory of the
+//! output token and refuses swaps below `min_out`, like a DEX router
+//! enforcing slippage.
+//!
+//! Run with:
+//!   cargo test --test payout_swap
+
+#![cfg(test)]
+
+mod common;
+
+use common::{approve_funding, create_trade, setup, Harness};
+use soroban_sdk::{contract, contractimpl, symbol_short, testutils::Address as _, token, Address, Env};
+use stellar_escrow_contract::{ContractError, PayoutSwap, TradeStatus};
+
+// ---------------------------------------------------------------------------
+// Mock swap adapter
+// ---------------------------------------------------------------------------
+
+/// Pays `rate` output tokens per input token.
+#[contract]
+pub struct MockSwapAdapter;
+
+#[contractimpl]
+impl MockSwapAdapter {
+    pub fn set_rate(env: Env, rate: i128) {
+        env.storage().instance().set(&symbol_short!("rate"), &rate);
+    }
+
+    pub fn swap_exact_in(
+        env: Env,
+        from: Address,
+        token_in: Address,
+        token_out: Address,
+        amount_in: i128,
+        min_out: i128,
+        to: Address,
+    ) -> i128 {
+        let rate: i128 = env.storage().instance().get(&symbol_short!("rate")).unwrap_or(1);
+        let out = amount_in * rate;
+        if out < min_out {
+            panic!("slippage");
+        }
+        let adapter = env.current_contract_address();
+        token::Client::new(&env, &token_in).transfer_from(&adapter, &from, &adapter, &amount_in);
+        token::Client::new(&env, &token_out).transfer(&adapter, &to, &out);
+        out
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Harness
+// ---------------------------------------------------------------------------
+
+struct Swap {
+    adapter: Address,
+    token_out: Address,
+}
+
+fn swap_setup(h: &Harness, rate: i128) -> Swap {
+    let adapter = h.env.register_contract(None, MockSwapAdapter);
+    MockSwapAdapterClient::new(&h.env, &adapter).set_rate(&rate);
+    let token_out = h.env.register_stellar_asset_contract_v2(h.admin.clone()).address();
+    token::StellarAssetClient::new(&h.env, &token_out).mint(&adapter, &100_000_000i128);
+    h.client.set_swap_adapter(&adapter, &true);
+    Swap { adapter, token_out }
+}
+
+fn release(h: &Harness, id: u64) {
+    approve_funding(h, 1_000_000);
+    h.client.fund_trade(&id);
+    h.client.complete_trade(&id, &None);
+    h.client.confirm_receipt(&id, &None);
+    assert_eq!(h.client.get_trade(&id).status, TradeStatus::Settled);
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn payout_is_swapped_into_the_requested_token() {
+    let h = setup();
+    let s = swap_setup(&h, 2);
+    let id = create_trade(&h, 1_000_000);
+    let swap = PayoutSwap { adapter: s.adapter.clone(), token_out: s.token_out.clone(), min_out: 1_900_000 };
+    h.client.set_payout_swap(&id, &Some(swap.clone()));
+    assert_eq!(h.client.get_payout_swap(&id), Some(swap));
+
+    release(&h, id);
+    assert_eq!(token::Client::new(&h.env, &s.token_out).balance(&h.seller), 1_980_000);
+    assert_eq!(token::Client::new(&h.env, &h.token_addr).balance(&h.seller), 0);
+    assert_eq!(token::Client::new(&h.env, &h.token_addr).balance(&s.adapter), 990_000);
+    assert_eq!(token::Client::new(&h.env, &h.token_addr).allowance(&h.client.address, &s.adapter), 0);
+}
+
+#[test]
+fn failed_swap_falls_back_to_direct_payout() {
+    let h = setup();
+    let s = swap_setup(&h, 1);
+    let id = create_trade(&h, 1_000_000);
+    // A rate of 1 cannot meet a 2x minimum, so the adapter refuses.
+    h.client.set_payout_swap(
+        &id,
+        &Some(PayoutSwap { adapter: s.adapter.clone(), token_out: s.token_out.clone(), min_out: 2_000_000 }),
+    );
+
+    release(&h, id);
+    assert_eq!(token::Client::new(&h.env, &h.token_addr).balance(&h.seller), 990_000);
+    assert_eq!(token::Client::new(&h.env, &s.token_out).balance(&h.seller), 0);
+    assert_eq!(token::Client::new(&h.env, &h.token_addr).allowance(&h.client.address, &s.adapter), 0);
+}
+
+#[test]
+fn payout_swap_requires_whitelisted_adapter() {
+    let h = setup();
+    let s = swap_setup(&h, 2);
+    let id = create_trade(&h, 1_000_000);
+    let rogue = Address::generate(&h.env);
+    assert_eq!(
+        h.client.try_set_payout_swap(
+            &id,
+            &Some(PayoutSwap { adapter: rogue, token_out: s.token_out.clone(), min_out: 0 }),
+        ),
+        Err(Ok(ContractError::SwapAdapterNotAllowed))
+    );
+    assert_eq!(
+        h.client.try_set_payout_swap(
+            &id,
+            &Some(PayoutSwap { adapter: s.adapter, token_out: h.token_addr.clone(), min_out: 0 }),
+        ),
+        Err(Ok(ContractError::InvalidPayoutSwap))
+    );
+}
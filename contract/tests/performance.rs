@@ -4,7 +4,7 @@ mod common;
 
 use common::{approve_funding, create_trade, setup};
 use std::time::Instant;
-use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{testutils::Address as _, BytesN};
 use stellar_escrow_contract::{DisputeResolution, OptionalMetadata};
 
 const MAX_LIFECYCLE_NS: u128 = 200_000_000;
@@ -29,8 +29,8 @@ fn perf_benchmark_standard_lifecycle() {
         let id = create_trade(&h, 1_000_000);
         approve_funding(&h, 1_000_000);
         h.client.fund_trade(&id);
-        h.client.complete_trade(&id);
-        h.client.confirm_receipt(&id);
+        h.client.complete_trade(&id, &None);
+        h.client.confirm_receipt(&id, &None);
     });
 
     std::println!(
@@ -53,16 +53,17 @@ fn perf_benchmark_dispute_and_insurance_path() {
         let id = h.client.create_trade(
             &h.seller,
             &h.buyer,
-            &1_000_000u64,
+            &1_000_000i128,
             &Some(h.arbitrator.clone()),
             &OptionalMetadata::None,
         );
+        h.client.accept_assignment(&id);
         approve_funding(&h, 1_000_000);
         h.client.fund_trade(&id);
-        h.client.purchase_insurance(&id, &provider, &100u32, &250_000u64);
-        h.client.raise_dispute(&id, &h.buyer);
+        h.client.purchase_insurance(&id, &provider, &100u32, &250_000i128);
+        h.client.raise_dispute(&h.buyer, &id);
         h.client
-            .resolve_dispute(&id, &DisputeResolution::ReleaseToSeller);
+            .resolve_dispute(&id, &DisputeResolution::ReleaseToSeller, &BytesN::from_array(&h.env, &[0u8; 32]));
     });
 
     std::println!(
@@ -0,0 +1,65 @@
+//! Escrow in a regulated (SEP-8) asset.
+//!
+//! The issuer authorizes each holder. A seller the issuer has not approved
+//! cannot receive the payout directly: for tokens marked regulated it is
+//! held until the seller is approved and claims it; for other tokens the
+//! payout fails with `AssetAuthorizationRequired`.
+//!
+//! Run with:
+//!   cargo test --test regulated_assets
+
+#![cfg(test)]
+
+mod common;
+
+use common::{approve_funding, create_trade, setup, Harness};
+use soroban_sdk::{testutils::IssuerFlags, token};
+use stellar_escrow_contract::{ContractError, TradeStatus};
+
+/// Fund and complete a trade, then have the issuer revoke the seller.
+fn completed_trade_with_unapproved_seller(h: &Harness, amount: i128) -> u64 {
+    h.sac.issuer().set_flag(IssuerFlags::RevocableFlag);
+    let id = create_trade(h, amount);
+    approve_funding(h, amount);
+    h.client.fund_trade(&id);
+    h.client.complete_trade(&id, &None);
+    token::StellarAssetClient::new(&h.env, &h.token_addr).set_authorized(&h.seller, &false);
+    id
+}
+
+#[test]
+fn regulated_payout_is_held_until_the_issuer_approves() {
+    let h = setup();
+    h.client.set_regulated_asset(&h.token_addr, &true);
+    let id = completed_trade_with_unapproved_seller(&h, 1_000_000);
+
+    h.client.confirm_receipt(&id, &None);
+    assert_eq!(h.client.get_trade(&id).status, TradeStatus::Settled);
+    assert_eq!(h.client.get_held_payout(&h.seller, &h.token_addr), 990_000);
+    assert_eq!(h.client.get_total_locked(&h.token_addr), 990_000);
+    assert_eq!(
+        h.client.try_claim_held_payout(&h.seller, &h.token_addr),
+        Err(Ok(ContractError::AssetAuthorizationRequired))
+    );
+
+    token::StellarAssetClient::new(&h.env, &h.token_addr).set_authorized(&h.seller, &true);
+    assert_eq!(h.client.claim_held_payout(&h.seller, &h.token_addr), 990_000);
+    assert_eq!(token::Client::new(&h.env, &h.token_addr).balance(&h.seller), 990_000);
+    assert_eq!(h.client.get_total_locked(&h.token_addr), 0);
+    assert_eq!(
+        h.client.try_claim_held_payout(&h.seller, &h.token_addr),
+        Err(Ok(ContractError::NoHeldPayout))
+    );
+}
+
+#[test]
+fn unmarked_asset_reports_authorization_failure() {
+    let h = setup();
+    let id = completed_trade_with_unapproved_seller(&h, 1_000_000);
+
+    assert_eq!(
+        h.client.try_confirm_receipt(&id, &None),
+        Err(Ok(ContractError::AssetAuthorizationRequired))
+    );
+    assert_eq!(h.client.get_held_payout(&h.seller, &h.token_addr), 0);
+}
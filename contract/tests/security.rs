@@ -3,7 +3,7 @@
 mod common;
 
 use common::{approve_funding, create_trade, setup};
-use soroban_sdk::{testutils::Address as _, token, Address, String};
+use soroban_sdk::{testutils::Address as _, token, Address, BytesN, String};
 use stellar_escrow_contract::{DisputeResolution, OptionalMetadata, TradeStatus};
 
 #[test]
@@ -14,10 +14,10 @@ fn security_blocks_state_changes_while_paused() {
     h.client.pause();
 
     assert!(h.client.try_cancel_trade(&id).is_err());
-    assert!(h.client.try_complete_trade(&id).is_err());
+    assert!(h.client.try_complete_trade(&id, &None).is_err());
     assert!(
         h.client
-            .try_create_trade(&h.seller, &h.buyer, &1_000_000u64, &None, &OptionalMetadata::None)
+            .try_create_trade(&h.seller, &h.buyer, &1_000_000i128, &None, &OptionalMetadata::None)
             .is_err()
     );
 }
@@ -49,7 +49,7 @@ fn security_rejects_bridge_trade_creation_without_oracle() {
             .try_create_cross_chain_trade(
                 &seller,
                 &buyer,
-                &1_000_000u64,
+                &1_000_000i128,
                 &None,
                 &String::from_str(&fresh_env, "ethereum"),
                 &12u32,
@@ -70,7 +70,7 @@ fn security_only_registered_arbitrators_can_back_disputed_trades() {
             .try_create_trade(
                 &h.seller,
                 &h.buyer,
-                &1_000_000u64,
+                &1_000_000i128,
                 &Some(rogue_arbitrator),
                 &OptionalMetadata::None,
             )
@@ -85,7 +85,7 @@ fn security_rejects_dispute_resolution_without_dispute_state() {
 
     assert!(
         h.client
-            .try_resolve_dispute(&id, &DisputeResolution::ReleaseToBuyer)
+            .try_resolve_dispute(&id, &DisputeResolution::ReleaseToBuyer, &BytesN::from_array(&h.env, &[0u8; 32]))
             .is_err()
     );
 }
@@ -95,7 +95,7 @@ fn security_rejects_fee_withdrawal_when_pool_is_empty() {
     let h = setup();
     let recipient = Address::generate(&h.env);
 
-    assert!(h.client.try_withdraw_fees(&recipient).is_err());
+    assert!(h.client.try_request_fee_withdrawal(&h.token_addr, &recipient, &1i128).is_err());
 }
 
 #[test]
@@ -107,7 +107,7 @@ fn security_prevents_double_bridge_confirmation() {
     let id = h.client.create_cross_chain_trade(
         &h.seller,
         &h.buyer,
-        &1_000_000u64,
+        &1_000_000i128,
         &None,
         &String::from_str(&h.env, "base"),
         &50u32,
@@ -134,7 +134,7 @@ fn security_requires_insurance_provider_registration() {
 
     assert!(
         h.client
-            .try_purchase_insurance(&id, &provider, &100u32, &500_000u64)
+            .try_purchase_insurance(&id, &provider, &100u32, &500_000i128)
             .is_err()
     );
 
@@ -0,0 +1,212 @@
+//! Property-based state machine tests.
+//!
+//! Generates random sequences of trade calls, runs them against the contract
+//! and against a small reference model of the trade lifecycle, and checks
+//! after every step that:
+//!   1. the contract accepts exactly the calls the model allows,
+//!   2. every trade's status matches the model, and
+//!   3. funds are conserved: nothing is minted or lost, and the contract
+//!      holds exactly the escrowed amounts plus accumulated fees.
+//!
+//! Run with:
+//!   cargo test --test state_machine
+
+#![cfg(test)]
+
+mod common;
+
+extern crate std;
+
+use std::vec::Vec;
+
+use common::{approve_funding, setup, Harness};
+use proptest::prelude::*;
+use soroban_sdk::{token, BytesN};
+use stellar_escrow_contract::{DisputeResolution, OptionalMetadata, TradeStatus};
+
+/// Upper bound on trades per generated run, keeping each case cheap.
+const MAX_TRADES: usize = 4;
+
+#[derive(Clone, Debug)]
+enum Op {
+    Create(i128),
+    Fund(usize),
+    Complete(usize),
+    Confirm(usize),
+    Cancel(usize),
+    Dispute(usize),
+    Resolve(usize, bool),
+    Refund(usize),
+}
+
+fn op_strategy() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        (10_000i128..1_000_000).prop_map(Op::Create),
+        any::<usize>().prop_map(Op::Fund),
+        any::<usize>().prop_map(Op::Complete),
+        any::<usize>().prop_map(Op::Confirm),
+        any::<usize>().prop_map(Op::Cancel),
+        any::<usize>().prop_map(Op::Dispute),
+        (any::<usize>(), any::<bool>()).prop_map(|(i, to_buyer)| Op::Resolve(i, to_buyer)),
+        any::<usize>().prop_map(Op::Refund),
+    ]
+}
+
+/// Reference model of one trade.
+struct ModelTrade {
+    id: u64,
+    amount: i128,
+    status: TradeStatus,
+}
+
+impl ModelTrade {
+    /// Amount the contract should still hold in escrow for this trade.
+    fn locked(&self) -> i128 {
+        match self.status {
+            TradeStatus::Funded | TradeStatus::Completed | TradeStatus::Disputed => self.amount,
+            _ => 0,
+        }
+    }
+}
+
+struct Model {
+    trades: Vec<ModelTrade>,
+}
+
+impl Model {
+    fn pick(&mut self, i: usize) -> Option<&mut ModelTrade> {
+        if self.trades.is_empty() {
+            return None;
+        }
+        let n = self.trades.len();
+        self.trades.get_mut(i % n)
+    }
+}
+
+fn balance(h: &Harness, who: &soroban_sdk::Address) -> i128 {
+    token::Client::new(&h.env, &h.token_addr).balance(who)
+}
+
+fn total_supply(h: &Harness) -> i128 {
+    balance(h, &h.buyer) + balance(h, &h.seller) + balance(h, &h.arbitrator) + balance(h, &h.client.address)
+}
+
+/// Apply `op` to both the contract and the model, asserting they agree on
+/// whether the call is allowed.
+fn step(h: &Harness, model: &mut Model, op: &Op) {
+    let reason = BytesN::from_array(&h.env, &[0u8; 32]);
+    match *op {
+        Op::Create(amount) => {
+            if model.trades.len() >= MAX_TRADES {
+                return;
+            }
+            let id = h.client.create_trade(
+                &h.seller,
+                &h.buyer,
+                &amount,
+                &Some(h.arbitrator.clone()),
+                &OptionalMetadata::None,
+            );
+            h.client.accept_assignment(&id);
+            model.trades.push(ModelTrade { id, amount, status: TradeStatus::Created });
+        }
+        Op::Fund(i) => {
+            let Some(t) = model.pick(i) else { return };
+            approve_funding(h, t.amount);
+            let allowed = t.status == TradeStatus::Created;
+            assert_eq!(h.client.try_fund_trade(&t.id).is_ok(), allowed, "fund {:?}", t.status);
+            if allowed {
+                t.status = TradeStatus::Funded;
+            }
+        }
+        Op::Complete(i) => {
+            let Some(t) = model.pick(i) else { return };
+            let allowed = t.status == TradeStatus::Funded;
+            assert_eq!(h.client.try_complete_trade(&t.id, &None).is_ok(), allowed, "complete {:?}", t.status);
+            if allowed {
+                t.status = TradeStatus::Completed;
+            }
+        }
+        Op::Confirm(i) => {
+            let Some(t) = model.pick(i) else { return };
+            let allowed = t.status == TradeStatus::Completed;
+            assert_eq!(h.client.try_confirm_receipt(&t.id, &None).is_ok(), allowed, "confirm {:?}", t.status);
+            if allowed {
+                t.status = TradeStatus::Settled;
+            }
+        }
+        Op::Cancel(i) => {
+            let Some(t) = model.pick(i) else { return };
+            let allowed = t.status == TradeStatus::Created;
+            assert_eq!(h.client.try_cancel_trade(&t.id).is_ok(), allowed, "cancel {:?}", t.status);
+            if allowed {
+                t.status = TradeStatus::Cancelled;
+            }
+        }
+        Op::Dispute(i) => {
+            let Some(t) = model.pick(i) else { return };
+            let allowed = matches!(t.status, TradeStatus::Funded | TradeStatus::Completed);
+            assert_eq!(h.client.try_raise_dispute(&h.buyer, &t.id).is_ok(), allowed, "dispute {:?}", t.status);
+            if allowed {
+                t.status = TradeStatus::Disputed;
+            }
+        }
+        Op::Resolve(i, to_buyer) => {
+            let Some(t) = model.pick(i) else { return };
+            let ruling = if to_buyer { DisputeResolution::ReleaseToBuyer } else { DisputeResolution::ReleaseToSeller };
+            let allowed = t.status == TradeStatus::Disputed;
+            assert_eq!(h.client.try_resolve_dispute(&t.id, &ruling, &reason).is_ok(), allowed, "resolve {:?}", t.status);
+            if allowed {
+                // No re-open window is configured, so the ruling pays out at once.
+                t.status = TradeStatus::Settled;
+            }
+        }
+        Op::Refund(i) => {
+            let Some(t) = model.pick(i) else { return };
+            let allowed = matches!(t.status, TradeStatus::Funded | TradeStatus::Completed);
+            assert_eq!(h.client.try_refund_trade(&t.id, &true).is_ok(), allowed, "refund {:?}", t.status);
+            if allowed {
+                t.status = TradeStatus::Refunded;
+            }
+        }
+    }
+}
+
+fn check_invariants(h: &Harness, model: &Model, supply: i128) {
+    for t in &model.trades {
+        assert_eq!(h.client.get_trade(&t.id).status, t.status, "trade {}", t.id);
+    }
+    assert_eq!(total_supply(h), supply, "funds created or lost");
+    let locked: i128 = model.trades.iter().map(ModelTrade::locked).sum();
+    assert_eq!(
+        balance(h, &h.client.address),
+        locked + h.client.get_accumulated_fees(),
+        "contract balance does not match escrow plus fees"
+    );
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn random_call_sequences_preserve_invariants(ops in prop::collection::vec(op_strategy(), 1..40)) {
+        let h = setup();
+        let supply = total_supply(&h);
+        let mut model = Model { trades: Vec::new() };
+        for op in &ops {
+            step(&h, &mut model, op);
+            check_invariants(&h, &model, supply);
+        }
+    }
+}
+
+#[test]
+fn settled_trade_cannot_be_confirmed_disputed_or_refunded() {
+    let h = setup();
+    let mut model = Model { trades: Vec::new() };
+    let supply = total_supply(&h);
+    for op in [Op::Create(500_000), Op::Fund(0), Op::Complete(0), Op::Confirm(0), Op::Confirm(0), Op::Dispute(0), Op::Refund(0)] {
+        step(&h, &mut model, &op);
+        check_invariants(&h, &model, supply);
+    }
+}
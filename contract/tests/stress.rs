@@ -18,7 +18,7 @@ use std::time::Instant;
 
 use soroban_sdk::{
     testutils::{Address as _, Ledger},
-    token, Address, Env,
+    token, Address, BytesN, Env,
 };
 
 use stellar_escrow_contract::{
@@ -107,7 +107,7 @@ fn create_n_trades(h: &Harness, n: u32) -> std::vec::Vec<u64> {
         let id = h.client.create_trade(
             &seller,
             &buyer,
-            &1_000_000u64,
+            &1_000_000i128,
             &None,
             &OptionalMetadata::None,
         );
@@ -128,13 +128,13 @@ fn run_happy_path(h: &Harness) -> u64 {
     let id = h.client.create_trade(
         &seller,
         &buyer,
-        &1_000_000u64,
+        &1_000_000i128,
         &None,
         &OptionalMetadata::None,
     );
     h.client.fund_trade(&id);
-    h.client.complete_trade(&id);
-    h.client.confirm_receipt(&id);
+    h.client.complete_trade(&id, &None);
+    h.client.confirm_receipt(&id, &None);
     id
 }
 
@@ -149,15 +149,17 @@ fn run_dispute_cycle(h: &Harness) {
     let id = h.client.create_trade(
         &seller,
         &buyer,
-        &1_000_000u64,
+        &1_000_000i128,
         &Some(h.arbitrator.clone()),
         &OptionalMetadata::None,
     );
+    h.client.accept_assignment(&id);
     h.client.fund_trade(&id);
-    h.client.raise_dispute(&id, &buyer);
+    h.client.raise_dispute(&buyer, &id);
     h.client.resolve_dispute(
         &id,
         &stellar_escrow_contract::DisputeResolution::ReleaseToSeller,
+        &BytesN::from_array(&h.env, &[0u8; 32]),
     );
 }
 
@@ -254,7 +256,7 @@ fn bench_create_trade_instructions() {
         h.client.create_trade(
             &seller,
             &buyer,
-            &1_000_000u64,
+            &1_000_000i128,
             &None,
             &OptionalMetadata::None,
         );
@@ -287,13 +289,13 @@ fn bench_full_happy_path_instructions() {
         let id = h.client.create_trade(
             &seller,
             &buyer,
-            &1_000_000u64,
+            &1_000_000i128,
             &None,
             &OptionalMetadata::None,
         );
         h.client.fund_trade(&id);
-        h.client.complete_trade(&id);
-        h.client.confirm_receipt(&id);
+        h.client.complete_trade(&id, &None);
+        h.client.confirm_receipt(&id, &None);
     });
 
     std::println!(
@@ -323,15 +325,17 @@ fn bench_dispute_cycle_instructions() {
         let id = h.client.create_trade(
             &seller,
             &buyer,
-            &1_000_000u64,
+            &1_000_000i128,
             &Some(h.arbitrator.clone()),
             &OptionalMetadata::None,
         );
+        h.client.accept_assignment(&id);
         h.client.fund_trade(&id);
-        h.client.raise_dispute(&id, &buyer);
+        h.client.raise_dispute(&buyer, &id);
         h.client.resolve_dispute(
             &id,
             &stellar_escrow_contract::DisputeResolution::ReleaseToBuyer,
+            &BytesN::from_array(&h.env, &[0u8; 32]),
         );
     });
 
@@ -356,12 +360,14 @@ fn bench_fee_withdrawal_instructions() {
     run_happy_path(&h);
 
     let recipient = Address::generate(&h.env);
+    h.client.request_fee_withdrawal(&h.token_addr, &recipient, &h.client.get_accumulated_fees());
+    h.env.ledger().with_mut(|l| l.timestamp += h.client.get_fee_withdrawal_delay());
     let r = bench(&h.env, || {
-        h.client.withdraw_fees(&recipient);
+        h.client.execute_fee_withdrawal();
     });
 
     std::println!(
-        "[bench] withdraw_fees: {} instructions, {} mem bytes, {}ns wall",
+        "[bench] execute_fee_withdrawal: {} instructions, {} mem bytes, {}ns wall",
         r.instructions,
         r.mem_bytes,
         r.wall_ns
@@ -385,7 +391,7 @@ fn monitor_resource_usage_scales_linearly() {
         h.client.create_trade(
             &seller1,
             &buyer1,
-            &1_000_000u64,
+            &1_000_000i128,
             &None,
             &OptionalMetadata::None,
         );
@@ -399,7 +405,7 @@ fn monitor_resource_usage_scales_linearly() {
             mark_compliant(&h, &s);
             mark_compliant(&h, &b);
             h.client
-                .create_trade(&s, &b, &1_000_000u64, &None, &OptionalMetadata::None);
+                .create_trade(&s, &b, &1_000_000i128, &None, &OptionalMetadata::None);
         }
     });
 
@@ -431,7 +437,7 @@ fn monitor_memory_usage_create_trade() {
         h.client.create_trade(
             &seller,
             &buyer,
-            &1_000_000u64,
+            &1_000_000i128,
             &None,
             &OptionalMetadata::None,
         );
@@ -457,8 +463,8 @@ fn stress_max_amount_trade() {
     let buyer = Address::generate(&h.env);
     mark_compliant(&h, &seller);
     mark_compliant(&h, &buyer);
-    let max_amount = u64::MAX / 10_000; // stays within fee calculation bounds
-    fund_account(&h, &buyer, max_amount as i128);
+    let max_amount = i128::from(u64::MAX) * 1_000; // well beyond the old u64 range
+    fund_account(&h, &buyer, max_amount);
 
     let id = h
         .client
@@ -471,7 +477,7 @@ fn stress_max_amount_trade() {
 fn stress_rapid_fee_updates() {
     let h = setup();
     for bps in [0u32, 50, 100, 500, 1000, 9999, 10000] {
-        h.client.update_fee(&bps);
+        h.client.update_fee(&bps, &0u32);
         assert_eq!(h.client.get_platform_fee_bps(), bps);
     }
 }
@@ -57,7 +57,8 @@ struct Effect {
     effect_type: String,
     created_at: String,
     contract: Option<String>,
-    /// topics[0] = category symbol, topics[1] = event_name symbol
+    /// topics[0] = category symbol, topics[1] = event_name symbol; trade-scoped
+    /// events add topics[2] = trade_id and optionally topics[3] = party address
     topics: Vec<String>,
     data: serde_json::Value,
 }
@@ -74,15 +75,38 @@ fn resolve_event_type(category: &str, event_name: &str) -> Option<(&'static str,
     match (category, event_name) {
         // trade category
         ("trade", "created")   => Some(("trade", "trade_created")),
+        ("trade", "memo")      => Some(("trade", "trade_memo")),
+        ("trade", "payee_set") => Some(("trade", "payee_set")),
+        ("trade", "order_set") => Some(("trade", "order_set")),
+        ("trade", "item_rel")  => Some(("trade", "line_item_released")),
+        ("trade", "item_disp") => Some(("trade", "line_item_disputed")),
+        ("trade", "item_res")  => Some(("trade", "line_item_resolved")),
+        ("trade", "contrib")   => Some(("trade", "contribution_added")),
+        ("trade", "contr_out") => Some(("trade", "contribution_returned")),
+        ("trade", "strm_go")   => Some(("trade", "stream_started")),
+        ("trade", "strm_clm")  => Some(("trade", "stream_claimed")),
+        ("trade", "held")      => Some(("trade", "release_held")),
+        ("trade", "rated")     => Some(("trade", "counterparty_rated")),
+        ("trade", "dlv_proof") => Some(("trade", "delivery_proof")),
+        ("trade", "split_set") => Some(("trade", "payout_split_set")),
+        ("trade", "status")    => Some(("trade", "status_changed")),
         ("trade", "funded")    => Some(("trade", "trade_funded")),
         ("trade", "complete")  => Some(("trade", "trade_completed")),
         ("trade", "confirm")   => Some(("trade", "trade_confirmed")),
         ("trade", "cancel")    => Some(("trade", "trade_cancelled")),
+        ("trade", "expired")   => Some(("trade", "trade_expired")),
+        ("trade", "rfnd_req")  => Some(("trade", "refund_requested")),
+        ("trade", "rfnd_ok")   => Some(("trade", "refund_approved")),
+        ("trade", "refunded")  => Some(("trade", "trade_refunded")),
         ("trade", "time_rel")  => Some(("trade", "time_released")),
         ("trade", "meta_upd")  => Some(("trade", "metadata_updated")),
         ("trade", "dispute")   => Some(("trade", "dispute_raised")),
         ("trade", "resolved")  => Some(("trade", "dispute_resolved")),
         ("trade", "part_res")  => Some(("trade", "partial_resolved")),
+        ("trade", "res_pend")  => Some(("trade", "resolution_pending")),
+        ("trade", "reopened")  => Some(("trade", "dispute_reopened")),
+        ("trade", "res_appr")  => Some(("trade", "resolution_approved")),
+        ("trade", "res_veto")  => Some(("trade", "resolution_vetoed")),
         // arb category
         ("arb", "arb_reg")     => Some(("arb", "arbitrator_registered")),
         ("arb", "arb_rem")     => Some(("arb", "arbitrator_removed")),
@@ -90,9 +114,17 @@ fn resolve_event_type(category: &str, event_name: &str) -> Option<(&'static str,
         ("arb", "arb_rep")     => Some(("arb", "arbitrator_rep_updated")),
         // fee category
         ("fee", "fee_upd")     => Some(("fee", "fee_updated")),
+        ("fee", "fee_sched")   => Some(("fee", "fee_scheduled")),
         ("fee", "fees_out")    => Some(("fee", "fees_withdrawn")),
         ("fee", "fee_dst")     => Some(("fee", "fees_distributed")),
         ("fee", "cust_fee")    => Some(("fee", "custom_fee_set")),
+        ("fee", "fee_appl")    => Some(("fee", "fee_applied")),
+        ("fee", "fee_cap")     => Some(("fee", "fee_cap_set")),
+        ("fee", "fee_min")     => Some(("fee", "min_fee_set")),
+        ("fee", "promo_set")   => Some(("fee", "promo_window_set")),
+        ("fee", "tok_fee")     => Some(("fee", "token_fee_set")),
+        ("fee", "prot_set")    => Some(("fee", "protocol_share_set")),
+        ("fee", "prot_fee")    => Some(("fee", "protocol_fee_paid")),
         ("fee", "tier_up")     => Some(("fee", "tier_upgraded")),
         ("fee", "tier_dn")     => Some(("fee", "tier_downgraded")),
         ("fee", "tier_cfg")    => Some(("fee", "tier_config_updated")),
@@ -113,6 +145,8 @@ fn resolve_event_type(category: &str, event_name: &str) -> Option<(&'static str,
         // sys category
         ("sys", "paused")      => Some(("sys", "paused")),
         ("sys", "unpaused")    => Some(("sys", "unpaused")),
+        ("sys", "emrg_init")   => Some(("sys", "emergency_withdraw_initiated")),
+        ("sys", "emrg_cncl")   => Some(("sys", "emergency_withdraw_cancelled")),
         ("sys", "emrg_wd")     => Some(("sys", "emergency_withdraw")),
         ("sys", "upgraded")    => Some(("sys", "upgraded")),
         ("sys", "migrated")    => Some(("sys", "migrated")),
@@ -568,6 +602,8 @@ impl EventMonitor {
             "fee_updated"     => (AuditCategory::Admin, "fee.updated",     AuditSeverity::Warn),
             "paused"          => (AuditCategory::Security, "contract.paused",   AuditSeverity::Error),
             "unpaused"        => (AuditCategory::Security, "contract.unpaused", AuditSeverity::Warn),
+            "emrg_init"       => (AuditCategory::Security, "emergency.initiated", AuditSeverity::Critical),
+            "emrg_cncl"       => (AuditCategory::Security, "emergency.cancelled", AuditSeverity::Warn),
             "emrg_wd"         => (AuditCategory::Security, "emergency.withdraw", AuditSeverity::Critical),
             _ => return, // skip non-auditable events
         };
@@ -678,6 +714,8 @@ impl EventMonitor {
             "fee_updated"     => (AuditCategory::Admin, "fee.updated",     AuditSeverity::Warn),
             "paused"          => (AuditCategory::Security, "contract.paused",   AuditSeverity::Error),
             "unpaused"        => (AuditCategory::Security, "contract.unpaused", AuditSeverity::Warn),
+            "emrg_init"       => (AuditCategory::Security, "emergency.initiated", AuditSeverity::Critical),
+            "emrg_cncl"       => (AuditCategory::Security, "emergency.cancelled", AuditSeverity::Warn),
             "emrg_wd"         => (AuditCategory::Security, "emergency.withdraw", AuditSeverity::Critical),
             _ => return, // skip non-auditable events
         };
@@ -754,7 +792,8 @@ impl EventMonitor {
             return Ok(None);
         }
 
-        // Contract events use a two-topic layout: (category, event_name)
+        // Contract events lead with (category, event_name); any trailing
+        // trade_id / party topics are filter aids and also appear in the data.
         if effect.topics.len() < 2 {
             return Ok(None);
         }
@@ -226,7 +226,7 @@ static DOCS: &[HelpArticle] = &[
         id: "doc-contract-overview",
         category: "contract",
         title: "Smart Contract Overview",
-        content: "The StellarEscrow smart contract is a Soroban contract written in Rust. It manages the full lifecycle of peer-to-peer escrow trades using USDC on the Stellar blockchain. Key functions: initialize, create_trade, fund_trade, complete_trade, confirm_trade, raise_dispute, resolve_dispute, cancel_trade, register_arbitrator, remove_arbitrator_fn, update_fee, withdraw_fees.",
+        content: "The StellarEscrow smart contract is a Soroban contract written in Rust. It manages the full lifecycle of peer-to-peer escrow trades using USDC on the Stellar blockchain. Key functions: initialize, create_trade, fund_trade, complete_trade, confirm_trade, raise_dispute, resolve_dispute, cancel_trade, register_arbitrator, remove_arbitrator_fn, update_fee, request_fee_withdrawal, execute_fee_withdrawal.",
         tags: &["contract", "soroban", "overview"],
     },
     HelpArticle {
@@ -240,7 +240,7 @@ static DOCS: &[HelpArticle] = &[
         id: "doc-fees",
         category: "contract",
         title: "Fee Structure",
-        content: "Platform fees are set in basis points (bps) at initialization and can be updated by the admin. 1 bps = 0.01%, so 100 bps = 1%. Fees are deducted from the trade amount at settlement and accumulated in the contract. The admin can withdraw accumulated fees at any time via withdraw_fees.",
+        content: "Platform fees are set in basis points (bps) at initialization and can be updated by the admin. 1 bps = 0.01%, so 100 bps = 1%. Fees are deducted from the trade amount at settlement and accumulated in the contract. The admin withdraws accumulated fees in two steps: request_fee_withdrawal, then execute_fee_withdrawal once the configurable delay has passed. A pending request can be cancelled with cancel_fee_withdrawal.",
         tags: &["fees", "bps", "admin"],
     },
     HelpArticle {
@@ -261,7 +261,7 @@ static DOCS: &[HelpArticle] = &[
         id: "doc-security",
         category: "security",
         title: "Security Model",
-        content: "All privileged operations require Stellar authorization (require_auth). Admin-only operations: register_arbitrator, remove_arbitrator_fn, update_fee, withdraw_fees. Seller-only: create_trade, complete_trade, cancel_trade. Buyer-only: fund_trade, confirm_trade. Arbitrator-only: resolve_dispute. The contract validates initialization state before every operation.",
+        content: "All privileged operations require Stellar authorization (require_auth). Admin-only operations: register_arbitrator, remove_arbitrator_fn, update_fee, request_fee_withdrawal, execute_fee_withdrawal, cancel_fee_withdrawal. Seller-only: create_trade, complete_trade, cancel_trade. Buyer-only: fund_trade, confirm_trade. Arbitrator-only: resolve_dispute. The contract validates initialization state before every operation.",
         tags: &["security", "auth", "roles"],
     },
 ];